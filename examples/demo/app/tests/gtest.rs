@@ -92,6 +92,43 @@ async fn counter_sub_works() {
     assert_eq!((demo_program.id(), CounterEvents::Subtracted(10)), event);
 }
 
+#[tokio::test]
+async fn counter_calls_are_profiled() {
+    use demo_client::counter::Counter as _;
+    // Arrange
+    let (env, code_id, _gas_limit) = create_env();
+    let env = env.with_profiling();
+
+    let demo_program = env
+        .deploy(code_id, vec![])
+        .new(Some(42), None)
+        .await
+        .unwrap()
+        .unwrap();
+    let mut counter_client = demo_program.counter();
+
+    // Act
+    counter_client.add(10).await.unwrap();
+    counter_client.add(5).await.unwrap();
+    counter_client.sub(1).await.unwrap();
+
+    // Assert
+    let report = env.profile_report();
+    assert_eq!(report.calls().len(), 4);
+    assert!(report.calls().iter().all(|call| call.is_success()));
+
+    let ctor = report.call_summary("demo_client::New").unwrap();
+    assert_eq!(ctor.calls, 1);
+    let add = report.call_summary("counter::Add").unwrap();
+    assert_eq!((add.calls, add.failures), (2, 0));
+    assert!(add.gas_min > 0 && add.gas_min <= add.gas_max);
+    assert_eq!(add.blocks_total, 2);
+    assert!(report.to_string().contains("counter::Sub"));
+
+    env.reset_profile();
+    assert!(env.profile_report().calls().is_empty());
+}
+
 #[tokio::test]
 async fn counter_query_works() {
     use demo_client::counter::Counter as _;
@@ -833,3 +870,98 @@ async fn override_generics_foo_is_overridden() {
     let result = client.base_service().foo().await.unwrap();
     assert_eq!(result, 20);
 }
+
+#[tokio::test]
+async fn mock_program_replies_and_emits_events() {
+    use demo_client::{
+        base_service::BaseService as _,
+        base_service::mock_program::BaseServiceMock as _,
+        counter::{Counter as _, events::CounterEvents, mock_program::CounterMock as _},
+    };
+    // Arrange
+    let system = System::new();
+    system.mint_to(ACTOR_ID, DEFAULT_USERS_INITIAL_BALANCE);
+    let env = GtestEnv::new(system, ACTOR_ID.into());
+
+    // Stub the Demo program with canned replies instead of deploying it
+    let mut mock = MockProgram::<DemoClientProgram>::new();
+    mock.counter()
+        .add()
+        .returns_with(|(value,)| value + 42)
+        .emits(CounterEvents::Added(10));
+    mock.counter().sub().panics("Underflow");
+    mock.override_generics().base_service().foo().returns(20);
+    let demo_program = env.mock_program(ActorId::from(1000), mock);
+
+    let mut counter_client = demo_program.counter();
+    let mut counter_events = counter_client.listen().await.unwrap();
+
+    // Act
+    let result = counter_client.add(10).await.unwrap();
+    let error = counter_client.sub(1).await.unwrap_err();
+    let foo = demo_program
+        .override_generics()
+        .base_service()
+        .foo()
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(result, 52);
+    assert_eq!(
+        (demo_program.id(), CounterEvents::Added(10)),
+        counter_events.next().await.unwrap()
+    );
+    assert_eq!(
+        error.userspace_panic_payload(),
+        Some(b"Underflow".as_slice())
+    );
+    assert_eq!(foo, 20);
+}
+
+#[tokio::test]
+async fn mock_program_events_are_recorded() {
+    use demo_client::counter::{
+        Counter as _, events::CounterEvents, mock_program::CounterMock as _,
+    };
+    // Arrange
+    let system = System::new();
+    system.mint_to(ACTOR_ID, DEFAULT_USERS_INITIAL_BALANCE);
+    let env = GtestEnv::new(system, ACTOR_ID.into());
+
+    let mut mock = MockProgram::<DemoClientProgram>::new();
+    mock.counter()
+        .add()
+        .returns(52)
+        .emits(CounterEvents::Added(10));
+    mock.counter()
+        .sub()
+        .returns(42)
+        .emits(CounterEvents::Subtracted(10));
+    let demo_program = env.mock_program(ActorId::from(1000), mock);
+    let mut counter_client = demo_program.counter();
+    let checkpoint = env.checkpoint();
+
+    // Act
+    counter_client.add(10).await.unwrap();
+
+    // Assert
+    counter_client.expect_event(CounterEvents::Added(10));
+    counter_client.expect_no_events();
+
+    // Act
+    counter_client.sub(10).await.unwrap();
+
+    // Assert
+    let events = counter_client.events_since(checkpoint);
+    assert_eq!(
+        events.iter().map(|e| &e.event).collect::<Vec<_>>(),
+        [&CounterEvents::Added(10), &CounterEvents::Subtracted(10)]
+    );
+    assert!(events.iter().all(|e| e.source == demo_program.id()));
+    assert!(events[0].block < events[1].block);
+    assert_eq!(env.events_since(checkpoint).len(), 2);
+
+    counter_client.expect_event(CounterEvents::Subtracted(10));
+    counter_client.expect_no_events();
+}
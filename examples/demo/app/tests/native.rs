@@ -0,0 +1,98 @@
+use demo::DemoProgram;
+use demo_client::*;
+use sails::{client::*, prelude::*};
+
+const ACTOR_ID: u64 = 42;
+
+fn create_env() -> (NativeEnv, CodeId) {
+    let env = NativeEnv::new(ACTOR_ID.into());
+    let code_id = env.submit_code::<DemoProgram>();
+    (env, code_id)
+}
+
+#[tokio::test]
+async fn native_counter_works() {
+    use demo_client::counter::Counter as _;
+    // Arrange
+    let (env, code_id) = create_env();
+
+    // Use generated client code for creating the natively executed Demo program
+    let demo_program = env
+        .deploy(code_id, vec![])
+        .new(Some(42), None)
+        .await
+        .unwrap()
+        .unwrap();
+
+    let mut counter_client = demo_program.counter();
+
+    // Act
+    let result = counter_client.add(10).await.unwrap();
+    let value = counter_client.value().await.unwrap();
+
+    // Assert
+    assert_eq!(result, 52);
+    assert_eq!(value, 52);
+}
+
+#[tokio::test]
+async fn native_ctor_throws_error() {
+    // Arrange
+    let (env, code_id) = create_env();
+
+    // Act
+    let result = env
+        .deploy::<DemoClientProgram>(code_id, vec![])
+        .new_with_error(0)
+        .await
+        .unwrap();
+
+    // Assert
+    assert!(matches!(result, Err(err) if err == "Constructor failed"));
+}
+
+#[tokio::test]
+async fn native_value_is_injected() {
+    use demo_client::value_fee::ValueFee as _;
+    // Arrange
+    let (env, code_id) = create_env();
+
+    let demo_program = env.deploy(code_id, vec![]).default().await.unwrap();
+
+    let mut client = demo_program.value_fee();
+
+    // Act
+    let not_enough = client
+        .do_something_and_take_fee()
+        .with_value(1_000)
+        .await
+        .unwrap();
+    let enough = client
+        .do_something_and_take_fee()
+        .with_value(15_000_000_000_000)
+        .await
+        .unwrap();
+
+    // Assert
+    assert_eq!(not_enough.unwrap_err(), "Not enough value");
+    assert_eq!(enough, Ok(()));
+}
+
+#[tokio::test]
+async fn native_unknown_program_is_not_found() {
+    use demo_client::counter::Counter as _;
+    // Arrange
+    let (env, _code_id) = create_env();
+    let program_id = ActorId::from(1000);
+
+    // Act
+    let err = DemoClientProgram::client(program_id)
+        .with_env(&env)
+        .counter()
+        .value()
+        .await
+        .unwrap_err();
+
+    // Assert
+    assert_eq!(err, NativeError::ProgramNotFound(program_id));
+}
@@ -76,6 +76,60 @@ fn override_generics(
         self.service(DemoClientProgram::ROUTE_ID_OVERRIDE_GENERICS)
     }
 }
+
+#[cfg(feature = "with_mocks")]
+#[cfg(not(target_arch = "wasm32"))]
+pub trait DemoClientMock {
+    fn ping_pong(&mut self) -> sails::client::MockService<'_, ping_pong::PingPongImpl>;
+    fn counter(&mut self) -> sails::client::MockService<'_, counter::CounterImpl>;
+    fn dog(&mut self) -> sails::client::MockService<'_, dog::DogImpl>;
+    fn references(&mut self) -> sails::client::MockService<'_, references::ReferencesImpl>;
+    fn this_that(&mut self) -> sails::client::MockService<'_, this_that::ThisThatImpl>;
+    fn value_fee(&mut self) -> sails::client::MockService<'_, value_fee::ValueFeeImpl>;
+    fn validator(&mut self) -> sails::client::MockService<'_, validator::ValidatorImpl>;
+    fn chaos(&mut self) -> sails::client::MockService<'_, chaos::ChaosImpl>;
+    fn chain(&mut self) -> sails::client::MockService<'_, chain::ChainImpl>;
+    fn override_generics(
+        &mut self,
+    ) -> sails::client::MockService<'_, override_generics::OverrideGenericsImpl>;
+}
+
+#[cfg(feature = "with_mocks")]
+#[cfg(not(target_arch = "wasm32"))]
+impl DemoClientMock for sails::client::MockProgram<DemoClientProgram> {
+    fn ping_pong(&mut self) -> sails::client::MockService<'_, ping_pong::PingPongImpl> {
+        self.service(DemoClientProgram::ROUTE_ID_PING_PONG)
+    }
+    fn counter(&mut self) -> sails::client::MockService<'_, counter::CounterImpl> {
+        self.service(DemoClientProgram::ROUTE_ID_COUNTER)
+    }
+    fn dog(&mut self) -> sails::client::MockService<'_, dog::DogImpl> {
+        self.service(DemoClientProgram::ROUTE_ID_DOG)
+    }
+    fn references(&mut self) -> sails::client::MockService<'_, references::ReferencesImpl> {
+        self.service(DemoClientProgram::ROUTE_ID_REFERENCES)
+    }
+    fn this_that(&mut self) -> sails::client::MockService<'_, this_that::ThisThatImpl> {
+        self.service(DemoClientProgram::ROUTE_ID_THIS_THAT)
+    }
+    fn value_fee(&mut self) -> sails::client::MockService<'_, value_fee::ValueFeeImpl> {
+        self.service(DemoClientProgram::ROUTE_ID_VALUE_FEE)
+    }
+    fn validator(&mut self) -> sails::client::MockService<'_, validator::ValidatorImpl> {
+        self.service(DemoClientProgram::ROUTE_ID_VALIDATOR)
+    }
+    fn chaos(&mut self) -> sails::client::MockService<'_, chaos::ChaosImpl> {
+        self.service(DemoClientProgram::ROUTE_ID_CHAOS)
+    }
+    fn chain(&mut self) -> sails::client::MockService<'_, chain::ChainImpl> {
+        self.service(DemoClientProgram::ROUTE_ID_CHAIN)
+    }
+    fn override_generics(
+        &mut self,
+    ) -> sails::client::MockService<'_, override_generics::OverrideGenericsImpl> {
+        self.service(DemoClientProgram::ROUTE_ID_OVERRIDE_GENERICS)
+    }
+}
 pub trait DemoClientCtors {
     type Env: sails::client::GearEnv + sails::client::EnvWithCtor;
     /// Program constructor (called once at the very beginning of the program lifetime)
@@ -166,6 +220,21 @@ impl ping_pong::PingPong for PingPong {
             }
         }
     }
+
+    #[cfg(feature = "with_mocks")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod mock_program {
+        use super::*;
+        pub trait PingPongMock {
+            fn ping(&mut self) -> sails::client::MockMethod<'_, io::Ping>;
+        }
+
+        impl PingPongMock for sails::client::MockService<'_, PingPongImpl> {
+            fn ping(&mut self) -> sails::client::MockMethod<'_, io::Ping> {
+                self.method()
+            }
+        }
+    }
 }
 
 pub mod counter {
@@ -266,6 +335,29 @@ impl counter::Counter for Counter {
             }
         }
     }
+
+    #[cfg(feature = "with_mocks")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod mock_program {
+        use super::*;
+        pub trait CounterMock {
+            fn add(&mut self) -> sails::client::MockMethod<'_, io::Add>;
+            fn sub(&mut self) -> sails::client::MockMethod<'_, io::Sub>;
+            fn value(&mut self) -> sails::client::MockMethod<'_, io::Value>;
+        }
+
+        impl CounterMock for sails::client::MockService<'_, CounterImpl> {
+            fn add(&mut self) -> sails::client::MockMethod<'_, io::Add> {
+                self.method()
+            }
+            fn sub(&mut self) -> sails::client::MockMethod<'_, io::Sub> {
+                self.method()
+            }
+            fn value(&mut self) -> sails::client::MockMethod<'_, io::Value> {
+                self.method()
+            }
+        }
+    }
 }
 
 pub mod mammal_service {
@@ -316,6 +408,25 @@ impl mammal_service::MammalService for MammalService {
             }
         }
     }
+
+    #[cfg(feature = "with_mocks")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod mock_program {
+        use super::*;
+        pub trait MammalServiceMock {
+            fn avg_weight(&mut self) -> sails::client::MockMethod<'_, io::AvgWeight>;
+            fn make_sound(&mut self) -> sails::client::MockMethod<'_, io::MakeSound>;
+        }
+
+        impl MammalServiceMock for sails::client::MockService<'_, MammalServiceImpl> {
+            fn avg_weight(&mut self) -> sails::client::MockMethod<'_, io::AvgWeight> {
+                self.method()
+            }
+            fn make_sound(&mut self) -> sails::client::MockMethod<'_, io::MakeSound> {
+                self.method()
+            }
+        }
+    }
 }
 
 pub mod walker_service {
@@ -403,6 +514,25 @@ impl walker_service::WalkerService for WalkerService {
             }
         }
     }
+
+    #[cfg(feature = "with_mocks")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod mock_program {
+        use super::*;
+        pub trait WalkerServiceMock {
+            fn position(&mut self) -> sails::client::MockMethod<'_, io::Position>;
+            fn walk(&mut self) -> sails::client::MockMethod<'_, io::Walk>;
+        }
+
+        impl WalkerServiceMock for sails::client::MockService<'_, WalkerServiceImpl> {
+            fn position(&mut self) -> sails::client::MockMethod<'_, io::Position> {
+                self.method()
+            }
+            fn walk(&mut self) -> sails::client::MockMethod<'_, io::Walk> {
+                self.method()
+            }
+        }
+    }
 }
 
 pub mod dog {
@@ -501,6 +631,39 @@ impl dog::Dog for Dog {
             }
         }
     }
+
+    #[cfg(feature = "with_mocks")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod mock_program {
+        use super::*;
+        pub trait DogMock {
+            fn make_sound(&mut self) -> sails::client::MockMethod<'_, io::MakeSound>;
+            fn mammal_service(
+                &mut self,
+            ) -> sails::client::MockService<'_, super::super::mammal_service::MammalServiceImpl>;
+            fn walker_service(
+                &mut self,
+            ) -> sails::client::MockService<'_, super::super::walker_service::WalkerServiceImpl>;
+        }
+
+        impl DogMock for sails::client::MockService<'_, DogImpl> {
+            fn make_sound(&mut self) -> sails::client::MockMethod<'_, io::MakeSound> {
+                self.method()
+            }
+            fn mammal_service(
+                &mut self,
+            ) -> sails::client::MockService<'_, super::super::mammal_service::MammalServiceImpl>
+            {
+                self.base_service()
+            }
+            fn walker_service(
+                &mut self,
+            ) -> sails::client::MockService<'_, super::super::walker_service::WalkerServiceImpl>
+            {
+                self.base_service()
+            }
+        }
+    }
 }
 
 pub mod references {
@@ -585,6 +748,49 @@ impl references::References for References {
             }
         }
     }
+
+    #[cfg(feature = "with_mocks")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod mock_program {
+        use super::*;
+        pub trait ReferencesMock {
+            fn add(&mut self) -> sails::client::MockMethod<'_, io::Add>;
+            fn add_byte(&mut self) -> sails::client::MockMethod<'_, io::AddByte>;
+            fn baked(&mut self) -> sails::client::MockMethod<'_, io::Baked>;
+            fn guess_num(&mut self) -> sails::client::MockMethod<'_, io::GuessNum>;
+            fn incr(&mut self) -> sails::client::MockMethod<'_, io::Incr>;
+            fn last_byte(&mut self) -> sails::client::MockMethod<'_, io::LastByte>;
+            fn message(&mut self) -> sails::client::MockMethod<'_, io::Message>;
+            fn set_num(&mut self) -> sails::client::MockMethod<'_, io::SetNum>;
+        }
+
+        impl ReferencesMock for sails::client::MockService<'_, ReferencesImpl> {
+            fn add(&mut self) -> sails::client::MockMethod<'_, io::Add> {
+                self.method()
+            }
+            fn add_byte(&mut self) -> sails::client::MockMethod<'_, io::AddByte> {
+                self.method()
+            }
+            fn baked(&mut self) -> sails::client::MockMethod<'_, io::Baked> {
+                self.method()
+            }
+            fn guess_num(&mut self) -> sails::client::MockMethod<'_, io::GuessNum> {
+                self.method()
+            }
+            fn incr(&mut self) -> sails::client::MockMethod<'_, io::Incr> {
+                self.method()
+            }
+            fn last_byte(&mut self) -> sails::client::MockMethod<'_, io::LastByte> {
+                self.method()
+            }
+            fn message(&mut self) -> sails::client::MockMethod<'_, io::Message> {
+                self.method()
+            }
+            fn set_num(&mut self) -> sails::client::MockMethod<'_, io::SetNum> {
+                self.method()
+            }
+        }
+    }
 }
 
 pub mod this_that {
@@ -699,6 +905,37 @@ impl this_that::ThisThat for ThisThat {
             }
         }
     }
+
+    #[cfg(feature = "with_mocks")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod mock_program {
+        use super::*;
+        pub trait ThisThatMock {
+            fn do_that(&mut self) -> sails::client::MockMethod<'_, io::DoThat>;
+            fn do_this(&mut self) -> sails::client::MockMethod<'_, io::DoThis>;
+            fn noop(&mut self) -> sails::client::MockMethod<'_, io::Noop>;
+            fn that(&mut self) -> sails::client::MockMethod<'_, io::That>;
+            fn this(&mut self) -> sails::client::MockMethod<'_, io::This>;
+        }
+
+        impl ThisThatMock for sails::client::MockService<'_, ThisThatImpl> {
+            fn do_that(&mut self) -> sails::client::MockMethod<'_, io::DoThat> {
+                self.method()
+            }
+            fn do_this(&mut self) -> sails::client::MockMethod<'_, io::DoThis> {
+                self.method()
+            }
+            fn noop(&mut self) -> sails::client::MockMethod<'_, io::Noop> {
+                self.method()
+            }
+            fn that(&mut self) -> sails::client::MockMethod<'_, io::That> {
+                self.method()
+            }
+            fn this(&mut self) -> sails::client::MockMethod<'_, io::This> {
+                self.method()
+            }
+        }
+    }
 }
 
 pub mod value_fee {
@@ -787,6 +1024,25 @@ impl value_fee::ValueFee for ValueFee {
             }
         }
     }
+
+    #[cfg(feature = "with_mocks")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod mock_program {
+        use super::*;
+        pub trait ValueFeeMock {
+            fn do_something_and_take_fee(
+                &mut self,
+            ) -> sails::client::MockMethod<'_, io::DoSomethingAndTakeFee>;
+        }
+
+        impl ValueFeeMock for sails::client::MockService<'_, ValueFeeImpl> {
+            fn do_something_and_take_fee(
+                &mut self,
+            ) -> sails::client::MockMethod<'_, io::DoSomethingAndTakeFee> {
+                self.method()
+            }
+        }
+    }
 }
 
 pub mod validator {
@@ -876,6 +1132,33 @@ impl validator::Validator for Validator {
             }
         }
     }
+
+    #[cfg(feature = "with_mocks")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod mock_program {
+        use super::*;
+        pub trait ValidatorMock {
+            fn total_errors(&mut self) -> sails::client::MockMethod<'_, io::TotalErrors>;
+            fn validate_even(&mut self) -> sails::client::MockMethod<'_, io::ValidateEven>;
+            fn validate_nonzero(&mut self) -> sails::client::MockMethod<'_, io::ValidateNonzero>;
+            fn validate_range(&mut self) -> sails::client::MockMethod<'_, io::ValidateRange>;
+        }
+
+        impl ValidatorMock for sails::client::MockService<'_, ValidatorImpl> {
+            fn total_errors(&mut self) -> sails::client::MockMethod<'_, io::TotalErrors> {
+                self.method()
+            }
+            fn validate_even(&mut self) -> sails::client::MockMethod<'_, io::ValidateEven> {
+                self.method()
+            }
+            fn validate_nonzero(&mut self) -> sails::client::MockMethod<'_, io::ValidateNonzero> {
+                self.method()
+            }
+            fn validate_range(&mut self) -> sails::client::MockMethod<'_, io::ValidateRange> {
+                self.method()
+            }
+        }
+    }
 }
 
 pub mod chaos {
@@ -934,6 +1217,32 @@ impl chaos::Chaos for Chaos {
             }
         }
     }
+
+    #[cfg(feature = "with_mocks")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod mock_program {
+        use super::*;
+        pub trait ChaosMock {
+            fn panic_after_wait(&mut self) -> sails::client::MockMethod<'_, io::PanicAfterWait>;
+            fn reply_hook_counter(&mut self)
+            -> sails::client::MockMethod<'_, io::ReplyHookCounter>;
+            fn timeout_wait(&mut self) -> sails::client::MockMethod<'_, io::TimeoutWait>;
+        }
+
+        impl ChaosMock for sails::client::MockService<'_, ChaosImpl> {
+            fn panic_after_wait(&mut self) -> sails::client::MockMethod<'_, io::PanicAfterWait> {
+                self.method()
+            }
+            fn reply_hook_counter(
+                &mut self,
+            ) -> sails::client::MockMethod<'_, io::ReplyHookCounter> {
+                self.method()
+            }
+            fn timeout_wait(&mut self) -> sails::client::MockMethod<'_, io::TimeoutWait> {
+                self.method()
+            }
+        }
+    }
 }
 
 pub mod chain {
@@ -983,6 +1292,25 @@ impl chain::Chain for Chain {
             }
         }
     }
+
+    #[cfg(feature = "with_mocks")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod mock_program {
+        use super::*;
+        pub trait ChainMock {
+            fn make_sound(&mut self) -> sails::client::MockMethod<'_, io::MakeSound>;
+            fn dog(&mut self) -> sails::client::MockService<'_, super::super::dog::DogImpl>;
+        }
+
+        impl ChainMock for sails::client::MockService<'_, ChainImpl> {
+            fn make_sound(&mut self) -> sails::client::MockMethod<'_, io::MakeSound> {
+                self.method()
+            }
+            fn dog(&mut self) -> sails::client::MockService<'_, super::super::dog::DogImpl> {
+                self.base_service()
+            }
+        }
+    }
 }
 
 pub mod base_service {
@@ -1033,6 +1361,25 @@ impl base_service::BaseService for BaseService {
             }
         }
     }
+
+    #[cfg(feature = "with_mocks")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod mock_program {
+        use super::*;
+        pub trait BaseServiceMock {
+            fn foo(&mut self) -> sails::client::MockMethod<'_, io::Foo>;
+            fn set_value(&mut self) -> sails::client::MockMethod<'_, io::SetValue>;
+        }
+
+        impl BaseServiceMock for sails::client::MockService<'_, BaseServiceImpl> {
+            fn foo(&mut self) -> sails::client::MockMethod<'_, io::Foo> {
+                self.method()
+            }
+            fn set_value(&mut self) -> sails::client::MockMethod<'_, io::SetValue> {
+                self.method()
+            }
+        }
+    }
 }
 
 #[allow(unused_imports)]
@@ -1080,4 +1427,24 @@ impl override_generics::OverrideGenerics for OverrideGenerics {
             }
         }
     }
+
+    #[cfg(feature = "with_mocks")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod mock_program {
+        use super::*;
+        pub trait OverrideGenericsMock {
+            fn base_service(
+                &mut self,
+            ) -> sails::client::MockService<'_, super::super::base_service::BaseServiceImpl>;
+        }
+
+        impl OverrideGenericsMock for sails::client::MockService<'_, OverrideGenericsImpl> {
+            fn base_service(
+                &mut self,
+            ) -> sails::client::MockService<'_, super::super::base_service::BaseServiceImpl>
+            {
+                self.base_service()
+            }
+        }
+    }
 }
@@ -0,0 +1,3 @@
+fn main() {
+    sails::build_wasm();
+}
@@ -0,0 +1,151 @@
+#![no_std]
+
+use sails::{cell::RefCell, prelude::*};
+
+/// Layouts of the state used by the previous releases of the program.
+pub mod legacy {
+    use sails::prelude::*;
+
+    /// State of version 1: balances only.
+    #[sails_type]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct StateV1 {
+        pub balances: Vec<(ActorId, u32)>,
+    }
+
+    /// State of version 2: balances widened to `u128`, the owner added.
+    #[sails_type]
+    #[derive(Clone, Debug, PartialEq)]
+    pub struct StateV2 {
+        pub owner: ActorId,
+        pub balances: Vec<(ActorId, u128)>,
+    }
+}
+
+/// State of the current version: the total supply is tracked along with balances.
+#[sails_type]
+#[derive(Clone, Debug, PartialEq)]
+pub struct State {
+    pub owner: ActorId,
+    pub balances: Vec<(ActorId, u128)>,
+    pub total_supply: u128,
+}
+
+pub struct LedgerService<'a> {
+    state: &'a RefCell<State>,
+}
+
+impl<'a> LedgerService<'a> {
+    pub fn new(state: &'a RefCell<State>) -> Self {
+        Self { state }
+    }
+}
+
+#[service]
+impl LedgerService<'_> {
+    /// Mints `amount` to `to`, only the owner is allowed to mint
+    #[export]
+    pub fn mint(&mut self, to: ActorId, amount: u128) -> u128 {
+        let mut state = self.state.borrow_mut();
+        if Syscall::message_source() != state.owner {
+            panic!("Only the owner can mint");
+        }
+        state.total_supply += amount;
+        match state
+            .balances
+            .iter_mut()
+            .find(|(account, _)| *account == to)
+        {
+            Some((_, balance)) => {
+                *balance += amount;
+                *balance
+            }
+            None => {
+                state.balances.push((to, amount));
+                amount
+            }
+        }
+    }
+
+    #[export]
+    pub fn balance_of(&self, account: ActorId) -> u128 {
+        self.state
+            .borrow()
+            .balances
+            .iter()
+            .find_map(|(holder, balance)| (*holder == account).then_some(*balance))
+            .unwrap_or_default()
+    }
+
+    #[export]
+    pub fn owner(&self) -> ActorId {
+        self.state.borrow().owner
+    }
+
+    #[export]
+    pub fn total_supply(&self) -> u128 {
+        self.state.borrow().total_supply
+    }
+
+    /// Returns the state to be passed to the `migrate` constructor of the next release
+    #[export]
+    pub fn state(&self) -> State {
+        self.state.borrow().clone()
+    }
+}
+
+pub struct MigrationProgram {
+    state: RefCell<State>,
+}
+
+// Every release bumps `state_version` and adds a migration from the previous one,
+// so the `migrate` constructor accepts the state exported by any earlier release.
+#[program(state_version = 3)]
+impl MigrationProgram {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            state: RefCell::new(State {
+                owner: Syscall::message_source(),
+                balances: Vec::new(),
+                total_supply: 0,
+            }),
+        }
+    }
+
+    #[migration(from = 1)]
+    fn from_v1(state: legacy::StateV1) -> legacy::StateV2 {
+        legacy::StateV2 {
+            owner: Syscall::message_source(),
+            balances: state
+                .balances
+                .into_iter()
+                .map(|(account, balance)| (account, balance.into()))
+                .collect(),
+        }
+    }
+
+    #[migration(from = 2)]
+    fn from_v2(state: legacy::StateV2) -> Self {
+        let total_supply = state.balances.iter().map(|(_, balance)| balance).sum();
+        Self {
+            state: RefCell::new(State {
+                owner: state.owner,
+                balances: state.balances,
+                total_supply,
+            }),
+        }
+    }
+
+    pub fn ledger(&self) -> LedgerService<'_> {
+        LedgerService::new(&self.state)
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use code::WASM_BINARY_OPT as WASM_BINARY;
+
+#[cfg(not(target_arch = "wasm32"))]
+mod code {
+    include!(concat!(env!("OUT_DIR"), "/wasm_binary.rs"));
+}
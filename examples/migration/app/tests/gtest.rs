@@ -0,0 +1,105 @@
+use migration_app::legacy::{StateV1, StateV2};
+use migration_client::{
+    MigrationClient, MigrationClientCtors, MigrationClientProgram,
+    ledger::{Ledger as _, State},
+};
+use sails::{client::*, prelude::*};
+
+const ACTOR_ID: u64 = 42;
+const ALICE: u64 = 100;
+const BOB: u64 = 101;
+
+fn create_env() -> (GtestEnv, CodeId) {
+    let system = sails::gtest::System::new();
+    system.init_logger_with_default_filter("gwasm=debug,gtest=info,sails_rs=debug");
+    system.mint_to(ACTOR_ID, 100_000_000_000_000);
+    let code_id = system.submit_code(migration_app::WASM_BINARY);
+    (GtestEnv::new(system, ACTOR_ID.into()), code_id)
+}
+
+#[tokio::test]
+async fn migrates_state_of_version_1() {
+    let (env, code_id) = create_env();
+
+    let state = StateV1 {
+        balances: vec![(ALICE.into(), 10), (BOB.into(), 32)],
+    };
+    let program = env
+        .deploy::<MigrationClientProgram>(code_id, vec![1])
+        .migrate(1, state.encode())
+        .await
+        .unwrap();
+
+    let ledger = program.ledger();
+    // The owner is set by the migration from version 1 to the deployer
+    assert_eq!(ledger.owner().await.unwrap(), ActorId::from(ACTOR_ID));
+    assert_eq!(ledger.balance_of(ALICE.into()).await.unwrap(), 10);
+    assert_eq!(ledger.balance_of(BOB.into()).await.unwrap(), 32);
+    // The total supply is computed by the migration from version 2
+    assert_eq!(ledger.total_supply().await.unwrap(), 42);
+}
+
+#[tokio::test]
+async fn migrates_state_of_version_2() {
+    let (env, code_id) = create_env();
+
+    let state = StateV2 {
+        owner: ALICE.into(),
+        balances: vec![(BOB.into(), u128::from(u64::MAX) + 1)],
+    };
+    let program = env
+        .deploy::<MigrationClientProgram>(code_id, vec![1])
+        .migrate(2, state.encode())
+        .await
+        .unwrap();
+
+    let expected = State {
+        owner: ALICE.into(),
+        balances: vec![(BOB.into(), u128::from(u64::MAX) + 1)],
+        total_supply: u128::from(u64::MAX) + 1,
+    };
+    assert_eq!(program.ledger().state().await.unwrap(), expected);
+}
+
+#[tokio::test]
+async fn migrated_program_keeps_working() {
+    let (env, code_id) = create_env();
+
+    let state = StateV2 {
+        owner: ACTOR_ID.into(),
+        balances: vec![(ALICE.into(), 5)],
+    };
+    let program = env
+        .deploy::<MigrationClientProgram>(code_id, vec![1])
+        .migrate(2, state.encode())
+        .await
+        .unwrap();
+
+    let mut ledger = program.ledger();
+    assert_eq!(ledger.mint(ALICE.into(), 7).await.unwrap(), 12);
+    assert_eq!(ledger.total_supply().await.unwrap(), 12);
+}
+
+#[tokio::test]
+async fn migrate_fails_for_unsupported_state_version() {
+    let (env, code_id) = create_env();
+
+    let res = env
+        .deploy::<MigrationClientProgram>(code_id, vec![1])
+        .migrate(3, Vec::new())
+        .await;
+
+    assert!(res.is_err());
+}
+
+#[tokio::test]
+async fn migrate_fails_for_malformed_state() {
+    let (env, code_id) = create_env();
+
+    let res = env
+        .deploy::<MigrationClientProgram>(code_id, vec![1])
+        .migrate(1, vec![0xff])
+        .await;
+
+    assert!(res.is_err());
+}
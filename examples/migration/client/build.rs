@@ -0,0 +1,6 @@
+fn main() {
+    sails::ClientBuilder::<migration_app::MigrationProgram>::from_env()
+        .build_idl()
+        .generate()
+        .unwrap();
+}
@@ -0,0 +1,4 @@
+#![no_std]
+
+// Incorporate code generated based on the IDL file
+include!("migration_client.rs");
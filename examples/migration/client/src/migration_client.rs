@@ -0,0 +1,131 @@
+// Code generated by sails-client-gen-v2. DO NOT EDIT.
+#[allow(unused_imports)]
+use sails::{client::*, collections::*, prelude::*};
+pub struct MigrationClientProgram;
+
+impl MigrationClientProgram {
+    pub const ROUTE_ID_LEDGER: u8 = 1;
+}
+
+impl sails::client::Program for MigrationClientProgram {}
+
+pub trait MigrationClient {
+    type Env: sails::client::GearEnv;
+    fn ledger(&self) -> sails::client::Service<ledger::LedgerImpl, Self::Env>;
+}
+
+impl<E: sails::client::GearEnv> MigrationClient
+    for sails::client::Actor<MigrationClientProgram, E>
+{
+    type Env = E;
+    fn ledger(&self) -> sails::client::Service<ledger::LedgerImpl, Self::Env> {
+        self.service(MigrationClientProgram::ROUTE_ID_LEDGER)
+    }
+}
+pub trait MigrationClientCtors {
+    type Env: sails::client::GearEnv + sails::client::EnvWithCtor;
+    /// Creates the program from the SCALE-encoded state of version 1 or later, migrating it to state version 3.
+    fn migrate(
+        self,
+        from_version: u32,
+        state: Vec<u8>,
+    ) -> sails::client::PendingCtor<MigrationClientProgram, io::Migrate, Self::Env>;
+    #[allow(clippy::new_ret_no_self)]
+    #[allow(clippy::wrong_self_convention)]
+    fn new(self) -> sails::client::PendingCtor<MigrationClientProgram, io::New, Self::Env>;
+}
+
+impl<E: sails::client::GearEnv + sails::client::EnvWithCtor> MigrationClientCtors
+    for sails::client::Deployment<MigrationClientProgram, E>
+{
+    type Env = E;
+    fn migrate(
+        self,
+        from_version: u32,
+        state: Vec<u8>,
+    ) -> sails::client::PendingCtor<MigrationClientProgram, io::Migrate, Self::Env> {
+        self.pending_ctor((from_version, state))
+    }
+    fn new(self) -> sails::client::PendingCtor<MigrationClientProgram, io::New, Self::Env> {
+        self.pending_ctor(())
+    }
+}
+
+pub mod io {
+    use super::*;
+    sails::io_struct_impl!(Migrate (from_version: u32, state: Vec<u8>) -> (), 0);
+    sails::io_struct_impl!(New () -> (), 1);
+}
+
+pub mod ledger {
+    use super::*;
+
+    /// State of the current version: the total supply is tracked along with balances.
+    #[sails::sails_type(crate = sails)]
+    #[derive(PartialEq, Clone, Debug)]
+    pub struct State {
+        pub owner: ActorId,
+        pub balances: Vec<(ActorId, u128)>,
+        pub total_supply: u128,
+    }
+
+    pub trait Ledger {
+        type Env: sails::client::GearEnv;
+        fn balance_of(
+            &self,
+            account: ActorId,
+        ) -> sails::client::PendingCall<io::BalanceOf, Self::Env>;
+        /// Mints `amount` to `to`, only the owner is allowed to mint
+        fn mint(
+            &mut self,
+            to: ActorId,
+            amount: u128,
+        ) -> sails::client::PendingCall<io::Mint, Self::Env>;
+        fn owner(&self) -> sails::client::PendingCall<io::Owner, Self::Env>;
+        /// Returns the state to be passed to the `migrate` constructor of the next release
+        fn state(&self) -> sails::client::PendingCall<io::State, Self::Env>;
+        fn total_supply(&self) -> sails::client::PendingCall<io::TotalSupply, Self::Env>;
+    }
+
+    pub struct LedgerImpl;
+
+    impl sails::client::Identifiable for LedgerImpl {
+        const INTERFACE_ID: sails::InterfaceId =
+            sails::InterfaceId::from_bytes_8([2, 4, 76, 136, 90, 196, 44, 32]);
+    }
+
+    impl<E: sails::client::GearEnv> Ledger for sails::client::Service<LedgerImpl, E> {
+        type Env = E;
+        fn balance_of(
+            &self,
+            account: ActorId,
+        ) -> sails::client::PendingCall<io::BalanceOf, Self::Env> {
+            self.pending_call((account,))
+        }
+        fn mint(
+            &mut self,
+            to: ActorId,
+            amount: u128,
+        ) -> sails::client::PendingCall<io::Mint, Self::Env> {
+            self.pending_call((to, amount))
+        }
+        fn owner(&self) -> sails::client::PendingCall<io::Owner, Self::Env> {
+            self.pending_call(())
+        }
+        fn state(&self) -> sails::client::PendingCall<io::State, Self::Env> {
+            self.pending_call(())
+        }
+        fn total_supply(&self) -> sails::client::PendingCall<io::TotalSupply, Self::Env> {
+            self.pending_call(())
+        }
+    }
+
+    pub mod io {
+        use super::*;
+        sails::io_struct_impl!(BalanceOf (account: ActorId) -> u128, 0, <super::LedgerImpl as sails::client::Identifiable>::INTERFACE_ID);
+        sails::io_struct_impl!(Mint (to: ActorId, amount: u128) -> u128, 1, <super::LedgerImpl as sails::client::Identifiable>::INTERFACE_ID);
+        sails::io_struct_impl!(Owner () -> ActorId, 2, <super::LedgerImpl as sails::client::Identifiable>::INTERFACE_ID);
+        sails::io_struct_impl!(State () -> super::State, 3, <super::LedgerImpl as sails::client::Identifiable>::INTERFACE_ID);
+        sails::io_struct_impl!(TotalSupply () -> u128, 4, <super::LedgerImpl as sails::client::Identifiable>::INTERFACE_ID);
+    }
+}
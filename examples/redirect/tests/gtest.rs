@@ -1,6 +1,6 @@
 use redirect_client::{redirect::Redirect as _, *};
 use redirect_proxy_client::{proxy::Proxy as _, *};
-use sails::{CodeId, GasUnit, client::*};
+use sails::{ActorId, CodeId, GasUnit, client::*};
 
 const ACTOR_ID: u64 = 42;
 
@@ -46,6 +46,52 @@ async fn redirect_on_exit_works() {
     assert_eq!(result, program_3.id());
 }
 
+#[tokio::test]
+async fn proxy_fails_when_target_fails() {
+    let (env, _program_code_id, proxy_code_id, _gas_limit) = create_env();
+    let target_id = ActorId::from(1_000);
+    env.inject_faults(
+        target_id,
+        FaultProgram::new()
+            .with_method_fault::<redirect::io::GetProgramId>(Fault::Panic("target is broken")),
+    );
+
+    let proxy_program = env
+        .deploy::<RedirectProxyClientProgram>(proxy_code_id, vec![])
+        .new(target_id)
+        .await
+        .unwrap();
+
+    let result = proxy_program.proxy().get_program_id().await;
+    assert!(matches!(
+        result,
+        Err(GtestError::ReplyHasError(
+            ErrorReplyReason::Execution(SimpleExecutionError::UserspacePanic),
+            _
+        ))
+    ));
+}
+
+#[tokio::test]
+async fn proxy_uses_target_reply() {
+    let (env, _program_code_id, proxy_code_id, _gas_limit) = create_env();
+    let target_id = ActorId::from(1_000);
+    let program_id = ActorId::from(2_000);
+    env.inject_faults(
+        target_id,
+        FaultProgram::new().with_reply::<redirect::io::GetProgramId>(program_id),
+    );
+
+    let proxy_program = env
+        .deploy::<RedirectProxyClientProgram>(proxy_code_id, vec![])
+        .new(target_id)
+        .await
+        .unwrap();
+
+    let result = proxy_program.proxy().get_program_id().await.unwrap();
+    assert_eq!(result, program_id);
+}
+
 fn create_env() -> (GtestEnv, CodeId, CodeId, GasUnit) {
     use sails::gtest::{System, constants::MAX_USER_GAS_LIMIT};
 
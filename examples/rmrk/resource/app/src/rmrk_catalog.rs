@@ -23,6 +23,20 @@ fn rmrk_catalog(&self) -> sails::client::Service<rmrk_catalog::RmrkCatalogImpl,
         self.service(RmrkCatalogProgram::ROUTE_ID_RMRK_CATALOG)
     }
 }
+
+#[cfg(feature = "mockall")]
+#[cfg(not(target_arch = "wasm32"))]
+pub trait RmrkCatalogMock {
+    fn rmrk_catalog(&mut self) -> sails::client::MockService<'_, rmrk_catalog::RmrkCatalogImpl>;
+}
+
+#[cfg(feature = "mockall")]
+#[cfg(not(target_arch = "wasm32"))]
+impl RmrkCatalogMock for sails::client::MockProgram<RmrkCatalogProgram> {
+    fn rmrk_catalog(&mut self) -> sails::client::MockService<'_, rmrk_catalog::RmrkCatalogImpl> {
+        self.service(RmrkCatalogProgram::ROUTE_ID_RMRK_CATALOG)
+    }
+}
 pub trait RmrkCatalogCtors {
     type Env: sails::client::GearEnv + sails::client::EnvWithCtor;
     #[allow(clippy::new_ret_no_self)]
@@ -210,4 +224,51 @@ impl rmrk_catalog::RmrkCatalog for RmrkCatalog {
             }
         }
     }
+
+    #[cfg(feature = "mockall")]
+    #[cfg(not(target_arch = "wasm32"))]
+    pub mod mock_program {
+        use super::*;
+        pub trait RmrkCatalogMock {
+            fn add_equippables(&mut self) -> sails::client::MockMethod<'_, io::AddEquippables>;
+            fn add_parts(&mut self) -> sails::client::MockMethod<'_, io::AddParts>;
+            fn equippable(&mut self) -> sails::client::MockMethod<'_, io::Equippable>;
+            fn part(&mut self) -> sails::client::MockMethod<'_, io::Part>;
+            fn remove_equippable(&mut self) -> sails::client::MockMethod<'_, io::RemoveEquippable>;
+            fn remove_parts(&mut self) -> sails::client::MockMethod<'_, io::RemoveParts>;
+            fn reset_equippables(&mut self) -> sails::client::MockMethod<'_, io::ResetEquippables>;
+            fn set_equippables_to_all(
+                &mut self,
+            ) -> sails::client::MockMethod<'_, io::SetEquippablesToAll>;
+        }
+
+        impl RmrkCatalogMock for sails::client::MockService<'_, RmrkCatalogImpl> {
+            fn add_equippables(&mut self) -> sails::client::MockMethod<'_, io::AddEquippables> {
+                self.method()
+            }
+            fn add_parts(&mut self) -> sails::client::MockMethod<'_, io::AddParts> {
+                self.method()
+            }
+            fn equippable(&mut self) -> sails::client::MockMethod<'_, io::Equippable> {
+                self.method()
+            }
+            fn part(&mut self) -> sails::client::MockMethod<'_, io::Part> {
+                self.method()
+            }
+            fn remove_equippable(&mut self) -> sails::client::MockMethod<'_, io::RemoveEquippable> {
+                self.method()
+            }
+            fn remove_parts(&mut self) -> sails::client::MockMethod<'_, io::RemoveParts> {
+                self.method()
+            }
+            fn reset_equippables(&mut self) -> sails::client::MockMethod<'_, io::ResetEquippables> {
+                self.method()
+            }
+            fn set_equippables_to_all(
+                &mut self,
+            ) -> sails::client::MockMethod<'_, io::SetEquippablesToAll> {
+                self.method()
+            }
+        }
+    }
 }
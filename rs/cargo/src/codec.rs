@@ -0,0 +1,107 @@
+use anyhow::{Context, Result, anyhow};
+use sails_idl_parser_v2::{ast::IdlDoc, parse_idl};
+use sails_idl_value::{DecodedMessage, EntryKind, IdlCodec, MessageKind};
+use std::{fs, path::Path};
+
+/// Kind of the payload passed to `cargo sails decode`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PayloadKind {
+    Call,
+    Reply,
+    Error,
+    Event,
+}
+
+impl From<PayloadKind> for MessageKind {
+    fn from(kind: PayloadKind) -> Self {
+        match kind {
+            PayloadKind::Call => MessageKind::Call,
+            PayloadKind::Reply => MessageKind::Reply,
+            PayloadKind::Error => MessageKind::Error,
+            PayloadKind::Event => MessageKind::Event,
+        }
+    }
+}
+
+/// Loads and parses an IDL from an `.idl` file or from the custom section of a `.wasm` binary.
+pub fn load_idl(path: &Path) -> Result<IdlDoc> {
+    let text = if path.extension().is_some_and(|ext| ext == "wasm") {
+        sails_idl_embed::extract_idl_from_file(path)?
+            .ok_or_else(|| anyhow!("no sails:idl section found in {}", path.display()))?
+    } else {
+        fs::read_to_string(path)
+            .with_context(|| format!("failed to read IDL from {}", path.display()))?
+    };
+    parse_idl(&text).with_context(|| format!("failed to parse IDL from {}", path.display()))
+}
+
+/// Decodes a hex-encoded `payload` against the IDL at `idl_path` and prints the result.
+pub fn decode(idl_path: &Path, kind: PayloadKind, payload: &str, json: bool) -> Result<()> {
+    let doc = load_idl(idl_path)?;
+    let payload = parse_hex(payload)?;
+    let message = IdlCodec::new(&doc).decode(kind.into(), &payload)?;
+    if json {
+        println!("{}", serde_json::to_string_pretty(&message.to_json())?);
+    } else {
+        println!("{}", format_message(&message));
+    }
+    Ok(())
+}
+
+/// Prepends the message header of `target` to hex-encoded SCALE `args` and prints the payload.
+///
+/// `target` is either `Route/Func` for a service function or a constructor name.
+/// `args` are validated by decoding them against the parameters of the target.
+pub fn encode(idl_path: &Path, target: &str, args: &str) -> Result<()> {
+    let doc = load_idl(idl_path)?;
+    let codec = IdlCodec::new(&doc);
+    let header = match target.split_once('/') {
+        Some((route, func)) => codec.call_header(route, func)?,
+        None => codec.ctor_header(target)?,
+    };
+    let mut payload = header.to_bytes();
+    payload.extend(parse_hex(args)?);
+    codec
+        .decode_call(&payload)
+        .with_context(|| format!("arguments do not match `{target}`"))?;
+    println!("0x{}", hex::encode(payload));
+    Ok(())
+}
+
+/// Encodes a call of `target` with JSON `args` and prints the hex-encoded payload.
+///
+/// `target` is either `Route/Func` for a service function or a constructor name.
+pub fn encode_json(idl_path: &Path, target: &str, args: &str) -> Result<()> {
+    let doc = load_idl(idl_path)?;
+    let codec = IdlCodec::new(&doc);
+    let args: serde_json::Value = serde_json::from_str(args).context("invalid JSON arguments")?;
+    let payload = match target.split_once('/') {
+        Some((route, func)) => codec.encode_call_json(route, func, &args)?,
+        None => codec.encode_ctor_json(target, &args)?,
+    };
+    println!("0x{}", hex::encode(payload));
+    Ok(())
+}
+
+fn parse_hex(s: &str) -> Result<Vec<u8>> {
+    let s = s.trim();
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    hex::decode(s).context("invalid hex string")
+}
+
+fn format_message(message: &DecodedMessage) -> String {
+    let kind = match message.entry_kind {
+        EntryKind::Ctor => "constructor",
+        EntryKind::Command => "command",
+        EntryKind::Query => "query",
+        EntryKind::Event => "event",
+    };
+    let path = match (&message.route, &message.service) {
+        (Some(route), Some(service)) if route != service => {
+            format!("{route}/{} ({service})", message.name)
+        }
+        (Some(scope), _) | (None, Some(scope)) => format!("{scope}/{}", message.name),
+        (None, None) => message.name.clone(),
+    };
+    format!("{kind} {path}: {}", message.value)
+}
@@ -0,0 +1,29 @@
+use crate::codec::load_idl;
+use anyhow::{Result, bail};
+use sails_idl_parser_v2::diff::{Severity, diff_idl};
+use std::path::Path;
+
+/// Compares two IDLs and prints the changes, failing if any of them is breaking.
+///
+/// Both paths may point to `.idl` files or to WASM binaries with embedded IDL.
+pub fn idl_diff(old_path: &Path, new_path: &Path, allow_breaking: bool) -> Result<()> {
+    let old = load_idl(old_path)?;
+    let new = load_idl(new_path)?;
+    let diff = diff_idl(&old, &new);
+    if diff.is_empty() {
+        println!("No changes");
+        return Ok(());
+    }
+    for change in &diff.changes {
+        let severity = match change.severity() {
+            Severity::Breaking => "breaking",
+            Severity::Compatible => "compatible",
+        };
+        println!("[{severity}] {change}");
+    }
+    let breaking = diff.breaking().count();
+    if breaking > 0 && !allow_breaking {
+        bail!("{breaking} breaking change(s) found");
+    }
+    Ok(())
+}
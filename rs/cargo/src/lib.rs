@@ -1,4 +1,8 @@
+pub mod codec;
+pub mod idl_diff;
 pub mod idlgen;
+pub mod local_env;
 pub mod program_new;
+pub mod repl;
 pub mod solgen;
 pub(crate) mod utils;
@@ -0,0 +1,404 @@
+use crate::codec::load_idl;
+use anyhow::{Context, Result, anyhow, bail};
+use futures::{FutureExt as _, Stream, StreamExt as _, channel::oneshot, executor::block_on};
+use sails::{
+    ActorId, CodeId, MessageId, ValueUnit,
+    client::{
+        BlockRunMode, DEFAULT_USER_ALICE, DEFAULT_USERS_INITIAL_BALANCE, ErrorReplyReason,
+        GtestEnv, GtestError, GtestParams, Listener as _, SimpleExecutionError,
+    },
+    gtest::System,
+};
+use sails_idl_parser_v2::ast::IdlDoc;
+use sails_idl_value::{DecodedMessage, IdlCodec, Value};
+use std::{
+    fmt, fs, mem,
+    path::{Path, PathBuf},
+};
+
+/// Program to deploy for `cargo sails call` and `cargo sails query`.
+#[derive(Debug, clap::Args)]
+pub struct LocalProgramArgs {
+    /// Path to the program WASM
+    #[arg(value_hint = clap::ValueHint::FilePath)]
+    pub wasm: PathBuf,
+    /// Path to the IDL file, if the IDL is not embedded into the WASM
+    #[arg(long, value_hint = clap::ValueHint::FilePath)]
+    pub idl: Option<PathBuf>,
+    /// Constructor to call, defaults to the first constructor declared in the IDL
+    #[arg(long)]
+    pub ctor: Option<String>,
+    /// Constructor arguments as a JSON array or object
+    #[arg(long, default_value = "[]")]
+    pub ctor_args: String,
+}
+
+type EventStream = Box<dyn Stream<Item = (ActorId, Vec<u8>)> + Unpin>;
+
+/// Program deployed into a [`LocalEnv`] together with its IDL.
+pub struct LocalProgram {
+    pub id: ActorId,
+    pub code_id: CodeId,
+    pub doc: IdlDoc,
+}
+
+/// Outcome of a constructor, command or query sent to a [`LocalProgram`].
+pub enum Reply {
+    /// Successful reply decoded against the IDL.
+    Ok(Value),
+    /// `throws` error decoded against the IDL.
+    Error(DecodedMessage),
+    /// Program panicked or the message failed for another reason.
+    Failed(String),
+    /// Reply has not been produced yet, e.g. in [`BlockRunMode::Manual`].
+    /// It is returned by [`LocalEnv::take_replies`] once the blocks producing it are run.
+    Pending(MessageId),
+}
+
+/// Event emitted by a program, decoded against the IDL of its source when possible.
+pub struct Event {
+    pub source: ActorId,
+    pub message: Result<DecodedMessage, Vec<u8>>,
+}
+
+/// In-process `gtest` environment for deploying and calling programs by their IDL.
+pub struct LocalEnv {
+    env: GtestEnv,
+    events: EventStream,
+    programs: Vec<LocalProgram>,
+    pending: Vec<PendingReply>,
+}
+
+struct PendingReply {
+    message_id: MessageId,
+    program: ActorId,
+    receiver: oneshot::Receiver<Result<Vec<u8>, GtestError>>,
+}
+
+impl LocalEnv {
+    /// Creates a new environment with `DEFAULT_USER_ALICE` as the sender.
+    pub fn new() -> Result<Self> {
+        let system = System::new();
+        system.mint_to(DEFAULT_USER_ALICE, DEFAULT_USERS_INITIAL_BALANCE);
+        let env = GtestEnv::new(system, DEFAULT_USER_ALICE.into());
+        let events = block_on(env.listen(Some))?;
+        Ok(Self {
+            env,
+            events: Box::new(events),
+            programs: Vec::new(),
+            pending: Vec::new(),
+        })
+    }
+
+    pub fn env(&self) -> &GtestEnv {
+        &self.env
+    }
+
+    /// Sets the sender of subsequent messages.
+    pub fn set_actor_id(&mut self, actor_id: ActorId) {
+        self.env = self.env.clone().with_actor_id(actor_id);
+    }
+
+    pub fn set_block_run_mode(&mut self, block_run_mode: BlockRunMode) {
+        self.env = self.env.clone().with_block_run_mode(block_run_mode);
+    }
+
+    /// Runs `count` blocks, producing pending replies and events.
+    pub fn run_blocks(&self, count: u32) {
+        for _ in 0..count {
+            self.env.run_next_block();
+        }
+    }
+
+    pub fn programs(&self) -> &[LocalProgram] {
+        &self.programs
+    }
+
+    pub fn program(&self, id: ActorId) -> Result<&LocalProgram> {
+        self.programs
+            .iter()
+            .find(|program| program.id == id)
+            .ok_or_else(|| anyhow!("program {id} is not deployed"))
+    }
+
+    /// Uploads the program code from `wasm` and creates a program calling constructor `ctor`.
+    ///
+    /// The IDL is read from `idl` or extracted from `wasm`. When `ctor` is `None`,
+    /// the first constructor declared in the IDL is used.
+    pub fn deploy(
+        &mut self,
+        wasm: &Path,
+        idl: Option<&Path>,
+        ctor: Option<&str>,
+        args: &serde_json::Value,
+        value: Option<ValueUnit>,
+    ) -> Result<(ActorId, Reply)> {
+        let doc = load_idl(idl.unwrap_or(wasm))?;
+        let codec = IdlCodec::new(&doc);
+        let ctor = match ctor {
+            Some(ctor) => ctor.to_string(),
+            None => doc
+                .program
+                .as_ref()
+                .and_then(|program| program.ctors.first())
+                .map(|ctor| ctor.name.clone())
+                .ok_or_else(|| anyhow!("IDL declares no constructors"))?,
+        };
+        let payload = codec.encode_ctor_json(&ctor, args)?;
+        let code = fs::read(wasm)
+            .with_context(|| format!("failed to read WASM from {}", wasm.display()))?;
+        // `gtest` panics on invalid code, so reject files that are not WASM at all beforehand.
+        if !code.starts_with(b"\0asm") {
+            bail!("{} is not a WASM binary", wasm.display());
+        }
+        let code_id = self.env.system().submit_code(code);
+        let salt = (self.programs.len() as u64).to_le_bytes();
+        let (id, message_id) =
+            self.env
+                .create_program(code_id, salt, payload, params(self.env.actor_id(), value))?;
+        self.programs.push(LocalProgram { id, code_id, doc });
+        let reply = self.wait_reply(id, message_id);
+        if matches!(reply, Reply::Error(_) | Reply::Failed(_)) {
+            // The program was not initialized, so it cannot be called.
+            self.programs.pop();
+        }
+        Ok((id, reply))
+    }
+
+    /// Sends a command `Route/Func` with JSON `args` to `program` and waits for the reply
+    /// according to the block run mode.
+    pub fn call(
+        &mut self,
+        program: ActorId,
+        target: &str,
+        args: &serde_json::Value,
+        value: Option<ValueUnit>,
+    ) -> Result<Reply> {
+        let program = self.program(program)?;
+        let payload = encode_target(&IdlCodec::new(&program.doc), target, args)?;
+        let message_id =
+            self.env
+                .send_one_way(program.id, payload, params(self.env.actor_id(), value))?;
+        Ok(self.wait_reply(program.id, message_id))
+    }
+
+    /// Calculates the reply of `Route/Func` with JSON `args` without changing the program state.
+    pub fn query(&self, program: ActorId, target: &str, args: &serde_json::Value) -> Result<Reply> {
+        let program = self.program(program)?;
+        let codec = IdlCodec::new(&program.doc);
+        let payload = encode_target(&codec, target, args)?;
+        let reply = self
+            .env
+            .query(program.id, payload, params(self.env.actor_id(), None));
+        Ok(decode_reply(&codec, reply))
+    }
+
+    /// Returns replies produced for [`Reply::Pending`] messages since the previous call.
+    pub fn take_replies(&mut self) -> Vec<(MessageId, Reply)> {
+        let mut replies = Vec::new();
+        for mut pending in mem::take(&mut self.pending) {
+            let reply = match pending.receiver.try_recv() {
+                Ok(Some(reply)) => reply,
+                Ok(None) => {
+                    self.pending.push(pending);
+                    continue;
+                }
+                Err(_) => Err(GtestError::ReplyIsMissing),
+            };
+            replies.push((
+                pending.message_id,
+                self.decode_reply(pending.program, reply),
+            ));
+        }
+        replies
+    }
+
+    /// Returns events emitted since the previous call.
+    pub fn take_events(&mut self) -> Vec<Event> {
+        let mut events = Vec::new();
+        while let Some(Some((source, payload))) = self.events.next().now_or_never() {
+            let message = match self.programs.iter().find(|p| p.id == source) {
+                Some(program) => IdlCodec::new(&program.doc)
+                    .decode_event(&payload)
+                    .map_err(|_| payload),
+                None => Err(payload),
+            };
+            events.push(Event { source, message });
+        }
+        events
+    }
+
+    fn wait_reply(&mut self, program: ActorId, message_id: MessageId) -> Reply {
+        let mut receiver = self.env.message_reply_from_next_blocks(message_id);
+        let reply = match receiver.try_recv() {
+            Ok(Some(reply)) => reply,
+            Ok(None) => {
+                self.pending.push(PendingReply {
+                    message_id,
+                    program,
+                    receiver,
+                });
+                return Reply::Pending(message_id);
+            }
+            Err(_) => Err(GtestError::ReplyIsMissing),
+        };
+        self.decode_reply(program, reply)
+    }
+
+    fn decode_reply(&self, program: ActorId, reply: Result<Vec<u8>, GtestError>) -> Reply {
+        match self.program(program) {
+            Ok(program) => decode_reply(&IdlCodec::new(&program.doc), reply),
+            Err(err) => Reply::Failed(err.to_string()),
+        }
+    }
+}
+
+fn params(actor_id: ActorId, value: Option<ValueUnit>) -> GtestParams {
+    let params = GtestParams::default().with_actor_id(actor_id);
+    match value {
+        Some(value) => params.with_value(value),
+        None => params,
+    }
+}
+
+fn encode_target(codec: &IdlCodec<'_>, target: &str, args: &serde_json::Value) -> Result<Vec<u8>> {
+    let Some((route, func)) = target.split_once('/') else {
+        bail!("expected `Route/Func`, got `{target}`");
+    };
+    Ok(codec.encode_call_json(route, func, args)?)
+}
+
+fn decode_reply(codec: &IdlCodec<'_>, reply: Result<Vec<u8>, GtestError>) -> Reply {
+    match reply {
+        // Functions returning `()` reply with an empty payload.
+        Ok(payload) if payload.is_empty() => Reply::Ok(Value::Unit),
+        Ok(payload) => match codec.decode_reply(&payload) {
+            Ok(message) => Reply::Ok(message.value),
+            Err(err) => Reply::Failed(format!(
+                "failed to decode reply 0x{}: {err}",
+                hex::encode(payload)
+            )),
+        },
+        Err(GtestError::ReplyHasError(
+            reason @ ErrorReplyReason::Execution(SimpleExecutionError::UserspacePanic),
+            payload,
+        )) => match codec.decode_error(&payload) {
+            Ok(message) => Reply::Error(message),
+            Err(_) => Reply::Failed(format!("{reason}: {}", String::from_utf8_lossy(&payload))),
+        },
+        Err(err) => Reply::Failed(err.to_string()),
+    }
+}
+
+impl fmt::Display for Reply {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Reply::Ok(value) => write!(f, "Ok: {value}"),
+            Reply::Error(message) => write!(f, "Error: {}", message.value),
+            Reply::Failed(reason) => write!(f, "Failed: {reason}"),
+            Reply::Pending(message_id) => write!(f, "Pending: {message_id}"),
+        }
+    }
+}
+
+impl Reply {
+    pub fn to_json(&self) -> serde_json::Value {
+        match self {
+            Reply::Ok(value) => serde_json::json!({ "ok": value.to_json() }),
+            Reply::Error(message) => serde_json::json!({ "err": message.value.to_json() }),
+            Reply::Failed(reason) => serde_json::json!({ "failed": reason }),
+            Reply::Pending(message_id) => serde_json::json!({ "pending": message_id.to_string() }),
+        }
+    }
+}
+
+impl fmt::Display for Event {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.message {
+            Ok(message) => write!(
+                f,
+                "{}/{}: {}",
+                message
+                    .route
+                    .as_deref()
+                    .or(message.service.as_deref())
+                    .unwrap_or("-"),
+                message.name,
+                message.value
+            ),
+            Err(payload) => write!(f, "0x{} from {}", hex::encode(payload), self.source),
+        }
+    }
+}
+
+impl Event {
+    pub fn to_json(&self) -> serde_json::Value {
+        match &self.message {
+            Ok(message) => message.to_json(),
+            Err(payload) => serde_json::json!({
+                "source": self.source.to_string(),
+                "payload": format!("0x{}", hex::encode(payload)),
+            }),
+        }
+    }
+}
+
+/// Deploys a program into a fresh [`LocalEnv`], sends `target` with JSON `args`
+/// as a command, or as a query if `query` is set, and prints the reply and emitted events.
+pub fn run(
+    program: &LocalProgramArgs,
+    target: &str,
+    args: &str,
+    value: Option<ValueUnit>,
+    query: bool,
+    json: bool,
+) -> Result<()> {
+    let ctor_args = parse_json_args(&program.ctor_args)?;
+    let args = parse_json_args(args)?;
+    let mut env = LocalEnv::new()?;
+    let (id, ctor_reply) = env.deploy(
+        &program.wasm,
+        program.idl.as_deref(),
+        program.ctor.as_deref(),
+        &ctor_args,
+        None,
+    )?;
+    let mut events = env.take_events();
+    if !matches!(ctor_reply, Reply::Ok(_)) {
+        print_result(id, &ctor_reply, &events, json)?;
+        bail!("program initialization failed");
+    }
+    let reply = if query {
+        env.query(id, target, &args)?
+    } else {
+        env.call(id, target, &args, value)?
+    };
+    events.extend(env.take_events());
+    print_result(id, &reply, &events, json)
+}
+
+/// Parses JSON arguments, treating an empty string as no arguments.
+pub fn parse_json_args(args: &str) -> Result<serde_json::Value> {
+    if args.trim().is_empty() {
+        return Ok(serde_json::Value::Array(Vec::new()));
+    }
+    serde_json::from_str(args).context("invalid JSON arguments")
+}
+
+fn print_result(id: ActorId, reply: &Reply, events: &[Event], json: bool) -> Result<()> {
+    if json {
+        let result = serde_json::json!({
+            "program": id.to_string(),
+            "reply": reply.to_json(),
+            "events": events.iter().map(Event::to_json).collect::<Vec<_>>(),
+        });
+        println!("{}", serde_json::to_string_pretty(&result)?);
+    } else {
+        println!("program: {id}");
+        println!("{reply}");
+        for event in events {
+            println!("event {event}");
+        }
+    }
+    Ok(())
+}
@@ -1,7 +1,11 @@
 use cargo_sails::{
+    codec::{self, PayloadKind},
+    idl_diff::idl_diff,
     idlgen::CrateIdlGenerator,
+    local_env::{self, LocalProgramArgs},
     program_new::ProgramGenerator,
-    solgen::{SolidityGenerator, SolidityGeneratorOutputType},
+    repl::Repl,
+    solgen::{self, SolidityGenerator, SolidityGeneratorOutputType},
 };
 use clap::{Parser, Subcommand};
 use convert_case::{Case, Casing};
@@ -76,6 +80,12 @@ enum SailsCommands {
         /// Derive only necessary [`parity_scale_codec::Encode`], [`parity_scale_codec::Decode`] and [`type_info::TypeInfo`] traits for the generated types
         #[arg(long)]
         no_derive_traits: bool,
+        /// Decode replies and events leniently, tolerating appended fields and unknown variants (IDL v2 only)
+        #[arg(long, conflicts_with = "v1")]
+        lenient: bool,
+        /// Generate Ethereum ABI accessors for `ethexe` services with the `ethabi` codec (IDL v2 only)
+        #[arg(long, conflicts_with = "v1")]
+        ethabi: bool,
         /// Generate client from IDL v1
         #[arg(long)]
         v1: bool,
@@ -155,6 +165,106 @@ enum SailsCommands {
         /// Generate Forge project instead of single Solidity file
         #[arg(long)]
         forge: bool,
+        /// Generate Solidity ABI JSON instead of single Solidity file
+        #[arg(long, conflicts_with = "forge")]
+        abi_json: bool,
+    },
+
+    /// Generate IDL service from Solidity ABI JSON or interface
+    #[command(name = "sol-import")]
+    SolImport {
+        /// Path to the ABI JSON (`.json`) or Solidity interface file
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        path: PathBuf,
+        /// Name of the service, defaults to the interface name or the ABI JSON file name
+        #[arg(long, short = 'n')]
+        service_name: Option<String>,
+        /// Path to the output IDL file, defaults to the input path with the `idl` extension
+        #[arg(long, short, value_hint = clap::ValueHint::FilePath)]
+        output: Option<PathBuf>,
+    },
+
+    /// Decode a hex-encoded message payload using IDL
+    #[command(name = "decode")]
+    Decode {
+        /// Path to the IDL file or to the WASM binary with embedded IDL
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        idl_path: PathBuf,
+        /// Hex-encoded payload, including the message header
+        payload: String,
+        /// Kind of the payload
+        #[arg(long, short, value_enum, default_value = "call")]
+        kind: PayloadKind,
+        /// Print the decoded message as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Encode a call payload from hex-encoded SCALE arguments using IDL
+    #[command(name = "encode")]
+    Encode {
+        /// Path to the IDL file or to the WASM binary with embedded IDL
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        idl_path: PathBuf,
+        /// Service function as `Route/Func`, or constructor name
+        target: String,
+        /// Hex-encoded SCALE arguments, empty if the target has no parameters
+        #[arg(long, short, default_value = "", conflicts_with = "json")]
+        args: String,
+        /// Arguments as a JSON array of positional values or an object keyed by parameter names
+        #[arg(long)]
+        json: Option<String>,
+    },
+
+    /// Deploy a program into a local gtest environment and send a command to it
+    #[command(name = "call")]
+    Call {
+        #[command(flatten)]
+        program: LocalProgramArgs,
+        /// Service function as `Route/Func`
+        target: String,
+        /// Arguments as a JSON array of positional values or an object keyed by parameter names
+        #[arg(long, default_value = "[]")]
+        args: String,
+        /// Value to send with the message
+        #[arg(long)]
+        value: Option<u128>,
+        /// Print the reply and events as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Deploy a program into a local gtest environment and query it without changing its state
+    #[command(name = "query")]
+    Query {
+        #[command(flatten)]
+        program: LocalProgramArgs,
+        /// Service function as `Route/Func`
+        target: String,
+        /// Arguments as a JSON array of positional values or an object keyed by parameter names
+        #[arg(long, default_value = "[]")]
+        args: String,
+        /// Print the reply and events as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Start an interactive session with a local gtest environment
+    #[command(name = "repl")]
+    Repl,
+
+    /// Compare two IDLs and report breaking changes
+    #[command(name = "idl-diff")]
+    IdlDiff {
+        /// Path to the old IDL file or to the WASM binary with embedded IDL
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        old: PathBuf,
+        /// Path to the new IDL file or to the WASM binary with embedded IDL
+        #[arg(value_hint = clap::ValueHint::FilePath)]
+        new: PathBuf,
+        /// Report breaking changes without failing
+        #[arg(long)]
+        allow_breaking: bool,
     },
 }
 
@@ -173,7 +283,15 @@ fn parse_key_val<T, U>(s: &str) -> Result<(T, U), Box<dyn Error + Send + Sync +
 }
 
 fn should_print_banner(command: &SailsCommands) -> bool {
-    !matches!(command, SailsCommands::IdlExtract { output: None, .. })
+    !matches!(
+        command,
+        SailsCommands::IdlExtract { output: None, .. }
+            | SailsCommands::Decode { .. }
+            | SailsCommands::Encode { .. }
+            | SailsCommands::Call { json: true, .. }
+            | SailsCommands::Query { json: true, .. }
+            | SailsCommands::IdlDiff { .. }
+    )
 }
 
 fn format_client_rs_config(
@@ -232,6 +350,8 @@ fn main() -> Result<(), i32> {
             sails_crate,
             external_types,
             no_derive_traits,
+            lenient,
+            ethabi,
             v1,
         } => {
             let out_path = out_path.unwrap_or_else(|| idl_path.with_extension("rs"));
@@ -282,6 +402,12 @@ fn main() -> Result<(), i32> {
                 if no_derive_traits {
                     client_gen = client_gen.with_no_derive_traits();
                 }
+                if lenient {
+                    client_gen = client_gen.with_lenient_decoding();
+                }
+                if ethabi {
+                    client_gen = client_gen.with_ethabi();
+                }
                 client_gen.generate_to(out_path)
             }
         }
@@ -341,9 +467,12 @@ fn main() -> Result<(), i32> {
             username,
             offline,
             forge,
+            abi_json,
         } => {
             let output_type = if forge {
                 SolidityGeneratorOutputType::ForgeProject
+            } else if abi_json {
+                SolidityGeneratorOutputType::AbiJson
             } else {
                 SolidityGeneratorOutputType::SingleSolidity
             };
@@ -358,6 +487,48 @@ fn main() -> Result<(), i32> {
             )
             .generate()
         }
+        SailsCommands::SolImport {
+            path,
+            service_name,
+            output,
+        } => solgen::import_solidity(&path, service_name.as_deref(), output),
+        SailsCommands::Decode {
+            idl_path,
+            payload,
+            kind,
+            json,
+        } => codec::decode(&idl_path, kind, &payload, json),
+        SailsCommands::Encode {
+            idl_path,
+            target,
+            args,
+            json: None,
+        } => codec::encode(&idl_path, &target, &args),
+        SailsCommands::Encode {
+            idl_path,
+            target,
+            json: Some(json),
+            ..
+        } => codec::encode_json(&idl_path, &target, &json),
+        SailsCommands::Call {
+            program,
+            target,
+            args,
+            value,
+            json,
+        } => local_env::run(&program, &target, &args, value, false, json),
+        SailsCommands::Query {
+            program,
+            target,
+            args,
+            json,
+        } => local_env::run(&program, &target, &args, None, true, json),
+        SailsCommands::Repl => Repl::new().and_then(Repl::run),
+        SailsCommands::IdlDiff {
+            old,
+            new,
+            allow_breaking,
+        } => idl_diff(&old, &new, allow_breaking),
     };
 
     if let Err(e) = result {
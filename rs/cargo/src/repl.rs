@@ -0,0 +1,289 @@
+use crate::local_env::{LocalEnv, Reply, parse_json_args};
+use anyhow::{Context, Result, anyhow, bail};
+use sails::{
+    ActorId,
+    client::{
+        BlockRunMode, DEFAULT_USER_ALICE, DEFAULT_USER_BOB, DEFAULT_USER_CHARLIE, DEFAULT_USER_EVE,
+        EPOCH_DURATION_IN_BLOCKS,
+    },
+};
+use std::{
+    io::{self, BufRead, Write},
+    path::Path,
+};
+
+const PROMPT: &str = "sails> ";
+const HELP: &str = "\
+Commands:
+  deploy <wasm> [--idl <path>] [--ctor <name>] [--value <value>] [args]
+                             deploy a program and make it current
+  call <Route/Func> [--value <value>] [args]
+                             send a command to the current program
+  query <Route/Func> [args]  query the current program without changing its state
+  use <index|id>             switch the current program
+  programs                   list deployed programs
+  actor [alice|bob|charlie|eve|<id>]
+                             show or switch the sender of messages
+  mode [auto|next|manual|upto <blocks>]
+                             show or switch the block run mode
+  run [blocks]               run blocks, 1 by default
+  balance [actor|index]      show the balance of an actor or program, the sender by default
+  mint <actor> <value>       mint value to an actor
+  events                     show events emitted since the last command
+  help                       show this help
+  exit                       quit the session
+
+`args` are JSON: an array of positional values or an object keyed by parameter names.
+Replies and events are printed after every command.";
+
+/// Interactive session keeping a [`LocalEnv`] alive across commands.
+pub struct Repl {
+    env: LocalEnv,
+    current: Option<ActorId>,
+    block_run_mode: BlockRunMode,
+}
+
+impl Repl {
+    pub fn new() -> Result<Self> {
+        Ok(Self {
+            env: LocalEnv::new()?,
+            current: None,
+            block_run_mode: BlockRunMode::UpTo(EPOCH_DURATION_IN_BLOCKS),
+        })
+    }
+
+    /// Reads commands from stdin until `exit` or the end of input.
+    pub fn run(mut self) -> Result<()> {
+        println!("Type `help` for the list of commands.");
+        let mut lines = io::stdin().lock().lines();
+        loop {
+            print!("{PROMPT}");
+            io::stdout().flush()?;
+            let Some(line) = lines.next().transpose()? else {
+                println!();
+                return Ok(());
+            };
+            match self.execute(&line) {
+                Ok(true) => {}
+                Ok(false) => return Ok(()),
+                Err(err) => eprintln!("Error: {err:#}"),
+            }
+            self.print_updates();
+        }
+    }
+
+    /// Executes a single command line, returns `false` if the session should end.
+    pub fn execute(&mut self, line: &str) -> Result<bool> {
+        let (words, args) = split_args(line);
+        let Some((&command, words)) = words.split_first() else {
+            return Ok(true);
+        };
+        let mut options = Options::parse(words)?;
+        match command {
+            "deploy" => {
+                let wasm = options.positional(0, "WASM path")?;
+                let args = parse_json_args(args)?;
+                let (id, reply) = self.env.deploy(
+                    Path::new(wasm),
+                    options.take("--idl").map(Path::new),
+                    options.take("--ctor"),
+                    &args,
+                    options.value()?,
+                )?;
+                println!("program: {id}");
+                println!("{reply}");
+                if !matches!(reply, Reply::Error(_) | Reply::Failed(_)) {
+                    self.current = Some(id);
+                }
+            }
+            "call" => {
+                let target = options.positional(0, "`Route/Func`")?;
+                let args = parse_json_args(args)?;
+                let reply = self
+                    .env
+                    .call(self.current()?, target, &args, options.value()?)?;
+                println!("{reply}");
+            }
+            "query" => {
+                let target = options.positional(0, "`Route/Func`")?;
+                let args = parse_json_args(args)?;
+                println!("{}", self.env.query(self.current()?, target, &args)?);
+            }
+            "use" => {
+                let id = self.program_id(options.positional(0, "program")?)?;
+                self.current = Some(id);
+            }
+            "programs" => {
+                for (idx, program) in self.env.programs().iter().enumerate() {
+                    let marker = if Some(program.id) == self.current {
+                        '*'
+                    } else {
+                        ' '
+                    };
+                    let name = program
+                        .doc
+                        .program
+                        .as_ref()
+                        .map_or("-", |p| p.name.as_str());
+                    println!("{marker} {idx}: {} {name}", program.id);
+                }
+            }
+            "actor" => {
+                if let Ok(actor) = options.positional(0, "actor") {
+                    let actor_id = parse_actor(actor)?;
+                    self.env.set_actor_id(actor_id);
+                }
+                println!("actor: {}", self.env.env().actor_id());
+            }
+            "mode" => {
+                if let Ok(mode) = options.positional(0, "mode") {
+                    self.block_run_mode = match mode {
+                        "auto" => BlockRunMode::Auto,
+                        "next" => BlockRunMode::Next,
+                        "manual" => BlockRunMode::Manual,
+                        "upto" => BlockRunMode::UpTo(
+                            options
+                                .positional(1, "block count")?
+                                .parse()
+                                .context("invalid block count")?,
+                        ),
+                        mode => bail!("unknown block run mode `{mode}`"),
+                    };
+                    self.env.set_block_run_mode(self.block_run_mode.clone());
+                }
+                println!("mode: {:?}", self.block_run_mode);
+            }
+            "run" => {
+                let count = match options.positional(0, "block count") {
+                    Ok(count) => count.parse().context("invalid block count")?,
+                    Err(_) => 1,
+                };
+                self.env.run_blocks(count);
+                println!("block: {}", self.env.env().system().block_height());
+            }
+            "balance" => {
+                let actor_id = match options.positional(0, "actor") {
+                    Ok(actor) => self.program_id(actor).or_else(|_| parse_actor(actor))?,
+                    Err(_) => self.env.env().actor_id(),
+                };
+                println!(
+                    "balance of {actor_id}: {}",
+                    self.env.env().system().balance_of(actor_id)
+                );
+            }
+            "mint" => {
+                let actor_id = parse_actor(options.positional(0, "actor")?)?;
+                let value = options
+                    .positional(1, "value")?
+                    .parse()
+                    .context("invalid value")?;
+                self.env.env().system().mint_to(actor_id, value);
+            }
+            // Events are printed after every command.
+            "events" => {}
+            "help" => println!("{HELP}"),
+            "exit" | "quit" => return Ok(false),
+            command => bail!("unknown command `{command}`, type `help` for the list of commands"),
+        }
+        Ok(true)
+    }
+
+    fn print_updates(&mut self) {
+        for (message_id, reply) in self.env.take_replies() {
+            println!("reply to {message_id}: {reply}");
+        }
+        for event in self.env.take_events() {
+            println!("event {event}");
+        }
+    }
+
+    fn current(&self) -> Result<ActorId> {
+        self.current
+            .ok_or_else(|| anyhow!("no program selected, use `deploy` or `use` first"))
+    }
+
+    /// Resolves a program by its index in `programs` or by its ID.
+    fn program_id(&self, program: &str) -> Result<ActorId> {
+        let id = match program.parse::<usize>() {
+            Ok(idx) => self
+                .env
+                .programs()
+                .get(idx)
+                .map(|program| program.id)
+                .ok_or_else(|| anyhow!("no program with index {idx}"))?,
+            Err(_) => parse_actor(program)?,
+        };
+        self.env.program(id)?;
+        Ok(id)
+    }
+}
+
+/// Positional words and `--name value` options of a command.
+struct Options<'a> {
+    positional: Vec<&'a str>,
+    named: Vec<(&'a str, &'a str)>,
+}
+
+impl<'a> Options<'a> {
+    fn parse(words: &[&'a str]) -> Result<Self> {
+        let mut options = Self {
+            positional: Vec::new(),
+            named: Vec::new(),
+        };
+        let mut words = words.iter();
+        while let Some(&word) = words.next() {
+            if word.starts_with("--") {
+                let value = words
+                    .next()
+                    .ok_or_else(|| anyhow!("missing value for `{word}`"))?;
+                options.named.push((word, value));
+            } else {
+                options.positional.push(word);
+            }
+        }
+        Ok(options)
+    }
+
+    fn positional(&self, idx: usize, what: &str) -> Result<&'a str> {
+        self.positional
+            .get(idx)
+            .copied()
+            .ok_or_else(|| anyhow!("missing {what}"))
+    }
+
+    fn take(&mut self, name: &str) -> Option<&'a str> {
+        let idx = self.named.iter().position(|(n, _)| *n == name)?;
+        Some(self.named.remove(idx).1)
+    }
+
+    fn value(&mut self) -> Result<Option<u128>> {
+        self.take("--value")
+            .map(|value| value.parse().context("invalid value"))
+            .transpose()
+    }
+}
+
+/// Splits a command line into words and the trailing JSON arguments,
+/// which start at the first word beginning with `[`, `{` or `null`.
+fn split_args(line: &str) -> (Vec<&str>, &str) {
+    let line = line.trim();
+    let json_start = line
+        .char_indices()
+        .filter(|&(idx, _)| idx == 0 || line[..idx].ends_with(char::is_whitespace))
+        .find(|&(idx, c)| c == '[' || c == '{' || line[idx..].starts_with("null"))
+        .map_or(line.len(), |(idx, _)| idx);
+    let (words, args) = line.split_at(json_start);
+    (words.split_whitespace().collect(), args)
+}
+
+fn parse_actor(actor: &str) -> Result<ActorId> {
+    Ok(match actor {
+        "alice" => DEFAULT_USER_ALICE.into(),
+        "bob" => DEFAULT_USER_BOB.into(),
+        "charlie" => DEFAULT_USER_CHARLIE.into(),
+        "eve" => DEFAULT_USER_EVE.into(),
+        actor => actor
+            .parse()
+            .map_err(|err| anyhow!("invalid actor `{actor}`: {err:?}"))?,
+    })
+}
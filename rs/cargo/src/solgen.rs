@@ -113,6 +113,7 @@ struct RootRemappings {
 pub enum SolidityGeneratorOutputType {
     SingleSolidity,
     ForgeProject,
+    AbiJson,
 }
 
 pub struct SolidityGenerator {
@@ -259,6 +260,9 @@ pub fn generate(self) -> Result<()> {
             SolidityGeneratorOutputType::ForgeProject => {
                 self.generate_forge_project(&idl_content, &contract_name.to_case(Case::Kebab))?
             }
+            SolidityGeneratorOutputType::AbiJson => {
+                self.generate_abi_json(&idl_content, &contract_name.to_case(Case::Pascal))?
+            }
         }
 
         Ok(())
@@ -287,6 +291,9 @@ fn print_config(&self, contract_name: &str) {
             SolidityGeneratorOutputType::ForgeProject => {
                 print_field("output:", &"Forge project");
             }
+            SolidityGeneratorOutputType::AbiJson => {
+                print_field("output:", &"ABI JSON file");
+            }
         }
     }
 
@@ -308,6 +315,20 @@ fn generate_single_solidity(&self, idl_content: &str, contract_name: &str) -> Re
         Ok(())
     }
 
+    fn generate_abi_json(&self, idl_content: &str, contract_name: &str) -> Result<()> {
+        let data = sails_sol_gen::generate_abi_json(idl_content)?;
+        let target_file = self.target_dir.join(format!("{contract_name}.abi.json"));
+
+        fs::write(&target_file, data)?;
+
+        println!(
+            "Generated Solidity ABI JSON file: {target_file}",
+            target_file = target_file.display()
+        );
+
+        Ok(())
+    }
+
     fn generate_forge_project(
         &self,
         idl_content: &str,
@@ -465,6 +486,36 @@ fn fmt<P: AsRef<Path>>(&self, project_dir: P) -> Result<()> {
     }
 }
 
+/// Generates the IDL of the service declared by the Solidity ABI JSON or interface at `path`.
+pub fn import_solidity(
+    path: &Path,
+    service_name: Option<&str>,
+    output: Option<PathBuf>,
+) -> Result<()> {
+    let content = fs::read_to_string(path)?;
+    let service = if path.extension().is_some_and(|ext| ext == "json") {
+        let file_name = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.split('.').next())
+            .context("No filename found")?;
+        sails_sol_gen::service_from_abi_json(
+            &service_name.unwrap_or(file_name).to_case(Case::Pascal),
+            &content,
+        )?
+    } else {
+        sails_sol_gen::service_from_interface(&content, service_name)?
+    };
+    let idl = sails_sol_gen::generate_service_idl(service)?;
+    let output = output.unwrap_or_else(|| path.with_extension("idl"));
+
+    fs::write(&output, idl)?;
+
+    println!("Generated IDL file: {}", output.display());
+
+    Ok(())
+}
+
 fn forge_soldeer_install<P: AsRef<Path>>(dir: P) -> Result<()> {
     let forge_command = forge_command();
     let mut cmd = Command::new(forge_command);
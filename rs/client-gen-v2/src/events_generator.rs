@@ -1,20 +1,27 @@
 use genco::prelude::*;
-use sails_idl_ast::codec::has_scale_codec;
+use sails_idl_ast::codec::{has_ethabi_codec, has_scale_codec};
 use sails_idl_parser_v2::{ast, visitor, visitor::Visitor};
 
 use crate::helpers::generate_doc_comments;
+use crate::type_generators::UNKNOWN_VARIANT;
 
 pub(crate) struct EventsModuleGenerator<'ast> {
     service_name: &'ast str,
     sails_path: &'ast str,
+    lenient_decoding: bool,
     tokens: rust::Tokens,
 }
 
 impl<'ast> EventsModuleGenerator<'ast> {
-    pub(crate) fn new(service_name: &'ast str, sails_path: &'ast str) -> Self {
+    pub(crate) fn new(
+        service_name: &'ast str,
+        sails_path: &'ast str,
+        lenient_decoding: bool,
+    ) -> Self {
         Self {
             service_name,
             sails_path,
+            lenient_decoding,
             tokens: rust::Tokens::new(),
         }
     }
@@ -36,13 +43,23 @@ fn visit_service_unit(&mut self, service: &'ast ast::ServiceUnit) {
         }
 
         let events_name = &format!("{}Events", self.service_name);
+        // Events declaring their own `Unknown` variant are decoded strictly.
+        let lenient = self.lenient_decoding
+            && !scale_events
+                .iter()
+                .any(|event| event.name == UNKNOWN_VARIANT);
+        let (sails_type_args, decode_event_fn) = if lenient {
+            (", lenient", "decode_event_v2_lenient")
+        } else {
+            ("", "decode_event_v2")
+        };
 
         quote_in! { self.tokens =>
             $['\n']
             #[cfg(not(target_arch = "wasm32"))]
             pub mod events $("{")
                 use super::*;
-                #[$(self.sails_path)::sails_type(crate = $(self.sails_path))]
+                #[$(self.sails_path)::sails_type(crate = $(self.sails_path)$sails_type_args)]
                 #[derive(PartialEq, Debug)]
                 pub enum $events_name $("{")
         };
@@ -59,6 +76,10 @@ pub fn entry_id(&self) -> u16 {
                         $(for event in &scale_events join ($['\r']) =>
                             Self::$(&event.name) { .. } => $(event.entry_id),
                         )
+                        $(if lenient {
+                            $['\r']
+                            Self::Unknown(bytes) => bytes.first().copied().unwrap_or_default().into(),
+                        })
                     }
                 }
             }
@@ -68,7 +89,7 @@ fn decode_event(
                     route: &$(self.sails_path)::client::RouteIdx,
                     payload: impl AsRef<[u8]>,
                 ) -> Result<Self, $(self.sails_path)::scale_codec::Error> {
-                    $(self.sails_path)::client::decode_event_v2::<Self>(route.0, payload)
+                    $(self.sails_path)::client::$decode_event_fn::<Self>(route.0, payload)
                 }
             }
 
@@ -136,3 +157,178 @@ fn visit_service_event(&mut self, event: &'ast ast::ServiceEvent) {
         }
     }
 }
+
+/// Generates the events of the Ethereum ABI transport, decoded from `EthEvent` logs
+pub(crate) struct EthEventsModuleGenerator<'ast> {
+    service_name: &'ast str,
+    sails_path: &'ast str,
+    tokens: rust::Tokens,
+    decode_tokens: rust::Tokens,
+}
+
+impl<'ast> EthEventsModuleGenerator<'ast> {
+    pub(crate) fn new(service_name: &'ast str, sails_path: &'ast str) -> Self {
+        Self {
+            service_name,
+            sails_path,
+            tokens: rust::Tokens::new(),
+            decode_tokens: rust::Tokens::new(),
+        }
+    }
+
+    pub(crate) fn finalize(self) -> rust::Tokens {
+        self.tokens
+    }
+}
+
+impl<'ast> Visitor<'ast> for EthEventsModuleGenerator<'ast> {
+    fn visit_service_unit(&mut self, service: &'ast ast::ServiceUnit) {
+        if !service
+            .events
+            .iter()
+            .any(|event| has_ethabi_codec(&event.annotations))
+        {
+            return;
+        }
+
+        let events_name = &format!("{}Events", self.service_name);
+
+        quote_in! { self.tokens =>
+            $['\n']
+            #[cfg(not(target_arch = "wasm32"))]
+            pub mod events $("{")
+                use super::*;
+                #[derive(PartialEq, Debug)]
+                pub enum $events_name $("{")
+        };
+
+        visitor::accept_service_unit(service, self);
+
+        quote_in! { self.tokens =>
+            $['\r'] $("}")
+
+            $['\r']
+            impl $(self.sails_path)::client::Event<$(self.sails_path)::client::RouteEthabi> for $events_name {
+                #[allow(clippy::useless_conversion)]
+                fn decode_event(
+                    _route: &$(self.sails_path)::client::RouteEthabi,
+                    payload: impl AsRef<[u8]>,
+                ) -> Result<Self, $(self.sails_path)::scale_codec::Error> {
+                    let log = $(self.sails_path)::client::EthEventLog::decode(payload.as_ref())?;
+                    $(&self.decode_tokens)
+                    Err("Unknown EthEvent signature".into())
+                }
+            }
+
+            impl $(self.sails_path)::client::ServiceWithEvents<$(self.sails_path)::client::RouteEthabi> for $(self.service_name)Impl {
+                type Event = $events_name;
+            }
+        }
+
+        quote_in! { self.tokens =>
+            $['\r'] $("}")
+        };
+    }
+
+    fn visit_service_event(&mut self, event: &'ast ast::ServiceEvent) {
+        if !has_ethabi_codec(&event.annotations) {
+            return;
+        }
+
+        generate_doc_comments(&mut self.tokens, &event.docs);
+
+        let variant_name = &event.name;
+        let field_types = event
+            .def
+            .fields
+            .iter()
+            .map(|field| crate::type_generators::generate_type_decl_with_path(&field.type_decl, ""))
+            .collect::<Vec<_>>();
+        let is_indexed =
+            |field: &ast::StructField| field.annotations.iter().any(|(name, _)| name == "indexed");
+        // Topic `0` is the event signature, indexed fields follow in declaration order
+        let mut topic_idx = 0;
+        let mut data_idx = 0;
+        let mut data_types = Vec::new();
+        let field_values = event
+            .def
+            .fields
+            .iter()
+            .zip(&field_types)
+            .map(|(field, type_code)| {
+                if event.def.is_tuple() || !is_indexed(field) {
+                    data_types.push(type_code.as_str());
+                    data_idx += 1;
+                    format!("f{}.into()", data_idx - 1)
+                } else {
+                    topic_idx += 1;
+                    format!("log.topic::<{type_code}>({topic_idx})?")
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let signature_type = format!(
+            "({})",
+            field_types
+                .iter()
+                .map(|t| format!("{t},"))
+                .collect::<String>()
+        );
+        let data_pattern = (0..data_types.len())
+            .map(|idx| format!("f{idx},"))
+            .collect::<String>();
+        let data_type = data_types
+            .iter()
+            .map(|t| format!("{t},"))
+            .collect::<String>();
+
+        if event.def.is_unit() {
+            quote_in! { self.tokens =>
+                $['\r'] $variant_name,
+            };
+        } else if event.def.is_tuple() {
+            quote_in! { self.tokens =>
+                $['\r'] $variant_name($(for t in &field_types join (, ) => $t)),
+            };
+        } else {
+            let mut field_tokens = rust::Tokens::new();
+            for (field, type_code) in event.def.fields.iter().zip(&field_types) {
+                generate_doc_comments(&mut field_tokens, &field.docs);
+                let field_name = field.name.as_ref().unwrap();
+                quote_in! { field_tokens =>
+                    $['\r'] $field_name: $type_code,
+                };
+            }
+            quote_in! { self.tokens =>
+                $['\r'] $variant_name {
+                    $(field_tokens)
+                $['\r'] },
+            };
+        }
+
+        let value = if event.def.is_unit() {
+            quote!(Self::$variant_name)
+        } else if event.def.is_tuple() {
+            quote!(Self::$variant_name($(for v in &field_values join (, ) => $v)))
+        } else {
+            let fields = event
+                .def
+                .fields
+                .iter()
+                .zip(&field_values)
+                .map(|(field, value)| format!("{}: {value}", field.name.as_ref().unwrap()))
+                .collect::<Vec<_>>();
+            quote!(Self::$variant_name { $(for f in &fields join (, ) => $f) })
+        };
+
+        quote_in! { self.decode_tokens =>
+            $['\r']
+            if log.is_event::<$(&signature_type)>($(quoted(variant_name))) {
+                $(if !data_types.is_empty() {
+                    let ($(&data_pattern)) = log.data::<($(&data_type))>()?;
+                })
+                return Ok($value);
+            }
+        };
+    }
+}
@@ -20,6 +20,8 @@ pub struct ClientGenerator<'ast, S> {
     mocks_feature_name: Option<&'ast str>,
     external_types: HashMap<&'ast str, &'ast str>,
     no_derive_traits: bool,
+    lenient_decoding: bool,
+    ethabi: bool,
     with_no_std: bool,
     client_path: Option<&'ast Path>,
     idl: S,
@@ -77,6 +79,32 @@ pub fn with_no_derive_traits(self) -> Self {
         }
     }
 
+    /// Decode replies and events leniently, so the client keeps working against upgraded programs
+    ///
+    /// The `interface_id` of replies and events is not checked, appended fields of a reply or
+    /// an event are ignored, and unknown variants of enums and events are decoded into
+    /// an additional `Unknown(Vec<u8>)` variant instead of failing.
+    /// Enums declaring their own `Unknown` variant are decoded strictly.
+    pub fn with_lenient_decoding(self) -> Self {
+        Self {
+            lenient_decoding: true,
+            ..self
+        }
+    }
+
+    /// Generate the Ethereum ABI transport of `ethexe` programs alongside the SCALE one
+    ///
+    /// Each service module gets an `ethabi` module with the service trait implemented for
+    /// `Service<_, _, RouteEthabi>`, and the program trait gets a `<service>_ethabi` accessor
+    /// per service. Calls are encoded with Solidity selectors, replies and `EthEvent` logs are
+    /// ABI-decoded. The generated code requires the `ethexe` feature of `sails`.
+    pub fn with_ethabi(self) -> Self {
+        Self {
+            ethabi: true,
+            ..self
+        }
+    }
+
     pub fn with_client_path(self, client_path: &'ast Path) -> Self {
         Self {
             client_path: Some(client_path),
@@ -92,6 +120,8 @@ pub fn from_idl_path(idl_path: &'ast Path) -> Self {
             mocks_feature_name: None,
             external_types: HashMap::new(),
             no_derive_traits: false,
+            lenient_decoding: false,
+            ethabi: false,
             with_no_std: false,
             client_path: None,
             idl: IdlPath(idl_path),
@@ -133,6 +163,8 @@ fn with_idl(self, idl: &'ast str) -> ClientGenerator<'ast, IdlString<'ast>> {
             mocks_feature_name: self.mocks_feature_name,
             external_types: self.external_types,
             no_derive_traits: self.no_derive_traits,
+            lenient_decoding: self.lenient_decoding,
+            ethabi: self.ethabi,
             with_no_std: self.with_no_std,
             client_path: self.client_path,
             idl: IdlString(idl),
@@ -147,6 +179,8 @@ pub fn from_idl(idl: &'ast str) -> Self {
             mocks_feature_name: None,
             external_types: HashMap::new(),
             no_derive_traits: false,
+            lenient_decoding: false,
+            ethabi: false,
             with_no_std: false,
             client_path: None,
             idl: IdlString(idl),
@@ -161,6 +195,8 @@ pub fn generate(self) -> Result<String> {
             sails_path,
             self.external_types,
             self.no_derive_traits,
+            self.lenient_decoding,
+            self.ethabi,
         );
         let doc = parse_idl(idl).context("Failed to parse IDL")?;
         visitor::accept_idl_doc(&doc, &mut generator);
@@ -78,3 +78,78 @@ fn visit_service_func(&mut self, func: &'ast ast::ServiceFunc) {
         };
     }
 }
+
+/// Generates the accessors configuring a service of a `MockProgram`
+pub(crate) struct MockProgramGenerator<'ast> {
+    service_name: &'ast str,
+    sails_path: &'ast str,
+    trait_tokens: Tokens,
+    impl_tokens: Tokens,
+}
+
+impl<'ast> MockProgramGenerator<'ast> {
+    pub(crate) fn new(service_name: &'ast str, sails_path: &'ast str) -> Self {
+        Self {
+            service_name,
+            sails_path,
+            trait_tokens: Tokens::new(),
+            impl_tokens: Tokens::new(),
+        }
+    }
+
+    pub(crate) fn finalize(self) -> Tokens {
+        quote! {
+            pub trait $(self.service_name)Mock {
+                $(self.trait_tokens)
+            }
+
+            impl $(self.service_name)Mock for $(self.sails_path)::client::MockService<'_, $(self.service_name)Impl> {
+                $(self.impl_tokens)
+            }
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for MockProgramGenerator<'ast> {
+    fn visit_service_unit(&mut self, service: &'ast ast::ServiceUnit) {
+        visitor::accept_service_unit(service, self);
+
+        for ServiceIdent {
+            name,
+            interface_id: _,
+        } in &service.extends
+        {
+            let method_name = name.to_case(Case::Snake);
+            let impl_name = name.to_case(Case::Pascal);
+            let mod_name = name.to_case(Case::Snake);
+
+            quote_in! { self.trait_tokens =>
+                $['\r'] fn $(&method_name)(&mut self) -> $(self.sails_path)::client::MockService<'_, super::super::$(mod_name.as_str())::$(impl_name.as_str())Impl>;
+            };
+
+            quote_in! { self.impl_tokens =>
+                $['\r'] fn $(&method_name)(&mut self) -> $(self.sails_path)::client::MockService<'_, super::super::$(mod_name.as_str())::$(impl_name.as_str())Impl> {
+                    self.base_service()
+                }
+            };
+        }
+    }
+
+    fn visit_service_func(&mut self, func: &'ast ast::ServiceFunc) {
+        if !has_scale_codec(&func.annotations) {
+            return;
+        }
+        let fn_name = &func.name;
+        let fn_name_snake = func.name.to_case(Case::Snake);
+
+        quote_in! { self.trait_tokens =>
+            $['\r'] fn $(&fn_name_snake)(&mut self) -> $(self.sails_path)::client::MockMethod<'_, io::$fn_name>;
+        };
+
+        quote_in! { self.impl_tokens =>
+            $['\r'] fn $(&fn_name_snake)(&mut self) -> $(self.sails_path)::client::MockMethod<'_, io::$fn_name> {
+                self.method()
+            }
+        };
+    }
+}
@@ -1,7 +1,7 @@
 use crate::{
     ctor_generators::*, helpers::generate_doc_comments, service_generators::*, type_generators::*,
 };
-use convert_case::{Case, Casing};
+use convert_case::{Boundary, Case, Casing};
 use genco::prelude::*;
 use rust::Tokens;
 use sails_idl_parser_v2::ast;
@@ -12,12 +12,16 @@ pub(crate) struct RootGenerator<'ast> {
     tokens: Tokens,
     service_impl_tokens: Tokens,
     service_trait_tokens: Tokens,
+    mock_trait_tokens: Tokens,
+    mock_impl_tokens: Tokens,
     program_meta_tokens: Tokens,
     program_name: Option<&'ast str>,
     mocks_feature_name: Option<&'ast str>,
     sails_path: &'ast str,
     external_types: HashMap<&'ast str, &'ast str>,
     no_derive_traits: bool,
+    lenient_decoding: bool,
+    ethabi: bool,
     program_types: HashSet<&'ast str>,
 }
 
@@ -27,17 +31,23 @@ pub(crate) fn new(
         sails_path: &'ast str,
         external_types: HashMap<&'ast str, &'ast str>,
         no_derive_traits: bool,
+        lenient_decoding: bool,
+        ethabi: bool,
     ) -> Self {
         Self {
             tokens: Tokens::new(),
             service_impl_tokens: Tokens::new(),
             service_trait_tokens: Tokens::new(),
+            mock_trait_tokens: Tokens::new(),
+            mock_impl_tokens: Tokens::new(),
             program_meta_tokens: Tokens::new(),
             program_name: None,
             mocks_feature_name,
             sails_path,
             external_types,
             no_derive_traits,
+            lenient_decoding,
+            ethabi,
             program_types: HashSet::new(),
         }
     }
@@ -86,6 +96,23 @@ impl<E: $(self.sails_path)::client::GearEnv> $program_name for $(self.sails_path
                     $(self.service_impl_tokens)
                 }
             };
+
+            if let Some(mocks_feature_name) = self.mocks_feature_name {
+                quote_in! { tokens =>
+                    $['\n']
+                    #[cfg(feature = $(quoted(mocks_feature_name)))]
+                    #[cfg(not(target_arch = "wasm32"))]
+                    pub trait $(program_name)Mock {
+                        $(self.mock_trait_tokens)
+                    }
+
+                    #[cfg(feature = $(quoted(mocks_feature_name)))]
+                    #[cfg(not(target_arch = "wasm32"))]
+                    impl $(program_name)Mock for $(self.sails_path)::client::MockProgram<$(program_name)Program> {
+                        $(self.mock_impl_tokens)
+                    }
+                };
+            }
         }
 
         tokens.extend(self.tokens);
@@ -130,7 +157,9 @@ fn visit_service_unit(&mut self, service: &'ast ast::ServiceUnit) {
                 .interface_id
                 .expect("Service must have an interface ID"),
             self.no_derive_traits,
-        );
+            self.lenient_decoding,
+        )
+        .with_ethabi(self.ethabi);
         client_gen.visit_service_unit(service);
         self.tokens.extend(client_gen.finalize());
     }
@@ -140,8 +169,12 @@ fn visit_type(&mut self, t: &'ast ast::Type) {
         if self.external_types.contains_key(t.name.as_str()) {
             return;
         }
-        let mut type_gen =
-            TopLevelTypeGenerator::new(&t.name, self.sails_path, self.no_derive_traits);
+        let mut type_gen = TopLevelTypeGenerator::new(
+            &t.name,
+            self.sails_path,
+            self.no_derive_traits,
+            self.lenient_decoding,
+        );
         type_gen.visit_type(t);
         self.tokens.extend(type_gen.finalize());
     }
@@ -174,5 +207,33 @@ fn visit_service_expo(&mut self, service_item: &'ast ast::ServiceExpo) {
                 self.service($(&program_program_name)::$(&route_id_const_name))
             }
         );
+
+        if self.ethabi {
+            // Solidity selectors are prefixed with the camelCase route of the service
+            let ethabi_route = service_route
+                .set_boundaries(&[Boundary::Underscore, Boundary::LowerUpper])
+                .to_case(Case::Camel);
+            let ethabi_method_name = format!("{method_name}_ethabi");
+
+            quote_in!(self.service_trait_tokens =>
+                $['\r'] fn $(&ethabi_method_name)(&self) -> $(self.sails_path)::client::Service<$(&name_snake_case)::$(&name_pascal_case)Impl, Self::Env, $(self.sails_path)::client::RouteEthabi>;
+            );
+
+            quote_in!(self.service_impl_tokens =>
+                $['\r'] fn $(&ethabi_method_name)(&self) -> $(self.sails_path)::client::Service<$(&name_snake_case)::$(&name_pascal_case)Impl, Self::Env, $(self.sails_path)::client::RouteEthabi> {
+                    self.service_ethabi($(quoted(&ethabi_route)))
+                }
+            );
+        }
+
+        quote_in!(self.mock_trait_tokens =>
+            $['\r'] fn $(&method_name)(&mut self) -> $(self.sails_path)::client::MockService<'_, $(&name_snake_case)::$(&name_pascal_case)Impl>;
+        );
+
+        quote_in!(self.mock_impl_tokens =>
+            $['\r'] fn $(&method_name)(&mut self) -> $(self.sails_path)::client::MockService<'_, $(&name_snake_case)::$(&name_pascal_case)Impl> {
+                self.service($(&program_program_name)::$(&route_id_const_name))
+            }
+        );
     }
 }
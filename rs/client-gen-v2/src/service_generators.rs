@@ -1,11 +1,11 @@
-use crate::events_generator::EventsModuleGenerator;
+use crate::events_generator::{EthEventsModuleGenerator, EventsModuleGenerator};
 use crate::helpers::*;
-use crate::mock_generator::MockGenerator;
+use crate::mock_generator::{MockGenerator, MockProgramGenerator};
 use crate::type_generators::{TopLevelTypeGenerator, generate_type_decl_with_path};
 use convert_case::{Case, Casing};
 use genco::prelude::*;
 use rust::Tokens;
-use sails_idl_ast::codec::has_scale_codec;
+use sails_idl_ast::codec::{has_ethabi_codec, has_scale_codec};
 use sails_idl_parser_v2::{ast, visitor, visitor::Visitor};
 use std::collections::HashMap;
 
@@ -21,8 +21,15 @@ pub(crate) struct ServiceGenerator<'ast> {
     events_tokens: Tokens,
     types_tokens: Tokens,
     mocks_tokens: Tokens,
+    mock_program_tokens: Tokens,
     interface_id: sails_idl_ast::InterfaceId,
     no_derive_traits: bool,
+    lenient_decoding: bool,
+    ethabi: bool,
+    ethabi_trait_tokens: Tokens,
+    ethabi_impl_tokens: Tokens,
+    ethabi_io_tokens: Tokens,
+    ethabi_events_tokens: Tokens,
 }
 
 impl<'ast> ServiceGenerator<'ast> {
@@ -33,6 +40,7 @@ pub(crate) fn new(
         mocks_feature_name: Option<&'ast str>,
         interface_id: sails_idl_ast::InterfaceId,
         no_derive_traits: bool,
+        lenient_decoding: bool,
     ) -> Self {
         Self {
             service_name,
@@ -45,11 +53,23 @@ pub(crate) fn new(
             events_tokens: Tokens::new(),
             types_tokens: Tokens::new(),
             mocks_tokens: Tokens::new(),
+            mock_program_tokens: Tokens::new(),
             interface_id,
             no_derive_traits,
+            lenient_decoding,
+            ethabi: false,
+            ethabi_trait_tokens: Tokens::new(),
+            ethabi_impl_tokens: Tokens::new(),
+            ethabi_io_tokens: Tokens::new(),
+            ethabi_events_tokens: Tokens::new(),
         }
     }
 
+    /// Also generates the `ethabi` module for the functions and events with the `ethabi` codec.
+    pub(crate) fn with_ethabi(self, ethabi: bool) -> Self {
+        Self { ethabi, ..self }
+    }
+
     pub(crate) fn finalize(self) -> Tokens {
         let service_name_snake = &self.service_name.to_case(Case::Snake);
         let mock_tokens = if let Some(mocks_feature_name) = self.mocks_feature_name {
@@ -62,6 +82,13 @@ pub mod mockall {
                     use $(self.sails_path)::mockall::*;
                     $(self.mocks_tokens)
                 }
+
+                #[cfg(feature = $(quoted(mocks_feature_name)))]
+                #[cfg(not(target_arch = "wasm32"))]
+                pub mod mock_program {
+                    use super::*;
+                    $(self.mock_program_tokens)
+                }
             }
         } else {
             quote!()
@@ -88,6 +115,42 @@ pub mod io {
             quote!()
         };
 
+        let ethabi_module = if self.ethabi {
+            let io_module = if self.ethabi_io_tokens.is_empty() {
+                quote!()
+            } else {
+                quote! {
+                    $['\n']
+                    pub mod io {
+                        use super::*;
+                        $(self.ethabi_io_tokens)
+                    }
+                }
+            };
+            quote! {
+                $['\n']
+                pub mod ethabi {
+                    use super::*;
+
+                    pub trait $(self.service_name) {
+                        type Env: $(self.sails_path)::client::GearEnv;
+                        $(self.ethabi_trait_tokens)
+                    }
+
+                    impl<E: $(self.sails_path)::client::GearEnv> $(self.service_name) for $(self.sails_path)::client::Service<$(self.service_name)Impl, E, $(self.sails_path)::client::RouteEthabi> {
+                        type Env = E;
+                        $(self.ethabi_impl_tokens)
+                    }
+
+                    $io_module
+
+                    $(self.ethabi_events_tokens)
+                }
+            }
+        } else {
+            quote!()
+        };
+
         let allow_unused = if !has_io && !has_events && !has_types {
             quote!(#[allow(unused_imports)])
         } else {
@@ -122,6 +185,8 @@ impl<E: $(self.sails_path)::client::GearEnv> $(self.service_name) for $(self.sai
 
                 $(self.events_tokens)
 
+                $(ethabi_module)
+
                 $(mock_tokens)
             }
         }
@@ -148,16 +213,43 @@ fn visit_service_unit(&mut self, service: &'ast ast::ServiceUnit) {
                     self.base_service()
                 }
             };
+
+            if self.ethabi {
+                quote_in! { self.ethabi_trait_tokens =>
+                    $['\r'] fn $(&method_name)(&self) -> $(self.sails_path)::client::Service<super::super::$(mod_name.as_str())::$(impl_name.as_str())Impl, Self::Env, $(self.sails_path)::client::RouteEthabi>;
+                };
+
+                quote_in! { self.ethabi_impl_tokens =>
+                    $['\r'] fn $(&method_name)(&self) -> $(self.sails_path)::client::Service<super::super::$(mod_name.as_str())::$(impl_name.as_str())Impl, Self::Env, $(self.sails_path)::client::RouteEthabi> {
+                        self.base_service()
+                    }
+                };
+            }
         }
 
         let mut mock_gen = MockGenerator::new(self.service_name, self.sails_path);
         mock_gen.visit_service_unit(service);
         self.mocks_tokens.extend(mock_gen.finalize());
 
+        let mut mock_program_gen = MockProgramGenerator::new(self.service_name, self.sails_path);
+        mock_program_gen.visit_service_unit(service);
+        self.mock_program_tokens.extend(mock_program_gen.finalize());
+
         if !service.events.is_empty() {
-            let mut events_mod_gen = EventsModuleGenerator::new(self.service_name, self.sails_path);
+            let mut events_mod_gen = EventsModuleGenerator::new(
+                self.service_name,
+                self.sails_path,
+                self.lenient_decoding,
+            );
             events_mod_gen.visit_service_unit(service);
             self.events_tokens = events_mod_gen.finalize();
+
+            if self.ethabi {
+                let mut eth_events_mod_gen =
+                    EthEventsModuleGenerator::new(self.service_name, self.sails_path);
+                eth_events_mod_gen.visit_service_unit(service);
+                self.ethabi_events_tokens = eth_events_mod_gen.finalize();
+            }
         }
     }
 
@@ -166,13 +258,20 @@ fn visit_type(&mut self, t: &'ast ast::Type) {
             return;
         }
 
-        let mut type_gen =
-            TopLevelTypeGenerator::new(&t.name, self.sails_path, self.no_derive_traits);
+        let mut type_gen = TopLevelTypeGenerator::new(
+            &t.name,
+            self.sails_path,
+            self.no_derive_traits,
+            self.lenient_decoding,
+        );
         type_gen.visit_type(t);
         self.types_tokens.extend(type_gen.finalize());
     }
 
     fn visit_service_func(&mut self, func: &'ast ast::ServiceFunc) {
+        if self.ethabi && has_ethabi_codec(&func.annotations) {
+            self.generate_ethabi_func(func);
+        }
         if !has_scale_codec(&func.annotations) {
             return;
         }
@@ -209,9 +308,51 @@ fn visit_service_func(&mut self, func: &'ast ast::ServiceFunc) {
 
         let params_with_types_super = &fn_args_with_types_path(&func.params, "super");
         let entry_id = func.entry_id;
+        let io_name = if self.lenient_decoding {
+            format!("lenient {fn_name}")
+        } else {
+            fn_name.clone()
+        };
 
         quote_in! { self.io_tokens =>
-            $(self.sails_path)::io_struct_impl!($fn_name ($params_with_types_super) -> $io_output_type, $entry_id, <super::$(self.service_name)Impl as $(self.sails_path)::client::Identifiable>::INTERFACE_ID);
+            $(self.sails_path)::io_struct_impl!($io_name ($params_with_types_super) -> $io_output_type, $entry_id, <super::$(self.service_name)Impl as $(self.sails_path)::client::Identifiable>::INTERFACE_ID);
+        };
+    }
+}
+
+impl<'ast> ServiceGenerator<'ast> {
+    /// Generates the method of the `ethabi` service trait and its IO struct.
+    ///
+    /// `throws` types are not part of the ABI reply, so only the output type is decoded.
+    fn generate_ethabi_func(&mut self, func: &'ast ast::ServiceFunc) {
+        let self_ref = if func.kind == ast::FunctionKind::Query {
+            "&self"
+        } else {
+            "&mut self"
+        };
+        let fn_name = &func.name;
+        let fn_name_snake = &fn_name.to_case(Case::Snake);
+
+        let params_with_types = &fn_args_with_types_path(&func.params, "");
+        let args = encoded_args(&func.params);
+
+        generate_doc_comments(&mut self.ethabi_trait_tokens, &func.docs);
+
+        quote_in! { self.ethabi_trait_tokens =>
+            $['\r'] fn $fn_name_snake ($self_ref, $params_with_types) -> $(self.sails_path)::client::PendingCall<io::$fn_name, Self::Env>;
+        };
+
+        quote_in! { self.ethabi_impl_tokens =>
+            $['\r'] fn $fn_name_snake ($self_ref, $params_with_types) -> $(self.sails_path)::client::PendingCall<io::$fn_name, Self::Env> {
+                self.pending_call($args)
+            }
+        };
+
+        let params_with_types_super = &fn_args_with_types_path(&func.params, "super");
+        let output_type = generate_type_decl_with_path(&func.output, "super");
+
+        quote_in! { self.ethabi_io_tokens =>
+            $(self.sails_path)::io_struct_impl_ethabi!($fn_name ($params_with_types_super) -> $output_type);
         };
     }
 }
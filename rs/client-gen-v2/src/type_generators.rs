@@ -4,21 +4,31 @@
 
 use crate::helpers::generate_doc_comments;
 
+/// Variant added to enums by `#[sails_type(lenient)]`.
+pub(crate) const UNKNOWN_VARIANT: &str = "Unknown";
+
 pub(crate) struct TopLevelTypeGenerator<'ast> {
     type_name: &'ast str,
     sails_path: &'ast str,
     derive_traits: Option<&'ast str>,
+    lenient_decoding: bool,
     type_params_tokens: Tokens,
     tokens: Tokens,
 }
 
 impl<'ast> TopLevelTypeGenerator<'ast> {
-    pub(crate) fn new(type_name: &'ast str, sails_path: &'ast str, no_derive_traits: bool) -> Self {
+    pub(crate) fn new(
+        type_name: &'ast str,
+        sails_path: &'ast str,
+        no_derive_traits: bool,
+        lenient_decoding: bool,
+    ) -> Self {
         let derive_traits = (!no_derive_traits).then_some("PartialEq, Clone, Debug");
         Self {
             type_name,
             sails_path,
             derive_traits,
+            lenient_decoding,
             type_params_tokens: Tokens::new(),
             tokens: Tokens::new(),
         }
@@ -60,10 +70,14 @@ fn visit_type_def(&mut self, type_def: &'ast ast::TypeDef) {
                 self.tokens.extend(struct_def_generator.finalize());
             }
             ast::TypeDef::Enum(enum_def) => {
+                // Enums declaring their own `Unknown` variant are decoded strictly.
+                let lenient = self.lenient_decoding
+                    && !enum_def.variants.iter().any(|v| v.name == UNKNOWN_VARIANT);
                 let mut enum_def_generator = EnumDefGenerator::new(
                     self.type_name,
                     self.sails_path,
                     self.derive_traits,
+                    lenient,
                     self.type_params_tokens.clone(),
                 );
                 enum_def_generator.visit_enum_def(enum_def);
@@ -149,6 +163,7 @@ struct EnumDefGenerator<'a> {
     type_name: &'a str,
     sails_path: &'a str,
     derive_traits: Option<&'a str>,
+    lenient: bool,
     type_params_tokens: Tokens,
     tokens: Tokens,
 }
@@ -158,21 +173,24 @@ pub(crate) fn new(
         type_name: &'a str,
         sails_path: &'a str,
         derive_traits: Option<&'a str>,
+        lenient: bool,
         type_params_tokens: Tokens,
     ) -> Self {
         Self {
             type_name,
             sails_path,
             derive_traits,
+            lenient,
             type_params_tokens,
             tokens: Tokens::new(),
         }
     }
 
     pub(crate) fn finalize(self) -> Tokens {
+        let lenient = self.lenient.then_some(", lenient");
         quote!(
             $['\r']
-            #[$(self.sails_path)::sails_type(crate = $(self.sails_path))]
+            #[$(self.sails_path)::sails_type(crate = $(self.sails_path)$lenient)]
             $(if let Some(traits) = self.derive_traits {
                 #[derive($traits)]
             })
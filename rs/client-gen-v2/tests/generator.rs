@@ -42,6 +42,28 @@ fn test_events_works() {
     insta::assert_snapshot!(gen_client(idl));
 }
 
+#[test]
+fn test_lenient_decoding() {
+    let idl = include_str!("idls/lenient_works.idl");
+
+    let code = ClientGenerator::from_idl(idl)
+        .with_lenient_decoding()
+        .generate()
+        .expect("generate client");
+    insta::assert_snapshot!(code);
+}
+
+#[test]
+fn test_ethabi_works() {
+    let idl = include_str!("idls/ethabi_works.idl");
+
+    let code = ClientGenerator::from_idl(idl)
+        .with_ethabi()
+        .generate()
+        .expect("generate client");
+    insta::assert_snapshot!(code);
+}
+
 #[test]
 fn full_with_sails_path() {
     const IDL: &str = include_str!("idls/full_coverage.idl");
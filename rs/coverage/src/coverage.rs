@@ -0,0 +1,151 @@
+use crate::report::{CoverageItem, CoverageKind, CoverageReport};
+use sails::{
+    client::TracedMessage,
+    prelude::{ErrorReplyReason, ReplyCode, SimpleExecutionError},
+};
+use sails_idl_parser_v2::{ast::IdlDoc, parse_idl};
+use sails_idl_value::{DecodedMessage, EntryKind, IdlCodec, Shape, TypeResolver, Value, ast};
+
+/// Maps traced messages to the entries of a program IDL.
+pub struct Coverage {
+    doc: IdlDoc,
+}
+
+impl Coverage {
+    pub fn new(doc: IdlDoc) -> Self {
+        Self { doc }
+    }
+
+    pub fn from_idl(idl: &str) -> sails_idl_parser_v2::error::Result<Self> {
+        Ok(Self::new(parse_idl(idl)?))
+    }
+
+    /// Creates a coverage for the IDL generated for the program `P`.
+    pub fn from_program_meta<P: sails::meta::ProgramMeta>() -> Self {
+        let name = core::any::type_name::<P>().rsplit("::").next();
+        let mut idl = String::new();
+        sails::generate_idl::<P>(name, &mut idl).expect("failed to generate the program IDL");
+        Self::from_idl(&idl).expect("generated IDL is valid")
+    }
+
+    /// Returns the report listing every entry of the IDL, none of them hit.
+    pub fn empty_report(&self) -> CoverageReport {
+        let codec = IdlCodec::new(&self.doc);
+        let mut report = CoverageReport::default();
+        if let Some(program) = &self.doc.program {
+            let resolver = codec.program_resolver();
+            for ctor in &program.ctors {
+                report.insert(CoverageItem::new(CoverageKind::Ctor, None, &ctor.name));
+                add_throws(
+                    &mut report,
+                    &resolver,
+                    None,
+                    &ctor.name,
+                    ctor.throws.as_ref(),
+                );
+            }
+        }
+        for service in &self.doc.services {
+            let resolver = codec.service_resolver(service);
+            let scope = Some(service.name.name.as_str());
+            for func in &service.funcs {
+                let kind = match func.kind {
+                    ast::FunctionKind::Command => CoverageKind::Command,
+                    ast::FunctionKind::Query => CoverageKind::Query,
+                };
+                report.insert(CoverageItem::new(kind, scope, &func.name));
+                add_throws(
+                    &mut report,
+                    &resolver,
+                    scope,
+                    &func.name,
+                    func.throws.as_ref(),
+                );
+            }
+            for event in &service.events {
+                report.insert(CoverageItem::new(CoverageKind::Event, scope, &event.name));
+            }
+        }
+        report
+    }
+
+    /// Returns the report of the entries hit by `trace`, see `GtestEnv::message_trace`.
+    ///
+    /// Calls and events count as hits of their entries and error replies carrying a `throws`
+    /// value as hits of its variant. Calls and events not resolved against the IDL are
+    /// counted as unmatched, other replies are ignored.
+    pub fn report(&self, trace: &[TracedMessage]) -> CoverageReport {
+        let codec = IdlCodec::new(&self.doc);
+        let mut report = self.empty_report();
+        for message in trace {
+            match message {
+                TracedMessage::Call { payload, .. } => match codec.decode_call(payload) {
+                    Ok(decoded) => report.hit(entry_item(&decoded)),
+                    Err(_) => report.unmatched += 1,
+                },
+                TracedMessage::Event { payload, .. } => match codec.decode_event(payload) {
+                    Ok(decoded) => report.hit(entry_item(&decoded)),
+                    Err(_) => report.unmatched += 1,
+                },
+                TracedMessage::Reply {
+                    code:
+                        ReplyCode::Error(ErrorReplyReason::Execution(
+                            SimpleExecutionError::UserspacePanic,
+                        )),
+                    payload,
+                    ..
+                } => {
+                    // Panics without a `throws` value are not entries of the IDL.
+                    if let Ok(decoded) = codec.decode_error(payload) {
+                        let variant = match &decoded.value {
+                            Value::Variant { name, .. } => Some(name.as_str()),
+                            _ => None,
+                        };
+                        report.hit(
+                            CoverageItem::new(
+                                CoverageKind::Throws,
+                                decoded.service.as_deref(),
+                                &decoded.name,
+                            )
+                            .with_variant(variant),
+                        );
+                    }
+                }
+                TracedMessage::Reply { .. } => {}
+            }
+        }
+        report
+    }
+}
+
+fn entry_item(decoded: &DecodedMessage) -> CoverageItem {
+    let kind = match decoded.entry_kind {
+        EntryKind::Ctor => CoverageKind::Ctor,
+        EntryKind::Command => CoverageKind::Command,
+        EntryKind::Query => CoverageKind::Query,
+        EntryKind::Event => CoverageKind::Event,
+    };
+    CoverageItem::new(kind, decoded.service.as_deref(), &decoded.name)
+}
+
+/// Adds an item per variant of the `throws` enum of `func`, or a single item if it is not an enum.
+fn add_throws(
+    report: &mut CoverageReport,
+    resolver: &TypeResolver<'_>,
+    service: Option<&str>,
+    func: &str,
+    throws: Option<&ast::TypeDecl>,
+) {
+    let Some(throws) = throws else {
+        return;
+    };
+    let item = CoverageItem::new(CoverageKind::Throws, service, func);
+    match resolver.shape(throws) {
+        Ok(Shape::Enum(variants)) => {
+            for variant in variants {
+                report.insert(item.clone().with_variant(Some(variant.name)));
+            }
+        }
+        _ => report.insert(item),
+    }
+}
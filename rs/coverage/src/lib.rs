@@ -0,0 +1,17 @@
+//! Interface coverage of Sails programs by `gtest` runs.
+//!
+//! [`Coverage`] reads the IDL of a program, from a parsed document or from the `ProgramMeta`
+//! of the program type, and maps the messages traced by a `GtestEnv` created with
+//! `GtestEnv::with_message_trace` back to the program interface by their `SailsMessageHeader`.
+//! The resulting [`CoverageReport`] counts the hits of every constructor, command, query,
+//! event and `throws` variant declared by the IDL, so the entries not exercised by the tests
+//! are easy to spot.
+//!
+//! Each test runs its own env, so the reports of a test suite are accumulated in a file with
+//! [`CoverageReport::merge_into_file`], which can be called concurrently from parallel tests.
+
+mod coverage;
+mod report;
+
+pub use coverage::Coverage;
+pub use report::{CoverageItem, CoverageKind, CoverageReport};
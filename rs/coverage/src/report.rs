@@ -0,0 +1,268 @@
+use core::fmt;
+use std::{
+    collections::BTreeMap,
+    fs::OpenOptions,
+    io::{self, Read as _, Seek as _, SeekFrom, Write as _},
+    path::Path,
+};
+
+/// Kind of an IDL entry tracked by a [`CoverageReport`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum CoverageKind {
+    Ctor,
+    Command,
+    Query,
+    Event,
+    /// Variant of the `throws` type of a constructor or a function.
+    Throws,
+}
+
+impl CoverageKind {
+    const ALL: [Self; 5] = [
+        Self::Ctor,
+        Self::Command,
+        Self::Query,
+        Self::Event,
+        Self::Throws,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Ctor => "ctor",
+            Self::Command => "command",
+            Self::Query => "query",
+            Self::Event => "event",
+            Self::Throws => "throws",
+        }
+    }
+}
+
+impl fmt::Display for CoverageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Entry of the IDL tracked by a [`CoverageReport`].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CoverageItem {
+    pub kind: CoverageKind,
+    /// Name of the service declaring the entry, `None` for constructors.
+    pub service: Option<String>,
+    /// Name of the constructor, function or event.
+    pub name: String,
+    /// Variant of the `throws` enum, `None` for other kinds and `throws` types other than enums.
+    pub variant: Option<String>,
+}
+
+impl CoverageItem {
+    pub fn new(kind: CoverageKind, service: Option<&str>, name: &str) -> Self {
+        Self {
+            kind,
+            service: service.map(ToString::to_string),
+            name: name.to_string(),
+            variant: None,
+        }
+    }
+
+    pub fn with_variant(self, variant: Option<&str>) -> Self {
+        Self {
+            variant: variant.map(ToString::to_string),
+            ..self
+        }
+    }
+}
+
+/// Formats the item as `<service>::<name>[::<variant>]`.
+impl fmt::Display for CoverageItem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(service) = &self.service {
+            write!(f, "{service}::")?;
+        }
+        f.write_str(&self.name)?;
+        if let Some(variant) = &self.variant {
+            write!(f, "::{variant}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Number of hits of every entry of a program IDL.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CoverageReport {
+    items: BTreeMap<CoverageItem, u32>,
+    /// Calls and events not resolved against the IDL.
+    pub unmatched: u32,
+}
+
+impl CoverageReport {
+    /// Adds `item` to the report, not hit.
+    pub fn insert(&mut self, item: CoverageItem) {
+        self.items.entry(item).or_default();
+    }
+
+    /// Counts a hit of `item`, adding it to the report if missing.
+    pub fn hit(&mut self, item: CoverageItem) {
+        *self.items.entry(item).or_default() += 1;
+    }
+
+    /// Returns the items with their number of hits, ordered by kind and name.
+    pub fn items(&self) -> impl Iterator<Item = (&CoverageItem, u32)> {
+        self.items.iter().map(|(item, hits)| (item, *hits))
+    }
+
+    /// Returns the number of hits of `item`, `None` if it is not in the report.
+    pub fn hits(&self, item: &CoverageItem) -> Option<u32> {
+        self.items.get(item).copied()
+    }
+
+    /// Returns the items not hit.
+    pub fn uncovered(&self) -> Vec<&CoverageItem> {
+        self.items()
+            .filter(|(_, hits)| *hits == 0)
+            .map(|(item, _)| item)
+            .collect()
+    }
+
+    pub fn is_complete(&self) -> bool {
+        self.items.values().all(|hits| *hits > 0)
+    }
+
+    /// Panics listing the uncovered items if the coverage is not complete.
+    #[track_caller]
+    pub fn assert_complete(&self) {
+        let uncovered = self.uncovered();
+        assert!(
+            uncovered.is_empty(),
+            "{} entries are not covered:\n{}",
+            uncovered.len(),
+            uncovered
+                .iter()
+                .map(|item| format!("  {} {item}", item.kind))
+                .collect::<Vec<_>>()
+                .join("\n")
+        );
+    }
+
+    /// Adds the hits of `other` to this report.
+    pub fn merge(&mut self, other: &CoverageReport) {
+        for (item, hits) in other.items() {
+            *self.items.entry(item.clone()).or_default() += hits;
+        }
+        self.unmatched += other.unmatched;
+    }
+
+    /// Merges this report into the report stored at `path`, creating it if it does not exist,
+    /// and returns the merged report. The file is locked while it is updated, so the reports
+    /// of tests running in parallel are accumulated.
+    pub fn merge_into_file(&self, path: impl AsRef<Path>) -> io::Result<CoverageReport> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .create(true)
+            .truncate(false)
+            .open(path)?;
+        file.lock()?;
+        let mut content = String::new();
+        file.read_to_string(&mut content)?;
+        let mut merged = Self::parse(&content)?;
+        merged.merge(self);
+        file.set_len(0)?;
+        file.seek(SeekFrom::Start(0))?;
+        file.write_all(merged.to_tsv().as_bytes())?;
+        Ok(merged)
+    }
+
+    /// Loads the report stored at `path` by [`CoverageReport::merge_into_file`].
+    pub fn load(path: impl AsRef<Path>) -> io::Result<CoverageReport> {
+        Self::parse(&std::fs::read_to_string(path)?)
+    }
+
+    /// Lines of `<kind>\t<service>\t<name>\t<variant>\t<hits>`, with empty optional fields,
+    /// and a last line of `unmatched\t\t\t\t<count>`.
+    fn to_tsv(&self) -> String {
+        let mut tsv = String::new();
+        for (item, hits) in self.items() {
+            tsv.push_str(&format!(
+                "{}\t{}\t{}\t{}\t{hits}\n",
+                item.kind,
+                item.service.as_deref().unwrap_or_default(),
+                item.name,
+                item.variant.as_deref().unwrap_or_default(),
+            ));
+        }
+        tsv.push_str(&format!("unmatched\t\t\t\t{}\n", self.unmatched));
+        tsv
+    }
+
+    fn parse(tsv: &str) -> io::Result<CoverageReport> {
+        let invalid = |line: &str| {
+            io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("invalid coverage line `{line}`"),
+            )
+        };
+        let non_empty = |field: &str| (!field.is_empty()).then(|| field.to_string());
+        let mut report = CoverageReport::default();
+        for line in tsv.lines().filter(|line| !line.is_empty()) {
+            let [kind, service, name, variant, hits] = line
+                .split('\t')
+                .collect::<Vec<_>>()
+                .try_into()
+                .map_err(|_| invalid(line))?;
+            let hits: u32 = hits.parse().map_err(|_| invalid(line))?;
+            if kind == "unmatched" {
+                report.unmatched += hits;
+                continue;
+            }
+            let kind = CoverageKind::ALL
+                .into_iter()
+                .find(|k| k.as_str() == kind)
+                .ok_or_else(|| invalid(line))?;
+            let item = CoverageItem {
+                kind,
+                service: non_empty(service),
+                name: name.to_string(),
+                variant: non_empty(variant),
+            };
+            *report.items.entry(item).or_default() += hits;
+        }
+        Ok(report)
+    }
+}
+
+impl fmt::Display for CoverageReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const HEADERS: [&str; 3] = ["kind", "entry", "hits"];
+        let rows: Vec<[String; 3]> = self
+            .items()
+            .map(|(item, hits)| [item.kind.to_string(), item.to_string(), hits.to_string()])
+            .collect();
+        let mut widths = HEADERS.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+        let write_row = |f: &mut fmt::Formatter<'_>, row: &[&str]| {
+            let [kind, entry, hits] = widths;
+            writeln!(
+                f,
+                "{:<kind$} | {:<entry$} | {:>hits$}",
+                row[0], row[1], row[2]
+            )
+        };
+        write_row(f, &HEADERS)?;
+        let total_width = widths.iter().sum::<usize>() + 3 * (widths.len() - 1);
+        writeln!(f, "{}", "-".repeat(total_width))?;
+        for row in &rows {
+            write_row(f, &row.each_ref().map(String::as_str))?;
+        }
+        let covered = self.items.values().filter(|hits| **hits > 0).count();
+        writeln!(f, "covered {covered} of {} entries", self.items.len())?;
+        if self.unmatched > 0 {
+            writeln!(f, "{} messages not matching the IDL", self.unmatched)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+use sails::{
+    ActorId,
+    client::{GtestEnv, TracedMessage},
+    prelude::{ErrorReplyReason, ReplyCode, SimpleExecutionError, SuccessReplyReason},
+};
+use sails_coverage::*;
+use sails_idl_value::{Field, IdlCodec, Value};
+
+const COUNTER_IDL: &str = r#"
+!@sails: 2.0.0
+
+service Counter {
+    events {
+        Added(u32),
+        Cleared,
+    }
+    functions {
+        Add(value: u32) -> u32 throws CounterError;
+        Reset();
+        @query
+        Value() -> u32;
+    }
+    types {
+        enum CounterError {
+            Overflow,
+            Locked,
+        }
+    }
+}
+
+program Counter {
+    constructors {
+        Create();
+    }
+    services {
+        Counter,
+    }
+}
+"#;
+
+const PROGRAM_ID: ActorId = ActorId::new([7; 32]);
+
+fn item(kind: CoverageKind, service: Option<&str>, name: &str) -> CoverageItem {
+    CoverageItem::new(kind, service, name)
+}
+
+fn call(payload: Vec<u8>) -> TracedMessage {
+    TracedMessage::Call {
+        destination: PROGRAM_ID,
+        payload,
+    }
+}
+
+fn panic_reply(payload: Vec<u8>) -> TracedMessage {
+    TracedMessage::Reply {
+        source: PROGRAM_ID,
+        code: ReplyCode::Error(ErrorReplyReason::Execution(
+            SimpleExecutionError::UserspacePanic,
+        )),
+        payload,
+    }
+}
+
+#[test]
+fn reports_hits_of_traced_messages() {
+    let doc = sails_idl_parser_v2::parse_idl(COUNTER_IDL).unwrap();
+    let codec = IdlCodec::new(&doc);
+    let overflow = Value::Variant {
+        name: "Overflow".into(),
+        fields: vec![],
+    };
+    let trace = [
+        call(codec.encode_ctor("Create", &[]).unwrap()),
+        call(
+            codec
+                .encode_call("Counter", "Add", &[Value::U32(1)])
+                .unwrap(),
+        ),
+        TracedMessage::Reply {
+            source: PROGRAM_ID,
+            code: ReplyCode::Success(SuccessReplyReason::Manual),
+            payload: codec
+                .encode_reply("Counter", "Add", &Value::U32(1))
+                .unwrap(),
+        },
+        call(
+            codec
+                .encode_call("Counter", "Add", &[Value::U32(2)])
+                .unwrap(),
+        ),
+        panic_reply(codec.encode_error("Counter", "Add", &overflow).unwrap()),
+        // Panics without a `throws` value are ignored.
+        panic_reply(b"panicked".to_vec()),
+        TracedMessage::Event {
+            source: PROGRAM_ID,
+            payload: codec
+                .encode_event(
+                    "Counter",
+                    "Added",
+                    &[Field {
+                        name: None,
+                        value: Value::U32(1),
+                    }],
+                )
+                .unwrap(),
+        },
+        call(vec![1, 2, 3]),
+    ];
+
+    let report = Coverage::new(doc).report(&trace);
+
+    let counter = Some("Counter");
+    let throws = |variant| item(CoverageKind::Throws, counter, "Add").with_variant(Some(variant));
+    assert_eq!(
+        report.hits(&item(CoverageKind::Ctor, None, "Create")),
+        Some(1)
+    );
+    assert_eq!(
+        report.hits(&item(CoverageKind::Command, counter, "Add")),
+        Some(2)
+    );
+    assert_eq!(report.hits(&throws("Overflow")), Some(1));
+    assert_eq!(
+        report.hits(&item(CoverageKind::Event, counter, "Added")),
+        Some(1)
+    );
+    assert_eq!(
+        report.uncovered(),
+        [
+            &item(CoverageKind::Command, counter, "Reset"),
+            &item(CoverageKind::Query, counter, "Value"),
+            &item(CoverageKind::Event, counter, "Cleared"),
+            &throws("Locked"),
+        ]
+    );
+    assert_eq!(report.unmatched, 1);
+    assert!(!report.is_complete());
+    let table = report.to_string();
+    assert!(
+        table.contains("throws  | Counter::Add::Overflow |    1"),
+        "{table}"
+    );
+    assert!(table.contains("covered 4 of 8 entries"), "{table}");
+}
+
+#[tokio::test]
+async fn reports_calls_sent_through_gtest_env() {
+    let doc = sails_idl_parser_v2::parse_idl(COUNTER_IDL).unwrap();
+    let payload = IdlCodec::new(&doc)
+        .encode_call("Counter", "Value", &[])
+        .unwrap();
+    let env = GtestEnv::system_default().with_message_trace();
+    env.inject_faults(
+        PROGRAM_ID,
+        sails::client::FaultProgram::new().with_handler(|payload| Some(payload.to_vec())),
+    );
+
+    env.send_for_reply(PROGRAM_ID, payload, Default::default())
+        .await
+        .unwrap();
+
+    let report = Coverage::new(doc).report(&env.message_trace());
+    assert_eq!(
+        report.hits(&item(CoverageKind::Query, Some("Counter"), "Value")),
+        Some(1)
+    );
+    assert_eq!(report.unmatched, 0);
+}
+
+#[test]
+fn merges_reports_into_file() {
+    let path = std::env::temp_dir().join(format!("sails-coverage-{}.tsv", std::process::id()));
+    _ = std::fs::remove_file(&path);
+    let coverage = Coverage::from_idl(COUNTER_IDL).unwrap();
+    let ctor = item(CoverageKind::Ctor, None, "Create");
+    let mut report = coverage.empty_report();
+    report.hit(ctor.clone());
+    report.unmatched = 1;
+
+    report.merge_into_file(&path).unwrap();
+    let merged = report.merge_into_file(&path).unwrap();
+
+    assert_eq!(merged.hits(&ctor), Some(2));
+    assert_eq!(merged.unmatched, 2);
+    assert_eq!(merged.uncovered().len(), 7);
+    assert_eq!(CoverageReport::load(&path).unwrap(), merged);
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+#[should_panic(expected = "8 entries are not covered")]
+fn incomplete_coverage_fails_assertion() {
+    Coverage::from_idl(COUNTER_IDL)
+        .unwrap()
+        .empty_report()
+        .assert_complete();
+}
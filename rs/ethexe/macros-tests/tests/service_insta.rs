@@ -266,12 +266,12 @@ pub fn add_byte(&mut self, byte: u8) -> &'static [u8] {
                 }
             }
 
-            #[export]
+            #[export(scale)]
             pub async fn first_byte<'a>(&self) -> Option<&'a u8> {
                 unsafe { BYTES.first() }
             }
 
-            #[export]
+            #[export(scale)]
             pub async fn last_byte<'a>(&self) -> Option<&'a u8> {
                 unsafe { BYTES.last() }
             }
@@ -406,7 +406,7 @@ pub fn do_this(&mut self) -> u32 {
 fn works_with_export() {
     let input = quote! {
         impl SomeService {
-            #[export(route = "DoSomething", unwrap_result)]
+            #[export(scale, route = "DoSomething", unwrap_result)]
             pub async fn do_this(&mut self, p1: u32, p2: String) -> Result<(u32, String), String> {
                 Ok((p1, p2))
             }
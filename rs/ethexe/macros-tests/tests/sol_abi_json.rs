@@ -0,0 +1,127 @@
+use sails::{
+    EthEvent,
+    alloy_primitives::hex,
+    prelude::*,
+    solidity::{self, ConstProgramMeta, ProgramSignature},
+};
+use serde_json::Value;
+
+#[event]
+#[derive(TypeInfo, Encode, Clone, Debug, PartialEq, ReflectHash)]
+#[codec(crate = sails::scale_codec)]
+#[reflect_hash(crate = sails)]
+pub enum CounterEvents {
+    Added {
+        #[indexed]
+        value: u32,
+        note: String,
+    },
+    Cleared,
+}
+
+pub struct Counter;
+
+#[service(events = CounterEvents)]
+impl Counter {
+    #[export]
+    pub fn add(&mut self, value: u32, note: String) -> u32 {
+        self.emit_eth_event(CounterEvents::Added { value, note })
+            .unwrap();
+        value
+    }
+
+    #[export]
+    pub fn reset(&mut self, values: Vec<u64>) {
+        _ = values;
+        self.emit_eth_event(CounterEvents::Cleared).unwrap();
+    }
+
+    #[export]
+    pub fn owner(&self) -> ActorId {
+        ActorId::zero()
+    }
+}
+
+pub struct CounterProgram;
+
+#[program]
+impl CounterProgram {
+    pub fn create() -> Self {
+        Self
+    }
+
+    pub fn create_with(value: u32) -> Self {
+        _ = value;
+        Self
+    }
+
+    pub fn counter(&self) -> Counter {
+        Counter
+    }
+}
+
+fn abi_entries(kind: &str) -> Vec<Value> {
+    let mut idl = String::new();
+    sails::generate_idl::<CounterProgram>(Some("CounterProgram"), &mut idl).unwrap();
+    let abi = sails_sol_gen::generate_abi_json(&idl).unwrap();
+    let abi: Value = serde_json::from_slice(&abi).unwrap();
+    abi.as_array()
+        .unwrap()
+        .iter()
+        .filter(|entry| entry["type"] == kind)
+        .cloned()
+        .collect()
+}
+
+fn input_types(entry: &Value) -> Vec<&str> {
+    entry["inputs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|input| input["type"].as_str().unwrap())
+        .collect()
+}
+
+#[test]
+fn abi_json_selectors_match_program_dispatch() {
+    const CTORS_LEN: usize = <CounterProgram as ProgramSignature>::CTORS.len();
+    const METHODS_LEN: usize = <CounterProgram as ProgramSignature>::METHODS_LEN;
+    let dispatch_selectors: Vec<String> =
+        ConstProgramMeta::<CounterProgram>::ctor_sigs::<CTORS_LEN>()
+            .into_iter()
+            .chain(ConstProgramMeta::<CounterProgram>::method_sigs::<METHODS_LEN>())
+            .map(hex::encode_prefixed)
+            .collect();
+
+    let functions = abi_entries("function");
+    assert_eq!(functions.len(), dispatch_selectors.len());
+    for function in functions {
+        let signature = format!(
+            "{}({})",
+            function["name"].as_str().unwrap(),
+            input_types(&function).join(",")
+        );
+        let selector = hex::encode_prefixed(solidity::selector(&signature));
+        assert_eq!(function["selector"], selector, "{signature}");
+        assert!(dispatch_selectors.contains(&selector), "{signature}");
+    }
+}
+
+#[test]
+fn abi_json_topics_match_eth_event_signatures() {
+    let events = abi_entries("event");
+    assert_eq!(events.len(), CounterEvents::SIGNATURES.len());
+    for (event, (name, params, hash)) in events.iter().zip(CounterEvents::SIGNATURES) {
+        assert_eq!(event["name"], *name);
+        assert_eq!(format!("({})", input_types(event).join(",")), *params);
+        assert_eq!(event["topic"], hex::encode_prefixed(hash));
+    }
+
+    let indexed: Vec<&Value> = events[0]["inputs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|input| &input["indexed"])
+        .collect();
+    assert_eq!(indexed, [true, false]);
+}
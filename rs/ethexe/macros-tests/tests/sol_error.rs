@@ -0,0 +1,157 @@
+use sails::{
+    EthError,
+    alloy_primitives::hex,
+    alloy_sol_types::{Revert, SolError, SolValue},
+    gstd::{native::take_panic_payload, services::Service},
+    meta::{Identifiable, SailsMessageHeader},
+    prelude::*,
+    solidity,
+};
+use serde_json::Value;
+
+#[derive(TypeInfo, Encode, EthError, Clone, Debug, PartialEq, ReflectHash)]
+#[codec(crate = sails::scale_codec)]
+#[reflect_hash(crate = sails)]
+pub enum TransferError {
+    InsufficientBalance { needed: u128 },
+    UnknownAccount(ActorId),
+    Paused,
+}
+
+pub struct Token;
+
+#[service]
+impl Token {
+    #[export(unwrap_result)]
+    pub fn transfer(&mut self, to: ActorId, amount: u128) -> Result<u128, TransferError> {
+        if to == ActorId::zero() {
+            return Err(TransferError::UnknownAccount(to));
+        }
+        Err(TransferError::InsufficientBalance { needed: amount })
+    }
+
+    #[export(unwrap_result)]
+    pub fn burn(&mut self, amount: u128) -> Result<u128, String> {
+        Err(format!("cannot burn {amount}"))
+    }
+}
+
+pub struct TokenProgram;
+
+#[program]
+impl TokenProgram {
+    pub fn create() -> Self {
+        Self
+    }
+
+    pub fn token(&self) -> Token {
+        Token
+    }
+}
+
+fn handle_solidity(entry_id: u16, input: &[u8]) -> Vec<u8> {
+    let header = SailsMessageHeader::v1(<Token as Identifiable>::INTERFACE_ID, entry_id, 1);
+    _ = take_panic_payload();
+    let result = std::panic::catch_unwind(|| {
+        Token
+            .expose(1)
+            .try_handle_solidity(header.interface_id(), header.entry_id(), input)
+    });
+    assert!(result.is_err(), "expected the method to revert");
+    take_panic_payload().expect("revert data")
+}
+
+#[test]
+fn eth_error_signatures() {
+    let signatures: Vec<(&str, &str)> = TransferError::SIGNATURES
+        .iter()
+        .map(|(name, params, _)| (*name, *params))
+        .collect();
+    assert_eq!(
+        signatures,
+        [
+            ("InsufficientBalance", "(uint128)"),
+            ("UnknownAccount", "(address)"),
+            ("Paused", "()"),
+        ]
+    );
+    for (name, params, selector) in TransferError::SIGNATURES {
+        assert_eq!(*selector, solidity::selector(format!("{name}{params}")));
+    }
+}
+
+#[test]
+fn eth_error_revert_data() {
+    let error = TransferError::InsufficientBalance { needed: 42 };
+    let data = error.revert_data();
+    assert_eq!(
+        data[..4],
+        solidity::selector("InsufficientBalance(uint128)")
+    );
+    assert_eq!(data[4..], 42u128.abi_encode());
+
+    let account = ActorId::from(7u64);
+    let data = TransferError::UnknownAccount(account).revert_data();
+    assert_eq!(data[..4], solidity::selector("UnknownAccount(address)"));
+    assert_eq!(
+        <sails::alloy_primitives::Address as SolValue>::abi_decode(&data[4..]).unwrap(),
+        sails::alloy_primitives::Address::from(account)
+    );
+
+    assert_eq!(
+        TransferError::Paused.revert_data(),
+        solidity::selector("Paused()").as_slice()
+    );
+}
+
+#[test]
+fn dispatch_reverts_with_eth_error() {
+    let input = (false, ActorId::from(1u64), 100u128).abi_encode_sequence();
+    let revert_data = handle_solidity(1, &input);
+    assert_eq!(
+        revert_data,
+        TransferError::InsufficientBalance { needed: 100 }.revert_data()
+    );
+
+    let input = (false, ActorId::zero(), 100u128).abi_encode_sequence();
+    let revert_data = handle_solidity(1, &input);
+    assert_eq!(
+        revert_data,
+        TransferError::UnknownAccount(ActorId::zero()).revert_data()
+    );
+}
+
+#[test]
+fn dispatch_reverts_with_string_error() {
+    let input = (false, 5u128).abi_encode_sequence();
+    let revert_data = handle_solidity(0, &input);
+    let revert = Revert::abi_decode(&revert_data).unwrap();
+    assert_eq!(revert.reason, "cannot burn 5");
+}
+
+#[test]
+fn abi_json_errors_match_eth_error_signatures() {
+    let mut idl = String::new();
+    sails::generate_idl::<TokenProgram>(Some("TokenProgram"), &mut idl).unwrap();
+    let abi = sails_sol_gen::generate_abi_json(&idl).unwrap();
+    let abi: Value = serde_json::from_slice(&abi).unwrap();
+    let errors: Vec<&Value> = abi
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|entry| entry["type"] == "error")
+        .collect();
+
+    assert_eq!(errors.len(), TransferError::SIGNATURES.len());
+    for (error, (name, params, selector)) in errors.iter().zip(TransferError::SIGNATURES) {
+        let input_types: Vec<&str> = error["inputs"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|input| input["type"].as_str().unwrap())
+            .collect();
+        assert_eq!(error["name"], *name);
+        assert_eq!(format!("({})", input_types.join(",")), *params);
+        assert_eq!(error["selector"], hex::encode_prefixed(selector));
+    }
+}
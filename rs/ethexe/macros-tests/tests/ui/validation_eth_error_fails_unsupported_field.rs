@@ -0,0 +1,8 @@
+use sails::prelude::*;
+
+#[derive(EthError)]
+pub enum TransferError {
+    InsufficientBalance { needed: Option<u128> },
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use sails::prelude::*;
+
+#[derive(Encode, Decode, TypeInfo, ReflectHash)]
+#[codec(crate = sails::scale_codec)]
+#[reflect_hash(crate = sails)]
+pub enum Status {
+    Active,
+    Paused,
+}
+
+struct SomeService;
+
+#[sails::service]
+impl SomeService {
+    #[export(ethabi)]
+    pub fn status(&self) -> Status {
+        Status::Active
+    }
+}
+
+fn main() {}
@@ -0,0 +1,20 @@
+use sails::prelude::*;
+
+#[derive(Encode, Decode, TypeInfo, ReflectHash)]
+#[codec(crate = sails::scale_codec)]
+#[reflect_hash(crate = sails)]
+pub enum TransferError {
+    InsufficientBalance { needed: u128 },
+}
+
+struct SomeService;
+
+#[sails::service]
+impl SomeService {
+    #[export(unwrap_result)]
+    pub fn transfer(&mut self, amount: u128) -> Result<u128, TransferError> {
+        Err(TransferError::InsufficientBalance { needed: amount })
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use sails::prelude::*;
+
+struct SomeService;
+
+#[sails::service]
+impl SomeService {
+    #[export(ethabi)]
+    pub fn set_balances(&mut self, balances: sails::collections::BTreeMap<ActorId, u128>) {
+        _ = balances;
+    }
+}
+
+fn main() {}
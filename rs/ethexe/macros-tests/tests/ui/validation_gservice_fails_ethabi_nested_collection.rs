@@ -0,0 +1,13 @@
+use sails::prelude::*;
+
+struct SomeService;
+
+#[sails::service]
+impl SomeService {
+    #[export(ethabi)]
+    pub fn batch(&mut self, values: Vec<[Option<u32>; 4]>) -> u32 {
+        values.len() as u32
+    }
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use sails::prelude::*;
+
+#[derive(Encode, Decode, TypeInfo, ReflectHash)]
+#[codec(crate = sails::scale_codec)]
+#[reflect_hash(crate = sails)]
+pub struct Point {
+    x: i64,
+    y: i64,
+}
+
+struct SomeService;
+
+#[sails::service]
+impl SomeService {
+    #[export]
+    pub fn points(&self) -> Vec<Vec<Point>> {
+        Vec::new()
+    }
+}
+
+fn main() {}
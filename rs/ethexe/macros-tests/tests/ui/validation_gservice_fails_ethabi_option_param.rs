@@ -0,0 +1,13 @@
+use sails::prelude::*;
+
+struct SomeService;
+
+#[sails::service]
+impl SomeService {
+    #[export(ethabi)]
+    pub fn set_limit(&mut self, owner: ActorId, limit: Option<u64>) {
+        _ = (owner, limit);
+    }
+}
+
+fn main() {}
@@ -0,0 +1,13 @@
+use sails::prelude::*;
+
+struct SomeService;
+
+#[sails::service]
+impl SomeService {
+    #[export(ethabi)]
+    pub fn parse(&self, value: String) -> Result<u32, String> {
+        value.parse().map_err(|_| value)
+    }
+}
+
+fn main() {}
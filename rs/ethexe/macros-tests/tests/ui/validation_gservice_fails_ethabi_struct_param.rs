@@ -0,0 +1,22 @@
+use sails::prelude::*;
+
+#[derive(Encode, Decode, TypeInfo, ReflectHash)]
+#[codec(crate = sails::scale_codec)]
+#[reflect_hash(crate = sails)]
+pub struct Transfer {
+    to: ActorId,
+    value: u128,
+}
+
+struct SomeService;
+
+#[sails::service]
+impl SomeService {
+    #[export(ethabi)]
+    pub fn transfer(&mut self, amount: u128, transfer: Transfer) -> bool {
+        _ = (amount, transfer);
+        true
+    }
+}
+
+fn main() {}
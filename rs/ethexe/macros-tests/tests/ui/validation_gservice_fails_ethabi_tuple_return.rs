@@ -0,0 +1,13 @@
+use sails::prelude::*;
+
+struct SomeService;
+
+#[sails::service]
+impl SomeService {
+    #[export(ethabi)]
+    pub fn reserves(&self) -> (u128, u128) {
+        (0, 0)
+    }
+}
+
+fn main() {}
@@ -0,0 +1,35 @@
+use sails::prelude::*;
+
+type Balance = u128;
+
+struct SomeService;
+
+#[sails::service]
+impl SomeService {
+    #[export(ethabi, unwrap_result)]
+    pub fn transfer(
+        &mut self,
+        to: ActorId,
+        values: Vec<[Balance; 2]>,
+        recipient: Address,
+        batches: Vec<Vec<u64>>,
+        memo: String,
+    ) -> Result<bool, String> {
+        _ = (to, values, recipient, batches, memo);
+        Ok(true)
+    }
+
+    #[export(ethabi)]
+    pub fn flags(&self, mask: [bool; 8], tags: Vec<i8>) -> Vec<u64> {
+        _ = (mask, tags);
+        Vec::new()
+    }
+
+    #[export(scale)]
+    pub fn scale_only(&self, limit: Option<u64>) -> (u32, Option<String>) {
+        _ = limit;
+        (0, None)
+    }
+}
+
+fn main() {}
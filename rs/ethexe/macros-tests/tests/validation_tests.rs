@@ -5,6 +5,17 @@ fn validation_tests() {
     t.compile_fail("tests/ui/validation_gservice_fails_export_payable_without_ethabi.rs");
     t.compile_fail("tests/ui/validation_gprogram_fails_for_solidity_reserved_name.rs");
     t.compile_fail("tests/ui/validation_gprogram_fails_for_service_constructor_reserved_name.rs");
+    t.compile_fail("tests/ui/validation_gservice_fails_ethabi_struct_param.rs");
+    t.compile_fail("tests/ui/validation_gservice_fails_ethabi_enum_return.rs");
+    t.compile_fail("tests/ui/validation_gservice_fails_ethabi_option_param.rs");
+    t.compile_fail("tests/ui/validation_gservice_fails_ethabi_result_return.rs");
+    t.compile_fail("tests/ui/validation_gservice_fails_ethabi_nested_collection.rs");
+    t.compile_fail("tests/ui/validation_gservice_fails_ethabi_nested_struct.rs");
+    t.compile_fail("tests/ui/validation_gservice_fails_ethabi_map_param.rs");
+    t.compile_fail("tests/ui/validation_gservice_fails_ethabi_tuple_return.rs");
+    t.compile_fail("tests/ui/validation_gservice_fails_ethabi_error_without_eth_error.rs");
+    t.compile_fail("tests/ui/validation_eth_error_fails_unsupported_field.rs");
     t.pass("tests/ui/validation_passes_for_non_exported_name.rs");
     t.pass("tests/ui/validation_passes_ethabi_only_non_scale_type.rs");
+    t.pass("tests/ui/validation_passes_ethabi_supported_types.rs");
 }
@@ -0,0 +1,227 @@
+use crate::rng::Rng;
+use sails::prelude::*;
+use sails_idl_value::{
+    Error, Field, FieldShape, Result, Shape, TypeResolver, Value,
+    ast::{PrimitiveType, TypeDecl},
+};
+
+/// Nesting depth at which generation fails, reached only by recursive types
+/// which cannot be terminated at [`Limits::max_depth`].
+const MAX_NESTING: usize = 64;
+
+/// Bounds of the generated values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Limits {
+    /// Maximal number of items of slices and of characters of strings.
+    pub max_len: usize,
+    /// Nesting depth from which slices are empty, options are `None` and enums take
+    /// the variant with the fewest fields, so recursive types terminate.
+    pub max_depth: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            max_len: 8,
+            max_depth: 4,
+        }
+    }
+}
+
+/// Generates arbitrary well-typed [`Value`]s for the [`TypeDecl`]s of an IDL.
+///
+/// Integers are biased to their edge values, and actor ids are mostly taken from
+/// the known `actors`, so the generated calls reach the interesting branches of a program.
+pub struct ValueGenerator<'a> {
+    resolver: TypeResolver<'a>,
+    limits: Limits,
+    actors: &'a [ActorId],
+}
+
+impl<'a> ValueGenerator<'a> {
+    pub fn new(resolver: TypeResolver<'a>, limits: Limits, actors: &'a [ActorId]) -> Self {
+        Self {
+            resolver,
+            limits,
+            actors,
+        }
+    }
+
+    pub fn resolver(&self) -> &TypeResolver<'a> {
+        &self.resolver
+    }
+
+    /// Generates a value of type `ty`.
+    pub fn generate(&self, ty: &TypeDecl, rng: &mut Rng) -> Result<Value> {
+        self.generate_at(ty, rng, 0)
+    }
+
+    fn generate_at(&self, ty: &TypeDecl, rng: &mut Rng, depth: usize) -> Result<Value> {
+        if depth > MAX_NESTING {
+            return Err(Error::InvalidType(format!(
+                "`{ty}` is nested deeper than {MAX_NESTING}"
+            )));
+        }
+        let shallow = depth >= self.limits.max_depth;
+        let depth = depth + 1;
+        Ok(match self.resolver.shape(ty)? {
+            Shape::Primitive(p) => self.primitive(p, rng),
+            Shape::NonZero(p) => match self.primitive(p, rng) {
+                value if is_zero(&value) => one(p),
+                value => value,
+            },
+            Shape::Slice(item) => {
+                let len = if shallow {
+                    0
+                } else {
+                    rng.index(self.limits.max_len + 1)
+                };
+                Value::Sequence(
+                    (0..len)
+                        .map(|_| self.generate_at(&item, rng, depth))
+                        .collect::<Result<_>>()?,
+                )
+            }
+            Shape::Array(item, len) => Value::Sequence(
+                (0..len)
+                    .map(|_| self.generate_at(&item, rng, depth))
+                    .collect::<Result<_>>()?,
+            ),
+            Shape::Tuple(types) => Value::Tuple(
+                types
+                    .iter()
+                    .map(|ty| self.generate_at(ty, rng, depth))
+                    .collect::<Result<_>>()?,
+            ),
+            Shape::Option(item) => {
+                if shallow || rng.one_in(3) {
+                    Value::none()
+                } else {
+                    Value::some(self.generate_at(&item, rng, depth)?)
+                }
+            }
+            Shape::Result(ok, err) => {
+                if rng.one_in(2) {
+                    Value::ok(self.generate_at(&ok, rng, depth)?)
+                } else {
+                    Value::err(self.generate_at(&err, rng, depth)?)
+                }
+            }
+            Shape::Struct(fields) => Value::Struct(self.fields(&fields, rng, depth)?),
+            Shape::Enum(variants) => {
+                let variant = if shallow {
+                    variants.iter().min_by_key(|variant| variant.fields.len())
+                } else {
+                    variants.get(rng.index(variants.len()))
+                }
+                .ok_or_else(|| Error::InvalidType(format!("`{ty}` has no variants")))?;
+                Value::Variant {
+                    name: variant.name.to_string(),
+                    fields: self.fields(&variant.fields, rng, depth)?,
+                }
+            }
+        })
+    }
+
+    fn fields(&self, fields: &[FieldShape<'_>], rng: &mut Rng, depth: usize) -> Result<Vec<Field>> {
+        fields
+            .iter()
+            .map(|field| {
+                Ok(Field {
+                    name: field.name.map(ToString::to_string),
+                    value: self.generate_at(&field.ty, rng, depth)?,
+                })
+            })
+            .collect()
+    }
+
+    fn primitive(&self, p: PrimitiveType, rng: &mut Rng) -> Value {
+        use PrimitiveType as P;
+        match p {
+            P::Void => Value::Unit,
+            P::Bool => Value::Bool(rng.one_in(2)),
+            P::Char => Value::Char(char(rng)),
+            P::String => Value::String(
+                (0..rng.index(self.limits.max_len + 1))
+                    .map(|_| char(rng))
+                    .collect(),
+            ),
+            P::U8 => Value::U8(int(rng, 8) as u8),
+            P::U16 => Value::U16(int(rng, 16) as u16),
+            P::U32 => Value::U32(int(rng, 32) as u32),
+            P::U64 => Value::U64(int(rng, 64) as u64),
+            P::U128 => Value::U128(int(rng, 128)),
+            P::I8 => Value::I8(int(rng, 8) as i8),
+            P::I16 => Value::I16(int(rng, 16) as i16),
+            P::I32 => Value::I32(int(rng, 32) as i32),
+            P::I64 => Value::I64(int(rng, 64) as i64),
+            P::I128 => Value::I128(int(rng, 128) as i128),
+            P::ActorId => match self.actors {
+                [] => Value::ActorId(decode_random(rng)),
+                _ if rng.one_in(4) => Value::ActorId(decode_random(rng)),
+                actors => Value::ActorId(actors[rng.index(actors.len())]),
+            },
+            P::CodeId => Value::CodeId(decode_random(rng)),
+            P::MessageId => Value::MessageId(decode_random(rng)),
+            P::H160 => Value::H160(decode_random(rng)),
+            P::H256 => Value::H256(decode_random(rng)),
+            P::U256 => Value::U256(decode_random(rng)),
+        }
+    }
+}
+
+/// Returns an integer of `bits` width, an edge value in a quarter of the cases.
+///
+/// The result is truncated by the caller, so `1 << (bits - 1)` and `max >> 1`
+/// are the minimal and maximal values of signed integers.
+fn int(rng: &mut Rng, bits: u32) -> u128 {
+    let max = u128::MAX >> (128 - bits);
+    if rng.one_in(4) {
+        let edges = [0, 1, max, max >> 1, 1 << (bits - 1)];
+        return edges[rng.index(edges.len())];
+    }
+    // Random width, so small values are as likely as large ones.
+    let width = rng.below(bits as u64 + 1) as u32;
+    if width == 0 {
+        return 0;
+    }
+    let random = ((rng.next_u64() as u128) << 64) | rng.next_u64() as u128;
+    random & (max >> (bits - width))
+}
+
+fn char(rng: &mut Rng) -> char {
+    if rng.one_in(8) {
+        char::from_u32(rng.below(0x11_0000) as u32).unwrap_or(char::REPLACEMENT_CHARACTER)
+    } else {
+        (b' ' + rng.below(95) as u8) as char
+    }
+}
+
+fn decode_random<T: Decode>(rng: &mut Rng) -> T {
+    let bytes = rng.bytes(32);
+    T::decode(&mut bytes.as_slice()).expect("32 bytes are enough for the fixed-size primitive")
+}
+
+pub(crate) fn is_zero(value: &Value) -> bool {
+    match value {
+        Value::U8(v) => *v == 0,
+        Value::U16(v) => *v == 0,
+        Value::U32(v) => *v == 0,
+        Value::U64(v) => *v == 0,
+        Value::U128(v) => *v == 0,
+        Value::U256(v) => v.is_zero(),
+        _ => false,
+    }
+}
+
+pub(crate) fn one(p: PrimitiveType) -> Value {
+    use PrimitiveType as P;
+    match p {
+        P::U8 => Value::U8(1),
+        P::U16 => Value::U16(1),
+        P::U32 => Value::U32(1),
+        P::U64 => Value::U64(1),
+        P::U256 => Value::U256(U256::one()),
+        _ => Value::U128(1),
+    }
+}
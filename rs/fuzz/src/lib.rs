@@ -0,0 +1,27 @@
+//! Property-based testing of Sails programs.
+//!
+//! [`Fuzzer`] reads the IDL of a program, from a parsed document or from the `ProgramMeta`
+//! of the program type, and generates arbitrary well-typed calls of its service functions
+//! with a [`ValueGenerator`]:
+//! - [`Fuzzer::run`] sends sequences of calls from multiple actors to a program deployed
+//!   in a `GtestEnv`, checks the invariants registered with [`Fuzzer::with_invariant`]
+//!   after each call and shrinks a failing sequence to a minimal one;
+//! - [`Fuzzer::run_raw`] dispatches malformed payloads to a [`RawTarget`] running the
+//!   program natively, to find panics of the dispatch code generated by `#[program]`.
+//!
+//! Runs are deterministic for a seed, a failure reports the seed reproducing it through
+//! the [`SEED_ENV`] environment variable.
+
+mod generate;
+mod raw;
+mod rng;
+mod runner;
+mod shrink;
+
+pub use generate::{Limits, ValueGenerator};
+pub use raw::{RawFailure, RawOutcome, RawReport, RawTarget};
+pub use rng::Rng;
+pub use runner::{Call, FuzzFailure, FuzzReport, Fuzzer, Outcome, SEED_ENV, Step};
+pub use shrink::shrink;
+// re-export
+pub use sails_idl_value::{Field, Value};
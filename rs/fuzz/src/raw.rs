@@ -0,0 +1,308 @@
+use crate::{Fuzzer, rng::Rng};
+use core::{cell::Cell, fmt};
+use sails::{
+    ActorId, CodeId,
+    client::{DEFAULT_USER_ALICE, NativeEnv, NativeError, NativeParams},
+    gstd::NativeProgram,
+    meta::SailsMessageHeader,
+};
+use sails_idl_value::IdlCodec;
+
+/// Prefixes of the panic messages of the dispatch code generated by `#[program]` and
+/// `#[service]` for payloads matching no constructor or service function.
+const REJECTIONS: &[&str] = &[
+    "Unknown request",
+    "Unknown call",
+    "Unknown route_id",
+    "Unknown service method",
+    "Failed to find matching service",
+    "Failed to decode params",
+    "Failed to decode ctor header",
+    "Non zero ctor interface_id",
+    "Unexpected ctor entry_id",
+];
+
+/// Result of dispatching a raw payload to a [`RawTarget`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RawOutcome {
+    /// The payload was handled, the reply is empty if the program did not reply explicitly.
+    Replied(Vec<u8>),
+    /// The payload was handled and failed with an error reply carrying a Sails header,
+    /// e.g. the `throws` value of a call.
+    Error(Vec<u8>),
+    /// The dispatch code rejected the payload as matching no entry.
+    Rejected(String),
+    /// The program awaits a reply of another program.
+    Pending,
+    /// The program panicked, unless it is a rejection this is a bug found by the fuzzer.
+    Panicked(String),
+}
+
+/// Program running natively which raw payloads are dispatched to, to find panics of the
+/// dispatch code generated by `#[program]` on malformed payloads.
+///
+/// The program is deployed once, so the calls dispatched to it change its state.
+/// Constructor payloads deploy a new program each.
+pub struct RawTarget {
+    env: NativeEnv,
+    code_id: CodeId,
+    program_id: ActorId,
+    salt: Cell<u64>,
+}
+
+impl RawTarget {
+    /// Deploys the program `P` with the encoded constructor call `ctor_payload`.
+    pub fn new<P: NativeProgram + 'static>(
+        ctor_payload: impl AsRef<[u8]>,
+    ) -> Result<Self, NativeError> {
+        let env = NativeEnv::new(DEFAULT_USER_ALICE.into());
+        let code_id = env.submit_code::<P>();
+        let (program_id, _) = env.create_program(code_id, [], ctor_payload, Default::default())?;
+        Ok(Self {
+            env,
+            code_id,
+            program_id,
+            salt: Cell::new(0),
+        })
+    }
+
+    pub fn program_id(&self) -> ActorId {
+        self.program_id
+    }
+
+    /// Dispatches the call `payload` to the program.
+    pub fn dispatch(&self, payload: &[u8]) -> RawOutcome {
+        match self
+            .env
+            .send_for_reply(self.program_id, payload, NativeParams::default())
+        {
+            Ok(reply) => RawOutcome::Replied(reply),
+            Err(err) => classify(err),
+        }
+    }
+
+    /// Deploys a new program with the constructor call `payload`.
+    pub fn init(&self, payload: &[u8]) -> RawOutcome {
+        let salt = self.salt.get() + 1;
+        self.salt.set(salt);
+        match self.env.create_program(
+            self.code_id,
+            salt.to_le_bytes(),
+            payload,
+            NativeParams::default(),
+        ) {
+            Ok((_, reply)) => RawOutcome::Replied(reply),
+            Err(err) => classify(err),
+        }
+    }
+}
+
+fn classify(err: NativeError) -> RawOutcome {
+    match err {
+        NativeError::Panic(payload) if SailsMessageHeader::try_from_bytes(&payload).is_ok() => {
+            RawOutcome::Error(payload)
+        }
+        NativeError::Panic(payload) => {
+            let message = String::from_utf8_lossy(&payload).into_owned();
+            if REJECTIONS.iter().any(|prefix| message.starts_with(prefix)) {
+                RawOutcome::Rejected(message)
+            } else {
+                RawOutcome::Panicked(message)
+            }
+        }
+        NativeError::Pending => RawOutcome::Pending,
+        err => RawOutcome::Panicked(err.to_string()),
+    }
+}
+
+/// Summary of a successful [`Fuzzer::run_raw`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RawReport {
+    pub seed: u64,
+    /// Number of payloads dispatched.
+    pub payloads: usize,
+    pub replied: usize,
+    pub errors: usize,
+    pub rejected: usize,
+    pub pending: usize,
+}
+
+/// Shrunk payload the program panicked on.
+#[derive(Clone)]
+pub struct RawFailure {
+    pub seed: u64,
+    /// `true` if the payload is a constructor call.
+    pub init: bool,
+    pub payload: Vec<u8>,
+    pub message: String,
+}
+
+impl fmt::Display for RawFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.init { "constructor" } else { "call" };
+        writeln!(f, "program panicked on {kind} payload: {}", self.message)?;
+        writeln!(f, "  payload: 0x{}", hex::encode(&self.payload))?;
+        writeln!(
+            f,
+            "reproduce with `{}={}`",
+            crate::runner::SEED_ENV,
+            self.seed
+        )
+    }
+}
+
+impl fmt::Debug for RawFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for RawFailure {}
+
+impl Fuzzer {
+    /// Dispatches `runs * steps` raw payloads to `target`, fails on the first panic which
+    /// is not a rejection of the payload by the dispatch code.
+    ///
+    /// Payloads are derived from the Sails headers and well-typed calls of the IDL: with
+    /// mutated header bytes, truncated, followed by random bytes or entirely random.
+    /// One in eight payloads is a constructor call. The failing payload is shrunk by
+    /// removing bytes while the program keeps panicking.
+    pub fn run_raw(&self, target: &RawTarget) -> Result<RawReport, RawFailure> {
+        let codec = IdlCodec::new(&self.doc);
+        let targets = self.callable();
+        let calls: Vec<_> = targets
+            .iter()
+            .filter_map(|t| codec.call_header(&t.route, &t.func).ok())
+            .map(|header| header.to_bytes())
+            .collect();
+        let ctors: Vec<_> = self
+            .doc
+            .program
+            .iter()
+            .flat_map(|program| &program.ctors)
+            .filter_map(|ctor| codec.ctor_header(&ctor.name).ok())
+            .map(|header| header.to_bytes())
+            .collect();
+
+        let mut report = RawReport {
+            seed: self.seed,
+            ..Default::default()
+        };
+        let mut seeds = Rng::new(self.seed);
+        for run in 0..self.runs {
+            let seed = if run == 0 {
+                self.seed
+            } else {
+                seeds.next_u64()
+            };
+            let mut rng = Rng::new(seed);
+            for _ in 0..self.steps {
+                let init = rng.one_in(8);
+                let payload = if init {
+                    mutate(&mut rng, &ctors, None)
+                } else {
+                    let valid = (!targets.is_empty() && rng.one_in(3)).then(|| {
+                        let call = self.generate_call(&targets, target.program_id(), &mut rng);
+                        codec
+                            .encode_call(&call.route, &call.func, &call.args)
+                            .unwrap_or_default()
+                    });
+                    mutate(&mut rng, &calls, valid)
+                };
+                let dispatch = |payload: &[u8]| {
+                    if init {
+                        target.init(payload)
+                    } else {
+                        target.dispatch(payload)
+                    }
+                };
+                report.payloads += 1;
+                match dispatch(&payload) {
+                    RawOutcome::Replied(_) => report.replied += 1,
+                    RawOutcome::Error(_) => report.errors += 1,
+                    RawOutcome::Rejected(_) => report.rejected += 1,
+                    RawOutcome::Pending => report.pending += 1,
+                    RawOutcome::Panicked(message) => {
+                        let (payload, message) = self.shrink_raw(&dispatch, payload, message);
+                        return Err(RawFailure {
+                            seed,
+                            init,
+                            payload,
+                            message,
+                        });
+                    }
+                }
+            }
+        }
+        Ok(report)
+    }
+
+    fn shrink_raw(
+        &self,
+        dispatch: &impl Fn(&[u8]) -> RawOutcome,
+        mut payload: Vec<u8>,
+        mut message: String,
+    ) -> (Vec<u8>, String) {
+        let mut budget = self.max_shrink_runs;
+        'shrink: while budget > 0 {
+            let mut candidates = vec![payload[..payload.len() / 2].to_vec()];
+            candidates.extend((0..payload.len()).rev().map(|idx| {
+                let mut candidate = payload.clone();
+                candidate.remove(idx);
+                candidate
+            }));
+            for candidate in candidates {
+                if budget == 0 {
+                    break 'shrink;
+                }
+                budget -= 1;
+                if let RawOutcome::Panicked(found) = dispatch(&candidate) {
+                    payload = candidate;
+                    message = found;
+                    continue 'shrink;
+                }
+            }
+            break;
+        }
+        (payload, message)
+    }
+}
+
+/// Returns a payload derived from a random one of the valid `headers` or the `valid` message.
+fn mutate(rng: &mut Rng, headers: &[Vec<u8>], valid: Option<Vec<u8>>) -> Vec<u8> {
+    let mut payload = match valid {
+        Some(valid) => valid,
+        None if headers.is_empty() => Vec::new(),
+        None => headers[rng.index(headers.len())].clone(),
+    };
+    match rng.below(5) {
+        // Corrupted header bytes.
+        0 => {
+            for _ in 0..=rng.below(2) {
+                let idx = rng.index(payload.len().min(16));
+                if let Some(byte) = payload.get_mut(idx) {
+                    *byte = rng.next_u64() as u8;
+                }
+            }
+        }
+        // Truncated payload.
+        1 => {
+            let len = rng.index(payload.len() + 1);
+            payload.truncate(len);
+        }
+        // Random params.
+        2 => {
+            payload.truncate(16);
+            let len = rng.index(64);
+            payload.extend(rng.bytes(len));
+        }
+        // Entirely random bytes.
+        3 => {
+            let len = rng.index(48);
+            payload = rng.bytes(len);
+        }
+        // Unchanged payload.
+        _ => {}
+    }
+    payload
+}
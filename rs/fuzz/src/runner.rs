@@ -0,0 +1,587 @@
+use crate::{
+    generate::{Limits, ValueGenerator},
+    rng::Rng,
+    shrink::shrink,
+};
+use core::fmt;
+use futures::future::LocalBoxFuture;
+use sails::{
+    ActorId,
+    client::{
+        DEFAULT_USER_ALICE, DEFAULT_USER_BOB, DEFAULT_USER_CHARLIE, DEFAULT_USERS_INITIAL_BALANCE,
+        ErrorReplyReason, GtestEnv, GtestError, GtestParams, SimpleExecutionError,
+    },
+};
+use sails_idl_parser_v2::{ast::*, parse_idl};
+use sails_idl_value::{IdlCodec, Value, ast::codec::has_scale_codec};
+
+/// Environment variable overriding the seed of [`Fuzzer`]s, to reproduce a reported failure.
+pub const SEED_ENV: &str = "SAILS_FUZZ_SEED";
+
+/// Service function call sent by an actor.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call {
+    pub actor: ActorId,
+    /// Route of the service exposure, as declared in the program section of the IDL.
+    pub route: String,
+    pub func: String,
+    pub args: Vec<Value>,
+}
+
+impl fmt::Display for Call {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}(", self.route, self.func)?;
+        for (idx, arg) in self.args.iter().enumerate() {
+            if idx > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{arg}")?;
+        }
+        write!(f, ") from {}", self.actor)
+    }
+}
+
+/// Decoded reply to a [`Call`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum Outcome {
+    /// Successful reply value.
+    Ok(Value),
+    /// `throws` value of a failed call.
+    Error(Value),
+    /// The call failed without a decodable error, e.g. with a panic or out of gas.
+    Failed(String),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Ok(value) => write!(f, "Ok: {value}"),
+            Outcome::Error(value) => write!(f, "Error: {value}"),
+            Outcome::Failed(reason) => write!(f, "Failed: {reason}"),
+        }
+    }
+}
+
+/// Executed call an invariant is checked against.
+pub struct Step<'a> {
+    pub env: &'a GtestEnv,
+    pub program_id: ActorId,
+    /// Index of the call in the sequence of the run.
+    pub index: usize,
+    pub call: &'a Call,
+    pub outcome: &'a Outcome,
+}
+
+trait Invariant {
+    fn check<'s>(&'s self, step: &'s Step<'s>) -> LocalBoxFuture<'s, Result<(), String>>;
+}
+
+impl<F: AsyncFn(&Step<'_>) -> Result<(), String> + 'static> Invariant for F {
+    fn check<'s>(&'s self, step: &'s Step<'s>) -> LocalBoxFuture<'s, Result<(), String>> {
+        Box::pin(self(step))
+    }
+}
+
+type Filter = Box<dyn Fn(&str, &str) -> bool>;
+
+/// Service function callable by the fuzzer.
+#[derive(Debug, Clone)]
+pub(crate) struct Target {
+    pub route: String,
+    pub func: String,
+    pub params: Vec<TypeDecl>,
+}
+
+/// Summary of a successful [`Fuzzer::run`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzReport {
+    pub seed: u64,
+    pub runs: usize,
+    /// Number of calls sent.
+    pub calls: usize,
+    /// Number of calls failed with a `throws` error.
+    pub errors: usize,
+    /// Number of calls failed without a decodable error.
+    pub failed: usize,
+}
+
+/// Shrunk sequence of calls violating an invariant.
+#[derive(Clone)]
+pub struct FuzzFailure {
+    /// Seed of the failed run, reproduces it with [`Fuzzer::with_seed`] and a single run.
+    pub seed: u64,
+    pub invariant: String,
+    pub message: String,
+    /// Calls of the shrunk sequence with their outcomes, the invariant fails after the last one.
+    pub steps: Vec<(Call, Outcome)>,
+}
+
+impl fmt::Display for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "invariant `{}` failed: {}", self.invariant, self.message)?;
+        writeln!(f, "reproduce with `{SEED_ENV}={}`", self.seed)?;
+        for (idx, (call, outcome)) in self.steps.iter().enumerate() {
+            writeln!(f, "  {idx}: {call}")?;
+            writeln!(f, "     {outcome}")?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Debug for FuzzFailure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+impl std::error::Error for FuzzFailure {}
+
+/// Violation found by a single sequence of calls.
+struct Violation {
+    invariant: usize,
+    message: String,
+    outcomes: Vec<Outcome>,
+}
+
+/// Property-based tester sending sequences of arbitrary well-typed calls to a program.
+///
+/// Each run deploys the program with the setup passed to [`Fuzzer::run`], then sends
+/// random calls of the service functions from random actors, checking every invariant
+/// after each call. A failing sequence is shrunk by removing calls and simplifying their
+/// arguments while the same invariant keeps failing.
+///
+/// ```rust,ignore
+/// let failure = Fuzzer::from_program_meta::<DemoProgram>()
+///     .with_invariant("counter never panics", async |step: &Step<'_>| {
+///         match step.outcome {
+///             Outcome::Failed(reason) => Err(reason.clone()),
+///             _ => Ok(()),
+///         }
+///     })
+///     .run(async || deploy().await)
+///     .await;
+/// ```
+pub struct Fuzzer {
+    pub(crate) doc: IdlDoc,
+    targets: Vec<Target>,
+    pub(crate) seed: u64,
+    pub(crate) runs: usize,
+    pub(crate) steps: usize,
+    actors: Vec<ActorId>,
+    limits: Limits,
+    pub(crate) max_shrink_runs: usize,
+    filter: Option<Filter>,
+    invariants: Vec<(String, Box<dyn Invariant>)>,
+}
+
+impl Fuzzer {
+    /// Creates a fuzzer calling the service functions exposed by the program of `doc`.
+    ///
+    /// Functions not available through SCALE dispatch are skipped.
+    pub fn new(doc: IdlDoc) -> Self {
+        let targets = targets(&doc);
+        Self {
+            doc,
+            targets,
+            seed: default_seed(),
+            runs: 64,
+            steps: 16,
+            actors: vec![
+                DEFAULT_USER_ALICE.into(),
+                DEFAULT_USER_BOB.into(),
+                DEFAULT_USER_CHARLIE.into(),
+            ],
+            limits: Limits::default(),
+            max_shrink_runs: 256,
+            filter: None,
+            invariants: Vec::new(),
+        }
+    }
+
+    pub fn from_idl(idl: &str) -> sails_idl_parser_v2::error::Result<Self> {
+        Ok(Self::new(parse_idl(idl)?))
+    }
+
+    /// Creates a fuzzer from the IDL generated for the program `P`.
+    pub fn from_program_meta<P: sails::meta::ProgramMeta>() -> Self {
+        let name = core::any::type_name::<P>().rsplit("::").next();
+        let mut idl = String::new();
+        sails::generate_idl::<P>(name, &mut idl).expect("failed to generate the program IDL");
+        Self::from_idl(&idl).expect("generated IDL is valid")
+    }
+
+    /// Sets the seed of the first run, the seeds of the next runs are derived from it.
+    ///
+    /// Defaults to the value of [`SEED_ENV`] if it is set, to a random seed otherwise.
+    pub fn with_seed(self, seed: u64) -> Self {
+        Self { seed, ..self }
+    }
+
+    /// Sets the number of runs, each deploying the program anew. Defaults to `64`.
+    pub fn with_runs(self, runs: usize) -> Self {
+        Self { runs, ..self }
+    }
+
+    /// Sets the number of calls of a run. Defaults to `16`.
+    pub fn with_steps(self, steps: usize) -> Self {
+        Self { steps, ..self }
+    }
+
+    /// Sets the actors sending the calls, which are also the most likely `actor_id` arguments.
+    ///
+    /// Actors are funded before each run. Defaults to Alice, Bob and Charlie of `gtest`.
+    pub fn with_actors(self, actors: impl IntoIterator<Item = ActorId>) -> Self {
+        let actors: Vec<_> = actors.into_iter().collect();
+        assert!(!actors.is_empty(), "at least one actor is required");
+        Self { actors, ..self }
+    }
+
+    pub fn with_limits(self, limits: Limits) -> Self {
+        Self { limits, ..self }
+    }
+
+    /// Sets the maximal number of sequences replayed while shrinking a failure. Defaults to `256`.
+    pub fn with_max_shrink_runs(self, max_shrink_runs: usize) -> Self {
+        Self {
+            max_shrink_runs,
+            ..self
+        }
+    }
+
+    /// Calls only the functions for which `filter` returns `true` given the route and
+    /// the function name.
+    pub fn with_filter(self, filter: impl Fn(&str, &str) -> bool + 'static) -> Self {
+        Self {
+            filter: Some(Box::new(filter)),
+            ..self
+        }
+    }
+
+    /// Adds an invariant checked after each call, an `Err` fails the run with its message.
+    pub fn with_invariant(
+        mut self,
+        name: impl Into<String>,
+        invariant: impl AsyncFn(&Step<'_>) -> Result<(), String> + 'static,
+    ) -> Self {
+        self.invariants.push((name.into(), Box::new(invariant)));
+        self
+    }
+
+    /// Runs the fuzzer, `setup` deploys the program in a new environment and returns
+    /// the environment and the program id.
+    ///
+    /// Panics if the program has no callable functions or arguments of a function cannot
+    /// be generated, e.g. because of an unbounded recursive type.
+    pub async fn run(
+        &self,
+        setup: impl AsyncFn() -> (GtestEnv, ActorId),
+    ) -> Result<FuzzReport, FuzzFailure> {
+        let targets = self.callable();
+        assert!(!targets.is_empty(), "the program has no functions to call");
+
+        let mut report = FuzzReport {
+            seed: self.seed,
+            runs: self.runs,
+            calls: 0,
+            errors: 0,
+            failed: 0,
+        };
+        let mut seeds = Rng::new(self.seed);
+        for run in 0..self.runs {
+            let seed = if run == 0 {
+                self.seed
+            } else {
+                seeds.next_u64()
+            };
+            let mut rng = Rng::new(seed);
+            let (env, program_id) = self.deploy(&setup).await;
+            let mut calls = Vec::with_capacity(self.steps);
+            let mut outcomes = Vec::with_capacity(self.steps);
+            let mut violation = None;
+            for index in 0..self.steps {
+                let call = self.generate_call(&targets, program_id, &mut rng);
+                let outcome = self.send(&env, program_id, &call).await;
+                report.calls += 1;
+                match outcome {
+                    Outcome::Error(_) => report.errors += 1,
+                    Outcome::Failed(_) => report.failed += 1,
+                    Outcome::Ok(_) => {}
+                }
+                calls.push(call);
+                outcomes.push(outcome);
+                let step = Step {
+                    env: &env,
+                    program_id,
+                    index,
+                    call: &calls[index],
+                    outcome: &outcomes[index],
+                };
+                violation = self.check(&step, None).await;
+                if violation.is_some() {
+                    break;
+                }
+            }
+            // `gtest` allows a single `System` per thread, the sequence is replayed in a new one.
+            drop(env);
+            if let Some((invariant, message)) = violation {
+                let violation = Violation {
+                    invariant,
+                    message,
+                    outcomes,
+                };
+                return Err(self.shrink(&setup, seed, calls, violation).await);
+            }
+        }
+        Ok(report)
+    }
+
+    async fn deploy(&self, setup: &impl AsyncFn() -> (GtestEnv, ActorId)) -> (GtestEnv, ActorId) {
+        let (env, program_id) = setup().await;
+        for actor in &self.actors {
+            env.system().mint_to(*actor, DEFAULT_USERS_INITIAL_BALANCE);
+        }
+        (env, program_id)
+    }
+
+    /// Returns the functions passing the filter.
+    pub(crate) fn callable(&self) -> Vec<&Target> {
+        self.targets
+            .iter()
+            .filter(|target| {
+                self.filter
+                    .as_ref()
+                    .is_none_or(|filter| filter(&target.route, &target.func))
+            })
+            .collect()
+    }
+
+    pub(crate) fn generate_call(
+        &self,
+        targets: &[&Target],
+        program_id: ActorId,
+        rng: &mut Rng,
+    ) -> Call {
+        let target = targets[rng.index(targets.len())];
+        let actor = self.actors[rng.index(self.actors.len())];
+        let codec = IdlCodec::new(&self.doc);
+        let (_, service, _) = codec
+            .func_by_route(&target.route, &target.func)
+            .expect("target is resolved from the IDL");
+        let mut pool = self.actors.clone();
+        pool.push(program_id);
+        let generator = ValueGenerator::new(codec.service_resolver(service), self.limits, &pool);
+        let args = target
+            .params
+            .iter()
+            .map(|ty| generator.generate(ty, rng))
+            .collect::<Result<_, _>>()
+            .unwrap_or_else(|err| {
+                panic!(
+                    "failed to generate arguments of `{}/{}`: {err}",
+                    target.route, target.func
+                )
+            });
+        Call {
+            actor,
+            route: target.route.clone(),
+            func: target.func.clone(),
+            args,
+        }
+    }
+
+    async fn send(&self, env: &GtestEnv, program_id: ActorId, call: &Call) -> Outcome {
+        let codec = IdlCodec::new(&self.doc);
+        let payload = match codec.encode_call(&call.route, &call.func, &call.args) {
+            Ok(payload) => payload,
+            Err(err) => return Outcome::Failed(format!("failed to encode call: {err}")),
+        };
+        let params = GtestParams::default().with_actor_id(call.actor);
+        decode_reply(
+            &codec,
+            env.send_for_reply(program_id, payload, params).await,
+        )
+    }
+
+    /// Checks the invariants, or only the invariant `only`, returns the first violated one.
+    async fn check(&self, step: &Step<'_>, only: Option<usize>) -> Option<(usize, String)> {
+        for (idx, (_, invariant)) in self.invariants.iter().enumerate() {
+            if only.is_some_and(|only| only != idx) {
+                continue;
+            }
+            if let Err(message) = invariant.check(step).await {
+                return Some((idx, message));
+            }
+        }
+        None
+    }
+
+    /// Sends `calls` to a newly deployed program, returns the violation of the invariant
+    /// `invariant` with the outcomes up to the violating call.
+    async fn replay(
+        &self,
+        setup: &impl AsyncFn() -> (GtestEnv, ActorId),
+        calls: &[Call],
+        invariant: usize,
+    ) -> Option<Violation> {
+        let (env, program_id) = self.deploy(setup).await;
+        let mut outcomes = Vec::with_capacity(calls.len());
+        for (index, call) in calls.iter().enumerate() {
+            outcomes.push(self.send(&env, program_id, call).await);
+            let step = Step {
+                env: &env,
+                program_id,
+                index,
+                call,
+                outcome: &outcomes[index],
+            };
+            if let Some((_, message)) = self.check(&step, Some(invariant)).await {
+                return Some(Violation {
+                    invariant,
+                    message,
+                    outcomes,
+                });
+            }
+        }
+        None
+    }
+
+    async fn shrink(
+        &self,
+        setup: &impl AsyncFn() -> (GtestEnv, ActorId),
+        seed: u64,
+        mut calls: Vec<Call>,
+        mut violation: Violation,
+    ) -> FuzzFailure {
+        let mut budget = self.max_shrink_runs;
+        'shrink: while budget > 0 {
+            for candidate in self.candidates(&calls) {
+                if budget == 0 {
+                    break 'shrink;
+                }
+                budget -= 1;
+                if let Some(found) = self.replay(setup, &candidate, violation.invariant).await {
+                    calls = candidate;
+                    calls.truncate(found.outcomes.len());
+                    violation = found;
+                    continue 'shrink;
+                }
+            }
+            break;
+        }
+        FuzzFailure {
+            seed,
+            invariant: self.invariants[violation.invariant].0.clone(),
+            message: violation.message,
+            steps: calls.into_iter().zip(violation.outcomes).collect(),
+        }
+    }
+
+    /// Returns simpler sequences than `calls`: with a call removed, then with a simpler
+    /// argument or sent from the first actor.
+    fn candidates(&self, calls: &[Call]) -> Vec<Vec<Call>> {
+        let mut candidates = Vec::new();
+        for idx in 0..calls.len() {
+            let mut candidate = calls.to_vec();
+            candidate.remove(idx);
+            candidates.push(candidate);
+        }
+        let codec = IdlCodec::new(&self.doc);
+        for (idx, call) in calls.iter().enumerate() {
+            let Ok((_, service, func)) = codec.func_by_route(&call.route, &call.func) else {
+                continue;
+            };
+            let resolver = codec.service_resolver(service);
+            for (arg_idx, (param, arg)) in func.params.iter().zip(&call.args).enumerate() {
+                for value in shrink(&resolver, &param.type_decl, arg).unwrap_or_default() {
+                    let mut candidate = calls.to_vec();
+                    candidate[idx].args[arg_idx] = value;
+                    candidates.push(candidate);
+                }
+            }
+            if call.actor != self.actors[0] {
+                let mut candidate = calls.to_vec();
+                candidate[idx].actor = self.actors[0];
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+}
+
+fn default_seed() -> u64 {
+    if let Some(seed) = std::env::var(SEED_ENV)
+        .ok()
+        .and_then(|seed| seed.parse().ok())
+    {
+        return seed;
+    }
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|time| time.as_nanos() as u64)
+        .unwrap_or_default()
+}
+
+/// Collects the functions of the services exposed by the program, including inherited ones.
+fn targets(doc: &IdlDoc) -> Vec<Target> {
+    let Some(program) = &doc.program else {
+        return Vec::new();
+    };
+    let mut targets = Vec::new();
+    for expo in &program.services {
+        let route = expo.route.as_deref().unwrap_or(&expo.name.name);
+        let mut funcs: Vec<&ServiceFunc> = Vec::new();
+        collect_funcs(doc, &expo.name.name, &mut funcs);
+        targets.extend(
+            funcs
+                .into_iter()
+                .filter(|func| has_scale_codec(&func.annotations))
+                .map(|func| Target {
+                    route: route.to_string(),
+                    func: func.name.clone(),
+                    params: func
+                        .params
+                        .iter()
+                        .map(|param| param.type_decl.clone())
+                        .collect(),
+                }),
+        );
+    }
+    targets
+}
+
+fn collect_funcs<'a>(doc: &'a IdlDoc, service: &str, funcs: &mut Vec<&'a ServiceFunc>) {
+    let Some(service) = doc.services.iter().find(|s| s.name.name == service) else {
+        return;
+    };
+    for func in &service.funcs {
+        // Own functions shadow the inherited ones.
+        if !funcs.iter().any(|f| f.name == func.name) {
+            funcs.push(func);
+        }
+    }
+    for base in &service.extends {
+        collect_funcs(doc, &base.name, funcs);
+    }
+}
+
+fn decode_reply(codec: &IdlCodec<'_>, reply: Result<Vec<u8>, GtestError>) -> Outcome {
+    match reply {
+        // Functions returning `()` reply with an empty payload.
+        Ok(payload) if payload.is_empty() => Outcome::Ok(Value::Unit),
+        Ok(payload) => match codec.decode_reply(&payload) {
+            Ok(message) => Outcome::Ok(message.value),
+            Err(err) => Outcome::Failed(format!(
+                "failed to decode reply 0x{}: {err}",
+                hex::encode(payload)
+            )),
+        },
+        Err(GtestError::ReplyHasError(
+            reason @ ErrorReplyReason::Execution(SimpleExecutionError::UserspacePanic),
+            payload,
+        )) => match codec.decode_error(&payload) {
+            Ok(message) => Outcome::Error(message.value),
+            Err(_) => Outcome::Failed(format!("{reason}: {}", String::from_utf8_lossy(&payload))),
+        },
+        Err(err) => Outcome::Failed(err.to_string()),
+    }
+}
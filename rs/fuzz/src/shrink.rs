@@ -0,0 +1,178 @@
+use crate::generate::{is_zero, one};
+use sails_idl_value::{Field, Result, Shape, TypeResolver, Value, ast::TypeDecl};
+
+/// Returns simpler values of type `ty` than `value`, the simplest first.
+///
+/// Every candidate is a valid value of `ty`: integers move towards zero, strings and
+/// slices lose items, options become `None`, nested values are shrunk in place.
+pub fn shrink(resolver: &TypeResolver<'_>, ty: &TypeDecl, value: &Value) -> Result<Vec<Value>> {
+    Ok(match (resolver.shape(ty)?, value) {
+        (Shape::Primitive(_), value) => shrink_primitive(value),
+        (Shape::NonZero(p), value) => shrink_primitive(value)
+            .into_iter()
+            .map(|value| if is_zero(&value) { one(p) } else { value })
+            .filter(|candidate| candidate != value)
+            .collect(),
+        (Shape::Slice(item), Value::Sequence(items)) => {
+            let mut candidates = Vec::new();
+            if !items.is_empty() {
+                candidates.push(Value::Sequence(Vec::new()));
+            }
+            if items.len() > 1 {
+                candidates.push(Value::Sequence(items[..items.len() / 2].to_vec()));
+            }
+            for idx in 0..items.len() {
+                let mut items = items.clone();
+                items.remove(idx);
+                candidates.push(Value::Sequence(items));
+            }
+            candidates.extend(
+                shrink_items(resolver, |_| &item, items)?
+                    .into_iter()
+                    .map(Value::Sequence),
+            );
+            candidates
+        }
+        (Shape::Array(item, _), Value::Sequence(items)) => {
+            shrink_items(resolver, |_| &item, items)?
+                .into_iter()
+                .map(Value::Sequence)
+                .collect()
+        }
+        (Shape::Tuple(types), Value::Tuple(items)) => {
+            shrink_items(resolver, |idx| &types[idx], items)?
+                .into_iter()
+                .map(Value::Tuple)
+                .collect()
+        }
+        (Shape::Option(item), Value::Option(Some(inner))) => {
+            let mut candidates = vec![Value::none()];
+            candidates.extend(shrink(resolver, &item, inner)?.into_iter().map(Value::some));
+            candidates
+        }
+        (Shape::Result(ok, _), Value::Result(Ok(inner))) => shrink(resolver, &ok, inner)?
+            .into_iter()
+            .map(Value::ok)
+            .collect(),
+        (Shape::Result(_, err), Value::Result(Err(inner))) => shrink(resolver, &err, inner)?
+            .into_iter()
+            .map(Value::err)
+            .collect(),
+        (Shape::Struct(shapes), Value::Struct(fields)) => {
+            shrink_fields(resolver, |idx| &shapes[idx].ty, fields)?
+                .into_iter()
+                .map(Value::Struct)
+                .collect()
+        }
+        (Shape::Enum(variants), Value::Variant { name, fields }) => {
+            let Some(variant) = variants.iter().find(|variant| variant.name == name) else {
+                return Ok(Vec::new());
+            };
+            let name = name.clone();
+            shrink_fields(resolver, |idx| &variant.fields[idx].ty, fields)?
+                .into_iter()
+                .map(|fields| Value::Variant {
+                    name: name.clone(),
+                    fields,
+                })
+                .collect()
+        }
+        _ => Vec::new(),
+    })
+}
+
+fn shrink_items<'t>(
+    resolver: &TypeResolver<'_>,
+    ty: impl Fn(usize) -> &'t TypeDecl,
+    items: &[Value],
+) -> Result<Vec<Vec<Value>>> {
+    let mut candidates = Vec::new();
+    for (idx, item) in items.iter().enumerate() {
+        for candidate in shrink(resolver, ty(idx), item)? {
+            let mut items = items.to_vec();
+            items[idx] = candidate;
+            candidates.push(items);
+        }
+    }
+    Ok(candidates)
+}
+
+fn shrink_fields<'t>(
+    resolver: &TypeResolver<'_>,
+    ty: impl Fn(usize) -> &'t TypeDecl,
+    fields: &[Field],
+) -> Result<Vec<Vec<Field>>> {
+    let values: Vec<_> = fields.iter().map(|field| field.value.clone()).collect();
+    Ok(shrink_items(resolver, ty, &values)?
+        .into_iter()
+        .map(|values| {
+            fields
+                .iter()
+                .zip(values)
+                .map(|(field, value)| Field {
+                    name: field.name.clone(),
+                    value,
+                })
+                .collect()
+        })
+        .collect())
+}
+
+fn shrink_primitive(value: &Value) -> Vec<Value> {
+    macro_rules! towards_zero {
+        ($variant:ident, $v:expr, $prev:ident) => {{
+            let v = *$v;
+            let mut candidates = Vec::new();
+            for candidate in [0, v / 2, $prev(v)] {
+                if candidate != v && !candidates.contains(&Value::$variant(candidate)) {
+                    candidates.push(Value::$variant(candidate));
+                }
+            }
+            candidates
+        }};
+    }
+
+    // Steps one towards zero.
+    fn prev_unsigned<T: Copy + PartialEq + From<u8> + core::ops::Sub<Output = T>>(v: T) -> T {
+        if v == T::from(0) { v } else { v - T::from(1) }
+    }
+    fn prev_signed<T: Copy + PartialOrd + From<i8> + core::ops::Sub<Output = T>>(v: T) -> T {
+        match v {
+            v if v > T::from(0) => v - T::from(1),
+            v if v < T::from(0) => v - T::from(-1),
+            v => v,
+        }
+    }
+
+    match value {
+        Value::Bool(true) => vec![Value::Bool(false)],
+        Value::Char(c) if *c != 'a' => vec![Value::Char('a')],
+        Value::String(s) if !s.is_empty() => {
+            let chars: Vec<char> = s.chars().collect();
+            let mut candidates = vec![Value::String(String::new())];
+            if chars.len() > 1 {
+                candidates.push(Value::String(chars[..chars.len() / 2].iter().collect()));
+                candidates.push(Value::String(chars[..chars.len() - 1].iter().collect()));
+            }
+            candidates
+        }
+        Value::U8(v) => towards_zero!(U8, v, prev_unsigned),
+        Value::U16(v) => towards_zero!(U16, v, prev_unsigned),
+        Value::U32(v) => towards_zero!(U32, v, prev_unsigned),
+        Value::U64(v) => towards_zero!(U64, v, prev_unsigned),
+        Value::U128(v) => towards_zero!(U128, v, prev_unsigned),
+        Value::I8(v) => towards_zero!(I8, v, prev_signed),
+        Value::I16(v) => towards_zero!(I16, v, prev_signed),
+        Value::I32(v) => towards_zero!(I32, v, prev_signed),
+        Value::I64(v) => towards_zero!(I64, v, prev_signed),
+        Value::I128(v) => towards_zero!(I128, v, prev_signed),
+        Value::U256(v) if !v.is_zero() => {
+            let mut candidates = vec![Value::U256(Default::default())];
+            if *v > 1.into() {
+                candidates.push(Value::U256(*v / 2));
+            }
+            candidates
+        }
+        _ => Vec::new(),
+    }
+}
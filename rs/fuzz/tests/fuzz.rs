@@ -0,0 +1,248 @@
+use sails::{
+    ActorId, Decode, Encode,
+    cell::RefCell,
+    client::{FaultProgram, GtestEnv},
+    meta::{InterfaceId, SailsMessageHeader},
+};
+use sails_fuzz::*;
+use sails_idl_value::{IdlCodec, TypeResolver, ast};
+use std::rc::Rc;
+
+const COUNTER_IDL: &str = r#"
+!@sails: 2.0.0
+
+service Counter {
+    functions {
+        Add(value: u32) -> u32;
+        Reset();
+        @query
+        Value() -> u32;
+    }
+}
+
+program Counter {
+    constructors {
+        Create();
+    }
+    services {
+        Counter,
+    }
+}
+"#;
+
+const PROGRAM_ID: ActorId = ActorId::new([7; 32]);
+
+/// Counter program adding values with wrapping arithmetic, so the counter decreases
+/// on overflow.
+fn wrapping_counter() -> FaultProgram {
+    let counter = Rc::new(RefCell::new(0u32));
+    FaultProgram::new().with_handler(move |payload| {
+        let mut params = payload;
+        let header = SailsMessageHeader::try_read_bytes(&mut params).ok()?;
+        let mut counter = counter.borrow_mut();
+        match header.entry_id() {
+            0 => *counter = counter.wrapping_add(u32::decode(&mut params).ok()?),
+            1 => *counter = 0,
+            _ => {}
+        }
+        let mut reply = header.to_bytes();
+        if header.entry_id() != 1 {
+            reply.extend(counter.encode());
+        }
+        Some(reply)
+    })
+}
+
+async fn setup() -> (GtestEnv, ActorId) {
+    let env = GtestEnv::system_default();
+    env.inject_faults(PROGRAM_ID, wrapping_counter());
+    (env, PROGRAM_ID)
+}
+
+async fn never_decreases(step: &Step<'_>) -> Result<(), String> {
+    match (&step.call.args[..], step.outcome) {
+        ([Value::U32(value)], Outcome::Ok(Value::U32(counter))) if counter < value => {
+            Err(format!("counter {counter} is less than the added {value}"))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[tokio::test]
+async fn finds_and_shrinks_violation() {
+    let fuzzer = Fuzzer::from_idl(COUNTER_IDL)
+        .unwrap()
+        .with_seed(1)
+        .with_runs(32)
+        .with_invariant("never decreases", never_decreases);
+
+    let failure = fuzzer.run(setup).await.unwrap_err();
+
+    assert_eq!(failure.invariant, "never decreases");
+    // Two additions are required to overflow, any other call is removed.
+    assert_eq!(failure.steps.len(), 2, "{failure}");
+    assert!(
+        failure
+            .steps
+            .iter()
+            .all(|(call, _)| call.func == "Add" && call.actor == failure.steps[0].0.actor)
+    );
+
+    // The reported seed reproduces the failure in a single run.
+    let reproduced = fuzzer
+        .with_seed(failure.seed)
+        .with_runs(1)
+        .with_max_shrink_runs(0)
+        .run(setup)
+        .await
+        .unwrap_err();
+    assert_eq!(reproduced.invariant, failure.invariant);
+}
+
+#[tokio::test]
+async fn reports_runs_without_violations() {
+    let report = Fuzzer::from_idl(COUNTER_IDL)
+        .unwrap()
+        .with_seed(2)
+        .with_runs(4)
+        .with_steps(8)
+        .with_filter(|_, func| func != "Add")
+        .with_invariant("never decreases", never_decreases)
+        .run(setup)
+        .await
+        .unwrap();
+
+    assert_eq!(report.calls, 32);
+    assert_eq!(report.failed, 0);
+}
+
+const VALUES_IDL: &str = r#"
+!@sails: 2.0.0
+
+service Shop {
+    functions {
+        Buy(item: Item<u64>, flags: [Option<bool>; 2], note: Option<String>) -> u8;
+        Lookup(ids: [u32], kind: Kind, nested: [[Option<Kind>]], pair: (i8, Result<char, U256>)) -> u8;
+    }
+    types {
+        struct Item<T> {
+            id: T,
+            owner: ActorId,
+            qty: u32,
+        }
+        enum Kind {
+            Any,
+            Exact(u16),
+            Range {
+                from: i64,
+                to: i64,
+            },
+        }
+    }
+}
+"#;
+
+#[test]
+fn generated_values_are_well_typed() {
+    let doc = sails_idl_parser_v2::parse_idl(VALUES_IDL).unwrap();
+    let service = &doc.services[0];
+    let resolver = IdlCodec::new(&doc).service_resolver(service);
+    let actors = [ActorId::from(1)];
+    let generator = ValueGenerator::new(resolver.clone(), Limits::default(), &actors);
+
+    for seed in 0..256 {
+        let mut rng = Rng::new(seed);
+        for param in service.funcs.iter().flat_map(|func| &func.params) {
+            let ty = &param.type_decl;
+            let value = generator.generate(ty, &mut rng).unwrap();
+            assert_round_trip(&resolver, ty, &value);
+            for candidate in shrink(&resolver, ty, &value).unwrap() {
+                assert_ne!(candidate, value);
+                assert_round_trip(&resolver, ty, &candidate);
+            }
+        }
+    }
+}
+
+fn assert_round_trip(resolver: &TypeResolver<'_>, ty: &ast::TypeDecl, value: &Value) {
+    let encoded = resolver.encode(ty, value).unwrap();
+    let decoded = resolver.decode(ty, &mut encoded.as_slice()).unwrap();
+    assert_eq!(&decoded, value, "`{ty}`");
+}
+
+#[test]
+fn shrinks_integers_towards_zero() {
+    let resolver = TypeResolver::default();
+    let ty = ast::TypeDecl::Primitive(ast::PrimitiveType::I32);
+
+    assert_eq!(
+        shrink(&resolver, &ty, &Value::I32(-10)).unwrap(),
+        [Value::I32(0), Value::I32(-5), Value::I32(-9)]
+    );
+    assert!(shrink(&resolver, &ty, &Value::I32(0)).unwrap().is_empty());
+}
+
+mod calc {
+    use sails::prelude::*;
+
+    pub struct CalcService;
+
+    #[service]
+    impl CalcService {
+        #[export]
+        pub fn divide(&mut self, a: u32, b: u32) -> u32 {
+            a / b
+        }
+
+        #[export]
+        pub fn checked_divide(&mut self, a: u32, b: u32) -> u32 {
+            a.checked_div(b).unwrap_or_default()
+        }
+    }
+
+    pub struct CalcProgram;
+
+    #[program]
+    impl CalcProgram {
+        pub fn create() -> Self {
+            Self
+        }
+
+        pub fn calc(&self) -> CalcService {
+            CalcService
+        }
+    }
+}
+
+fn ctor() -> Vec<u8> {
+    SailsMessageHeader::v1(InterfaceId::zero(), 0, 0).to_bytes()
+}
+
+#[test]
+fn raw_payloads_are_rejected_by_dispatch() {
+    let fuzzer = Fuzzer::from_program_meta::<calc::CalcProgram>()
+        .with_seed(3)
+        .with_filter(|_, func| func == "CheckedDivide");
+    let target = RawTarget::new::<calc::CalcProgram>(ctor()).unwrap();
+
+    let report = fuzzer.run_raw(&target).unwrap();
+
+    assert_eq!(report.payloads, 64 * 16);
+    assert!(report.replied > 0 && report.rejected > 0);
+}
+
+#[test]
+fn raw_payloads_find_panics() {
+    let fuzzer = Fuzzer::from_program_meta::<calc::CalcProgram>()
+        .with_seed(4)
+        .with_filter(|_, func| func == "Divide");
+    let target = RawTarget::new::<calc::CalcProgram>(ctor()).unwrap();
+
+    let failure = fuzzer.run_raw(&target).unwrap_err();
+
+    assert!(failure.message.contains("divide by zero"), "{failure}");
+    assert!(matches!(
+        target.dispatch(&failure.payload),
+        RawOutcome::Panicked(_)
+    ));
+}
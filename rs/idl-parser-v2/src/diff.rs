@@ -0,0 +1,564 @@
+//! Compatibility check between two versions of an IDL.
+//!
+//! [`diff_idl`] compares an old and a new [`IdlDoc`] and lists the changes that matter
+//! to existing clients: removed or renamed functions, changed `entry_id`s, signatures,
+//! `throws` types, event payloads, type definitions, routes and resulting `InterfaceId`s.
+//! Each change is classified as [`Severity::Breaking`] or [`Severity::Compatible`].
+//!
+//! Both documents are expected to be post-processed by [`parse_idl`](crate::parse_idl),
+//! so that `entry_id`s and interface IDs are assigned.
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{Display, Formatter, Result as FmtResult};
+use sails_idl_ast::*;
+
+/// Impact of a change on clients built against the old IDL.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Existing clients keep working.
+    Compatible,
+    /// Existing clients may fail to encode calls or decode replies and events.
+    Breaking,
+}
+
+/// Single difference between two IDLs.
+///
+/// `path` locates the changed item, e.g. `Counter`, `Counter/Add`, `Counter/types/Item`
+/// or `Program/New` for constructors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdlChange {
+    pub path: String,
+    pub kind: ChangeKind,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ChangeKind {
+    ServiceAdded,
+    ServiceRemoved,
+    InterfaceIdChanged {
+        old: InterfaceId,
+        new: InterfaceId,
+    },
+    ExtendsChanged {
+        old: Vec<String>,
+        new: Vec<String>,
+    },
+    FunctionAdded,
+    FunctionRemoved,
+    /// A function was removed and another one with the same signature was added.
+    FunctionRenamed {
+        new_name: String,
+    },
+    EntryIdChanged {
+        old: u16,
+        new: u16,
+    },
+    FunctionKindChanged {
+        old: FunctionKind,
+        new: FunctionKind,
+    },
+    ParamsChanged {
+        old: Vec<FuncParam>,
+        new: Vec<FuncParam>,
+    },
+    /// Parameter names changed while their types stayed the same.
+    ParamsRenamed {
+        old: Vec<FuncParam>,
+        new: Vec<FuncParam>,
+    },
+    OutputChanged {
+        old: TypeDecl,
+        new: TypeDecl,
+    },
+    ThrowsChanged {
+        old: Option<TypeDecl>,
+        new: Option<TypeDecl>,
+    },
+    EventAdded,
+    EventRemoved,
+    EventPayloadChanged,
+    TypeAdded,
+    /// Type is no longer declared. Types still referenced by functions or events cannot be
+    /// removed, so this does not affect encoding.
+    TypeRemoved,
+    TypeChanged,
+    RouteAdded,
+    RouteRemoved,
+    RouteChanged {
+        old_service: String,
+        new_service: String,
+    },
+    RouteIndexChanged {
+        old: u8,
+        new: u8,
+    },
+}
+
+impl ChangeKind {
+    pub fn severity(&self) -> Severity {
+        use ChangeKind::*;
+        match self {
+            ServiceAdded
+            | FunctionAdded
+            | ParamsRenamed { .. }
+            | EventAdded
+            | TypeAdded
+            | TypeRemoved
+            | RouteAdded => Severity::Compatible,
+            ServiceRemoved
+            | InterfaceIdChanged { .. }
+            | ExtendsChanged { .. }
+            | FunctionRemoved
+            | FunctionRenamed { .. }
+            | EntryIdChanged { .. }
+            | FunctionKindChanged { .. }
+            | ParamsChanged { .. }
+            | OutputChanged { .. }
+            | ThrowsChanged { .. }
+            | EventRemoved
+            | EventPayloadChanged
+            | TypeChanged
+            | RouteRemoved
+            | RouteChanged { .. }
+            | RouteIndexChanged { .. } => Severity::Breaking,
+        }
+    }
+}
+
+impl IdlChange {
+    pub fn severity(&self) -> Severity {
+        self.kind.severity()
+    }
+}
+
+impl Display for IdlChange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        use ChangeKind::*;
+        write!(f, "{}: ", self.path)?;
+        match &self.kind {
+            ServiceAdded => f.write_str("service added"),
+            ServiceRemoved => f.write_str("service removed"),
+            InterfaceIdChanged { old, new } => write!(f, "interface ID changed {old} -> {new}"),
+            ExtendsChanged { old, new } => write!(
+                f,
+                "base services changed [{}] -> [{}]",
+                old.join(", "),
+                new.join(", ")
+            ),
+            FunctionAdded => f.write_str("function added"),
+            FunctionRemoved => f.write_str("function removed"),
+            FunctionRenamed { new_name } => write!(f, "function renamed to `{new_name}`"),
+            EntryIdChanged { old, new } => write!(f, "entry ID changed {old} -> {new}"),
+            FunctionKindChanged { old, new } => {
+                write!(f, "function kind changed {old:?} -> {new:?}")
+            }
+            ParamsChanged { old, new } => {
+                write!(f, "parameters changed ({}) -> ({})", join(old), join(new))
+            }
+            ParamsRenamed { old, new } => {
+                write!(f, "parameters renamed ({}) -> ({})", join(old), join(new))
+            }
+            OutputChanged { old, new } => write!(f, "output changed {old} -> {new}"),
+            ThrowsChanged {
+                old: None,
+                new: Some(new),
+            } => write!(f, "throws {new} added"),
+            ThrowsChanged {
+                old: Some(old),
+                new: None,
+            } => write!(f, "throws {old} removed"),
+            ThrowsChanged { old, new } => write!(
+                f,
+                "throws changed {} -> {}",
+                old.as_ref().map(ToString::to_string).unwrap_or_default(),
+                new.as_ref().map(ToString::to_string).unwrap_or_default()
+            ),
+            EventAdded => f.write_str("event added"),
+            EventRemoved => f.write_str("event removed"),
+            EventPayloadChanged => f.write_str("event payload changed"),
+            TypeAdded => f.write_str("type added"),
+            TypeRemoved => f.write_str("type removed"),
+            TypeChanged => f.write_str("type definition changed"),
+            RouteAdded => f.write_str("route added"),
+            RouteRemoved => f.write_str("route removed"),
+            RouteChanged {
+                old_service,
+                new_service,
+            } => write!(f, "route service changed {old_service} -> {new_service}"),
+            RouteIndexChanged { old, new } => write!(f, "route index changed {old} -> {new}"),
+        }
+    }
+}
+
+/// Result of [`diff_idl`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct IdlDiff {
+    pub changes: Vec<IdlChange>,
+}
+
+impl IdlDiff {
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns `true` if any change breaks clients built against the old IDL.
+    pub fn is_breaking(&self) -> bool {
+        self.breaking().next().is_some()
+    }
+
+    pub fn breaking(&self) -> impl Iterator<Item = &IdlChange> {
+        self.changes
+            .iter()
+            .filter(|change| change.severity() == Severity::Breaking)
+    }
+}
+
+/// Compares two IDLs and classifies the differences.
+pub fn diff_idl(old: &IdlDoc, new: &IdlDoc) -> IdlDiff {
+    let mut diff = Differ::default();
+    diff.program(old.program.as_ref(), new.program.as_ref());
+    for old_service in &old.services {
+        match find_service(new, &old_service.name.name) {
+            Some(new_service) => diff.service(old_service, new_service),
+            None => diff.push(&old_service.name.name, ChangeKind::ServiceRemoved),
+        }
+    }
+    for new_service in &new.services {
+        if find_service(old, &new_service.name.name).is_none() {
+            diff.push(&new_service.name.name, ChangeKind::ServiceAdded);
+        }
+    }
+    IdlDiff {
+        changes: diff.changes,
+    }
+}
+
+#[derive(Default)]
+struct Differ {
+    changes: Vec<IdlChange>,
+}
+
+impl Differ {
+    fn push(&mut self, path: impl Into<String>, kind: ChangeKind) {
+        self.changes.push(IdlChange {
+            path: path.into(),
+            kind,
+        });
+    }
+
+    fn program(&mut self, old: Option<&ProgramUnit>, new: Option<&ProgramUnit>) {
+        let (Some(old), Some(new)) = (old, new) else {
+            // Adding or removing the program section changes no service interface,
+            // constructors and routes are compared only between two programs.
+            return;
+        };
+        let scope = new.name.as_str();
+        for old_ctor in &old.ctors {
+            let path = format!("{scope}/{}", old_ctor.name);
+            let Some(new_ctor) = new.ctors.iter().find(|c| c.name == old_ctor.name) else {
+                self.push(path, ChangeKind::FunctionRemoved);
+                continue;
+            };
+            if old_ctor.entry_id != new_ctor.entry_id {
+                self.push(
+                    path.clone(),
+                    ChangeKind::EntryIdChanged {
+                        old: old_ctor.entry_id,
+                        new: new_ctor.entry_id,
+                    },
+                );
+            }
+            self.params(&path, &old_ctor.params, &new_ctor.params);
+            self.throws(&path, &old_ctor.throws, &new_ctor.throws);
+        }
+        for new_ctor in &new.ctors {
+            if !old.ctors.iter().any(|c| c.name == new_ctor.name) {
+                self.push(
+                    format!("{scope}/{}", new_ctor.name),
+                    ChangeKind::FunctionAdded,
+                );
+            }
+        }
+        for old_expo in &old.services {
+            let route = expo_route(old_expo);
+            let path = format!("{scope}/routes/{route}");
+            let Some(new_expo) = new.services.iter().find(|e| expo_route(e) == route) else {
+                self.push(path, ChangeKind::RouteRemoved);
+                continue;
+            };
+            if old_expo.name.name != new_expo.name.name {
+                self.push(
+                    path.clone(),
+                    ChangeKind::RouteChanged {
+                        old_service: old_expo.name.name.clone(),
+                        new_service: new_expo.name.name.clone(),
+                    },
+                );
+            }
+            if old_expo.route_idx != new_expo.route_idx {
+                self.push(
+                    path,
+                    ChangeKind::RouteIndexChanged {
+                        old: old_expo.route_idx,
+                        new: new_expo.route_idx,
+                    },
+                );
+            }
+        }
+        for new_expo in &new.services {
+            let route = expo_route(new_expo);
+            if !old.services.iter().any(|e| expo_route(e) == route) {
+                self.push(format!("{scope}/routes/{route}"), ChangeKind::RouteAdded);
+            }
+        }
+        self.types(scope, &old.types, &new.types);
+    }
+
+    fn service(&mut self, old: &ServiceUnit, new: &ServiceUnit) {
+        let scope = new.name.name.as_str();
+        if let (Some(old_id), Some(new_id)) = (interface_id(old), interface_id(new))
+            && old_id != new_id
+        {
+            self.push(
+                scope,
+                ChangeKind::InterfaceIdChanged {
+                    old: old_id,
+                    new: new_id,
+                },
+            );
+        }
+        let old_extends = base_names(old);
+        let new_extends = base_names(new);
+        if old_extends != new_extends {
+            self.push(
+                scope,
+                ChangeKind::ExtendsChanged {
+                    old: old_extends,
+                    new: new_extends,
+                },
+            );
+        }
+
+        let added: Vec<_> = new
+            .funcs
+            .iter()
+            .filter(|f| !old.funcs.iter().any(|o| o.name == f.name))
+            .collect();
+        let mut renamed = Vec::new();
+        for old_func in &old.funcs {
+            let path = format!("{scope}/{}", old_func.name);
+            let Some(new_func) = new.funcs.iter().find(|f| f.name == old_func.name) else {
+                match added
+                    .iter()
+                    .find(|f| !renamed.contains(&f.name) && same_signature(old_func, f))
+                {
+                    Some(new_func) => {
+                        renamed.push(new_func.name.clone());
+                        self.push(
+                            path,
+                            ChangeKind::FunctionRenamed {
+                                new_name: new_func.name.clone(),
+                            },
+                        );
+                    }
+                    None => self.push(path, ChangeKind::FunctionRemoved),
+                }
+                continue;
+            };
+            self.func(&path, old_func, new_func);
+        }
+        for new_func in added {
+            if !renamed.contains(&new_func.name) {
+                self.push(
+                    format!("{scope}/{}", new_func.name),
+                    ChangeKind::FunctionAdded,
+                );
+            }
+        }
+
+        for old_event in &old.events {
+            let path = format!("{scope}/events/{}", old_event.name);
+            let Some(new_event) = new.events.iter().find(|e| e.name == old_event.name) else {
+                self.push(path, ChangeKind::EventRemoved);
+                continue;
+            };
+            if old_event.entry_id != new_event.entry_id {
+                self.push(
+                    path.clone(),
+                    ChangeKind::EntryIdChanged {
+                        old: old_event.entry_id,
+                        new: new_event.entry_id,
+                    },
+                );
+            }
+            if !same_struct(&old_event.def, &new_event.def) {
+                self.push(path, ChangeKind::EventPayloadChanged);
+            }
+        }
+        for new_event in &new.events {
+            if !old.events.iter().any(|e| e.name == new_event.name) {
+                self.push(
+                    format!("{scope}/events/{}", new_event.name),
+                    ChangeKind::EventAdded,
+                );
+            }
+        }
+
+        self.types(scope, &old.types, &new.types);
+    }
+
+    fn func(&mut self, path: &str, old: &ServiceFunc, new: &ServiceFunc) {
+        if old.entry_id != new.entry_id {
+            self.push(
+                path,
+                ChangeKind::EntryIdChanged {
+                    old: old.entry_id,
+                    new: new.entry_id,
+                },
+            );
+        }
+        if old.kind != new.kind {
+            self.push(
+                path,
+                ChangeKind::FunctionKindChanged {
+                    old: old.kind,
+                    new: new.kind,
+                },
+            );
+        }
+        self.params(path, &old.params, &new.params);
+        if old.output != new.output {
+            self.push(
+                path,
+                ChangeKind::OutputChanged {
+                    old: old.output.clone(),
+                    new: new.output.clone(),
+                },
+            );
+        }
+        self.throws(path, &old.throws, &new.throws);
+    }
+
+    fn params(&mut self, path: &str, old: &[FuncParam], new: &[FuncParam]) {
+        let kind = if !same_param_types(old, new) {
+            ChangeKind::ParamsChanged {
+                old: old.to_vec(),
+                new: new.to_vec(),
+            }
+        } else if old != new {
+            ChangeKind::ParamsRenamed {
+                old: old.to_vec(),
+                new: new.to_vec(),
+            }
+        } else {
+            return;
+        };
+        self.push(path, kind);
+    }
+
+    fn throws(&mut self, path: &str, old: &Option<TypeDecl>, new: &Option<TypeDecl>) {
+        if old != new {
+            self.push(
+                path,
+                ChangeKind::ThrowsChanged {
+                    old: old.clone(),
+                    new: new.clone(),
+                },
+            );
+        }
+    }
+
+    fn types(&mut self, scope: &str, old: &[Type], new: &[Type]) {
+        for old_ty in old {
+            let path = format!("{scope}/types/{}", old_ty.name);
+            match new.iter().find(|t| t.name == old_ty.name) {
+                Some(new_ty) if !same_type(old_ty, new_ty) => {
+                    self.push(path, ChangeKind::TypeChanged)
+                }
+                Some(_) => {}
+                None => self.push(path, ChangeKind::TypeRemoved),
+            }
+        }
+        for new_ty in new {
+            if !old.iter().any(|t| t.name == new_ty.name) {
+                self.push(
+                    format!("{scope}/types/{}", new_ty.name),
+                    ChangeKind::TypeAdded,
+                );
+            }
+        }
+    }
+}
+
+fn find_service<'a>(doc: &'a IdlDoc, name: &str) -> Option<&'a ServiceUnit> {
+    doc.services.iter().find(|s| s.name.name == name)
+}
+
+fn interface_id(service: &ServiceUnit) -> Option<InterfaceId> {
+    service
+        .name
+        .interface_id
+        .or_else(|| service.interface_id().ok())
+}
+
+fn base_names(service: &ServiceUnit) -> Vec<String> {
+    service.extends.iter().map(|s| s.name.clone()).collect()
+}
+
+fn expo_route(expo: &ServiceExpo) -> &str {
+    expo.route.as_deref().unwrap_or(&expo.name.name)
+}
+
+fn join(params: &[FuncParam]) -> String {
+    params
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn same_param_types(old: &[FuncParam], new: &[FuncParam]) -> bool {
+    old.len() == new.len()
+        && old
+            .iter()
+            .zip(new)
+            .all(|(old, new)| old.type_decl == new.type_decl)
+}
+
+fn same_signature(old: &ServiceFunc, new: &ServiceFunc) -> bool {
+    old.kind == new.kind
+        && same_param_types(&old.params, &new.params)
+        && old.output == new.output
+        && old.throws == new.throws
+}
+
+/// Compares the shape of two types ignoring docs and annotations.
+fn same_type(old: &Type, new: &Type) -> bool {
+    old.type_params == new.type_params
+        && match (&old.def, &new.def) {
+            (TypeDef::Struct(old), TypeDef::Struct(new)) => same_struct(old, new),
+            (TypeDef::Enum(old), TypeDef::Enum(new)) => {
+                old.variants.len() == new.variants.len()
+                    && old
+                        .variants
+                        .iter()
+                        .zip(&new.variants)
+                        .all(|(old, new)| old.name == new.name && same_struct(&old.def, &new.def))
+            }
+            (TypeDef::Alias(old), TypeDef::Alias(new)) => old.target == new.target,
+            _ => false,
+        }
+}
+
+fn same_struct(old: &StructDef, new: &StructDef) -> bool {
+    old.fields.len() == new.fields.len()
+        && old
+            .fields
+            .iter()
+            .zip(&new.fields)
+            .all(|(old, new)| old.name == new.name && old.type_decl == new.type_decl)
+}
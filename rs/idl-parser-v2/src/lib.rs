@@ -6,6 +6,7 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+pub mod diff;
 pub mod error;
 mod post_process;
 pub mod preprocess;
@@ -0,0 +1,215 @@
+use sails_idl_parser_v2::{
+    diff::{ChangeKind, IdlChange, Severity, diff_idl},
+    parse_idl,
+};
+
+const BASE: &str = r#"
+    service Counter {
+        events {
+            Added(u32),
+            Reset,
+        }
+        functions {
+            /// Adds a value.
+            Add(value: u32) -> u32;
+            Sub(value: u32) -> u32 throws String;
+            @query
+            Value() -> u32;
+        }
+        types {
+            struct Limits {
+                min: u32,
+                max: u32,
+            }
+        }
+    }
+
+    program Demo {
+        constructors {
+            New(limit: u32);
+        }
+        services {
+            Counter,
+        }
+    }
+"#;
+
+fn diff(old: &str, new: &str) -> Vec<IdlChange> {
+    let old = parse_idl(old).expect("parse old IDL");
+    let new = parse_idl(new).expect("parse new IDL");
+    diff_idl(&old, &new).changes
+}
+
+fn kinds(changes: &[IdlChange]) -> Vec<(&str, &ChangeKind)> {
+    changes
+        .iter()
+        .map(|change| (change.path.as_str(), &change.kind))
+        .collect()
+}
+
+#[test]
+fn diff_ignores_docs_and_formatting() {
+    let new = BASE
+        .replace("/// Adds a value.", "")
+        .replace("min: u32,", "/// Lower bound.\n min: u32,");
+    let old = parse_idl(BASE).unwrap();
+    let diff = diff_idl(&old, &parse_idl(&new).unwrap());
+    assert!(diff.is_empty(), "{:?}", diff.changes);
+    assert!(!diff.is_breaking());
+}
+
+#[test]
+fn diff_detects_removed_and_added_functions() {
+    // `Mul` takes the place of `Sub`, so entry IDs stay the same.
+    let new = BASE
+        .replace(
+            "Sub(value: u32) -> u32 throws String;",
+            "Mul(factor: u16) -> u32;",
+        )
+        .replace("Add(value: u32)", "Add(amount: u32)");
+    let changes = diff(BASE, &new);
+    let counter = parse_idl(BASE).unwrap().services[0].name.interface_id;
+    let new_counter = parse_idl(&new).unwrap().services[0].name.interface_id;
+    assert_eq!(
+        kinds(&changes),
+        vec![
+            (
+                "Counter",
+                &ChangeKind::InterfaceIdChanged {
+                    old: counter.unwrap(),
+                    new: new_counter.unwrap()
+                }
+            ),
+            (
+                "Counter/Add",
+                &ChangeKind::ParamsRenamed {
+                    old: parse_idl(BASE).unwrap().services[0].funcs[0].params.clone(),
+                    new: parse_idl(&new).unwrap().services[0].funcs[0].params.clone(),
+                }
+            ),
+            ("Counter/Sub", &ChangeKind::FunctionRemoved),
+            ("Counter/Mul", &ChangeKind::FunctionAdded),
+        ]
+    );
+    let severities: Vec<_> = changes.iter().map(IdlChange::severity).collect();
+    assert_eq!(
+        severities,
+        vec![
+            Severity::Breaking,
+            Severity::Compatible,
+            Severity::Breaking,
+            Severity::Compatible
+        ]
+    );
+}
+
+#[test]
+fn diff_detects_renames_and_shifted_entry_ids() {
+    let new = BASE.replace("Sub(value: u32)", "Decrement(value: u32)");
+    let changes = diff(BASE, &new);
+    assert_eq!(
+        kinds(&changes)[1..],
+        [(
+            "Counter/Sub",
+            &ChangeKind::FunctionRenamed {
+                new_name: "Decrement".into()
+            }
+        )]
+    );
+
+    // Entry IDs follow the sorted function names, so a new function shifts the following ones.
+    let new = BASE.replace("/// Adds a value.", "Init();");
+    let changes = diff(BASE, &new);
+    assert_eq!(
+        kinds(&changes)[1..],
+        [
+            (
+                "Counter/Sub",
+                &ChangeKind::EntryIdChanged { old: 1, new: 2 }
+            ),
+            (
+                "Counter/Value",
+                &ChangeKind::EntryIdChanged { old: 2, new: 3 }
+            ),
+            ("Counter/Init", &ChangeKind::FunctionAdded),
+        ]
+    );
+}
+
+#[test]
+fn diff_detects_signature_changes() {
+    let new = BASE
+        .replace(
+            "Add(value: u32) -> u32;",
+            "Add(value: u64) -> u32 throws String;",
+        )
+        .replace(
+            "Sub(value: u32) -> u32 throws String;",
+            "Sub(value: u32) -> u64;",
+        )
+        .replace("@query\n            Value()", "Value()");
+    let changes = diff(BASE, &new);
+    assert_eq!(
+        changes[1..]
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        [
+            "Counter/Add: parameters changed (value: u32) -> (value: u64)",
+            "Counter/Add: throws String added",
+            "Counter/Sub: output changed u32 -> u64",
+            "Counter/Sub: throws String removed",
+            "Counter/Value: function kind changed Query -> Command",
+        ]
+    );
+    assert!(changes.iter().all(|c| c.severity() == Severity::Breaking));
+}
+
+#[test]
+fn diff_detects_event_and_type_changes() {
+    let new = BASE
+        .replace("Added(u32),", "Added(u64),\n            Cleared,")
+        .replace("Reset,\n", "")
+        .replace("max: u32,", "max: u64,");
+    let changes = diff(BASE, &new);
+    let kinds = kinds(&changes);
+    assert_eq!(
+        kinds[1..],
+        [
+            ("Counter/events/Added", &ChangeKind::EventPayloadChanged),
+            ("Counter/events/Reset", &ChangeKind::EventRemoved),
+            ("Counter/events/Cleared", &ChangeKind::EventAdded),
+            ("Counter/types/Limits", &ChangeKind::TypeChanged),
+        ]
+    );
+}
+
+#[test]
+fn diff_detects_program_changes() {
+    let new = BASE
+        .replace(
+            "New(limit: u32);",
+            "New(limit: u32) throws String;\n Default();",
+        )
+        .replace(
+            "Counter,\n        }",
+            "Counter,\n            Counter: Extra,\n        }",
+        );
+    let changes = diff(BASE, &new);
+    assert_eq!(
+        changes.iter().map(ToString::to_string).collect::<Vec<_>>(),
+        [
+            "Demo/New: throws String added",
+            "Demo/Default: function added",
+            "Demo/routes/Extra: route added",
+        ]
+    );
+
+    let removed = BASE.replace("Counter,\n        }", "}");
+    let diff = diff_idl(&parse_idl(BASE).unwrap(), &parse_idl(&removed).unwrap());
+    assert!(diff.is_breaking());
+    assert_eq!(
+        diff.breaking().map(|c| c.path.as_str()).collect::<Vec<_>>(),
+        ["Demo/routes/Counter"]
+    );
+}
@@ -0,0 +1,56 @@
+use alloc::string::String;
+use sails_idl_ast::InterfaceId;
+use thiserror::Error;
+
+pub type Result<T, E = Error> = core::result::Result<T, E>;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("unknown type `{0}`")]
+    UnknownType(String),
+    #[error("unresolved generic parameter `{0}`")]
+    UnresolvedGeneric(String),
+    #[error("invalid type: {0}")]
+    InvalidType(String),
+    #[error("value does not match type `{ty}`: {reason}")]
+    TypeMismatch { ty: String, reason: String },
+    #[error("codec error: {0}")]
+    Codec(String),
+    #[error("unexpected {0} trailing bytes")]
+    TrailingBytes(usize),
+    #[error("header error: {0}")]
+    Header(&'static str),
+    #[error("payload is empty")]
+    EmptyPayload,
+    #[error("IDL has no program section")]
+    NoProgram,
+    #[error("no service with interface_id {0}")]
+    UnknownInterface(InterfaceId),
+    #[error("no route with index {0}")]
+    UnknownRoute(u8),
+    #[error("unknown service `{0}`")]
+    UnknownService(String),
+    #[error("no {kind} with entry_id {entry_id} in `{scope}`")]
+    UnknownEntryId {
+        kind: &'static str,
+        scope: String,
+        entry_id: u16,
+    },
+    #[error("unknown {kind} `{name}` in `{scope}`")]
+    UnknownEntry {
+        kind: &'static str,
+        scope: String,
+        name: String,
+    },
+    #[error("`{0}` does not declare a `throws` type")]
+    NoThrows(String),
+    #[error("expected {expected} arguments, got {actual}")]
+    ArgumentCount { expected: usize, actual: usize },
+}
+
+impl From<parity_scale_codec::Error> for Error {
+    fn from(err: parity_scale_codec::Error) -> Self {
+        use alloc::string::ToString as _;
+        Error::Codec(err.to_string())
+    }
+}
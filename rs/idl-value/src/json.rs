@@ -0,0 +1,407 @@
+//! Type-directed conversion between [`Value`]s and JSON.
+//!
+//! JSON mapping:
+//! - `()` is `null`, `bool`, `String` and integers up to 64 bits are JSON booleans, strings and numbers;
+//! - `u128`, `i128` and `U256` are decimal strings, numbers are accepted on input as well;
+//!   `U256` also accepts `0x`-prefixed hex strings;
+//! - `char` is a single-character string;
+//! - `ActorId`, `CodeId`, `MessageId`, `H160` and `H256` are `0x`-prefixed hex strings;
+//! - slices, arrays and tuples are arrays; `[u8]` and `[u8; N]` also accept hex strings;
+//! - `Option<T>` is `null` or the value of `T`;
+//! - `Result<T, E>` is `{ "ok": T }` or `{ "err": E }`;
+//! - structs with named fields are objects, tuple structs are arrays, except for
+//!   single-field tuple structs which are the value of the field;
+//! - unit variants are strings with the variant name, other variants are objects
+//!   with a single key holding the variant payload encoded as a struct.
+use crate::{
+    error::{Error, Result},
+    message::{DecodedMessage, IdlCodec},
+    resolver::{FieldShape, Shape, TypeResolver, mismatch},
+    value::{Field, Value},
+};
+use alloc::{borrow::ToOwned, format, string::ToString as _, vec::Vec};
+use gprimitives::{ActorId, CodeId, H160, H256, MessageId, U256};
+use sails_idl_ast::*;
+use serde_json::{Map, Value as Json};
+
+impl Value {
+    /// Converts the value to JSON.
+    ///
+    /// The conversion needs no type information: it is the inverse of
+    /// [`TypeResolver::from_json`] for the type the value was decoded with.
+    pub fn to_json(&self) -> Json {
+        use Value::*;
+        match self {
+            Unit => Json::Null,
+            Bool(v) => Json::Bool(*v),
+            Char(v) => Json::String(v.to_string()),
+            String(v) => Json::String(v.clone()),
+            U8(v) => (*v).into(),
+            U16(v) => (*v).into(),
+            U32(v) => (*v).into(),
+            U64(v) => (*v).into(),
+            U128(v) => Json::String(v.to_string()),
+            I8(v) => (*v).into(),
+            I16(v) => (*v).into(),
+            I32(v) => (*v).into(),
+            I64(v) => (*v).into(),
+            I128(v) => Json::String(v.to_string()),
+            ActorId(v) => hex_string(v.as_ref()),
+            CodeId(v) => hex_string(v.as_ref()),
+            MessageId(v) => hex_string(v.as_ref()),
+            H160(v) => hex_string(v.as_bytes()),
+            H256(v) => hex_string(v.as_bytes()),
+            U256(v) => Json::String(v.to_string()),
+            Sequence(items) | Tuple(items) => {
+                Json::Array(items.iter().map(Value::to_json).collect())
+            }
+            Option(None) => Json::Null,
+            Option(Some(v)) => v.to_json(),
+            Result(Ok(v)) => single_key("ok", v.to_json()),
+            Result(Err(v)) => single_key("err", v.to_json()),
+            Struct(fields) => fields_to_json(fields),
+            Variant { name, fields } if fields.is_empty() => Json::String(name.clone()),
+            Variant { name, fields } => single_key(name, fields_to_json(fields)),
+        }
+    }
+}
+
+impl DecodedMessage {
+    /// Converts the message to a JSON object with `service`, `route`, `name` and `value` keys.
+    pub fn to_json(&self) -> Json {
+        let mut map = Map::new();
+        map.insert("service".to_owned(), self.service.clone().into());
+        map.insert("route".to_owned(), self.route.clone().into());
+        map.insert("name".to_owned(), self.name.clone().into());
+        map.insert("value".to_owned(), self.value.to_json());
+        Json::Object(map)
+    }
+}
+
+impl TypeResolver<'_> {
+    /// Converts `json` to a [`Value`] of type `ty`.
+    pub fn from_json(&self, ty: &TypeDecl, json: &Json) -> Result<Value> {
+        Ok(match self.shape(ty)? {
+            Shape::Primitive(p) | Shape::NonZero(p) => primitive_from_json(ty, p, json)?,
+            Shape::Slice(item) => Value::Sequence(self.items_from_json(ty, &item, json, None)?),
+            Shape::Array(item, len) => {
+                Value::Sequence(self.items_from_json(ty, &item, json, Some(len as usize))?)
+            }
+            Shape::Tuple(types) => {
+                let items = as_array(ty, json, Some(types.len()))?;
+                Value::Tuple(
+                    types
+                        .iter()
+                        .zip(items)
+                        .map(|(ty, json)| self.from_json(ty, json))
+                        .collect::<Result<_>>()?,
+                )
+            }
+            Shape::Option(item) => match json {
+                Json::Null => Value::none(),
+                json => Value::some(self.from_json(&item, json)?),
+            },
+            Shape::Result(ok, err) => match single_entry(json) {
+                Some(("ok", json)) => Value::ok(self.from_json(&ok, json)?),
+                Some(("err", json)) => Value::err(self.from_json(&err, json)?),
+                _ => return Err(mismatch(ty, "expected `{\"ok\": ..}` or `{\"err\": ..}`")),
+            },
+            Shape::Struct(fields) => Value::Struct(self.fields_from_json(ty, &fields, json)?),
+            Shape::Enum(variants) => {
+                let (name, payload) = match json {
+                    Json::String(name) => (name.as_str(), None),
+                    json => single_entry(json)
+                        .map(|(name, payload)| (name, Some(payload)))
+                        .ok_or_else(|| {
+                            mismatch(ty, "expected variant name or `{\"Variant\": ..}`")
+                        })?,
+                };
+                let variant = variants
+                    .iter()
+                    .find(|v| v.name == name)
+                    .ok_or_else(|| mismatch(ty, format!("unknown variant `{name}`")))?;
+                let fields = match payload {
+                    None if variant.fields.is_empty() => Vec::new(),
+                    None => return Err(mismatch(ty, format!("variant `{name}` has fields"))),
+                    Some(payload) => self.fields_from_json(ty, &variant.fields, payload)?,
+                };
+                Value::variant(name, fields)
+            }
+        })
+    }
+
+    /// Converts `json` to SCALE bytes of type `ty`.
+    pub fn encode_json(&self, ty: &TypeDecl, json: &Json) -> Result<Vec<u8>> {
+        self.encode(ty, &self.from_json(ty, json)?)
+    }
+
+    /// Decodes a value of type `ty` consuming the whole `bytes` and converts it to JSON.
+    pub fn decode_json(&self, ty: &TypeDecl, bytes: &[u8]) -> Result<Json> {
+        Ok(self.decode_all(ty, bytes)?.to_json())
+    }
+
+    fn items_from_json(
+        &self,
+        ty: &TypeDecl,
+        item: &TypeDecl,
+        json: &Json,
+        len: Option<usize>,
+    ) -> Result<Vec<Value>> {
+        if let (Json::String(s), TypeDecl::Primitive(PrimitiveType::U8)) = (json, item) {
+            let bytes = parse_hex(ty, s)?;
+            if let Some(len) = len
+                && bytes.len() != len
+            {
+                return Err(mismatch(
+                    ty,
+                    format!("expected {len} bytes, got {}", bytes.len()),
+                ));
+            }
+            return Ok(bytes.into_iter().map(Value::U8).collect());
+        }
+        as_array(ty, json, len)?
+            .iter()
+            .map(|json| self.from_json(item, json))
+            .collect()
+    }
+
+    fn fields_from_json(
+        &self,
+        ty: &TypeDecl,
+        shapes: &[FieldShape<'_>],
+        json: &Json,
+    ) -> Result<Vec<Field>> {
+        match (shapes, json) {
+            ([], Json::Null) => Ok(Vec::new()),
+            ([shape], json) if shape.name.is_none() => Ok(alloc::vec![Field::unnamed(
+                self.from_json(&shape.ty, json)?
+            )]),
+            (shapes, Json::Object(map)) if shapes.iter().all(|s| s.name.is_some()) => {
+                if let Some(key) = map
+                    .keys()
+                    .find(|key| !shapes.iter().any(|s| s.name == Some(key)))
+                {
+                    return Err(mismatch(ty, format!("unknown field `{key}`")));
+                }
+                shapes
+                    .iter()
+                    .map(|shape| {
+                        let name = shape.name.unwrap_or_default();
+                        let json = map
+                            .get(name)
+                            .ok_or_else(|| mismatch(ty, format!("missing field `{name}`")))?;
+                        Ok(Field::named(name, self.from_json(&shape.ty, json)?))
+                    })
+                    .collect()
+            }
+            (shapes, json) => shapes
+                .iter()
+                .zip(as_array(ty, json, Some(shapes.len()))?)
+                .map(|(shape, json)| {
+                    Ok(Field {
+                        name: shape.name.map(ToOwned::to_owned),
+                        value: self.from_json(&shape.ty, json)?,
+                    })
+                })
+                .collect(),
+        }
+    }
+}
+
+impl IdlCodec<'_> {
+    /// Encodes a constructor call with `args` given as a JSON array of positional
+    /// arguments or as a JSON object keyed by parameter names.
+    pub fn encode_ctor_json(&self, name: &str, args: &Json) -> Result<Vec<u8>> {
+        let ctor = self.ctor_by_name(name)?;
+        let args = params_from_json(&self.program_resolver(), &ctor.params, args)?;
+        self.encode_ctor(name, &args)
+    }
+
+    /// Encodes a call of `func` on the service exposed as `route` with `args` given as
+    /// a JSON array of positional arguments or as a JSON object keyed by parameter names.
+    pub fn encode_call_json(&self, route: &str, func: &str, args: &Json) -> Result<Vec<u8>> {
+        let (_, service, func_def) = self.func_by_route(route, func)?;
+        let args = params_from_json(&self.service_resolver(service), &func_def.params, args)?;
+        self.encode_call(route, func, &args)
+    }
+}
+
+fn params_from_json(
+    resolver: &TypeResolver<'_>,
+    params: &[FuncParam],
+    args: &Json,
+) -> Result<Vec<Value>> {
+    match args {
+        Json::Array(items) => {
+            if items.len() != params.len() {
+                return Err(Error::ArgumentCount {
+                    expected: params.len(),
+                    actual: items.len(),
+                });
+            }
+            params
+                .iter()
+                .zip(items)
+                .map(|(param, json)| resolver.from_json(&param.type_decl, json))
+                .collect()
+        }
+        Json::Object(map) => {
+            if let Some(key) = map
+                .keys()
+                .find(|key| !params.iter().any(|p| &p.name == *key))
+            {
+                return Err(Error::UnknownEntry {
+                    kind: "parameter",
+                    scope: "arguments".to_string(),
+                    name: key.clone(),
+                });
+            }
+            params
+                .iter()
+                .map(|param| {
+                    let json = map.get(&param.name).ok_or_else(|| Error::TypeMismatch {
+                        ty: param.type_decl.to_string(),
+                        reason: format!("missing argument `{}`", param.name),
+                    })?;
+                    resolver.from_json(&param.type_decl, json)
+                })
+                .collect()
+        }
+        Json::Null if params.is_empty() => Ok(Vec::new()),
+        _ => Err(Error::TypeMismatch {
+            ty: "arguments".to_string(),
+            reason: "expected array or object".to_string(),
+        }),
+    }
+}
+
+fn primitive_from_json(ty: &TypeDecl, p: PrimitiveType, json: &Json) -> Result<Value> {
+    use PrimitiveType as P;
+    let value = match (p, json) {
+        (P::Void, Json::Null) => Value::Unit,
+        (P::Bool, Json::Bool(v)) => Value::Bool(*v),
+        (P::Char, Json::String(s)) => {
+            let mut chars = s.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) => Value::Char(c),
+                _ => return Err(mismatch(ty, "expected a single character")),
+            }
+        }
+        (P::String, Json::String(s)) => Value::String(s.clone()),
+        (P::U8, json) => Value::U8(int_from_json(ty, json)?),
+        (P::U16, json) => Value::U16(int_from_json(ty, json)?),
+        (P::U32, json) => Value::U32(int_from_json(ty, json)?),
+        (P::U64, json) => Value::U64(int_from_json(ty, json)?),
+        (P::U128, json) => Value::U128(int_from_json(ty, json)?),
+        (P::I8, json) => Value::I8(int_from_json(ty, json)?),
+        (P::I16, json) => Value::I16(int_from_json(ty, json)?),
+        (P::I32, json) => Value::I32(int_from_json(ty, json)?),
+        (P::I64, json) => Value::I64(int_from_json(ty, json)?),
+        (P::I128, json) => Value::I128(int_from_json(ty, json)?),
+        (P::ActorId, Json::String(s)) => Value::ActorId(ActorId::from(hex_array::<32>(ty, s)?)),
+        (P::CodeId, Json::String(s)) => Value::CodeId(CodeId::from(hex_array::<32>(ty, s)?)),
+        (P::MessageId, Json::String(s)) => {
+            Value::MessageId(MessageId::from(hex_array::<32>(ty, s)?))
+        }
+        (P::H160, Json::String(s)) => Value::H160(H160::from(hex_array::<20>(ty, s)?)),
+        (P::H256, Json::String(s)) => Value::H256(H256::from(hex_array::<32>(ty, s)?)),
+        (P::U256, json) => Value::U256(u256_from_json(ty, json)?),
+        (_, json) => return Err(mismatch(ty, format!("found {}", json_kind(json)))),
+    };
+    Ok(value)
+}
+
+/// Parses an integer from a JSON number or a decimal string.
+fn int_from_json<T: core::str::FromStr>(ty: &TypeDecl, json: &Json) -> Result<T> {
+    let s = match json {
+        Json::Number(n) => n.to_string(),
+        Json::String(s) => s.clone(),
+        json => return Err(mismatch(ty, format!("found {}", json_kind(json)))),
+    };
+    s.parse()
+        .map_err(|_| mismatch(ty, format!("`{s}` is not a valid integer")))
+}
+
+fn u256_from_json(ty: &TypeDecl, json: &Json) -> Result<U256> {
+    let parsed = match json {
+        Json::Number(n) => n.as_u64().map(U256::from),
+        Json::String(s) => match s.strip_prefix("0x") {
+            Some(hex) => U256::from_str_radix(hex, 16).ok(),
+            None => U256::from_dec_str(s).ok(),
+        },
+        json => return Err(mismatch(ty, format!("found {}", json_kind(json)))),
+    };
+    parsed.ok_or_else(|| mismatch(ty, format!("`{json}` is not a valid U256")))
+}
+
+fn parse_hex(ty: &TypeDecl, s: &str) -> Result<Vec<u8>> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    hex::decode(s).map_err(|err| mismatch(ty, format!("invalid hex string: {err}")))
+}
+
+fn hex_array<const N: usize>(ty: &TypeDecl, s: &str) -> Result<[u8; N]> {
+    let mut bytes = [0u8; N];
+    hex::decode_to_slice(s.strip_prefix("0x").unwrap_or(s), &mut bytes)
+        .map_err(|err| mismatch(ty, format!("invalid hex string: {err}")))?;
+    Ok(bytes)
+}
+
+fn hex_string(bytes: &[u8]) -> Json {
+    Json::String(format!("0x{}", hex::encode(bytes)))
+}
+
+fn as_array<'j>(ty: &TypeDecl, json: &'j Json, len: Option<usize>) -> Result<&'j Vec<Json>> {
+    let Json::Array(items) = json else {
+        return Err(mismatch(
+            ty,
+            format!("expected array, found {}", json_kind(json)),
+        ));
+    };
+    if let Some(len) = len
+        && items.len() != len
+    {
+        return Err(mismatch(
+            ty,
+            format!("expected {len} items, got {}", items.len()),
+        ));
+    }
+    Ok(items)
+}
+
+fn single_entry(json: &Json) -> Option<(&str, &Json)> {
+    match json {
+        Json::Object(map) if map.len() == 1 => map.iter().next().map(|(k, v)| (k.as_str(), v)),
+        _ => None,
+    }
+}
+
+fn single_key(key: &str, value: Json) -> Json {
+    let mut map = Map::new();
+    map.insert(key.to_owned(), value);
+    Json::Object(map)
+}
+
+fn fields_to_json(fields: &[Field]) -> Json {
+    match fields {
+        [] => Json::Null,
+        [field] if field.name.is_none() => field.value.to_json(),
+        fields if fields.iter().all(|f| f.name.is_some()) => Json::Object(
+            fields
+                .iter()
+                .map(|f| (f.name.clone().unwrap_or_default(), f.value.to_json()))
+                .collect(),
+        ),
+        fields => Json::Array(fields.iter().map(|f| f.value.to_json()).collect()),
+    }
+}
+
+fn json_kind(json: &Json) -> &'static str {
+    match json {
+        Json::Null => "null",
+        Json::Bool(_) => "boolean",
+        Json::Number(_) => "number",
+        Json::String(_) => "string",
+        Json::Array(_) => "array",
+        Json::Object(_) => "object",
+    }
+}
@@ -0,0 +1,30 @@
+//! Dynamic, IDL-driven codec for Sails messages.
+//!
+//! Generated clients decode payloads through static Rust types. This crate does the
+//! same at runtime using only a parsed [`IdlDoc`](sails_idl_ast::IdlDoc):
+//! - [`Value`] is a dynamic value tree able to hold any IDL type;
+//! - [`TypeResolver`] encodes and decodes [`Value`]s in SCALE for any [`TypeDecl`](sails_idl_ast::TypeDecl),
+//!   resolving named types, aliases and generics from the IDL `types { ... }` sections;
+//! - [`IdlCodec`] reads the `SailsMessageHeader` of a payload and resolves the matching
+//!   constructor, service function or event, so calls, replies, `throws` errors and
+//!   events of any program can be decoded by a single binary.
+//!
+//! With the `serde` feature, values can also be converted from and to JSON guided by
+//! their IDL types, see [`TypeResolver::from_json`] and [`Value::to_json`].
+#![no_std]
+
+extern crate alloc;
+
+mod error;
+#[cfg(feature = "serde")]
+mod json;
+mod message;
+mod resolver;
+mod value;
+
+pub use error::{Error, Result};
+pub use message::{DecodedMessage, EntryKind, IdlCodec, MessageKind};
+pub use resolver::{FieldShape, Shape, TypeResolver, VariantShape};
+pub use value::{Field, Value};
+// re-export
+pub use sails_idl_ast as ast;
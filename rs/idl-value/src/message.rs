@@ -0,0 +1,507 @@
+use crate::{
+    error::{Error, Result},
+    resolver::{TypeResolver, ensure_consumed},
+    value::{Field, Value},
+};
+use alloc::{
+    borrow::ToOwned as _,
+    string::{String, ToString as _},
+    vec::Vec,
+};
+use sails_idl_ast::*;
+use sails_idl_meta::SailsMessageHeader;
+
+/// Direction of a message relative to the program.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MessageKind {
+    /// Incoming constructor or service function call.
+    Call,
+    /// Successful reply to a call.
+    Reply,
+    /// Error reply carrying the `throws` value of a call.
+    Error,
+    /// Event emitted by a service.
+    Event,
+}
+
+/// Kind of the IDL entry a message was resolved to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    Ctor,
+    Command,
+    Query,
+    Event,
+}
+
+/// Message payload decoded against an IDL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecodedMessage {
+    pub header: SailsMessageHeader,
+    pub kind: MessageKind,
+    pub entry_kind: EntryKind,
+    /// Name of the service declaring the entry; `None` for constructors.
+    pub service: Option<String>,
+    /// Name under which the service is exposed at `header.route_id()`, if the IDL has a program.
+    pub route: Option<String>,
+    /// Name of the constructor, function or event.
+    pub name: String,
+    /// Call arguments as a [`Value::Struct`] with named fields, the reply or error value,
+    /// or the event payload as a [`Value::Struct`].
+    pub value: Value,
+}
+
+/// Encodes and decodes Sails messages of a program described by an [`IdlDoc`].
+///
+/// Messages are resolved by their `SailsMessageHeader`: constructors by `entry_id` with a
+/// zero `interface_id`, service functions and events by `interface_id` and `entry_id`.
+/// Entries inherited through `extends` are resolved by the interface ID of the base service.
+#[derive(Debug, Clone, Copy)]
+pub struct IdlCodec<'a> {
+    doc: &'a IdlDoc,
+}
+
+impl<'a> IdlCodec<'a> {
+    /// Creates a codec for a parsed and post-processed IDL document.
+    pub fn new(doc: &'a IdlDoc) -> Self {
+        Self { doc }
+    }
+
+    pub fn doc(&self) -> &'a IdlDoc {
+        self.doc
+    }
+
+    /// Returns the type resolver for constructors and program-level types.
+    pub fn program_resolver(&self) -> TypeResolver<'a> {
+        self.doc
+            .program
+            .as_ref()
+            .map(|program| TypeResolver::new(&program.types))
+            .unwrap_or_default()
+    }
+
+    /// Returns the type resolver for functions and events of `service`.
+    pub fn service_resolver(&self, service: &'a ServiceUnit) -> TypeResolver<'a> {
+        TypeResolver::new(&service.types)
+    }
+
+    pub fn decode(&self, kind: MessageKind, payload: &[u8]) -> Result<DecodedMessage> {
+        match kind {
+            MessageKind::Call => self.decode_call(payload),
+            MessageKind::Reply => self.decode_reply(payload),
+            MessageKind::Error => self.decode_error(payload),
+            MessageKind::Event => self.decode_event(payload),
+        }
+    }
+
+    /// Decodes an incoming constructor or service function call.
+    pub fn decode_call(&self, payload: &[u8]) -> Result<DecodedMessage> {
+        let (header, mut input) = read_header(payload)?;
+        let message = if header.interface_id() == InterfaceId::zero() {
+            let ctor = self.ctor_by_entry_id(header.entry_id())?;
+            let value = decode_params(&self.program_resolver(), &ctor.params, &mut input)?;
+            DecodedMessage {
+                header,
+                kind: MessageKind::Call,
+                entry_kind: EntryKind::Ctor,
+                service: None,
+                route: None,
+                name: ctor.name.clone(),
+                value,
+            }
+        } else {
+            let (service, func) = self.func_by_header(&header)?;
+            let value = decode_params(&self.service_resolver(service), &func.params, &mut input)?;
+            self.service_message(header, MessageKind::Call, service, func, value)
+        };
+        ensure_consumed(input)?;
+        Ok(message)
+    }
+
+    /// Decodes a successful reply.
+    ///
+    /// Replies of functions returning `()` have an empty payload and cannot be
+    /// attributed to a function, so [`Error::EmptyPayload`] is returned for them.
+    pub fn decode_reply(&self, payload: &[u8]) -> Result<DecodedMessage> {
+        let (header, input) = read_header(payload)?;
+        if header.interface_id() == InterfaceId::zero() {
+            let ctor = self.ctor_by_entry_id(header.entry_id())?;
+            ensure_consumed(input)?;
+            return Ok(DecodedMessage {
+                header,
+                kind: MessageKind::Reply,
+                entry_kind: EntryKind::Ctor,
+                service: None,
+                route: None,
+                name: ctor.name.clone(),
+                value: Value::Unit,
+            });
+        }
+        let (service, func) = self.func_by_header(&header)?;
+        let value = self
+            .service_resolver(service)
+            .decode_all(&func.output, input)?;
+        Ok(self.service_message(header, MessageKind::Reply, service, func, value))
+    }
+
+    /// Decodes an error reply carrying a `throws` value.
+    pub fn decode_error(&self, payload: &[u8]) -> Result<DecodedMessage> {
+        let (header, input) = read_header(payload)?;
+        if header.interface_id() == InterfaceId::zero() {
+            let ctor = self.ctor_by_entry_id(header.entry_id())?;
+            let throws = ctor
+                .throws
+                .as_ref()
+                .ok_or_else(|| Error::NoThrows(ctor.name.clone()))?;
+            let value = self.program_resolver().decode_all(throws, input)?;
+            return Ok(DecodedMessage {
+                header,
+                kind: MessageKind::Error,
+                entry_kind: EntryKind::Ctor,
+                service: None,
+                route: None,
+                name: ctor.name.clone(),
+                value,
+            });
+        }
+        let (service, func) = self.func_by_header(&header)?;
+        let throws = func
+            .throws
+            .as_ref()
+            .ok_or_else(|| Error::NoThrows(func.name.clone()))?;
+        let value = self.service_resolver(service).decode_all(throws, input)?;
+        Ok(self.service_message(header, MessageKind::Error, service, func, value))
+    }
+
+    /// Decodes an event emitted by a service.
+    pub fn decode_event(&self, payload: &[u8]) -> Result<DecodedMessage> {
+        let (header, mut input) = read_header(payload)?;
+        let service = self.service_by_interface_id(header.interface_id())?;
+        let event = service
+            .events
+            .iter()
+            .find(|event| event.entry_id == header.entry_id())
+            .ok_or_else(|| Error::UnknownEntryId {
+                kind: "event",
+                scope: service.name.name.clone(),
+                entry_id: header.entry_id(),
+            })?;
+        let fields = self
+            .service_resolver(service)
+            .decode_fields(&struct_shape(&event.def), &mut input)?;
+        ensure_consumed(input)?;
+        Ok(DecodedMessage {
+            route: self.route_name(header.route_id()),
+            header,
+            kind: MessageKind::Event,
+            entry_kind: EntryKind::Event,
+            service: Some(service.name.name.clone()),
+            name: event.name.clone(),
+            value: Value::Struct(fields),
+        })
+    }
+
+    /// Returns the header of a constructor call.
+    pub fn ctor_header(&self, name: &str) -> Result<SailsMessageHeader> {
+        let ctor = self.ctor_by_name(name)?;
+        Ok(SailsMessageHeader::v1(
+            InterfaceId::zero(),
+            ctor.entry_id,
+            0,
+        ))
+    }
+
+    /// Returns the header of a call of `func` on the service exposed as `route`.
+    pub fn call_header(&self, route: &str, func: &str) -> Result<SailsMessageHeader> {
+        let (route_idx, service, func) = self.func_by_route(route, func)?;
+        service_header(service, func.entry_id, route_idx)
+    }
+
+    /// Encodes a constructor call with positional `args`.
+    pub fn encode_ctor(&self, name: &str, args: &[Value]) -> Result<Vec<u8>> {
+        let ctor = self.ctor_by_name(name)?;
+        let mut out = self.ctor_header(name)?.to_bytes();
+        encode_params(&self.program_resolver(), &ctor.params, args, &mut out)?;
+        Ok(out)
+    }
+
+    /// Encodes a call of `func` on the service exposed as `route` with positional `args`.
+    ///
+    /// Functions inherited through `extends` are encoded with the interface ID of the base service.
+    pub fn encode_call(&self, route: &str, func: &str, args: &[Value]) -> Result<Vec<u8>> {
+        let (route_idx, service, func) = self.func_by_route(route, func)?;
+        let mut out = service_header(service, func.entry_id, route_idx)?.to_bytes();
+        encode_params(
+            &self.service_resolver(service),
+            &func.params,
+            args,
+            &mut out,
+        )?;
+        Ok(out)
+    }
+
+    /// Encodes a successful reply of `func` on the service exposed as `route`.
+    pub fn encode_reply(&self, route: &str, func: &str, value: &Value) -> Result<Vec<u8>> {
+        let (route_idx, service, func) = self.func_by_route(route, func)?;
+        if func.returns_void() {
+            return Ok(Vec::new());
+        }
+        let mut out = service_header(service, func.entry_id, route_idx)?.to_bytes();
+        self.service_resolver(service)
+            .encode_to(&func.output, value, &mut out)?;
+        Ok(out)
+    }
+
+    /// Encodes an error reply of `func` carrying its `throws` value.
+    pub fn encode_error(&self, route: &str, func: &str, value: &Value) -> Result<Vec<u8>> {
+        let (route_idx, service, func) = self.func_by_route(route, func)?;
+        let throws = func
+            .throws
+            .as_ref()
+            .ok_or_else(|| Error::NoThrows(func.name.clone()))?;
+        let mut out = service_header(service, func.entry_id, route_idx)?.to_bytes();
+        self.service_resolver(service)
+            .encode_to(throws, value, &mut out)?;
+        Ok(out)
+    }
+
+    /// Encodes `event` of the service exposed as `route` with its payload `fields`.
+    pub fn encode_event(&self, route: &str, event: &str, fields: &[Field]) -> Result<Vec<u8>> {
+        let (route_idx, root) = self.service_by_route(route)?;
+        let (service, event) = self
+            .find_in_hierarchy(root, &|s| s.events.iter().find(|e| e.name == event))
+            .ok_or_else(|| Error::UnknownEntry {
+                kind: "event",
+                scope: root.name.name.clone(),
+                name: event.to_string(),
+            })?;
+        let mut out = service_header(service, event.entry_id, route_idx)?.to_bytes();
+        let ty = TypeDecl::named(event.name.clone());
+        self.service_resolver(service).encode_fields(
+            &ty,
+            &struct_shape(&event.def),
+            fields,
+            &mut out,
+        )?;
+        Ok(out)
+    }
+
+    /// Finds the service with the given `interface_id`.
+    pub fn service_by_interface_id(&self, interface_id: InterfaceId) -> Result<&'a ServiceUnit> {
+        self.doc
+            .services
+            .iter()
+            .find(|service| service_interface_id(service).ok() == Some(interface_id))
+            .ok_or(Error::UnknownInterface(interface_id))
+    }
+
+    /// Finds the service exposed as `route` and its route index.
+    ///
+    /// Without a program section, `route` is the service name and the route index is `0`,
+    /// which lets the program infer the route from the interface ID.
+    pub fn service_by_route(&self, route: &str) -> Result<(u8, &'a ServiceUnit)> {
+        let (route_idx, name) = match &self.doc.program {
+            Some(program) => program
+                .services
+                .iter()
+                .find(|expo| expo_route(expo) == route)
+                .map(|expo| (expo.route_idx, expo.name.name.as_str()))
+                .ok_or_else(|| Error::UnknownService(route.to_string()))?,
+            None => (0, route),
+        };
+        let service = self
+            .service_by_name(name)
+            .ok_or_else(|| Error::UnknownService(name.to_string()))?;
+        Ok((route_idx, service))
+    }
+
+    /// Finds `func` on the service exposed as `route`, including functions inherited through `extends`.
+    pub fn func_by_route(
+        &self,
+        route: &str,
+        func: &str,
+    ) -> Result<(u8, &'a ServiceUnit, &'a ServiceFunc)> {
+        let (route_idx, root) = self.service_by_route(route)?;
+        let (service, func) = self
+            .find_in_hierarchy(root, &|s| s.funcs.iter().find(|f| f.name == func))
+            .ok_or_else(|| Error::UnknownEntry {
+                kind: "function",
+                scope: root.name.name.clone(),
+                name: func.to_string(),
+            })?;
+        Ok((route_idx, service, func))
+    }
+
+    pub fn ctor_by_name(&self, name: &str) -> Result<&'a CtorFunc> {
+        let program = self.doc.program.as_ref().ok_or(Error::NoProgram)?;
+        program
+            .ctors
+            .iter()
+            .find(|ctor| ctor.name == name)
+            .ok_or_else(|| Error::UnknownEntry {
+                kind: "constructor",
+                scope: program.name.clone(),
+                name: name.to_string(),
+            })
+    }
+
+    fn ctor_by_entry_id(&self, entry_id: u16) -> Result<&'a CtorFunc> {
+        let program = self.doc.program.as_ref().ok_or(Error::NoProgram)?;
+        program
+            .ctors
+            .iter()
+            .find(|ctor| ctor.entry_id == entry_id)
+            .ok_or_else(|| Error::UnknownEntryId {
+                kind: "constructor",
+                scope: program.name.clone(),
+                entry_id,
+            })
+    }
+
+    fn func_by_header(
+        &self,
+        header: &SailsMessageHeader,
+    ) -> Result<(&'a ServiceUnit, &'a ServiceFunc)> {
+        let service = self.service_by_interface_id(header.interface_id())?;
+        let func = service
+            .funcs
+            .iter()
+            .find(|func| func.entry_id == header.entry_id())
+            .ok_or_else(|| Error::UnknownEntryId {
+                kind: "function",
+                scope: service.name.name.clone(),
+                entry_id: header.entry_id(),
+            })?;
+        Ok((service, func))
+    }
+
+    fn service_by_name(&self, name: &str) -> Option<&'a ServiceUnit> {
+        self.doc
+            .services
+            .iter()
+            .find(|service| service.name.name == name)
+    }
+
+    /// Depth-first search of `root` and its base services; own entries shadow inherited ones.
+    fn find_in_hierarchy<T: 'a>(
+        &self,
+        root: &'a ServiceUnit,
+        find: &dyn Fn(&'a ServiceUnit) -> Option<&'a T>,
+    ) -> Option<(&'a ServiceUnit, &'a T)> {
+        if let Some(found) = find(root) {
+            return Some((root, found));
+        }
+        root.extends.iter().find_map(|base| {
+            self.service_by_name(&base.name)
+                .and_then(|base| self.find_in_hierarchy(base, find))
+        })
+    }
+
+    fn route_name(&self, route_idx: u8) -> Option<String> {
+        self.doc
+            .program
+            .as_ref()?
+            .services
+            .iter()
+            .find(|expo| expo.route_idx == route_idx)
+            .map(|expo| expo_route(expo).to_owned())
+    }
+
+    fn service_message(
+        &self,
+        header: SailsMessageHeader,
+        kind: MessageKind,
+        service: &ServiceUnit,
+        func: &ServiceFunc,
+        value: Value,
+    ) -> DecodedMessage {
+        DecodedMessage {
+            route: self.route_name(header.route_id()),
+            header,
+            kind,
+            entry_kind: match func.kind {
+                FunctionKind::Command => EntryKind::Command,
+                FunctionKind::Query => EntryKind::Query,
+            },
+            service: Some(service.name.name.clone()),
+            name: func.name.clone(),
+            value,
+        }
+    }
+}
+
+fn expo_route(expo: &ServiceExpo) -> &str {
+    expo.route.as_deref().unwrap_or(&expo.name.name)
+}
+
+fn service_interface_id(service: &ServiceUnit) -> Result<InterfaceId> {
+    match service.name.interface_id {
+        Some(id) => Ok(id),
+        None => service.interface_id().map_err(Error::InvalidType),
+    }
+}
+
+fn service_header(
+    service: &ServiceUnit,
+    entry_id: u16,
+    route_idx: u8,
+) -> Result<SailsMessageHeader> {
+    Ok(SailsMessageHeader::v1(
+        service_interface_id(service)?,
+        entry_id,
+        route_idx,
+    ))
+}
+
+fn read_header(payload: &[u8]) -> Result<(SailsMessageHeader, &[u8])> {
+    if payload.is_empty() {
+        return Err(Error::EmptyPayload);
+    }
+    let mut input = payload;
+    let header = SailsMessageHeader::try_read_bytes(&mut input).map_err(Error::Header)?;
+    Ok((header, input))
+}
+
+fn struct_shape(def: &StructDef) -> Vec<crate::resolver::FieldShape<'_>> {
+    def.fields
+        .iter()
+        .map(|field| crate::resolver::FieldShape {
+            name: field.name.as_deref(),
+            ty: field.type_decl.clone(),
+        })
+        .collect()
+}
+
+fn decode_params(
+    resolver: &TypeResolver<'_>,
+    params: &[FuncParam],
+    input: &mut &[u8],
+) -> Result<Value> {
+    params
+        .iter()
+        .map(|param| {
+            Ok(Field::named(
+                &param.name,
+                resolver.decode(&param.type_decl, input)?,
+            ))
+        })
+        .collect::<Result<_>>()
+        .map(Value::Struct)
+}
+
+fn encode_params(
+    resolver: &TypeResolver<'_>,
+    params: &[FuncParam],
+    args: &[Value],
+    out: &mut Vec<u8>,
+) -> Result<()> {
+    if params.len() != args.len() {
+        return Err(Error::ArgumentCount {
+            expected: params.len(),
+            actual: args.len(),
+        });
+    }
+    for (param, arg) in params.iter().zip(args) {
+        resolver.encode_to(&param.type_decl, arg, out)?;
+    }
+    Ok(())
+}
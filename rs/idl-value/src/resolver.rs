@@ -0,0 +1,485 @@
+use crate::{
+    error::{Error, Result},
+    value::{Field, Value},
+};
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    collections::BTreeMap,
+    format,
+    string::{String, ToString as _},
+    vec::Vec,
+};
+use gprimitives::{ActorId, CodeId, H160, H256, MessageId, U256};
+use parity_scale_codec::{Compact, Decode, Encode};
+use sails_idl_ast::*;
+
+/// Maximum number of alias hops followed while resolving a single type.
+const MAX_ALIAS_DEPTH: usize = 64;
+
+/// Resolves [`TypeDecl`]s against a set of named IDL [`Type`]s and converts
+/// [`Value`]s to and from SCALE according to them.
+///
+/// A resolver is scoped to a single `types { ... }` section: service functions and
+/// events resolve against the service types, constructors against the program types.
+/// Named types defined in the scope shadow the built-in `NonZero*` types.
+#[derive(Debug, Clone, Default)]
+pub struct TypeResolver<'a> {
+    types: BTreeMap<&'a str, &'a Type>,
+}
+
+/// Structural shape of a type with all named types, aliases and generics resolved.
+#[derive(Debug, Clone)]
+pub enum Shape<'a> {
+    Primitive(PrimitiveType),
+    NonZero(PrimitiveType),
+    Slice(TypeDecl),
+    Array(TypeDecl, u32),
+    Tuple(Vec<TypeDecl>),
+    Option(TypeDecl),
+    Result(TypeDecl, TypeDecl),
+    Struct(Vec<FieldShape<'a>>),
+    Enum(Vec<VariantShape<'a>>),
+}
+
+/// Field of a struct or an enum variant, `name` is `None` for tuple-like fields.
+#[derive(Debug, Clone)]
+pub struct FieldShape<'a> {
+    pub name: Option<&'a str>,
+    pub ty: TypeDecl,
+}
+
+#[derive(Debug, Clone)]
+pub struct VariantShape<'a> {
+    pub name: &'a str,
+    pub fields: Vec<FieldShape<'a>>,
+}
+
+impl<'a> TypeResolver<'a> {
+    pub fn new(types: &'a [Type]) -> Self {
+        Self {
+            types: types.iter().map(|ty| (ty.name.as_str(), ty)).collect(),
+        }
+    }
+
+    /// Decodes a value of type `ty`, advancing `input`.
+    pub fn decode(&self, ty: &TypeDecl, input: &mut &[u8]) -> Result<Value> {
+        Ok(match self.shape(ty)? {
+            Shape::Primitive(p) => decode_primitive(p, input)?,
+            Shape::NonZero(p) => {
+                let value = decode_primitive(p, input)?;
+                if is_zero(&value) {
+                    return Err(Error::Codec(format!("zero value for `{ty}`")));
+                }
+                value
+            }
+            Shape::Slice(item) => {
+                let len = Compact::<u32>::decode(input)?.0 as usize;
+                // Do not trust the length prefix for the allocation: every item takes at least a byte
+                // unless it is zero-sized, so cap the capacity by the remaining input.
+                let mut items = Vec::with_capacity(len.min(input.len()));
+                for _ in 0..len {
+                    items.push(self.decode(&item, input)?);
+                }
+                Value::Sequence(items)
+            }
+            Shape::Array(item, len) => {
+                let mut items = Vec::with_capacity((len as usize).min(input.len()));
+                for _ in 0..len {
+                    items.push(self.decode(&item, input)?);
+                }
+                Value::Sequence(items)
+            }
+            Shape::Tuple(types) => Value::Tuple(
+                types
+                    .iter()
+                    .map(|ty| self.decode(ty, input))
+                    .collect::<Result<_>>()?,
+            ),
+            Shape::Option(item) => match u8::decode(input)? {
+                0 => Value::none(),
+                1 => Value::some(self.decode(&item, input)?),
+                _ => return Err(Error::Codec("invalid `Option` variant index".to_string())),
+            },
+            Shape::Result(ok, err) => match u8::decode(input)? {
+                0 => Value::ok(self.decode(&ok, input)?),
+                1 => Value::err(self.decode(&err, input)?),
+                _ => return Err(Error::Codec("invalid `Result` variant index".to_string())),
+            },
+            Shape::Struct(fields) => Value::Struct(self.decode_fields(&fields, input)?),
+            Shape::Enum(variants) => {
+                let idx = u8::decode(input)? as usize;
+                let variant = variants.get(idx).ok_or_else(|| {
+                    Error::Codec(format!("invalid variant index {idx} for `{ty}`"))
+                })?;
+                Value::Variant {
+                    name: variant.name.to_string(),
+                    fields: self.decode_fields(&variant.fields, input)?,
+                }
+            }
+        })
+    }
+
+    /// Decodes a value of type `ty` consuming the whole `bytes`.
+    pub fn decode_all(&self, ty: &TypeDecl, mut bytes: &[u8]) -> Result<Value> {
+        let value = self.decode(ty, &mut bytes)?;
+        ensure_consumed(bytes)?;
+        Ok(value)
+    }
+
+    pub(crate) fn decode_fields(
+        &self,
+        fields: &[FieldShape<'_>],
+        input: &mut &[u8],
+    ) -> Result<Vec<Field>> {
+        fields
+            .iter()
+            .map(|field| {
+                Ok(Field {
+                    name: field.name.map(ToOwned::to_owned),
+                    value: self.decode(&field.ty, input)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Encodes `value` as type `ty`, appending to `out`.
+    pub fn encode_to(&self, ty: &TypeDecl, value: &Value, out: &mut Vec<u8>) -> Result<()> {
+        match (self.shape(ty)?, value) {
+            (Shape::Primitive(p), value) => encode_primitive(ty, p, value, out)?,
+            (Shape::NonZero(p), value) => {
+                if is_zero(value) {
+                    return Err(mismatch(ty, "value must be non-zero"));
+                }
+                encode_primitive(ty, p, value, out)?
+            }
+            (Shape::Slice(item), Value::Sequence(items)) => {
+                Compact(items.len() as u32).encode_to(out);
+                for value in items {
+                    self.encode_to(&item, value, out)?;
+                }
+            }
+            (Shape::Array(item, len), Value::Sequence(items)) => {
+                if items.len() != len as usize {
+                    return Err(mismatch(
+                        ty,
+                        format!("expected {len} items, got {}", items.len()),
+                    ));
+                }
+                for value in items {
+                    self.encode_to(&item, value, out)?;
+                }
+            }
+            (Shape::Tuple(types), Value::Tuple(items)) => {
+                if items.len() != types.len() {
+                    return Err(mismatch(
+                        ty,
+                        format!("expected {} items, got {}", types.len(), items.len()),
+                    ));
+                }
+                for (ty, value) in types.iter().zip(items) {
+                    self.encode_to(ty, value, out)?;
+                }
+            }
+            (Shape::Option(item), Value::Option(value)) => match value {
+                None => out.push(0),
+                Some(value) => {
+                    out.push(1);
+                    self.encode_to(&item, value, out)?;
+                }
+            },
+            (Shape::Result(ok, _), Value::Result(Ok(value))) => {
+                out.push(0);
+                self.encode_to(&ok, value, out)?;
+            }
+            (Shape::Result(_, err), Value::Result(Err(value))) => {
+                out.push(1);
+                self.encode_to(&err, value, out)?;
+            }
+            (Shape::Struct(shapes), Value::Struct(fields)) => {
+                self.encode_fields(ty, &shapes, fields, out)?;
+            }
+            (Shape::Enum(variants), Value::Variant { name, fields }) => {
+                let (idx, variant) = variants
+                    .iter()
+                    .enumerate()
+                    .find(|(_, v)| v.name == name)
+                    .ok_or_else(|| mismatch(ty, format!("unknown variant `{name}`")))?;
+                out.push(idx as u8);
+                self.encode_fields(ty, &variant.fields, fields, out)?;
+            }
+            (_, value) => return Err(mismatch(ty, format!("found {}", value.kind()))),
+        }
+        Ok(())
+    }
+
+    /// Encodes `value` as type `ty`.
+    pub fn encode(&self, ty: &TypeDecl, value: &Value) -> Result<Vec<u8>> {
+        let mut out = Vec::new();
+        self.encode_to(ty, value, &mut out)?;
+        Ok(out)
+    }
+
+    /// Encodes fields by position; when both sides are named, the names must match.
+    pub(crate) fn encode_fields(
+        &self,
+        ty: &TypeDecl,
+        shapes: &[FieldShape<'_>],
+        fields: &[Field],
+        out: &mut Vec<u8>,
+    ) -> Result<()> {
+        if shapes.len() != fields.len() {
+            return Err(mismatch(
+                ty,
+                format!("expected {} fields, got {}", shapes.len(), fields.len()),
+            ));
+        }
+        for (shape, field) in shapes.iter().zip(fields) {
+            if let (Some(expected), Some(actual)) = (shape.name, field.name.as_deref())
+                && expected != actual
+            {
+                return Err(mismatch(
+                    ty,
+                    format!("expected field `{expected}`, got `{actual}`"),
+                ));
+            }
+            self.encode_to(&shape.ty, &field.value, out)?;
+        }
+        Ok(())
+    }
+
+    /// Resolves `ty` to its structural shape.
+    ///
+    /// `ty` must not contain unresolved generic parameters: generics of named types are
+    /// substituted here, so field types of the returned shape are concrete as well.
+    pub fn shape(&self, ty: &TypeDecl) -> Result<Shape<'a>> {
+        let mut ty = ty.clone();
+        for _ in 0..MAX_ALIAS_DEPTH {
+            let (name, generics) = match ty {
+                TypeDecl::Primitive(p) => return Ok(Shape::Primitive(p)),
+                TypeDecl::Slice { item } => return Ok(Shape::Slice(*item)),
+                TypeDecl::Array { item, len } => return Ok(Shape::Array(*item, len)),
+                TypeDecl::Tuple { types } if types.is_empty() => {
+                    return Ok(Shape::Primitive(PrimitiveType::Void));
+                }
+                TypeDecl::Tuple { types } => return Ok(Shape::Tuple(types)),
+                TypeDecl::Generic { name } => return Err(Error::UnresolvedGeneric(name)),
+                TypeDecl::Named { name, generics } => (name, generics),
+            };
+            let Some(def) = self.types.get(name.as_str()).copied() else {
+                return builtin_shape(name, generics);
+            };
+            let params = type_params(def, generics)?;
+            match &def.def {
+                TypeDef::Struct(struct_def) => {
+                    return Ok(Shape::Struct(field_shapes(struct_def, &params)));
+                }
+                TypeDef::Enum(enum_def) => {
+                    return Ok(Shape::Enum(
+                        enum_def
+                            .variants
+                            .iter()
+                            .map(|variant| VariantShape {
+                                name: variant.name.as_str(),
+                                fields: field_shapes(&variant.def, &params),
+                            })
+                            .collect(),
+                    ));
+                }
+                TypeDef::Alias(alias) => ty = substitute(&alias.target, &params),
+            }
+        }
+        Err(Error::InvalidType(format!(
+            "alias chain is longer than {MAX_ALIAS_DEPTH}"
+        )))
+    }
+}
+
+fn builtin_shape<'a>(name: String, mut generics: Vec<TypeDecl>) -> Result<Shape<'a>> {
+    use PrimitiveType as P;
+    let arity = |n: usize| {
+        if generics.len() == n {
+            Ok(())
+        } else {
+            Err(Error::InvalidType(format!(
+                "`{name}` expects {n} generic arguments, got {}",
+                generics.len()
+            )))
+        }
+    };
+    let non_zero = match name.as_str() {
+        "Option" => {
+            arity(1)?;
+            return Ok(Shape::Option(generics.remove(0)));
+        }
+        "Result" => {
+            arity(2)?;
+            let err = generics.remove(1);
+            return Ok(Shape::Result(generics.remove(0), err));
+        }
+        "NonZeroU8" => P::U8,
+        "NonZeroU16" => P::U16,
+        "NonZeroU32" => P::U32,
+        "NonZeroU64" => P::U64,
+        "NonZeroU128" => P::U128,
+        "NonZeroU256" => P::U256,
+        _ => return Err(Error::UnknownType(name)),
+    };
+    arity(0)?;
+    Ok(Shape::NonZero(non_zero))
+}
+
+/// Maps type parameters of `def` to the concrete `generics`, falling back to
+/// the declared parameter type when an argument is omitted.
+fn type_params(def: &Type, generics: Vec<TypeDecl>) -> Result<BTreeMap<String, TypeDecl>> {
+    if generics.len() > def.type_params.len() {
+        return Err(Error::InvalidType(format!(
+            "`{}` expects {} generic arguments, got {}",
+            def.name,
+            def.type_params.len(),
+            generics.len()
+        )));
+    }
+    let mut args = generics.into_iter();
+    def.type_params
+        .iter()
+        .map(|param| {
+            let ty = args
+                .next()
+                .or_else(|| param.ty.clone())
+                .ok_or_else(|| Error::UnresolvedGeneric(param.name.clone()))?;
+            Ok((param.name.clone(), ty))
+        })
+        .collect()
+}
+
+fn field_shapes<'a>(
+    def: &'a StructDef,
+    params: &BTreeMap<String, TypeDecl>,
+) -> Vec<FieldShape<'a>> {
+    def.fields
+        .iter()
+        .map(|field| FieldShape {
+            name: field.name.as_deref(),
+            ty: substitute(&field.type_decl, params),
+        })
+        .collect()
+}
+
+/// Replaces generic parameters in `ty` with their concrete types.
+pub(crate) fn substitute(ty: &TypeDecl, params: &BTreeMap<String, TypeDecl>) -> TypeDecl {
+    if params.is_empty() {
+        return ty.clone();
+    }
+    match ty {
+        TypeDecl::Generic { name } => params.get(name).cloned().unwrap_or_else(|| ty.clone()),
+        TypeDecl::Slice { item } => TypeDecl::Slice {
+            item: Box::new(substitute(item, params)),
+        },
+        TypeDecl::Array { item, len } => TypeDecl::Array {
+            item: Box::new(substitute(item, params)),
+            len: *len,
+        },
+        TypeDecl::Tuple { types } => TypeDecl::Tuple {
+            types: types.iter().map(|ty| substitute(ty, params)).collect(),
+        },
+        TypeDecl::Named { name, generics } => TypeDecl::Named {
+            name: name.clone(),
+            generics: generics.iter().map(|ty| substitute(ty, params)).collect(),
+        },
+        TypeDecl::Primitive(_) => ty.clone(),
+    }
+}
+
+pub(crate) fn ensure_consumed(bytes: &[u8]) -> Result<()> {
+    if bytes.is_empty() {
+        Ok(())
+    } else {
+        Err(Error::TrailingBytes(bytes.len()))
+    }
+}
+
+pub(crate) fn mismatch(ty: &TypeDecl, reason: impl Into<String>) -> Error {
+    Error::TypeMismatch {
+        ty: ty.to_string(),
+        reason: reason.into(),
+    }
+}
+
+fn is_zero(value: &Value) -> bool {
+    match value {
+        Value::U8(v) => *v == 0,
+        Value::U16(v) => *v == 0,
+        Value::U32(v) => *v == 0,
+        Value::U64(v) => *v == 0,
+        Value::U128(v) => *v == 0,
+        Value::U256(v) => v.is_zero(),
+        _ => false,
+    }
+}
+
+fn decode_primitive(p: PrimitiveType, input: &mut &[u8]) -> Result<Value> {
+    use PrimitiveType as P;
+    Ok(match p {
+        P::Void => Value::Unit,
+        P::Bool => Value::Bool(bool::decode(input)?),
+        P::Char => {
+            let code = u32::decode(input)?;
+            Value::Char(
+                char::from_u32(code)
+                    .ok_or_else(|| Error::Codec(format!("invalid char code {code}")))?,
+            )
+        }
+        P::String => Value::String(String::decode(input)?),
+        P::U8 => Value::U8(u8::decode(input)?),
+        P::U16 => Value::U16(u16::decode(input)?),
+        P::U32 => Value::U32(u32::decode(input)?),
+        P::U64 => Value::U64(u64::decode(input)?),
+        P::U128 => Value::U128(u128::decode(input)?),
+        P::I8 => Value::I8(i8::decode(input)?),
+        P::I16 => Value::I16(i16::decode(input)?),
+        P::I32 => Value::I32(i32::decode(input)?),
+        P::I64 => Value::I64(i64::decode(input)?),
+        P::I128 => Value::I128(i128::decode(input)?),
+        P::ActorId => Value::ActorId(ActorId::decode(input)?),
+        P::CodeId => Value::CodeId(CodeId::decode(input)?),
+        P::MessageId => Value::MessageId(MessageId::decode(input)?),
+        P::H160 => Value::H160(H160::decode(input)?),
+        P::H256 => Value::H256(H256::decode(input)?),
+        P::U256 => Value::U256(U256::decode(input)?),
+    })
+}
+
+fn encode_primitive(
+    ty: &TypeDecl,
+    p: PrimitiveType,
+    value: &Value,
+    out: &mut Vec<u8>,
+) -> Result<()> {
+    use PrimitiveType as P;
+    match (p, value) {
+        (P::Void, Value::Unit) => {}
+        (P::Void, Value::Tuple(items)) if items.is_empty() => {}
+        (P::Bool, Value::Bool(v)) => v.encode_to(out),
+        (P::Char, Value::Char(v)) => (*v as u32).encode_to(out),
+        (P::String, Value::String(v)) => v.encode_to(out),
+        (P::U8, Value::U8(v)) => v.encode_to(out),
+        (P::U16, Value::U16(v)) => v.encode_to(out),
+        (P::U32, Value::U32(v)) => v.encode_to(out),
+        (P::U64, Value::U64(v)) => v.encode_to(out),
+        (P::U128, Value::U128(v)) => v.encode_to(out),
+        (P::I8, Value::I8(v)) => v.encode_to(out),
+        (P::I16, Value::I16(v)) => v.encode_to(out),
+        (P::I32, Value::I32(v)) => v.encode_to(out),
+        (P::I64, Value::I64(v)) => v.encode_to(out),
+        (P::I128, Value::I128(v)) => v.encode_to(out),
+        (P::ActorId, Value::ActorId(v)) => v.encode_to(out),
+        (P::CodeId, Value::CodeId(v)) => v.encode_to(out),
+        (P::MessageId, Value::MessageId(v)) => v.encode_to(out),
+        (P::H160, Value::H160(v)) => v.encode_to(out),
+        (P::H256, Value::H256(v)) => v.encode_to(out),
+        (P::U256, Value::U256(v)) => v.encode_to(out),
+        (_, value) => return Err(mismatch(ty, format!("found {}", value.kind()))),
+    }
+    Ok(())
+}
@@ -0,0 +1,224 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+use core::fmt::{Display, Formatter, Result as FmtResult, Write as _};
+use gprimitives::{ActorId, CodeId, H160, H256, MessageId, U256};
+
+/// Dynamic value of any type expressible in the IDL.
+///
+/// Values carry no type information of their own: the [`TypeDecl`](sails_idl_ast::TypeDecl)
+/// they are encoded with or decoded from is tracked by [`TypeResolver`](crate::TypeResolver).
+/// `NonZero*` types are represented by their underlying integer value.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value {
+    /// Unit value `()`.
+    Unit,
+    Bool(bool),
+    Char(char),
+    String(String),
+    U8(u8),
+    U16(u16),
+    U32(u32),
+    U64(u64),
+    U128(u128),
+    I8(i8),
+    I16(i16),
+    I32(i32),
+    I64(i64),
+    I128(i128),
+    ActorId(ActorId),
+    CodeId(CodeId),
+    MessageId(MessageId),
+    H160(H160),
+    H256(H256),
+    U256(U256),
+    /// Items of a slice `[T]` or a fixed-length array `[T; N]`.
+    Sequence(Vec<Value>),
+    /// Items of a tuple `(T1, T2, ...)`.
+    Tuple(Vec<Value>),
+    /// `Option<T>` value.
+    Option(Option<Box<Value>>),
+    /// `Result<T, E>` value.
+    Result(core::result::Result<Box<Value>, Box<Value>>),
+    /// Fields of a struct, in declaration order.
+    Struct(Vec<Field>),
+    /// Enum variant with its payload fields.
+    Variant {
+        name: String,
+        fields: Vec<Field>,
+    },
+}
+
+/// Single field of a [`Value::Struct`] or a [`Value::Variant`].
+///
+/// `name` is `None` for tuple-like structs and variants.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field {
+    pub name: Option<String>,
+    pub value: Value,
+}
+
+impl Field {
+    pub fn named(name: impl Into<String>, value: Value) -> Self {
+        Self {
+            name: Some(name.into()),
+            value,
+        }
+    }
+
+    pub fn unnamed(value: Value) -> Self {
+        Self { name: None, value }
+    }
+}
+
+impl Value {
+    pub fn some(value: Value) -> Self {
+        Value::Option(Some(Box::new(value)))
+    }
+
+    pub fn none() -> Self {
+        Value::Option(None)
+    }
+
+    pub fn ok(value: Value) -> Self {
+        Value::Result(Ok(Box::new(value)))
+    }
+
+    pub fn err(value: Value) -> Self {
+        Value::Result(Err(Box::new(value)))
+    }
+
+    pub fn variant(name: impl Into<String>, fields: Vec<Field>) -> Self {
+        Value::Variant {
+            name: name.into(),
+            fields,
+        }
+    }
+
+    /// Short description of the value kind used in error messages.
+    pub fn kind(&self) -> &'static str {
+        use Value::*;
+        match self {
+            Unit => "()",
+            Bool(_) => "bool",
+            Char(_) => "char",
+            String(_) => "String",
+            U8(_) => "u8",
+            U16(_) => "u16",
+            U32(_) => "u32",
+            U64(_) => "u64",
+            U128(_) => "u128",
+            I8(_) => "i8",
+            I16(_) => "i16",
+            I32(_) => "i32",
+            I64(_) => "i64",
+            I128(_) => "i128",
+            ActorId(_) => "ActorId",
+            CodeId(_) => "CodeId",
+            MessageId(_) => "MessageId",
+            H160(_) => "H160",
+            H256(_) => "H256",
+            U256(_) => "U256",
+            Sequence(_) => "sequence",
+            Tuple(_) => "tuple",
+            Option(_) => "Option",
+            Result(_) => "Result",
+            Struct(_) => "struct",
+            Variant { .. } => "variant",
+        }
+    }
+}
+
+/// Renders the value in a Rust-like notation, e.g. `Four { a: 1, b: Some(2) }`.
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        use Value::*;
+        match self {
+            Unit => f.write_str("()"),
+            Bool(v) => write!(f, "{v}"),
+            Char(v) => write!(f, "{v:?}"),
+            String(v) => write!(f, "{v:?}"),
+            U8(v) => write!(f, "{v}"),
+            U16(v) => write!(f, "{v}"),
+            U32(v) => write!(f, "{v}"),
+            U64(v) => write!(f, "{v}"),
+            U128(v) => write!(f, "{v}"),
+            I8(v) => write!(f, "{v}"),
+            I16(v) => write!(f, "{v}"),
+            I32(v) => write!(f, "{v}"),
+            I64(v) => write!(f, "{v}"),
+            I128(v) => write!(f, "{v}"),
+            ActorId(v) => write_hex(f, v.as_ref()),
+            CodeId(v) => write_hex(f, v.as_ref()),
+            MessageId(v) => write_hex(f, v.as_ref()),
+            H160(v) => write_hex(f, v.as_bytes()),
+            H256(v) => write_hex(f, v.as_bytes()),
+            U256(v) => write!(f, "{v}"),
+            Sequence(items) => {
+                f.write_char('[')?;
+                write_list(f, items)?;
+                f.write_char(']')
+            }
+            Tuple(items) => {
+                f.write_char('(')?;
+                write_list(f, items)?;
+                f.write_char(')')
+            }
+            Option(None) => f.write_str("None"),
+            Option(Some(v)) => write!(f, "Some({v})"),
+            Result(Ok(v)) => write!(f, "Ok({v})"),
+            Result(Err(v)) => write!(f, "Err({v})"),
+            Struct(fields) => write_fields(f, fields),
+            Variant { name, fields } => {
+                f.write_str(name)?;
+                if fields.is_empty() {
+                    return Ok(());
+                }
+                if fields.iter().any(|field| field.name.is_some()) {
+                    f.write_char(' ')?;
+                }
+                write_fields(f, fields)
+            }
+        }
+    }
+}
+
+fn write_hex(f: &mut Formatter<'_>, bytes: &[u8]) -> FmtResult {
+    f.write_str("0x")?;
+    for byte in bytes {
+        write!(f, "{byte:02x}")?;
+    }
+    Ok(())
+}
+
+fn write_list(f: &mut Formatter<'_>, items: &[Value]) -> FmtResult {
+    for (i, item) in items.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        write!(f, "{item}")?;
+    }
+    Ok(())
+}
+
+fn write_fields(f: &mut Formatter<'_>, fields: &[Field]) -> FmtResult {
+    if fields.iter().all(|field| field.name.is_none()) {
+        f.write_char('(')?;
+        for (i, field) in fields.iter().enumerate() {
+            if i > 0 {
+                f.write_str(", ")?;
+            }
+            write!(f, "{}", field.value)?;
+        }
+        return f.write_char(')');
+    }
+    f.write_str("{ ")?;
+    for (i, field) in fields.iter().enumerate() {
+        if i > 0 {
+            f.write_str(", ")?;
+        }
+        if let Some(name) = &field.name {
+            write!(f, "{name}: ")?;
+        }
+        write!(f, "{}", field.value)?;
+    }
+    f.write_str(" }")
+}
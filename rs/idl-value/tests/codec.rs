@@ -0,0 +1,393 @@
+use gprimitives::{ActorId, H256, U256};
+use parity_scale_codec::{Decode, Encode};
+use sails_idl_ast::{InterfaceId, TypeDecl};
+use sails_idl_meta::SailsMessageHeader;
+use sails_idl_value::*;
+
+const IDL: &str = include_str!("idls/value.idl");
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+struct Item<T> {
+    id: T,
+    owner: H256,
+    qty: core::num::NonZeroU32,
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+enum Kind {
+    Any,
+    Exact(u16),
+    Range { from: i64, to: i64 },
+}
+
+#[derive(Encode, Decode, Debug, PartialEq)]
+enum ShopError {
+    SoldOut,
+    TooExpensive(U256),
+}
+
+fn parse() -> ast::IdlDoc {
+    sails_idl_parser_v2::parse_idl(IDL).expect("parse IDL")
+}
+
+fn interface_id(doc: &ast::IdlDoc, name: &str) -> InterfaceId {
+    doc.services
+        .iter()
+        .find(|s| s.name.name == name)
+        .and_then(|s| s.name.interface_id)
+        .unwrap()
+}
+
+fn entry_id(doc: &ast::IdlDoc, service: &str, func: &str) -> u16 {
+    doc.services
+        .iter()
+        .find(|s| s.name.name == service)
+        .and_then(|s| s.funcs.iter().find(|f| f.name == func))
+        .map(|f| f.entry_id)
+        .unwrap()
+}
+
+fn with_header(header: SailsMessageHeader, body: impl Encode) -> Vec<u8> {
+    let mut bytes = header.to_bytes();
+    body.encode_to(&mut bytes);
+    bytes
+}
+
+fn item_value() -> Value {
+    Value::Struct(vec![
+        Field::named("id", Value::U64(7)),
+        Field::named("owner", Value::H256(H256::repeat_byte(1))),
+        Field::named("qty", Value::U32(3)),
+    ])
+}
+
+fn item() -> Item<u64> {
+    Item {
+        id: 7,
+        owner: H256::repeat_byte(1),
+        qty: core::num::NonZeroU32::new(3).unwrap(),
+    }
+}
+
+#[test]
+fn decode_call_resolves_func_by_header() {
+    let doc = parse();
+    let codec = IdlCodec::new(&doc);
+    let header =
+        SailsMessageHeader::v1(interface_id(&doc, "Shop"), entry_id(&doc, "Shop", "Buy"), 1);
+    let payload = with_header(
+        header.clone(),
+        (item(), [Some(true), None::<bool>], Some("gift".to_string())),
+    );
+
+    let message = codec.decode_call(&payload).unwrap();
+
+    assert_eq!(message.header, header);
+    assert_eq!(message.kind, MessageKind::Call);
+    assert_eq!(message.entry_kind, EntryKind::Command);
+    assert_eq!(message.service.as_deref(), Some("Shop"));
+    assert_eq!(message.route.as_deref(), Some("Shop"));
+    assert_eq!(message.name, "Buy");
+    assert_eq!(
+        message.value,
+        Value::Struct(vec![
+            Field::named("item", item_value()),
+            Field::named(
+                "flags",
+                Value::Sequence(vec![Value::some(Value::Bool(true)), Value::none()])
+            ),
+            Field::named("note", Value::some(Value::String("gift".into()))),
+        ])
+    );
+}
+
+#[test]
+fn encode_call_roundtrips_through_decode() {
+    let doc = parse();
+    let codec = IdlCodec::new(&doc);
+    let args = [
+        Value::Sequence(vec![Value::U32(1), Value::U32(2)]),
+        Value::variant(
+            "Range",
+            vec![
+                Field::named("from", Value::I64(-5)),
+                Field::named("to", Value::I64(5)),
+            ],
+        ),
+    ];
+
+    let payload = codec.encode_call("Shop", "Lookup", &args).unwrap();
+
+    let expected = with_header(
+        SailsMessageHeader::v1(
+            interface_id(&doc, "Shop"),
+            entry_id(&doc, "Shop", "Lookup"),
+            1,
+        ),
+        (vec![1u32, 2], Kind::Range { from: -5, to: 5 }),
+    );
+    assert_eq!(payload, expected);
+    let message = codec.decode_call(&payload).unwrap();
+    assert_eq!(message.entry_kind, EntryKind::Query);
+    assert_eq!(
+        message.value,
+        Value::Struct(vec![
+            Field::named("ids", args[0].clone()),
+            Field::named("kind", args[1].clone()),
+        ])
+    );
+}
+
+#[test]
+fn inherited_func_uses_base_interface_id() {
+    let doc = parse();
+    let codec = IdlCodec::new(&doc);
+
+    let payload = codec.encode_call("Shop", "Ping", &[Value::U32(5)]).unwrap();
+
+    let header = SailsMessageHeader::try_from_bytes(&payload).unwrap();
+    assert_eq!(header.interface_id(), interface_id(&doc, "Base"));
+    assert_eq!(header.route_id(), 1);
+    let message = codec.decode_call(&payload).unwrap();
+    assert_eq!(message.service.as_deref(), Some("Base"));
+    assert_eq!(message.route.as_deref(), Some("Shop"));
+
+    // The same service exposed under a custom route name
+    let payload = codec
+        .encode_call("Extra", "Ping", &[Value::U32(5)])
+        .unwrap();
+    let message = codec.decode_call(&payload).unwrap();
+    assert_eq!(message.header.route_id(), 2);
+    assert_eq!(message.route.as_deref(), Some("Extra"));
+}
+
+#[test]
+fn decode_reply_with_aliases_and_generics() {
+    let doc = parse();
+    let codec = IdlCodec::new(&doc);
+    let header = SailsMessageHeader::v1(
+        interface_id(&doc, "Shop"),
+        entry_id(&doc, "Shop", "Lookup"),
+        1,
+    );
+    // `char` has no `Encode` impl, it is encoded as `u32`
+    let reply: Result<(u8, Vec<(String, u32)>), String> = Ok((2, vec![("a".into(), 'z' as u32)]));
+    let payload = with_header(header, reply);
+
+    let message = codec.decode_reply(&payload).unwrap();
+
+    assert_eq!(message.kind, MessageKind::Reply);
+    assert_eq!(
+        message.value,
+        Value::ok(Value::Tuple(vec![
+            Value::U8(2),
+            Value::Sequence(vec![Value::Tuple(vec![
+                Value::String("a".into()),
+                Value::Char('z')
+            ])]),
+        ]))
+    );
+    assert_eq!(
+        codec
+            .encode_reply("Shop", "Lookup", &message.value)
+            .unwrap(),
+        payload
+    );
+}
+
+#[test]
+fn decode_error_uses_throws_type() {
+    let doc = parse();
+    let codec = IdlCodec::new(&doc);
+    let header =
+        SailsMessageHeader::v1(interface_id(&doc, "Shop"), entry_id(&doc, "Shop", "Buy"), 1);
+    let payload = with_header(header, ShopError::TooExpensive(U256::from(1000)));
+
+    let message = codec.decode_error(&payload).unwrap();
+
+    assert_eq!(message.kind, MessageKind::Error);
+    assert_eq!(
+        message.value,
+        Value::variant(
+            "TooExpensive",
+            vec![Field::unnamed(Value::U256(U256::from(1000)))]
+        )
+    );
+    assert_eq!(message.value.to_string(), "TooExpensive(1000)");
+
+    let clear = SailsMessageHeader::v1(
+        interface_id(&doc, "Shop"),
+        entry_id(&doc, "Shop", "Clear"),
+        1,
+    );
+    assert_eq!(
+        codec.decode_error(&clear.to_bytes()),
+        Err(Error::NoThrows("Clear".into()))
+    );
+}
+
+#[test]
+fn decode_event_payload_fields() {
+    let doc = parse();
+    let codec = IdlCodec::new(&doc);
+    let buyer = ActorId::from(42);
+
+    let payload = codec
+        .encode_event(
+            "Shop",
+            "Bought",
+            &[
+                Field::named("buyer", Value::ActorId(buyer)),
+                Field::named("item", item_value()),
+            ],
+        )
+        .unwrap();
+
+    let shop = doc.services.iter().find(|s| s.name.name == "Shop").unwrap();
+    let bought = shop.events.iter().find(|e| e.name == "Bought").unwrap();
+    let expected = with_header(
+        SailsMessageHeader::v1(interface_id(&doc, "Shop"), bought.entry_id, 1),
+        (buyer, item()),
+    );
+    assert_eq!(payload, expected);
+
+    let message = codec.decode_event(&payload).unwrap();
+    assert_eq!(message.entry_kind, EntryKind::Event);
+    assert_eq!(message.name, "Bought");
+    assert_eq!(
+        message.value.to_string(),
+        format!(
+            "{{ buyer: {}, item: {{ id: 7, owner: 0x{}, qty: 3 }} }}",
+            Value::ActorId(buyer),
+            "01".repeat(32)
+        )
+    );
+
+    // Inherited events are emitted with the base interface ID
+    let payload = codec
+        .encode_event("Shop", "Pinged", &[Field::unnamed(Value::U32(1))])
+        .unwrap();
+    let message = codec.decode_event(&payload).unwrap();
+    assert_eq!(message.service.as_deref(), Some("Base"));
+    assert_eq!(message.value.to_string(), "(1)");
+}
+
+#[test]
+fn ctor_call_and_error() {
+    let doc = parse();
+    let codec = IdlCodec::new(&doc);
+    let owner = ActorId::from(1);
+
+    let payload = codec
+        .encode_ctor("New", &[Value::ActorId(owner), Value::some(Value::U8(5))])
+        .unwrap();
+
+    assert_eq!(
+        payload,
+        with_header(
+            SailsMessageHeader::v1(InterfaceId::zero(), 0, 0),
+            (owner, Some(5u8))
+        )
+    );
+    let message = codec.decode_call(&payload).unwrap();
+    assert_eq!(message.entry_kind, EntryKind::Ctor);
+    assert_eq!(message.service, None);
+    assert_eq!(message.name, "New");
+
+    let error = with_header(
+        SailsMessageHeader::v1(InterfaceId::zero(), 0, 0),
+        "denied".to_string(),
+    );
+    let message = codec.decode_error(&error).unwrap();
+    assert_eq!(message.value, Value::String("denied".into()));
+}
+
+#[test]
+fn decode_rejects_malformed_payloads() {
+    let doc = parse();
+    let codec = IdlCodec::new(&doc);
+    let header = SailsMessageHeader::v1(
+        interface_id(&doc, "Base"),
+        entry_id(&doc, "Base", "Ping"),
+        1,
+    );
+
+    assert_eq!(codec.decode_reply(&[]), Err(Error::EmptyPayload));
+    assert!(matches!(
+        codec.decode_call(&[1, 2, 3]),
+        Err(Error::Header(_))
+    ));
+
+    let unknown = SailsMessageHeader::v1(InterfaceId::from_u64(1), 0, 1);
+    assert_eq!(
+        codec.decode_call(&unknown.to_bytes()),
+        Err(Error::UnknownInterface(InterfaceId::from_u64(1)))
+    );
+
+    let mut trailing = with_header(header, 1u32);
+    trailing.push(0);
+    assert_eq!(codec.decode_reply(&trailing), Err(Error::TrailingBytes(1)));
+}
+
+#[test]
+fn encode_rejects_mismatched_values() {
+    let doc = parse();
+    let codec = IdlCodec::new(&doc);
+
+    assert_eq!(
+        codec.encode_call("Shop", "Ping", &[]),
+        Err(Error::ArgumentCount {
+            expected: 1,
+            actual: 0
+        })
+    );
+    assert!(matches!(
+        codec.encode_call("Shop", "Ping", &[Value::String("1".into())]),
+        Err(Error::TypeMismatch { .. })
+    ));
+    assert!(matches!(
+        codec.encode_call(
+            "Shop",
+            "Lookup",
+            &[Value::Sequence(vec![]), Value::variant("All", vec![])]
+        ),
+        Err(Error::TypeMismatch { .. })
+    ));
+    assert_eq!(
+        codec.encode_call("Nope", "Ping", &[]),
+        Err(Error::UnknownService("Nope".into()))
+    );
+}
+
+#[test]
+fn type_resolver_without_program() {
+    let resolver = TypeResolver::default();
+    let ty = TypeDecl::option(TypeDecl::Array {
+        item: Box::new(TypeDecl::Primitive(ast::PrimitiveType::U16)),
+        len: 2,
+    });
+    let bytes = Some([1u16, 2]).encode();
+
+    let value = resolver.decode_all(&ty, &bytes).unwrap();
+
+    assert_eq!(value.to_string(), "Some([1, 2])");
+    assert_eq!(resolver.encode(&ty, &value).unwrap(), bytes);
+    assert_eq!(
+        resolver.decode_all(&TypeDecl::named("Missing"), &bytes),
+        Err(Error::UnknownType("Missing".into()))
+    );
+
+    let non_zero = TypeDecl::named("NonZeroU32");
+    assert_eq!(
+        resolver.decode_all(&non_zero, &7u32.encode()).unwrap(),
+        Value::U32(7)
+    );
+    assert!(matches!(
+        resolver.decode_all(&non_zero, &0u32.encode()),
+        Err(Error::Codec(_))
+    ));
+    assert!(matches!(
+        resolver.encode(&non_zero, &Value::U32(0)),
+        Err(Error::TypeMismatch { .. })
+    ));
+}
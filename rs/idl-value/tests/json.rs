@@ -0,0 +1,209 @@
+use gprimitives::{ActorId, U256};
+use parity_scale_codec::Encode;
+use sails_idl_ast::{PrimitiveType as P, TypeDecl};
+use sails_idl_value::*;
+use serde_json::json;
+
+const IDL: &str = include_str!("idls/value.idl");
+
+fn parse() -> ast::IdlDoc {
+    sails_idl_parser_v2::parse_idl(IDL).expect("parse IDL")
+}
+
+fn shop(doc: &ast::IdlDoc) -> &ast::ServiceUnit {
+    doc.services.iter().find(|s| s.name.name == "Shop").unwrap()
+}
+
+fn prim(p: P) -> TypeDecl {
+    TypeDecl::Primitive(p)
+}
+
+fn array(item: TypeDecl, len: u32) -> TypeDecl {
+    TypeDecl::Array {
+        item: Box::new(item),
+        len,
+    }
+}
+
+fn item_u64() -> TypeDecl {
+    TypeDecl::named_with_generics("Item", vec![prim(P::U64)])
+}
+
+fn lookup_output() -> TypeDecl {
+    TypeDecl::result(
+        TypeDecl::tuple(vec![prim(P::U8), TypeDecl::named("Entries")]),
+        prim(P::String),
+    )
+}
+
+fn hex32(byte: u8) -> String {
+    format!("0x{}", format!("{byte:02x}").repeat(32))
+}
+
+#[test]
+fn json_roundtrips_through_scale() {
+    let doc = parse();
+    let codec = IdlCodec::new(&doc);
+    let resolver = codec.service_resolver(shop(&doc));
+
+    let cases = [
+        (item_u64(), json!({ "id": 7, "owner": hex32(1), "qty": 3 })),
+        (TypeDecl::named("Price"), json!("1000000000000000000000")),
+        (TypeDecl::named("Kind"), json!("Any")),
+        (TypeDecl::named("Kind"), json!({ "Exact": 5 })),
+        (
+            TypeDecl::named("Kind"),
+            json!({ "Range": { "from": -1, "to": 1 } }),
+        ),
+        (lookup_output(), json!({ "ok": [1, [["a", "z"]]] })),
+        (lookup_output(), json!({ "err": "nope" })),
+        (
+            array(TypeDecl::option(prim(P::Bool)), 2),
+            json!([true, null]),
+        ),
+        (
+            TypeDecl::tuple(vec![prim(P::U128), prim(P::I128)]),
+            json!(["340282366920938463463374607431768211455", "-1"]),
+        ),
+        (prim(P::Void), json!(null)),
+    ];
+    for (ty, value) in cases {
+        let bytes = resolver.encode_json(&ty, &value).unwrap();
+        assert_eq!(resolver.decode_json(&ty, &bytes).unwrap(), value, "{ty}");
+    }
+}
+
+#[test]
+fn json_encodes_as_rust_types() {
+    let doc = parse();
+    let codec = IdlCodec::new(&doc);
+    let resolver = codec.service_resolver(shop(&doc));
+
+    let bytes = resolver
+        .encode_json(&TypeDecl::named("Price"), &json!("0xff"))
+        .unwrap();
+    assert_eq!(bytes, U256::from(255).encode());
+
+    let bytes = resolver
+        .encode_json(&array(prim(P::U8), 4), &json!("0x01020304"))
+        .unwrap();
+    assert_eq!(bytes, [1u8, 2, 3, 4].encode());
+
+    let bytes = resolver
+        .encode_json(&TypeDecl::named("Entries"), &json!([["ab", "c"]]))
+        .unwrap();
+    assert_eq!(bytes, vec![("ab".to_string(), 'c' as u32)].encode());
+
+    let bytes = resolver
+        .encode_json(&prim(P::U64), &json!("18446744073709551615"))
+        .unwrap();
+    assert_eq!(bytes, u64::MAX.encode());
+}
+
+#[test]
+fn encode_call_json_accepts_positional_and_named_args() {
+    let doc = parse();
+    let codec = IdlCodec::new(&doc);
+    let item = json!({ "id": 7, "owner": hex32(1), "qty": 3 });
+
+    let positional = codec
+        .encode_call_json("Shop", "Buy", &json!([item, [true, false], "gift"]))
+        .unwrap();
+    let named = codec
+        .encode_call_json(
+            "Shop",
+            "Buy",
+            &json!({ "note": "gift", "flags": [true, false], "item": item }),
+        )
+        .unwrap();
+    assert_eq!(positional, named);
+
+    let message = codec.decode_call(&named).unwrap();
+    assert_eq!(
+        message.to_json(),
+        json!({
+            "service": "Shop",
+            "route": "Shop",
+            "name": "Buy",
+            "value": { "item": item, "flags": [true, false], "note": "gift" },
+        })
+    );
+
+    let ctor = codec
+        .encode_ctor_json("New", &json!({ "owner": hex32(5), "fee": null }))
+        .unwrap();
+    assert_eq!(
+        codec.decode_call(&ctor).unwrap().value.to_json(),
+        json!({ "owner": hex32(5), "fee": null })
+    );
+    assert_eq!(
+        codec
+            .encode_call_json("Shop", "Clear", &json!(null))
+            .unwrap(),
+        codec.encode_call("Shop", "Clear", &[]).unwrap()
+    );
+}
+
+#[test]
+fn json_rejects_mismatched_values() {
+    let doc = parse();
+    let codec = IdlCodec::new(&doc);
+    let resolver = codec.service_resolver(shop(&doc));
+
+    let mismatch = |ty: TypeDecl, value| {
+        assert!(
+            matches!(
+                resolver.from_json(&ty, &value),
+                Err(Error::TypeMismatch { .. })
+            ),
+            "{ty}: {value}"
+        )
+    };
+    mismatch(prim(P::U8), json!(256));
+    mismatch(prim(P::U8), json!(-1));
+    mismatch(prim(P::Char), json!("ab"));
+    mismatch(prim(P::H256), json!("0x01"));
+    mismatch(array(prim(P::U8), 2), json!("0x010203"));
+    mismatch(TypeDecl::named("Kind"), json!("Unknown"));
+    mismatch(TypeDecl::named("Kind"), json!("Exact"));
+    mismatch(item_u64(), json!({ "id": 1, "owner": hex32(0) }));
+    mismatch(
+        item_u64(),
+        json!({ "id": 1, "owner": hex32(0), "qty": 1, "extra": 0 }),
+    );
+    mismatch(TypeDecl::result(prim(P::U8), prim(P::String)), json!(1));
+
+    assert_eq!(
+        codec.encode_call_json("Shop", "Lookup", &json!([[1]])),
+        Err(Error::ArgumentCount {
+            expected: 2,
+            actual: 1
+        })
+    );
+    assert!(
+        codec
+            .encode_call_json(
+                "Shop",
+                "Lookup",
+                &json!({ "ids": [], "kind": "Any", "x": 1 })
+            )
+            .is_err()
+    );
+}
+
+#[test]
+fn value_to_json_renders_ids_as_hex() {
+    let value = Value::Struct(vec![
+        Field::named("actor", Value::ActorId(ActorId::from([0xab; 32]))),
+        Field::named("big", Value::U256(U256::MAX)),
+        Field::named("ch", Value::Char('x')),
+    ]);
+    assert_eq!(
+        value.to_json(),
+        json!({
+            "actor": hex32(0xab),
+            "big": U256::MAX.to_string(),
+            "ch": "x",
+        })
+    );
+}
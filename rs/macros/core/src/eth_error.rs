@@ -0,0 +1,128 @@
+use crate::{
+    event::{CratePathAttr, SAILS_PATH},
+    sails_paths::sails_path_or_default,
+};
+use proc_macro_error::abort;
+use proc_macro2::TokenStream;
+use syn::{ItemEnum, parse::Parse};
+
+pub fn derive_eth_error(input: TokenStream) -> TokenStream {
+    // Parse the input tokens into a syntax tree.
+    let input: ItemEnum = syn::parse2(input).unwrap_or_else(|err| {
+        abort!(
+            err.span(),
+            "`EthError` can only be derived for enums: {}",
+            err
+        )
+    });
+
+    if !input.generics.params.is_empty() {
+        abort!(
+            input.generics,
+            "`EthError` cannot be derived for generic enums"
+        );
+    }
+
+    let sails_path_attr = input
+        .attrs
+        .iter()
+        .find(|attr| attr.path().is_ident(SAILS_PATH))
+        .map(|attr| {
+            attr.parse_args_with(CratePathAttr::parse)
+                .unwrap_or_else(|_| abort!(attr, "unexpected value for `crate` argument",))
+        });
+    #[cfg_attr(not(feature = "ethexe"), allow(unused_variables))]
+    let sails_path = &sails_path_or_default(sails_path_attr.map(|attr| attr.path()));
+
+    // Errors are reverted as Solidity custom errors by the `ethexe` dispatch only
+    #[cfg(feature = "ethexe")]
+    return ethexe::generate_eth_error_impl(&input, sails_path);
+    #[cfg(not(feature = "ethexe"))]
+    TokenStream::new()
+}
+
+#[cfg(feature = "ethexe")]
+mod ethexe {
+    use super::*;
+    use proc_macro2::Span;
+    use quote::{quote, quote_spanned};
+    use syn::{Fields, Ident, Path, Type, spanned::Spanned};
+
+    pub(super) fn generate_eth_error_impl(input: &ItemEnum, sails_path: &Path) -> TokenStream {
+        let enum_ident = &input.ident;
+
+        let mut sigs_const = Vec::new();
+        let mut data_match_arms = Vec::new();
+        let mut type_checks = Vec::new();
+
+        for (idx, variant) in input.variants.iter().enumerate() {
+            let variant_ident = &variant.ident;
+            let field_idents: Vec<Ident> = (0..variant.fields.len())
+                .map(|i| Ident::new(&format!("f{i}"), Span::call_site()))
+                .collect();
+
+            // Build the pattern binding the fields in declaration order.
+            let pattern = match &variant.fields {
+                Fields::Named(named) => {
+                    let names = named.named.iter().map(|f| f.ident.as_ref().unwrap());
+                    quote! { #enum_ident::#variant_ident { #( #names: #field_idents ),* } }
+                }
+                Fields::Unnamed(_) => {
+                    quote! { #enum_ident::#variant_ident( #( #field_idents ),* ) }
+                }
+                Fields::Unit => quote! { #enum_ident::#variant_ident },
+            };
+
+            let field_types: Vec<&Type> = variant.fields.iter().map(|f| &f.ty).collect();
+            type_checks.extend(field_types.iter().map(|ty| {
+                quote_spanned! { ty.span() =>
+                    #sails_path::solidity::assert_sol_type_mapping::<#ty>();
+                }
+            }));
+
+            // Build the signature constant, the selector being the first 4 bytes of its hash.
+            let variant_name = variant_ident.to_string();
+            let sol_types = quote! {
+                <<( #( #field_types, )* ) as #sails_path::alloy_sol_types::SolValue>::SolType as #sails_path::alloy_sol_types::SolType>::SOL_NAME
+            };
+            sigs_const.push(quote! {
+                (
+                    #variant_name,
+                    #sol_types,
+                    {
+                        let hash = #sails_path::keccak_const::Keccak256::new().update(#variant_name.as_bytes()).update(#sol_types.as_bytes()).finalize();
+                        [hash[0], hash[1], hash[2], hash[3]]
+                    },
+                )
+            });
+
+            // Build the revert data match arm: the selector followed by the ABI-encoded fields,
+            // cloned as not every Solidity value is implemented for references, e.g. `ActorId`.
+            data_match_arms.push(quote! {
+                #pattern => {
+                    let (_, _, selector) = Self::SIGNATURES[#idx];
+                    let data = #sails_path::alloy_sol_types::SolValue::abi_encode_sequence(&( #( ::core::clone::Clone::clone(#field_idents), )* ));
+                    [selector.as_slice(), data.as_slice()].concat()
+                }
+            });
+        }
+
+        quote! {
+            const _: () = {
+                #( #type_checks )*
+            };
+
+            impl #sails_path::EthError for #enum_ident {
+                const SIGNATURES: &'static [#sails_path::gstd::EthErrorExpo] = &[
+                    #( #sigs_const ),*
+                ];
+
+                fn revert_data(&self) -> #sails_path::Vec<u8> {
+                    match self {
+                        #( #data_match_arms ),*
+                    }
+                }
+            }
+        }
+    }
+}
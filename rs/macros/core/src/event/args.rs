@@ -3,10 +3,10 @@
     parse::{Parse, ParseBuffer},
 };
 
-pub(super) const SAILS_PATH: &str = "sails_path";
+pub(crate) const SAILS_PATH: &str = "sails_path";
 
 #[derive(Debug, PartialEq)]
-pub(super) struct CratePathAttr {
+pub(crate) struct CratePathAttr {
     path: Path,
 }
 
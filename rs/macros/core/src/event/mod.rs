@@ -1,5 +1,6 @@
 use crate::sails_paths::sails_path_or_default;
-use args::{CratePathAttr, EventArgs, SAILS_PATH};
+use args::EventArgs;
+pub(crate) use args::{CratePathAttr, SAILS_PATH};
 use parity_scale_codec::Encode;
 use proc_macro_error::abort;
 use proc_macro2::TokenStream;
@@ -61,6 +62,8 @@ pub fn event(attrs: TokenStream, input: TokenStream) -> TokenStream {
     ethexe::process_indexed(&mut input);
     #[cfg(not(feature = "ethexe"))]
     let eth_event_impl = quote!();
+    #[cfg(not(feature = "ethexe"))]
+    strip_indexed(&mut input);
 
     quote! {
         #input
@@ -71,6 +74,15 @@ pub fn event(attrs: TokenStream, input: TokenStream) -> TokenStream {
     }
 }
 
+/// `#[indexed]` only affects Ethereum logs, so it is dropped when they are not generated
+/// to keep events portable between builds with and without `ethexe`.
+#[cfg(not(feature = "ethexe"))]
+fn strip_indexed(input: &mut ItemEnum) {
+    for field in input.variants.iter_mut().flat_map(|v| v.fields.iter_mut()) {
+        field.attrs.retain(|attr| !attr.path().is_ident("indexed"));
+    }
+}
+
 fn annotate_variants_with_codec(input: &mut ItemEnum, codec: &str) {
     for variant in &mut input.variants {
         let span = variant.ident.span();
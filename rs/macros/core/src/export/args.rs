@@ -13,6 +13,7 @@ pub(crate) struct ExportArgs {
     payable: bool,
     overrides: Option<Path>,
     entry_id: Option<u16>,
+    requires_role: Option<String>,
     scale: bool,
     #[cfg(feature = "ethexe")]
     ethabi: bool,
@@ -27,6 +28,7 @@ fn default() -> Self {
             payable: false,
             overrides: None,
             entry_id: None,
+            requires_role: None,
             scale: true,
             #[cfg(feature = "ethexe")]
             ethabi: true,
@@ -56,6 +58,10 @@ pub fn entry_id(&self) -> Option<u16> {
         self.entry_id
     }
 
+    pub fn requires_role(&self) -> Option<&str> {
+        self.requires_role.as_deref()
+    }
+
     pub fn scale(&self) -> bool {
         self.scale
     }
@@ -76,6 +82,7 @@ fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
             payable: false,
             overrides: None,
             entry_id: None,
+            requires_role: None,
             scale: false,
             #[cfg(feature = "ethexe")]
             ethabi: false,
@@ -106,6 +113,9 @@ fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
                 ImportArg::EntryId(entry_id) => {
                     args.entry_id = Some(entry_id);
                 }
+                ImportArg::RequiresRole(role) => {
+                    args.requires_role = Some(role);
+                }
                 ImportArg::Scale(span) => {
                     if scale_seen {
                         return Err(syn::Error::new(
@@ -162,6 +172,7 @@ enum ImportArg {
     Payable(proc_macro2::Span),
     Overrides(Path),
     EntryId(u16),
+    RequiresRole(String),
     Scale(proc_macro2::Span),
     #[cfg(feature = "ethexe")]
     Ethabi(proc_macro2::Span),
@@ -209,6 +220,18 @@ fn parse(input: ParseStream) -> syn::Result<Self> {
                 let entry_id = lit.base10_parse::<u16>()?;
                 Ok(Self::EntryId(entry_id))
             }
+            "requires_role" => {
+                input.parse::<Token![=]>()?;
+                let lit = input.parse::<LitStr>()?;
+                let role = lit.value();
+                if role.is_empty() {
+                    abort!(
+                        lit,
+                        "`requires_role` argument requires a non-empty role name"
+                    );
+                }
+                Ok(Self::RequiresRole(role))
+            }
             "scale" => Ok(Self::Scale(ident_span)),
             #[cfg(feature = "ethexe")]
             "ethabi" => Ok(Self::Ethabi(ident_span)),
@@ -233,6 +256,7 @@ fn export_parse_args() {
             payable: false,
             overrides: None,
             entry_id: None,
+            requires_role: None,
             scale: true,
             #[cfg(feature = "ethexe")]
             ethabi: true,
@@ -256,6 +280,7 @@ fn export_parse_args_unwrap_result() {
             payable: false,
             overrides: None,
             entry_id: None,
+            requires_role: None,
             scale: true,
             #[cfg(feature = "ethexe")]
             ethabi: true,
@@ -279,6 +304,7 @@ fn export_parse_args_unwrap_result_eq_false() {
             payable: false,
             overrides: None,
             entry_id: None,
+            requires_role: None,
             scale: true,
             #[cfg(feature = "ethexe")]
             ethabi: true,
@@ -302,6 +328,7 @@ fn export_parse_args_payable() {
             payable: true,
             overrides: None,
             entry_id: None,
+            requires_role: None,
             scale: true,
             ethabi: true,
         };
@@ -325,6 +352,7 @@ fn export_parse_args_overrides() {
             payable: false,
             overrides: Some(expected_path),
             entry_id: Some(42),
+            requires_role: None,
             scale: true,
             #[cfg(feature = "ethexe")]
             ethabi: true,
@@ -427,4 +455,14 @@ fn export_parse_args_plain_payable_ok_under_ethexe() {
         assert!(args.ethabi());
         assert!(args.payable());
     }
+
+    #[test]
+    fn export_parse_args_requires_role() {
+        let input = quote!(requires_role = "MINTER", unwrap_result);
+        let args = syn::parse2::<ExportArgs>(input).unwrap();
+
+        assert_eq!(args.requires_role(), Some("MINTER"));
+        assert!(args.unwrap_result());
+        assert!(args.scale());
+    }
 }
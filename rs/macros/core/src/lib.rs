@@ -18,12 +18,14 @@
 
 //! Implementation of the procedural macros exposed via the `sails-macros` crate.
 
+pub use eth_error::derive_eth_error;
 pub use event::{derive_sails_event, event};
 pub use export::export;
 pub use program::{__gprogram_internal, gprogram};
 pub use sails_type::sails_type;
 pub use service::{__gservice_internal, gservice};
 
+mod eth_error;
 mod event;
 mod export;
 mod program;
@@ -1,7 +1,7 @@
 use crate::sails_paths;
 use proc_macro_error::abort;
 use syn::{
-    LitBool, Path, Token,
+    LitBool, LitInt, Path, Token,
     parse::{Parse, ParseStream},
     punctuated::Punctuated,
 };
@@ -11,6 +11,7 @@ pub(super) struct ProgramArgs {
     handle_signal: Option<Path>,
     sails_path: Option<Path>,
     payable: bool,
+    state_version: Option<u32>,
     default_sails_path: Path,
 }
 
@@ -26,6 +27,10 @@ pub fn sails_path(&self) -> &syn::Path {
     pub fn payable(&self) -> bool {
         self.payable
     }
+
+    pub fn state_version(&self) -> Option<u32> {
+        self.state_version
+    }
 }
 
 impl Parse for ProgramArgs {
@@ -35,6 +40,7 @@ fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
             handle_signal: None,
             sails_path: None,
             payable: false,
+            state_version: None,
             default_sails_path: syn::parse_str(sails_paths::SAILS).unwrap(),
         };
 
@@ -49,6 +55,9 @@ fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
                 ProgramArg::AcceptTransfer(val) => {
                     attrs.payable = val;
                 }
+                ProgramArg::StateVersion(version) => {
+                    attrs.state_version = Some(version);
+                }
             }
         }
 
@@ -60,6 +69,7 @@ enum ProgramArg {
     HandleSignal(Path),
     SailsPath(Path),
     AcceptTransfer(bool),
+    StateVersion(u32),
 }
 
 impl Parse for ProgramArg {
@@ -92,9 +102,14 @@ fn parse(input: ParseStream<'_>) -> syn::Result<Self> {
                 }
                 Ok(Self::AcceptTransfer(true))
             }
+            "state_version" => {
+                input.parse::<Token![=]>()?;
+                let version: LitInt = input.parse()?;
+                Ok(Self::StateVersion(version.base10_parse()?))
+            }
             _ => abort!(
                 ident,
-                "`program` attribute can only contain `handle_signal`, `crate`, `payable` parameters",
+                "`program` attribute can only contain `handle_signal`, `crate`, `payable`, `state_version` parameters",
             ),
         }
     }
@@ -118,6 +133,7 @@ fn gprogram_parse_attrs() {
             ),
             sails_path: None,
             payable: false,
+            state_version: None,
             default_sails_path: syn::parse_str(sails_paths::SAILS).unwrap(),
         };
 
@@ -138,6 +154,7 @@ fn gprogram_parse_crate() {
                 PathSegment::from(Ident::new("sails_rename", Span::call_site())).into(),
             ),
             payable: false,
+            state_version: None,
             default_sails_path: syn::parse_str(sails_paths::SAILS).unwrap(),
         };
 
@@ -156,6 +173,26 @@ fn program_parse_payable() {
             handle_signal: None,
             sails_path: None,
             payable: true,
+            state_version: None,
+            default_sails_path: syn::parse_str(sails_paths::SAILS).unwrap(),
+        };
+
+        // act
+        let args = syn::parse2::<ProgramArgs>(input).unwrap();
+
+        // arrange
+        assert_eq!(expected, args);
+    }
+
+    #[test]
+    fn program_parse_state_version() {
+        // arrange
+        let input = quote!(state_version = 3);
+        let expected = ProgramArgs {
+            handle_signal: None,
+            sails_path: None,
+            payable: false,
+            state_version: Some(3),
             default_sails_path: syn::parse_str(sails_paths::SAILS).unwrap(),
         };
 
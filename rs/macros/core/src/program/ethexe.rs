@@ -221,18 +221,21 @@ fn sol_ctor_branch_impl(
             }
         });
 
-        let unwrap_token = self.error_type.is_some().then(|| quote!(.unwrap()));
+        let await_token = self.is_async().then(|| quote!(.await));
+        let raw_call =
+            quote!(#program_type_path :: #handler_ident (#(#handler_params_into),*) #await_token);
+        let call = self.ok_or_revert_call(raw_call);
 
         let ctor_invocation = if self.is_async() {
             quote! {
                 gstd::message_loop(async move {
-                    let program = #program_type_path :: #handler_ident (#(#handler_params_into),*).await #unwrap_token;
+                    let program = #call;
                     unsafe { #program_ident = Some(program) };
                 });
             }
         } else {
             quote! {
-                let program = #program_type_path :: #handler_ident (#(#handler_params_into),*) #unwrap_token;
+                let program = #call;
                 unsafe { #program_ident = Some(program) };
             }
         };
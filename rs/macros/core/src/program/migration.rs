@@ -0,0 +1,195 @@
+use proc_macro_error::abort;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use std::collections::BTreeMap;
+use syn::{
+    Attribute, FnArg, Ident, ImplItem, ImplItemFn, ItemImpl, LitInt, Path, ReturnType, Type,
+    Visibility, parse_quote,
+};
+
+/// Name of the constructor generated for programs declaring migrations.
+pub(super) const MIGRATION_CTOR: &str = "migrate";
+
+/// A function marked with `#[migration(from = N)]` transforming the state of version `N`
+/// into the state of version `N + 1`.
+struct Migration {
+    from: u32,
+    ident: Ident,
+    state_type: Type,
+}
+
+/// Collects `migration` functions of the program, strips their attributes and
+/// adds the `migrate` constructor chaining them up to `state_version`.
+pub(super) fn expand_migrations(
+    program_impl: &mut ItemImpl,
+    state_version: Option<u32>,
+    sails_path: &Path,
+) {
+    let mut migrations = BTreeMap::new();
+    for item in program_impl.items.iter_mut() {
+        if let ImplItem::Fn(fn_item) = item
+            && let Some(from) = take_migration_attr(fn_item)
+        {
+            let migration = parse_migration(fn_item, from);
+            if migrations.insert(from, migration).is_some() {
+                abort!(
+                    fn_item,
+                    "only one `migration` function from state version {} is allowed",
+                    from
+                );
+            }
+        }
+    }
+
+    let Some((_, first)) = migrations.first_key_value() else {
+        return;
+    };
+    let Some(state_version) = state_version else {
+        abort!(
+            first.ident,
+            "`migration` functions require the `state_version` argument of the `program` attribute"
+        );
+    };
+    for migration in migrations.values() {
+        if migration.from >= state_version {
+            abort!(
+                migration.ident,
+                "`migration` from state version {} must be lower than the program state version {}",
+                migration.from,
+                state_version
+            );
+        }
+    }
+    if let Some(missing) = (first.from..state_version).find(|from| !migrations.contains_key(from)) {
+        abort!(
+            first.ident,
+            "missing `migration` from state version {} to reach state version {}",
+            missing,
+            state_version
+        );
+    }
+
+    if let Some(existing) = program_impl.items.iter().find_map(|item| match item {
+        ImplItem::Fn(fn_item) if fn_item.sig.ident == MIGRATION_CTOR => Some(fn_item),
+        _ => None,
+    }) {
+        abort!(
+            existing,
+            "`{}` is reserved for the constructor generated from `migration` functions",
+            MIGRATION_CTOR
+        );
+    }
+
+    let migrations: Vec<_> = migrations.into_values().collect();
+    program_impl.items.push(ImplItem::Fn(migration_ctor(
+        &migrations,
+        state_version,
+        sails_path,
+    )));
+}
+
+/// Generates the `migrate` constructor: the state of the requested version is decoded
+/// and passed through all migrations up to the current state version.
+fn migration_ctor(migrations: &[Migration], state_version: u32, sails_path: &Path) -> ImplItemFn {
+    let migrate_ident = Ident::new(MIGRATION_CTOR, Span::call_site());
+    let first = migrations.first().map(|m| m.from).unwrap_or_default();
+    let arms = migrations.iter().enumerate().map(|(idx, migration)| {
+        let from = migration.from;
+        let state_type = &migration.state_type;
+        let steps = migrations[idx..].iter().map(|m| &m.ident);
+        quote! {
+            #from => {
+                let state = <#state_type as #sails_path::scale_codec::DecodeAll>::decode_all(&mut state.as_slice())
+                    .unwrap_or_else(|_| panic!("Failed to decode state of version {}", #from));
+                #( let state = Self:: #steps (state); )*
+                state
+            }
+        }
+    });
+    let doc = format!(
+        " Creates the program from the SCALE-encoded state of version {first} or later, \
+         migrating it to state version {state_version}."
+    );
+    parse_quote! {
+        #[doc = #doc]
+        pub fn #migrate_ident(from_version: u32, state: #sails_path::prelude::Vec<u8>) -> Self {
+            match from_version {
+                #( #arms )*
+                _ => panic!("Unsupported state version {}", from_version),
+            }
+        }
+    }
+}
+
+/// Annotation marking the `migrate` constructor with the state version it produces.
+pub(super) fn state_version_annotation(state_version: u32) -> TokenStream2 {
+    let state_version = state_version.to_string();
+    quote!(#[annotate(state_version = #state_version)])
+}
+
+fn take_migration_attr(fn_item: &mut ImplItemFn) -> Option<u32> {
+    let mut attrs = fn_item
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("migration"));
+    let attr = attrs.next()?;
+    if let Some(duplicate) = attrs.next() {
+        abort!(duplicate, "only one `migration` attribute is allowed");
+    }
+    let from = parse_migration_attr(attr);
+    fn_item
+        .attrs
+        .retain(|attr| !attr.path().is_ident("migration"));
+    Some(from)
+}
+
+fn parse_migration_attr(attr: &Attribute) -> u32 {
+    let mut from = None;
+    attr.parse_nested_meta(|meta| {
+        if meta.path.is_ident("from") {
+            let lit: LitInt = meta.value()?.parse()?;
+            from = Some(lit.base10_parse::<u32>()?);
+            Ok(())
+        } else {
+            Err(meta.error("`migration` attribute can only contain the `from` parameter"))
+        }
+    })
+    .unwrap_or_else(|err| {
+        abort!(
+            err.span(),
+            "failed to parse `migration` attribute arguments: {}",
+            err
+        )
+    });
+    from.unwrap_or_else(|| {
+        abort!(
+            attr,
+            "`migration` attribute requires `from = <state version>`"
+        )
+    })
+}
+
+fn parse_migration(fn_item: &ImplItemFn, from: u32) -> Migration {
+    let signature_error = || -> ! {
+        abort!(
+            fn_item,
+            "`migration` function must be private, synchronous, take a single state argument \
+             and return the state of the next version"
+        )
+    };
+    if !matches!(fn_item.vis, Visibility::Inherited)
+        || fn_item.sig.asyncness.is_some()
+        || fn_item.sig.inputs.len() != 1
+        || matches!(fn_item.sig.output, ReturnType::Default)
+    {
+        signature_error();
+    }
+    let Some(FnArg::Typed(arg)) = fn_item.sig.inputs.first() else {
+        signature_error();
+    };
+    Migration {
+        from,
+        ident: fn_item.sig.ident.clone(),
+        state_type: arg.ty.as_ref().clone(),
+    }
+}
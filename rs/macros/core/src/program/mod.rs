@@ -19,6 +19,7 @@
 mod args;
 #[cfg(feature = "ethexe")]
 mod ethexe;
+mod migration;
 
 /// Static Spans of Program `impl` block
 static mut PROGRAM_SPANS: BTreeMap<String, Span> = BTreeMap::new();
@@ -81,6 +82,11 @@ fn new(program_impl: ItemImpl, program_args: ProgramArgs) -> Self {
         let mut program_impl = program_impl;
         let type_constraints = program_impl.generics.where_clause.take();
         let has_default_ctor_only = ensure_default_program_ctor(&mut program_impl);
+        migration::expand_migrations(
+            &mut program_impl,
+            program_args.state_version(),
+            program_args.sails_path(),
+        );
 
         Self {
             program_impl,
@@ -141,7 +147,13 @@ impl ProgramBuilder {
     fn wire_up_service_exposure(
         &mut self,
         program_ident: &Ident,
-    ) -> (TokenStream2, TokenStream2, TokenStream2, TokenStream2) {
+    ) -> (
+        TokenStream2,
+        TokenStream2,
+        TokenStream2,
+        TokenStream2,
+        TokenStream2,
+    ) {
         let mut services_meta = Vec::new();
         let mut meta_asyncness = Vec::new();
         let mut routes = BTreeMap::new();
@@ -179,6 +191,12 @@ fn wire_up_service_exposure(
                 }
 
                 let mut invocation_export = shared::invocation_export_or_default(fn_item);
+                if invocation_export.requires_role.is_some() {
+                    abort!(
+                        invocation_export.span,
+                        "`requires_role` can only be applied to service methods"
+                    );
+                }
                 let entry_id = routes.len() as u16;
 
                 #[cfg(feature = "ethexe")]
@@ -332,6 +350,23 @@ impl #generics #sails_path::meta::ProgramMeta for #program_type_path #program_ty
             })
             .collect::<Vec<_>>();
 
+        let native_route_dispatches = route_dispatch_data
+            .iter()
+            .map(|(route_idx, service_ctor_ident, service_type)| {
+                quote! {
+                    #route_idx => {
+                        let svc = self.#service_ctor_ident();
+                        #sails_path::native_route_dispatch!(
+                            svc: #service_type,
+                            interface_id = interface_id,
+                            entry_id = entry_id,
+                            input = &input[header_len..],
+                        );
+                    }
+                }
+            })
+            .collect::<Vec<_>>();
+
         let solidity_main = self.sol_main(solidity_dispatchers.as_slice());
 
         let payable = self.program_args.payable().then(|| {
@@ -342,6 +377,36 @@ impl #generics #sails_path::meta::ProgramMeta for #program_type_path #program_ty
             }
         });
 
+        let native_payable = self.program_args.payable().then(|| {
+            quote! {
+                if #sails_path::gstd::Syscall::message_value() > 0 && input.is_empty() {
+                    return (#sails_path::prelude::Vec::new(), 0);
+                }
+            }
+        });
+
+        let native_handle_fn = quote!(
+            async fn handle(&self, input: &[u8]) -> (#sails_path::prelude::Vec<u8>, #sails_path::ValueUnit) {
+                #services_count_expr
+                #services_ids_expr
+
+                #native_payable
+
+                if let Ok(header) = <#sails_path::meta::SailsMessageHeader as #sails_path::Decode>::decode(&mut &input[..]) {
+                    let header_len = header.hlen().inner() as usize;
+                    let (interface_id, route_id, entry_id) = header
+                        .try_match_interfaces(INTERFACE_IDS)
+                        .expect("Failed to find matching service")
+                        .into_inner();
+                    match route_id {
+                        #(#native_route_dispatches)*
+                        _ => #sails_path::gstd::unknown_input_panic("Unknown route_id", &[route_id])
+                    }
+                }
+                #sails_path::gstd::native::take_reply().unwrap_or_default()
+            }
+        );
+
         let main_fn = quote!(
             #services_count_expr
             #services_ids_expr
@@ -402,10 +467,11 @@ extern "C" fn handle_signal() {
             main_fn,
             handle_reply_fn,
             handle_signal_fn,
+            native_handle_fn,
         )
     }
 
-    fn generate_init(&self, program_ident: &Ident) -> (TokenStream2, TokenStream2) {
+    fn generate_init(&self, program_ident: &Ident) -> (TokenStream2, TokenStream2, TokenStream2) {
         let sails_path = self.sails_path();
         let type_info_path = sails_paths::type_info_path(sails_path);
 
@@ -415,6 +481,7 @@ fn generate_init(&self, program_ident: &Ident) -> (TokenStream2, TokenStream2) {
         let program_ctors = self.program_ctors();
 
         let mut ctor_dispatches = Vec::with_capacity(program_ctors.len() + 1);
+        let mut native_ctor_dispatches = Vec::with_capacity(program_ctors.len());
         let mut ctor_params_structs = Vec::with_capacity(program_ctors.len());
         let mut ctor_meta_variants = Vec::with_capacity(program_ctors.len());
 
@@ -425,9 +492,17 @@ fn generate_init(&self, program_ident: &Ident) -> (TokenStream2, TokenStream2) {
                     &input_ident,
                     program_ident,
                 ));
+                native_ctor_dispatches
+                    .push(fn_builder.native_ctor_branch_impl(program_type_path, &input_ident));
             }
             ctor_params_structs.push(fn_builder.ctor_params_struct());
-            ctor_meta_variants.push(fn_builder.ctor_meta_variant());
+            let state_version_ann = self
+                .program_args
+                .state_version()
+                .filter(|_| fn_builder.ident == migration::MIGRATION_CTOR)
+                .map(migration::state_version_annotation);
+            let ctor_meta_variant = fn_builder.ctor_meta_variant();
+            ctor_meta_variants.push(quote!(#state_version_ann #ctor_meta_variant));
         }
 
         let solidity_init = self.sol_init(&input_ident);
@@ -447,23 +522,31 @@ fn generate_init(&self, program_ident: &Ident) -> (TokenStream2, TokenStream2) {
         // For programs with no user-defined constructors, also accept an empty payload.
         // The default create() takes no arguments and is idempotent, so an empty slice is
         // unambiguous: it cannot be confused with any real constructor message.
-        let empty_input_guard = if self.has_default_ctor_only {
+        let (empty_input_guard, native_empty_input_guard) = if self.has_default_ctor_only {
             let fn_builder = program_ctors
-                .first()
+                .iter()
+                .find(|fn_builder| fn_builder.ident == "create")
                 .expect("default ctor must exist when has_default_ctor_only is true");
             let ctor_ident = fn_builder.ident;
             let params_struct_ident = &fn_builder.params_struct_ident;
-            quote! {
-                if #input_ident.is_empty() {
-                    #sails_path::program_ctor!(
-                        #program_ident = #program_type_path :: #ctor_ident (),
-                        params_struct = meta_in_program::#params_struct_ident
-                    );
-                    return;
-                }
-            }
+            (
+                quote! {
+                    if #input_ident.is_empty() {
+                        #sails_path::program_ctor!(
+                            #program_ident = #program_type_path :: #ctor_ident (),
+                            params_struct = meta_in_program::#params_struct_ident
+                        );
+                        return;
+                    }
+                },
+                quote! {
+                    if #input_ident.is_empty() {
+                        return #program_type_path :: #ctor_ident ();
+                    }
+                },
+            )
         } else {
-            quote!()
+            (quote!(), quote!())
         };
 
         let init_fn = quote! {
@@ -479,6 +562,23 @@ extern "C" fn init() {
             }
         };
 
+        let native_init_fn = quote! {
+            async fn init(mut #input_ident: &[u8]) -> Self {
+                #native_empty_input_guard
+
+                let Ok(header) = <#sails_path::meta::SailsMessageHeader as #sails_path::Decode>::decode(&mut #input_ident) else {
+                    #sails_path::gstd::unknown_input_panic("Failed to decode ctor header", #input_ident);
+                };
+                if header.interface_id() != #sails_path::meta::InterfaceId::zero() {
+                    #sails_path::gstd::unknown_input_panic("Non zero ctor interface_id", header.to_bytes().as_slice());
+                }
+                match header.entry_id() {
+                    #(#native_ctor_dispatches)*
+                    _ => #sails_path::gstd::unknown_input_panic("Unexpected ctor entry_id", #input_ident),
+                }
+            }
+        };
+
         let meta_in_program = quote! {
             mod meta_in_program {
                 use super::*;
@@ -492,7 +592,21 @@ pub enum ConstructorsMeta {
                 }
             }
         };
-        (meta_in_program, init_fn)
+        (meta_in_program, init_fn, native_init_fn)
+    }
+
+    fn state_version_impl(&self) -> TokenStream2 {
+        let Some(state_version) = self.program_args.state_version() else {
+            return quote!();
+        };
+        let (program_type_path, ..) = self.impl_type();
+        let (generics, program_type_constraints) = self.impl_constraints();
+        quote! {
+            impl #generics #program_type_path #program_type_constraints {
+                /// Version of the state layout of the program.
+                pub const STATE_VERSION: u32 = #state_version;
+            }
+        }
     }
 }
 
@@ -546,17 +660,21 @@ fn gen_gprogram_impl(program_impl: ItemImpl, program_args: ProgramArgs) -> Token
     let match_ctor_impl = program_builder.match_ctor_impl(&program_ident);
     let program_const = program_builder.program_const();
 
-    let (program_meta_impl, main_fn, handle_reply_fn, handle_signal_fn) =
+    let (program_meta_impl, main_fn, handle_reply_fn, handle_signal_fn, native_handle_fn) =
         program_builder.wire_up_service_exposure(&program_ident);
-    let (meta_in_program, init_fn) = program_builder.generate_init(&program_ident);
+    let (meta_in_program, init_fn, native_init_fn) = program_builder.generate_init(&program_ident);
+    let state_version_impl = program_builder.state_version_impl();
 
     let (program_type_path, ..) = program_builder.impl_type();
+    let (generics, program_type_constraints) = program_builder.impl_constraints();
 
     let program_impl = program_builder.deref();
 
     quote!(
         #program_impl
 
+        #state_version_impl
+
         #program_meta_impl
 
         #meta_in_program
@@ -565,6 +683,14 @@ fn gen_gprogram_impl(program_impl: ItemImpl, program_args: ProgramArgs) -> Token
 
         #program_const
 
+        #sails_path::native_program! {
+            impl #generics #sails_path::gstd::NativeProgram for #program_type_path #program_type_constraints {
+                #native_init_fn
+
+                #native_handle_fn
+            }
+        }
+
         #[cfg(target_arch = "wasm32")]
         pub mod wasm {
             use super::*;
@@ -609,6 +735,12 @@ fn discover_program_ctors<'a>(
         |fn_item| program_ctor_predicate(fn_item, &self_type_path, program_type_path),
         sails_path,
     );
+    if let Some(ctor) = vec.iter().find(|f| f.requires_role.is_some()) {
+        abort!(
+            ctor.ident,
+            "`requires_role` can only be applied to service methods"
+        );
+    }
     vec.sort_by_key(|f| f.route.to_lowercase());
     vec.iter_mut()
         .enumerate()
@@ -795,6 +927,36 @@ fn ctor_branch_impl(
         )
     }
 
+    fn native_ctor_branch_impl(
+        &self,
+        program_type_path: &TypePath,
+        input_ident: &Ident,
+    ) -> TokenStream2 {
+        let sails_path = self.sails_path;
+        let handler_ident = self.ident;
+        let entry_id = self.entry_id;
+        let handler_args = self.params_idents();
+        let handler_types = self.params_types();
+
+        let await_token = self.is_async().then(|| quote!(.await));
+        let raw_call =
+            quote! { #program_type_path :: #handler_ident (#(#handler_args),*) #await_token };
+        let params_struct_ident = &self.params_struct_ident;
+        let ctor_call_impl = if self.error_type.is_some() {
+            quote!(#sails_path::ok_or_throws!(#raw_call, meta_in_program::#params_struct_ident, 0))
+        } else {
+            raw_call
+        };
+
+        quote!(
+            #entry_id => {
+                let (#(#handler_args),*): (#(#handler_types),*)  = #sails_path::Decode::decode(&mut #input_ident)
+                    .unwrap_or_else(|_| #sails_path::gstd::unknown_input_panic("Unknown request", #input_ident));
+                #ctor_call_impl
+            }
+        )
+    }
+
     fn ctor_params_struct(&self) -> TokenStream2 {
         let sails_path = self.sails_path;
         let params_struct_ident = &self.params_struct_ident;
@@ -7,6 +7,7 @@
 pub(super) struct SailsTypeArgs {
     pub path: Option<Path>,
     pub no_reflect_hash: bool,
+    pub lenient: bool,
 }
 
 impl Parse for SailsTypeArgs {
@@ -39,11 +40,19 @@ fn parse(input: &ParseBuffer) -> syn::Result<Self> {
                         ));
                     }
                     out.no_reflect_hash = true;
+                } else if ident == "lenient" {
+                    if out.lenient {
+                        return Err(syn::Error::new_spanned(
+                            &ident,
+                            "`lenient` specified more than once",
+                        ));
+                    }
+                    out.lenient = true;
                 } else {
                     return Err(syn::Error::new_spanned(
                         &ident,
                         format!(
-                            "unknown `sails_type` argument `{ident}` (expected `crate = <path>` or `no_reflect_hash`)"
+                            "unknown `sails_type` argument `{ident}` (expected `crate = <path>`, `no_reflect_hash` or `lenient`)"
                         ),
                     ));
                 }
@@ -0,0 +1,142 @@
+use proc_macro_error::abort;
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{Fields, ItemEnum, LitInt, Path, Variant, parse_quote};
+
+const UNKNOWN_VARIANT: &str = "Unknown";
+
+/// Appends the `Unknown(Vec<u8>)` variant to the enum and implements `Encode` and `Decode`
+/// so that variants with unknown indices are decoded into it instead of failing.
+///
+/// The payload of an unknown variant has no known length, so `Unknown` takes the index byte
+/// and all remaining input. This makes it usable for enums decoded last in a payload,
+/// e.g. replies, errors and events.
+pub(super) fn lenient_codec(item: &mut ItemEnum, sails_path: &Path) -> TokenStream {
+    if let Some(variant) = item.variants.iter().find(|v| v.ident == UNKNOWN_VARIANT) {
+        abort!(
+            variant,
+            "`lenient` enums cannot declare the `{}` variant, it is added by `sails_type`",
+            UNKNOWN_VARIANT
+        );
+    }
+
+    let scale_codec = quote! { #sails_path::scale_codec };
+    let mut decode_arms = Vec::with_capacity(item.variants.len());
+    let mut encode_arms = Vec::with_capacity(item.variants.len());
+    for (position, variant) in item.variants.iter_mut().enumerate() {
+        let index = variant_index(variant, position);
+        // No codec derive consumes `#[codec(...)]` attributes on lenient enums.
+        variant.attrs.retain(|attr| !attr.path().is_ident("codec"));
+        let name = &variant.ident;
+        match &variant.fields {
+            Fields::Unit => {
+                decode_arms.push(quote! { #index => Ok(Self::#name) });
+                encode_arms.push(quote! { Self::#name => dest.push_byte(#index) });
+            }
+            Fields::Unnamed(fields) => {
+                let bindings: Vec<_> = (0..fields.unnamed.len())
+                    .map(|i| format_ident!("f{i}"))
+                    .collect();
+                let decode = bindings
+                    .iter()
+                    .map(|_| quote! { #scale_codec::Decode::decode(input)? });
+                decode_arms.push(quote! { #index => Ok(Self::#name( #(#decode),* )) });
+                encode_arms.push(quote! {
+                    Self::#name( #(#bindings),* ) => {
+                        dest.push_byte(#index);
+                        #( #scale_codec::Encode::encode_to(#bindings, dest); )*
+                    }
+                });
+            }
+            Fields::Named(fields) => {
+                let names: Vec<_> = fields.named.iter().map(|f| &f.ident).collect();
+                decode_arms.push(quote! {
+                    #index => Ok(Self::#name { #( #names: #scale_codec::Decode::decode(input)? ),* })
+                });
+                encode_arms.push(quote! {
+                    Self::#name { #(#names),* } => {
+                        dest.push_byte(#index);
+                        #( #scale_codec::Encode::encode_to(#names, dest); )*
+                    }
+                });
+            }
+        }
+    }
+
+    let vec = quote! { #sails_path::prelude::Vec };
+    item.variants.push(parse_quote! {
+        /// Variant unknown to this version of the type, holds its SCALE encoding
+        /// including the index byte.
+        #[type_info(skip)]
+        Unknown(#vec<u8>)
+    });
+
+    let name = &item.ident;
+    let (impl_generics, ty_generics, _) = item.generics.split_for_impl();
+    let type_params: Vec<_> = item.generics.type_params().map(|p| &p.ident).collect();
+    let where_clause = |bound: TokenStream| {
+        let predicates = item
+            .generics
+            .where_clause
+            .iter()
+            .flat_map(|w| w.predicates.iter());
+        quote! { where #( #predicates, )* #( #type_params: #bound, )* }
+    };
+    let decode_where = where_clause(quote! { #scale_codec::Decode });
+    let encode_where = where_clause(quote! { #scale_codec::Encode });
+
+    quote! {
+        impl #impl_generics #scale_codec::Decode for #name #ty_generics #decode_where {
+            fn decode<__I: #scale_codec::Input>(input: &mut __I) -> Result<Self, #scale_codec::Error> {
+                match input.read_byte()? {
+                    #( #decode_arms, )*
+                    index => {
+                        let mut bytes = #vec::from([index]);
+                        match input.remaining_len()? {
+                            Some(len) => {
+                                bytes.resize(1 + len, 0);
+                                input.read(&mut bytes[1..])?;
+                            }
+                            None => {
+                                while let Ok(byte) = input.read_byte() {
+                                    bytes.push(byte);
+                                }
+                            }
+                        }
+                        Ok(Self::Unknown(bytes))
+                    }
+                }
+            }
+        }
+
+        impl #impl_generics #scale_codec::Encode for #name #ty_generics #encode_where {
+            fn encode_to<__O: #scale_codec::Output + ?Sized>(&self, dest: &mut __O) {
+                match self {
+                    #( #encode_arms, )*
+                    Self::Unknown(bytes) => dest.write(bytes),
+                }
+            }
+        }
+
+        impl #impl_generics #scale_codec::EncodeLike for #name #ty_generics #encode_where {}
+    }
+}
+
+/// Returns the index set by `#[codec(index = N)]` or the position of the variant,
+/// the same way the `parity-scale-codec` derive does.
+fn variant_index(variant: &Variant, position: usize) -> u8 {
+    let mut index = None;
+    for attr in variant.attrs.iter().filter(|a| a.path().is_ident("codec")) {
+        let _ = attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("index") {
+                let lit: LitInt = meta.value()?.parse()?;
+                index = Some(lit.base10_parse::<u8>()?);
+            }
+            Ok(())
+        });
+    }
+    index.unwrap_or_else(|| {
+        u8::try_from(position)
+            .unwrap_or_else(|_| abort!(variant, "enums cannot have more than 256 variants"))
+    })
+}
@@ -6,12 +6,13 @@
 use syn::{Item, Path};
 
 mod args;
+mod lenient;
 
 pub fn sails_type(attrs: TokenStream, item: TokenStream) -> TokenStream {
     let args: SailsTypeArgs = syn::parse2(attrs)
         .unwrap_or_else(|err| abort!(err.span(), "invalid `sails_type` arguments: {}", err));
 
-    let parsed: Item = syn::parse2(item).unwrap_or_else(|err| {
+    let mut parsed: Item = syn::parse2(item).unwrap_or_else(|err| {
         abort!(
             err.span(),
             "`sails_type` can only be applied to structs or enums: {}",
@@ -53,16 +54,29 @@ pub fn sails_type(attrs: TokenStream, item: TokenStream) -> TokenStream {
     let scale_codec = quote! { #sails_path::scale_codec };
     let type_info = quote! { #sails_path::type_info };
 
-    let (derive_list, reflect_hash_attr) = if args.no_reflect_hash {
-        (
-            quote! { #scale_codec::Encode, #scale_codec::Decode, #type_info::TypeInfo },
+    // Lenient enums implement `Encode` and `Decode` manually.
+    let (codec_derives, codec_attr, lenient_impls) = match &mut parsed {
+        Item::Enum(item_enum) if args.lenient => (
             quote! {},
-        )
+            quote! {},
+            lenient::lenient_codec(item_enum, &sails_path),
+        ),
+        Item::Struct(item_struct) if args.lenient => {
+            abort!(item_struct, "`lenient` can only be applied to enums")
+        }
+        _ => (
+            quote! { #scale_codec::Encode, #scale_codec::Decode, },
+            quote! { #[codec(crate = #scale_codec)] },
+            quote! {},
+        ),
+    };
+
+    let (derive_list, reflect_hash_attr) = if args.no_reflect_hash {
+        (quote! { #codec_derives #type_info::TypeInfo }, quote! {})
     } else {
         (
             quote! {
-                #scale_codec::Encode,
-                #scale_codec::Decode,
+                #codec_derives
                 #type_info::TypeInfo,
                 #sails_path::ReflectHash
             },
@@ -72,9 +86,11 @@ pub fn sails_type(attrs: TokenStream, item: TokenStream) -> TokenStream {
 
     quote! {
         #[derive(#derive_list)]
-        #[codec(crate = #scale_codec)]
+        #codec_attr
         #[type_info(crate = #type_info)]
         #reflect_hash_attr
         #parsed
+
+        #lenient_impls
     }
 }
@@ -1,6 +1,7 @@
 use super::*;
 use proc_macro2::TokenStream;
-use quote::quote;
+use quote::{quote, quote_spanned};
+use syn::{GenericArgument, PathArguments, spanned::Spanned};
 
 impl ServiceBuilder<'_> {
     pub(super) fn service_signature_impl(&self) -> TokenStream {
@@ -132,18 +133,20 @@ fn generate_sol_decode_and_handle(&self, fn_builder: &FnBuilder) -> TokenStream
         });
 
         let (result_type, reply_with_value) = fn_builder.result_type_with_value();
+        let sol_type_checks = self.sol_type_checks(fn_builder, result_type);
 
         let await_token = fn_builder.is_async().then(|| quote!(.await));
-        let unwrap_token = fn_builder.error_type.is_some().then(|| quote!(.unwrap()));
+        let raw_call = quote!(self.#handler_ident(#(#handler_params_into),*)#await_token);
+        let call = fn_builder.ok_or_revert_call(raw_call);
 
         let handle_token = if reply_with_value {
             quote! {
-                let command_reply: CommandReply< #result_type > = self.#handler_ident(#(#handler_params_into),*)#await_token #unwrap_token.into();
+                let command_reply: CommandReply< #result_type > = #call.into();
                 let (result, value) = command_reply.to_tuple();
             }
         } else {
             quote! {
-                let result = self.#handler_ident(#(#handler_params_into),*)#await_token #unwrap_token;
+                let result = #call;
                 let value = 0u128;
             }
         };
@@ -151,6 +154,7 @@ fn generate_sol_decode_and_handle(&self, fn_builder: &FnBuilder) -> TokenStream
         let payable_check = fn_builder.payable_check();
 
         quote! {
+            #sol_type_checks
             let (__encode_reply, #(#handler_params,)*) : (bool, #(#sol_types,)*) = #sails_path::alloy_sol_types::SolValue::abi_decode_params(input).ok()?;
             #payable_check
             #handle_token
@@ -164,4 +168,114 @@ fn generate_sol_decode_and_handle(&self, fn_builder: &FnBuilder) -> TokenStream
             return Some((output, value, __encode_reply));
         }
     }
+
+    /// Asserts the Solidity type mapping of the parameters and the result of an `ethabi` method.
+    ///
+    /// Types without a mapping are rejected at their span, while named types are checked
+    /// against `SolTypeMapping` by the generated code.
+    fn sol_type_checks(&self, fn_builder: &FnBuilder, result_type: &Type) -> TokenStream {
+        let sails_path = self.sails_path;
+        let mut named_types = Vec::new();
+        for ty in fn_builder
+            .params_types()
+            .iter()
+            .copied()
+            .chain([result_type])
+        {
+            collect_sol_named_types(ty, &self.generics, &mut named_types);
+        }
+        let checks = named_types.into_iter().map(|ty| {
+            quote_spanned! { ty.span() =>
+                #sails_path::solidity::assert_sol_type_mapping::<#ty>();
+            }
+        });
+        quote! {
+            #( #checks )*
+        }
+    }
+}
+
+/// Collects named types of `ty` checked by `SolTypeMapping`, going through sequences
+/// and references, and aborts on types Solidity has no counterpart for.
+///
+/// Type parameters of the service are left to the bounds of the dispatch code.
+fn collect_sol_named_types<'a>(ty: &'a Type, generics: &Generics, named_types: &mut Vec<&'a Type>) {
+    match ty {
+        Type::Array(array) => collect_sol_named_types(&array.elem, generics, named_types),
+        Type::Slice(slice) => collect_sol_named_types(&slice.elem, generics, named_types),
+        Type::Reference(reference) => {
+            collect_sol_named_types(&reference.elem, generics, named_types)
+        }
+        Type::Paren(paren) => collect_sol_named_types(&paren.elem, generics, named_types),
+        Type::Group(group) => collect_sol_named_types(&group.elem, generics, named_types),
+        Type::Tuple(tuple) if tuple.elems.is_empty() => {}
+        Type::Tuple(tuple) => abort!(
+            tuple,
+            "tuples have no Solidity type mapping";
+            help = "use separate parameters, or a `Vec` or an array of items of the same type"
+        ),
+        Type::Path(type_path) if type_path.qself.is_none() => {
+            let is_type_param = type_path
+                .path
+                .get_ident()
+                .is_some_and(|ident| generics.type_params().any(|param| param.ident == *ident));
+            if is_type_param {
+                return;
+            }
+            let Some(segment) = type_path.path.segments.last() else {
+                return;
+            };
+            match segment.ident.to_string().as_str() {
+                "bool" | "u8" | "u16" | "u32" | "u64" | "u128" | "i8" | "i16" | "i32" | "i64"
+                | "i128" | "str" | "String" => {}
+                "Vec" => match single_type_argument(&segment.arguments) {
+                    Some(item) => collect_sol_named_types(item, generics, named_types),
+                    None => named_types.push(ty),
+                },
+                "Option" => abort!(
+                    ty,
+                    "`Option` has no Solidity type mapping";
+                    help = "Solidity has no optional values, use a default value instead"
+                ),
+                "Result" => abort!(
+                    ty,
+                    "`Result` has no Solidity type mapping";
+                    help = "use `#[export(unwrap_result)]` to reply with the `Ok` value of a method returning `Result`"
+                ),
+                name @ ("BTreeMap" | "BTreeSet" | "HashMap" | "HashSet" | "VecDeque"
+                | "BinaryHeap" | "LinkedList") => abort!(
+                    ty,
+                    "`{}` has no Solidity type mapping", name;
+                    help = "use a `Vec` instead"
+                ),
+                name @ ("f32" | "f64") => abort!(
+                    ty,
+                    "`{}` has no Solidity type mapping", name;
+                    help = "Solidity has no floating point numbers, use an integer holding a fixed-point value instead"
+                ),
+                name @ ("usize" | "isize") => abort!(
+                    ty,
+                    "`{}` has no Solidity type mapping", name;
+                    help = "use an integer of a fixed size, e.g. `u64`"
+                ),
+                "char" => abort!(
+                    ty,
+                    "`char` has no Solidity type mapping";
+                    help = "use `String` or `u32` instead"
+                ),
+                _ => named_types.push(ty),
+            }
+        }
+        _ => abort!(ty, "type has no Solidity type mapping"),
+    }
+}
+
+fn single_type_argument(arguments: &PathArguments) -> Option<&Type> {
+    let PathArguments::AngleBracketed(arguments) = arguments else {
+        return None;
+    };
+    match arguments.args.first() {
+        Some(GenericArgument::Type(ty)) if arguments.args.len() == 1 => Some(ty),
+        _ => None,
+    }
 }
@@ -305,7 +305,15 @@ pub(super) fn exposure_impl(&self) -> TokenStream {
         let exposure_emit_event_impls = self.exposure_emit_event_impls();
         let exposure_emit_eth_impls = self.exposure_emit_eth_impls();
 
-        let exposure_funcs = &self.service_impl.items;
+        let exposure_funcs = self.service_impl.items.iter().map(|item| match item {
+            ImplItem::Fn(fn_item) => self
+                .service_handlers
+                .iter()
+                .find(|fn_builder| fn_builder.ident == &fn_item.sig.ident)
+                .and_then(|fn_builder| fn_builder.role_guarded_fn())
+                .map_or_else(|| quote!(#item), |guarded_fn| quote!(#guarded_fn)),
+            _ => quote!(#item),
+        });
 
         quote! {
             #( #exposure_allow_attrs )*
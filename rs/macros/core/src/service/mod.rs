@@ -9,7 +9,10 @@
 use proc_macro_error::abort;
 use proc_macro2::{Span, TokenStream};
 use quote::quote;
-use syn::{Generics, Ident, ItemImpl, Path, Type, TypePath, Visibility, WhereClause};
+use syn::{
+    Generics, Ident, ImplItem, ImplItemFn, ItemImpl, Path, Type, TypePath, Visibility, WhereClause,
+    parse_quote,
+};
 
 mod args;
 #[cfg(feature = "ethexe")]
@@ -208,6 +211,18 @@ fn discover_service_handlers<'a>(
 }
 
 impl FnBuilder<'_> {
+    /// Returns the handler with the `requires_role` check prepended to its body.
+    fn role_guarded_fn(&self) -> Option<ImplItemFn> {
+        let role = self.requires_role.as_ref()?;
+        let sails_path = self.sails_path;
+        let mut guarded_fn = self.impl_fn.clone();
+        guarded_fn.block.stmts.insert(
+            0,
+            parse_quote!(#sails_path::access_control::RoleGuard::ensure_role(&**self, #role);),
+        );
+        Some(guarded_fn)
+    }
+
     fn result_type_with_static_lifetime(&self) -> Type {
         let (result_type, _) = self.result_type_with_value();
 
@@ -247,6 +262,11 @@ fn handler_meta_variant(&self) -> TokenStream {
         #[cfg(not(feature = "ethexe"))]
         let codec_ann: Option<TokenStream> = None;
 
+        let requires_role_ann = self
+            .requires_role
+            .as_ref()
+            .map(|role| quote!(#[annotate(requires_role = #role)]));
+
         if let Some(err_ty) = &self.error_type {
             let err_ty = shared::replace_any_lifetime_with_static(err_ty.clone());
             quote!(
@@ -254,6 +274,7 @@ fn handler_meta_variant(&self) -> TokenStream {
                 #payable_ann
                 #returns_value_ann
                 #codec_ann
+                #requires_role_ann
                 #handler_route_ident(#params_struct_ident, #result_type, #err_ty)
             )
         } else {
@@ -262,6 +283,7 @@ fn handler_meta_variant(&self) -> TokenStream {
                 #payable_ann
                 #returns_value_ann
                 #codec_ann
+                #requires_role_ann
                 #handler_route_ident(#params_struct_ident, #result_type)
             )
         }
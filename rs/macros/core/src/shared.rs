@@ -86,6 +86,7 @@ pub(crate) struct InvocationExport {
     pub payable: bool,
     pub overrides: Option<Path>,
     pub entry_id: Option<u16>,
+    pub requires_role: Option<String>,
     pub scale: bool,
     #[cfg(feature = "ethexe")]
     pub ethabi: bool,
@@ -114,6 +115,7 @@ pub(crate) fn invocation_export(fn_impl: &ImplItemFn) -> Option<InvocationExport
             payable,
             overrides: args.overrides().cloned(),
             entry_id: args.entry_id(),
+            requires_role: args.requires_role().map(ToOwned::to_owned),
             scale,
             #[cfg(feature = "ethexe")]
             ethabi,
@@ -133,6 +135,7 @@ pub(crate) fn invocation_export_or_default(fn_impl: &ImplItemFn) -> InvocationEx
             payable: false,
             overrides: None,
             entry_id: None,
+            requires_role: None,
             scale: true,
             #[cfg(feature = "ethexe")]
             ethabi: true,
@@ -312,6 +315,7 @@ pub(crate) struct FnBuilder<'a> {
     pub payable: bool,
     pub overrides: Option<Path>,
     pub override_entry_id: Option<u16>,
+    pub requires_role: Option<String>,
     pub impl_fn: &'a ImplItemFn,
     pub ident: &'a Ident,
     pub params_struct_ident: Ident,
@@ -340,6 +344,7 @@ pub(crate) fn new(
             payable,
             overrides,
             entry_id: override_entry_id,
+            requires_role,
             scale,
             #[cfg(feature = "ethexe")]
             ethabi,
@@ -365,6 +370,7 @@ pub(crate) fn new(
             payable,
             overrides,
             override_entry_id,
+            requires_role,
             impl_fn,
             ident,
             params_struct_ident,
@@ -440,6 +446,29 @@ pub(crate) fn route_camel_case(&self) -> String {
             .to_case(Case::Camel)
     }
 
+    /// Wraps the handler call of an `unwrap_result` method to revert its error as a Solidity error.
+    ///
+    /// The invocation is spanned at the error type, so a missing `EthError` implementation
+    /// is reported there.
+    #[cfg(feature = "ethexe")]
+    pub(crate) fn ok_or_revert_call(
+        &self,
+        raw_call: proc_macro2::TokenStream,
+    ) -> proc_macro2::TokenStream {
+        let Some(error_type) = &self.error_type else {
+            return raw_call;
+        };
+        let span = error_type.span();
+        let mut sails_path = self.sails_path.clone();
+        sails_path
+            .segments
+            .iter_mut()
+            .for_each(|segment| segment.ident.set_span(span));
+        quote::quote_spanned! { span =>
+            #sails_path::ok_or_revert!(#raw_call)
+        }
+    }
+
     #[cfg(feature = "ethexe")]
     pub(crate) fn payable_check(&self) -> proc_macro2::TokenStream {
         if !self.payable {
@@ -232,3 +232,30 @@ pub fn service(&self) -> MyService {
 
     insta::assert_snapshot!(result);
 }
+
+#[test]
+fn generates_migration_ctor() {
+    let args = quote!(state_version = 3);
+    let input = quote! {
+        impl MyProgram {
+            pub fn new() -> Self {
+                Self { value: 0 }
+            }
+
+            #[migration(from = 2)]
+            fn from_v2(state: StateV2) -> Self {
+                Self { value: state.value }
+            }
+
+            #[migration(from = 1)]
+            fn from_v1(state: StateV1) -> StateV2 {
+                StateV2 { value: state.value.into() }
+            }
+        }
+    };
+
+    let result = gprogram(args, input).to_string();
+    let result = prettyplease::unparse(&syn::parse_str(&result).unwrap());
+
+    insta::assert_snapshot!(result);
+}
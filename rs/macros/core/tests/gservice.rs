@@ -447,3 +447,26 @@ pub fn method_three(&self) -> u32 { 3 }
 
     insta::assert_snapshot!(result);
 }
+
+#[test]
+fn works_with_requires_role() {
+    let args = quote!();
+    let input = quote! {
+        impl SomeService {
+            #[export(requires_role = "MINTER")]
+            pub fn mint(&mut self, to: ActorId, amount: u128) -> bool {
+                true
+            }
+
+            #[export]
+            pub fn total_supply(&self) -> u128 {
+                0
+            }
+        }
+    };
+
+    let result = gservice(args, input).to_string();
+    let result = prettyplease::unparse(&syn::parse_str(&result).unwrap());
+
+    insta::assert_snapshot!(result);
+}
@@ -65,3 +65,18 @@ fn custom_crate_path_and_no_reflect_hash() {
     let result = format(sails_type(attrs, input));
     insta::assert_snapshot!(result);
 }
+
+#[test]
+fn lenient_enum() {
+    let attrs = quote! { lenient };
+    let input = quote! {
+        pub enum MyEnum<T> {
+            A,
+            #[codec(index = 3)]
+            B(u32, T),
+            C { x: String },
+        }
+    };
+    let result = format(sails_type(attrs, input));
+    insta::assert_snapshot!(result);
+}
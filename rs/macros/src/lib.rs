@@ -67,11 +67,17 @@ pub fn service(args: TokenStream, impl_tokens: TokenStream) -> TokenStream {
 /// - `handle_signal` - specifies a path to a function that will be called
 ///                     after standard signal handling provided by the `gstd` crate.
 /// - `payable` - specifies that the program can accept transfers of value.
+/// - `state_version` - specifies the version of the program state layout.
 ///
 /// The macro also accepts a `handle_reply` attribute that can be used to specify a function
 /// that will handle replies. This function should be defined within the program and accepts `&self`.
 /// The function will be called automatically when a reply is received.
 ///
+/// Private functions marked with the `migration(from = N)` attribute transform the state of
+/// version `N` into the state of version `N + 1`, the last one returning the program itself.
+/// They require `state_version` and are chained by the generated `migrate` constructor,
+/// which creates the program from the SCALE-encoded state of any supported earlier version.
+///
 /// # Examples
 ///
 /// ```rust
@@ -112,6 +118,9 @@ pub fn program(args: TokenStream, impl_tokens: TokenStream) -> TokenStream {
 ///    string of your choice.
 /// - `unwrap_result` - Indicates that the method's `Result<T, E>` return value should be unwrapped.
 ///   If specified, the method will panic if the result is an `Err`.
+/// - `requires_role = "ROLE"` - Restricts a service method to callers having the `ROLE` role.
+///   The service must implement `sails::access_control::RoleGuard`, whose `ensure_role` is called
+///   before the method body and panics if the message source does not have the role.
 ///
 /// # Examples
 ///
@@ -156,6 +165,9 @@ pub fn export(args: TokenStream, impl_item_fn_tokens: TokenStream) -> TokenStrea
 ///   For static types, the ABI-encoded value is left-padded with zeros to 32 bytes.
 /// - **Data:** A byte array containing the ABI-encoded non-indexed fields of the event, encoded as a tuple.
 ///
+/// Fields marked with `#[indexed]` become topics. Without the `ethexe` feature the attribute is ignored,
+/// so the same event definition can be used with and without it.
+///
 /// This is intended to be used with the `#[sails::event]` procedural macro, which automatically
 /// implements the trait for your enum-based event definitions.
 ///
@@ -234,6 +246,39 @@ pub fn event(args: TokenStream, input: TokenStream) -> TokenStream {
     sails_macros_core::event(args.into(), input.into()).into()
 }
 
+/// Derives `EthError`, reverting each variant of an error enum as a Solidity custom error.
+///
+/// Errors of methods exported with `unwrap_result` are reverted by the Ethereum ABI dispatch
+/// (`ethexe` feature) with the 4-byte selector of `Variant(types...)` followed by the ABI-encoded
+/// fields of the variant. The same errors are declared by the interface generated by `sails-sol-gen`
+/// from the `throws` type of the IDL, so EVM tooling can decode the reverts.
+///
+/// Field types must have a Solidity counterpart, the same as parameters of `ethabi` methods.
+/// Without the `ethexe` feature the derive expands to nothing.
+///
+/// # Arguments
+///
+/// - `#[sails_path(crate = <path>)]` — override the path to the `sails` crate (defaults to `sails`).
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[derive(sails::Encode, sails::TypeInfo, sails::EthError)]
+/// #[codec(crate = sails::scale_codec)]
+/// #[type_info(crate = sails::type_info)]
+/// pub enum TransferError {
+///     // error InsufficientBalance(uint128 needed);
+///     InsufficientBalance { needed: u128 },
+///     // error Paused();
+///     Paused,
+/// }
+/// ```
+#[proc_macro_error]
+#[proc_macro_derive(EthError, attributes(sails_path))]
+pub fn derive_eth_error(input: TokenStream) -> TokenStream {
+    sails_macros_core::derive_eth_error(input.into()).into()
+}
+
 /// Derives the canonical Sails type bundle: `Encode`, `Decode`, `TypeInfo`,
 /// and `ReflectHash`, together with their `crate =` helper attributes routed
 /// to the `sails` re-exports.
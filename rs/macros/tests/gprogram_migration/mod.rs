@@ -0,0 +1,49 @@
+use sails::{Decode, Encode};
+use sails_macros::program;
+
+#[derive(Encode, Decode)]
+#[codec(crate = sails::scale_codec)]
+pub(super) struct StateV1 {
+    pub(super) counter: u32,
+}
+
+#[derive(Encode, Decode)]
+#[codec(crate = sails::scale_codec)]
+pub(super) struct StateV2 {
+    pub(super) counter: u64,
+    pub(super) label: String,
+}
+
+pub(super) struct MyProgram {
+    pub(super) counter: u64,
+    pub(super) label: String,
+    pub(super) migrated: bool,
+}
+
+#[program(state_version = 3)]
+impl MyProgram {
+    pub fn new(label: String) -> Self {
+        Self {
+            counter: 0,
+            label,
+            migrated: false,
+        }
+    }
+
+    #[migration(from = 1)]
+    fn from_v1(state: StateV1) -> StateV2 {
+        StateV2 {
+            counter: state.counter.into(),
+            label: String::from("v1"),
+        }
+    }
+
+    #[migration(from = 2)]
+    fn from_v2(state: StateV2) -> Self {
+        Self {
+            counter: state.counter,
+            label: state.label,
+            migrated: true,
+        }
+    }
+}
@@ -0,0 +1,39 @@
+#![cfg(not(feature = "ethexe"))]
+
+mod gprogram_migration;
+
+#[test]
+fn gprogram_migration_runs_migrations_up_to_state_version() {
+    use gprogram_migration::{MyProgram, StateV1, StateV2};
+    use sails::Encode;
+
+    assert_eq!(MyProgram::STATE_VERSION, 3);
+
+    let prg = MyProgram::new("fresh".into());
+    assert!(!prg.migrated);
+
+    let prg = MyProgram::migrate(1, StateV1 { counter: 7 }.encode());
+    assert_eq!(prg.counter, 7);
+    assert_eq!(prg.label, "v1");
+    assert!(prg.migrated);
+
+    let state = StateV2 {
+        counter: 42,
+        label: "v2".into(),
+    };
+    let prg = MyProgram::migrate(2, state.encode());
+    assert_eq!(prg.counter, 42);
+    assert_eq!(prg.label, "v2");
+}
+
+#[test]
+#[should_panic(expected = "Unsupported state version 3")]
+fn gprogram_migration_rejects_unsupported_state_version() {
+    gprogram_migration::MyProgram::migrate(3, Vec::new());
+}
+
+#[test]
+#[should_panic(expected = "Failed to decode state of version 1")]
+fn gprogram_migration_rejects_malformed_state() {
+    gprogram_migration::MyProgram::migrate(1, vec![1, 2, 3, 4, 5]);
+}
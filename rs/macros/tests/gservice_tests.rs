@@ -4,6 +4,7 @@
 use sails::meta::{Identifiable, InterfaceId, SailsMessageHeader};
 use sails::{Decode, Encode};
 
+mod gservice_with_access_control;
 mod gservice_with_basics;
 mod gservice_with_events;
 mod gservice_with_export_unwrap_result;
@@ -13,6 +14,7 @@
 mod gservice_with_lifetimes_and_events;
 mod gservice_with_multiple_names;
 mod gservice_with_reply_with_value;
+mod gservice_with_state_wrappers;
 mod gservice_with_trait_bounds;
 
 #[tokio::test]
@@ -630,3 +632,131 @@ async fn gservice_with_export_unwrap_result_panic() {
         .await
         .unwrap();
 }
+
+#[test]
+fn gservice_with_access_control() {
+    use gservice_with_access_control::{MINTER_ROLE, TokenService};
+    use sails::{
+        access_control::{AccessControlEvents, AccessControlService, RolesData},
+        cell::RefCell,
+        gstd::Syscall,
+        prelude::ActorId,
+    };
+
+    let admin = ActorId::from(1);
+    let minter = ActorId::from(2);
+    let roles = RefCell::new(RolesData::new(admin));
+    let total_supply = RefCell::new(0);
+
+    Syscall::with_message_source(admin);
+    let mut access_control = AccessControlService::new(&roles).expose(1);
+    let mut emitter = access_control.emitter();
+    access_control
+        .grant_role(MINTER_ROLE.into(), minter)
+        .unwrap();
+
+    assert_eq!(
+        emitter.take_events(),
+        vec![AccessControlEvents::RoleGranted {
+            role: MINTER_ROLE.into(),
+            account: minter,
+            sender: admin,
+        }]
+    );
+    assert_eq!(
+        access_control.role_members(MINTER_ROLE.into()),
+        vec![minter]
+    );
+
+    Syscall::with_message_source(minter);
+    let mut token = TokenService::new(&roles, &total_supply).expose(1);
+    assert_eq!(token.mint(42), 42);
+    assert_eq!(token.total_supply(), 42);
+}
+
+#[test]
+#[should_panic(expected = "is missing role MINTER")]
+fn gservice_with_access_control_missing_role() {
+    use gservice_with_access_control::TokenService;
+    use sails::{access_control::RolesData, cell::RefCell, gstd::Syscall, prelude::ActorId};
+
+    let roles = RefCell::new(RolesData::new(ActorId::from(1)));
+    let total_supply = RefCell::new(0);
+
+    Syscall::with_message_source(ActorId::from(2));
+    let mut token = TokenService::new(&roles, &total_supply).expose(1);
+    token.mint(42);
+}
+
+#[test]
+fn gservice_with_access_control_grant_requires_admin_role() {
+    use sails::{
+        access_control::{AccessControlError, AccessControlService, DEFAULT_ADMIN_ROLE, RolesData},
+        cell::RefCell,
+        gstd::Syscall,
+        prelude::ActorId,
+    };
+
+    let roles = RefCell::new(RolesData::new(ActorId::from(1)));
+    let sender = ActorId::from(2);
+
+    Syscall::with_message_source(sender);
+    let mut access_control = AccessControlService::new(&roles).expose(1);
+    assert_eq!(
+        access_control.grant_role("MINTER".into(), sender),
+        Err(AccessControlError::MissingRole {
+            role: DEFAULT_ADMIN_ROLE.into(),
+            account: sender,
+        })
+    );
+    assert!(!roles.borrow().has_role("MINTER", sender));
+}
+
+#[test]
+fn gservice_with_state_wrappers() {
+    use gservice_with_state_wrappers::CounterService;
+    use sails::{
+        cell::{Cell, RefCell},
+        gstd::Syscall,
+        prelude::ActorId,
+        state::{AdminError, AdminEvents, AdminService},
+    };
+
+    let owner = ActorId::from(1);
+    let counter = RefCell::new(0);
+    let owner_cell = Cell::new(owner);
+    let paused = Cell::new(false);
+
+    Syscall::with_message_source(owner);
+    let mut svc = CounterService::new(&counter, &owner_cell, &paused).expose(1);
+    assert_eq!(svc.increment(), Ok(1));
+
+    let mut admin = AdminService::new(&owner_cell, &paused).expose(1);
+    let mut emitter = admin.emitter();
+    admin.pause().unwrap();
+    assert!(admin.paused());
+    assert_eq!(
+        emitter.take_events(),
+        vec![AdminEvents::Paused { account: owner }]
+    );
+
+    let mut svc = CounterService::new(&counter, &owner_cell, &paused).expose(1);
+    assert_eq!(svc.increment(), Err("state is paused".into()));
+    assert_eq!(svc.value(), 1);
+
+    let new_owner = ActorId::from(2);
+    admin.transfer_ownership(new_owner).unwrap();
+    assert_eq!(admin.owner(), new_owner);
+    assert_eq!(admin.unpause(), Err(AdminError::NotOwner));
+    assert_eq!(
+        emitter.take_events(),
+        vec![AdminEvents::OwnershipTransferred {
+            previous_owner: owner,
+            new_owner,
+        }]
+    );
+
+    Syscall::with_message_source(new_owner);
+    admin.unpause().unwrap();
+    assert_eq!(svc.increment(), Ok(2));
+}
@@ -0,0 +1,44 @@
+use sails::{access_control::*, cell::RefCell, prelude::*};
+
+pub const MINTER_ROLE: &str = "MINTER";
+
+pub struct TokenService<'a> {
+    roles: &'a RefCell<RolesData>,
+    total_supply: &'a RefCell<u128>,
+}
+
+impl<'a> TokenService<'a> {
+    pub fn new(roles: &'a RefCell<RolesData>, total_supply: &'a RefCell<u128>) -> Self {
+        Self {
+            roles,
+            total_supply,
+        }
+    }
+}
+
+impl RoleGuard for TokenService<'_> {
+    fn has_role(&self, role: &str, account: ActorId) -> bool {
+        self.roles.borrow().has_role(role, account)
+    }
+}
+
+impl<'a> From<TokenService<'a>> for AccessControlService<&'a RefCell<RolesData>> {
+    fn from(value: TokenService<'a>) -> Self {
+        AccessControlService::new(value.roles)
+    }
+}
+
+#[service(extends = AccessControlService<&'a RefCell<RolesData>>)]
+impl<'a> TokenService<'a> {
+    #[export(requires_role = "MINTER")]
+    pub fn mint(&mut self, amount: u128) -> u128 {
+        let mut total_supply = self.total_supply.borrow_mut();
+        *total_supply += amount;
+        *total_supply
+    }
+
+    #[export]
+    pub fn total_supply(&self) -> u128 {
+        *self.total_supply.borrow()
+    }
+}
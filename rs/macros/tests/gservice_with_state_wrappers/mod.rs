@@ -0,0 +1,46 @@
+use sails::{
+    cell::{Cell, RefCell},
+    prelude::*,
+    state::{AdminService, Paused},
+};
+
+pub struct CounterService<'a> {
+    counter: Paused<'a, &'a RefCell<u32>>,
+    owner: &'a Cell<ActorId>,
+    paused: &'a Cell<bool>,
+}
+
+impl<'a> CounterService<'a> {
+    pub fn new(
+        counter: &'a RefCell<u32>,
+        owner: &'a Cell<ActorId>,
+        paused: &'a Cell<bool>,
+    ) -> Self {
+        Self {
+            counter: Paused::new(counter, paused),
+            owner,
+            paused,
+        }
+    }
+}
+
+impl<'a> From<CounterService<'a>> for AdminService<'a> {
+    fn from(value: CounterService<'a>) -> Self {
+        AdminService::new(value.owner, value.paused)
+    }
+}
+
+#[service(extends = AdminService<'a>)]
+impl<'a> CounterService<'a> {
+    #[export(unwrap_result)]
+    pub fn increment(&mut self) -> Result<u32, String> {
+        let mut counter = self.counter.write().map_err(|e| e.to_string())?;
+        *counter += 1;
+        Ok(*counter)
+    }
+
+    #[export]
+    pub fn value(&self) -> u32 {
+        *self.counter.read().unwrap()
+    }
+}
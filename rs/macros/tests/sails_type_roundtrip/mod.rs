@@ -20,3 +20,14 @@ pub enum MyEnum {
 pub struct LegacyType {
     pub a: u32,
 }
+
+#[sails_type(lenient)]
+#[derive(PartialEq, Clone, Debug)]
+pub enum LenientEnum {
+    Unit,
+    Tuple(u32, String),
+    #[codec(index = 5)]
+    Named {
+        x: u32,
+    },
+}
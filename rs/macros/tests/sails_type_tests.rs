@@ -4,7 +4,7 @@
 
 mod sails_type_roundtrip;
 
-use sails_type_roundtrip::{LegacyType, MyEnum, MyType};
+use sails_type_roundtrip::{LegacyType, LenientEnum, MyEnum, MyType};
 
 #[test]
 fn struct_round_trips() {
@@ -32,3 +32,25 @@ fn legacy_type_round_trips() {
     let decoded = LegacyType::decode(&mut &bytes[..]).unwrap();
     assert_eq!(value, decoded);
 }
+
+#[test]
+fn lenient_enum_round_trips() {
+    let values = [
+        LenientEnum::Unit,
+        LenientEnum::Tuple(1, "a".to_string()),
+        LenientEnum::Named { x: 7 },
+    ];
+    for value in values {
+        let bytes = value.encode();
+        assert_eq!(LenientEnum::decode(&mut &bytes[..]).unwrap(), value);
+    }
+    assert_eq!(LenientEnum::Named { x: 7 }.encode(), [5, 7, 0, 0, 0]);
+}
+
+#[test]
+fn lenient_enum_decodes_unknown_variants() {
+    let bytes = [9u8, 1, 2, 3];
+    let value = LenientEnum::decode(&mut &bytes[..]).unwrap();
+    assert_eq!(value, LenientEnum::Unknown(bytes.to_vec()));
+    assert_eq!(value.encode(), bytes);
+}
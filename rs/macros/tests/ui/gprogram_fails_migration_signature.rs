@@ -0,0 +1,20 @@
+use sails_macros::program;
+
+struct MyProgram;
+
+struct StateV1;
+
+#[program(state_version = 2)]
+impl MyProgram {
+    pub fn new() -> Self {
+        Self
+    }
+
+    #[migration(from = 1)]
+    pub fn from_v1(_state: StateV1) -> Self {
+        Self
+    }
+}
+
+#[tokio::main]
+async fn main() {}
@@ -0,0 +1,24 @@
+use sails_macros::{export, program, service};
+
+struct MyService;
+
+#[service]
+impl MyService {
+    #[export]
+    pub fn do_this(&mut self) -> u32 {
+        42
+    }
+}
+
+struct MyProgram;
+
+#[program]
+impl MyProgram {
+    #[export(requires_role = "ADMIN")]
+    pub fn service(&self) -> MyService {
+        MyService
+    }
+}
+
+#[tokio::main]
+async fn main() {}
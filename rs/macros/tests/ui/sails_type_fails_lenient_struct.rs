@@ -0,0 +1,8 @@
+use sails::sails_type;
+
+#[sails_type(lenient)]
+pub struct S {
+    pub a: u32,
+}
+
+fn main() {}
@@ -8,6 +8,8 @@ pub enum Error {
     Idl(#[from] sails_idl_parser_v2::error::Error),
     #[error("Conversion type to Solidity error: {0}")]
     Conversion(#[from] crate::sol_conversion::ConversionError),
+    #[error("Solidity import error: {0}")]
+    Import(#[from] crate::import::ImportError),
     #[error("Askama template rendering error: {0}")]
     Askama(#[from] askama::Error),
 }
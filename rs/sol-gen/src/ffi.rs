@@ -26,6 +26,7 @@ pub enum ErrorCode {
     Conversion,
     Askama,
     NullPtr,
+    Import,
 }
 
 #[repr(C)]
@@ -73,6 +74,7 @@ fn error_code(error: &ContractGenerationError) -> ErrorCode {
         ContractGenerationError::Idl(_) => ErrorCode::Idl,
         ContractGenerationError::Conversion(_) => ErrorCode::Conversion,
         ContractGenerationError::Askama(_) => ErrorCode::Askama,
+        ContractGenerationError::Import(_) => ErrorCode::Import,
     }
 }
 
@@ -7,9 +7,10 @@
 use askama::Template;
 use convert_case::{Case, Casing};
 use sails_idl_parser_v2::{
-    ast::{IdlDoc, PrimitiveType, Type, TypeDecl, codec::has_ethabi_codec},
+    ast::{IdlDoc, PrimitiveType, Type, TypeDecl, TypeDef, codec::has_ethabi_codec},
     parse_idl,
 };
+use serde_json::{Value, json};
 
 struct Arg {
     ty: String,
@@ -26,6 +27,14 @@ struct Function {
     returns_value: bool,
 }
 
+impl Function {
+    /// Solidity signature of the function, the leading `bool` being the `_callReply` flag.
+    fn signature(&self) -> String {
+        let args: String = self.args.iter().map(|arg| format!(",{}", arg.ty)).collect();
+        format!("{}(bool{args})", self.name)
+    }
+}
+
 struct EventArg {
     ty: String,
     indexed: bool,
@@ -37,6 +46,32 @@ struct Event {
     args: Vec<EventArg>,
 }
 
+impl Event {
+    /// Solidity signature of the event, indexed fields included.
+    fn signature(&self) -> String {
+        let args: Vec<&str> = self.args.iter().map(|arg| arg.ty.as_str()).collect();
+        format!("{}({})", self.name, args.join(","))
+    }
+}
+
+struct ErrorArg {
+    ty: String,
+    name: Option<String>,
+}
+
+struct CustomError {
+    name: String,
+    args: Vec<ErrorArg>,
+}
+
+impl CustomError {
+    /// Solidity signature of the custom error, its selector being the first 4 bytes of the hash.
+    fn signature(&self) -> String {
+        let args: Vec<&str> = self.args.iter().map(|arg| arg.ty.as_str()).collect();
+        format!("{}({})", self.name, args.join(","))
+    }
+}
+
 #[repr(C)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SolidityFile {
@@ -53,6 +88,7 @@ struct ContractData {
     contract_name: String,
     functions: Vec<Function>,
     events: Vec<Event>,
+    errors: Vec<CustomError>,
 }
 
 macro_rules! define_template {
@@ -66,6 +102,7 @@ struct $name {
             contract_name: String,
             functions: Vec<Function>,
             events: Vec<Event>,
+            errors: Vec<CustomError>,
         }
 
         impl From<ContractData> for $name {
@@ -76,6 +113,7 @@ fn from(data: ContractData) -> Self {
                     contract_name: data.contract_name,
                     functions: data.functions,
                     events: data.events,
+                    errors: data.errors,
                 }
             }
         }
@@ -104,6 +142,7 @@ pub fn generate_solidity_contract(
         contract_name: contract_name.into(),
         functions: functions_from_idl(&idl_doc)?,
         events: events_from_idl(&idl_doc)?,
+        errors: errors_from_idl(&idl_doc)?,
     };
 
     let rendered = match solidity_file {
@@ -119,6 +158,82 @@ pub fn generate_solidity_contract(
     Ok(rendered.into_bytes())
 }
 
+/// Generates the Solidity ABI JSON of the program interface declared by `I<Contract>`.
+///
+/// Besides the standard fields, functions and errors carry their 4-byte `selector` and events the
+/// `topic` hash of their signature, the same as `EthEventExpo` of the program, as `0x` hex.
+pub fn generate_abi_json(idl_content: &str) -> Result<Vec<u8>> {
+    let idl_doc = parse_idl(idl_content)?;
+
+    let mut abi = vec![];
+
+    for event in events_from_idl(&idl_doc)? {
+        let inputs: Vec<Value> = event
+            .args
+            .iter()
+            .map(|arg| {
+                json!({
+                    "name": arg.name.clone().unwrap_or_default(),
+                    "type": arg.ty,
+                    "indexed": arg.indexed,
+                })
+            })
+            .collect();
+        abi.push(json!({
+            "type": "event",
+            "name": event.name,
+            "inputs": inputs,
+            "anonymous": false,
+            "topic": to_hex(&keccak256(&event.signature())),
+        }));
+    }
+
+    for error in errors_from_idl(&idl_doc)? {
+        let inputs: Vec<Value> = error
+            .args
+            .iter()
+            .map(|arg| json!({ "name": arg.name.clone().unwrap_or_default(), "type": arg.ty }))
+            .collect();
+        abi.push(json!({
+            "type": "error",
+            "name": error.name,
+            "inputs": inputs,
+            "selector": to_hex(&keccak256(&error.signature())[..4]),
+        }));
+    }
+
+    for function in functions_from_idl(&idl_doc)? {
+        let mut inputs = vec![json!({ "name": "_callReply", "type": "bool" })];
+        inputs.extend(
+            function
+                .args
+                .iter()
+                .map(|arg| json!({ "name": arg.name, "type": arg.ty })),
+        );
+        abi.push(json!({
+            "type": "function",
+            "name": function.name,
+            "inputs": inputs,
+            "outputs": [{ "name": "messageId", "type": "bytes32" }],
+            "stateMutability": if function.payable { "payable" } else { "nonpayable" },
+            "selector": to_hex(&keccak256(&function.signature())[..4]),
+        }));
+    }
+
+    Ok(format!("{:#}\n", Value::Array(abi)).into_bytes())
+}
+
+fn keccak256(s: &str) -> [u8; 32] {
+    keccak_const::Keccak256::new()
+        .update(s.as_bytes())
+        .finalize()
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    let hex: String = bytes.iter().map(|byte| format!("{byte:02x}")).collect();
+    format!("0x{hex}")
+}
+
 fn resolve_type_decl(decl: &TypeDecl, types: &[Type]) -> Result<String, ConversionError> {
     match decl {
         TypeDecl::Named { name, .. } => types
@@ -246,3 +361,70 @@ fn events_from_idl(idl_doc: &IdlDoc) -> Result<Vec<Event>> {
 
     Ok(events)
 }
+
+/// Collects Solidity custom errors from the `throws` types of `ethabi` functions and constructors.
+///
+/// Each variant of a `throws` enum is an error, while `String` and `()` are reverted as the built-in
+/// `Error(string)` and without data, so they are not declared. Errors shared by several functions
+/// are declared once.
+fn errors_from_idl(idl_doc: &IdlDoc) -> Result<Vec<CustomError>> {
+    let mut errors: Vec<CustomError> = vec![];
+
+    let ctor_throws = idl_doc.program.iter().flat_map(|program| {
+        program
+            .ctors
+            .iter()
+            .filter_map(|ctor_func| ctor_func.throws.as_ref())
+            .map(|throws| (throws, program.types.as_slice()))
+    });
+    let service_throws = idl_doc.services.iter().flat_map(|service_unit| {
+        service_unit
+            .funcs
+            .iter()
+            .filter(|service_func| has_ethabi_codec(&service_func.annotations))
+            .filter_map(|service_func| service_func.throws.as_ref())
+            .map(|throws| (throws, service_unit.types.as_slice()))
+    });
+
+    for (throws, types) in ctor_throws.chain(service_throws) {
+        let variants = match throws {
+            TypeDecl::Primitive(PrimitiveType::String | PrimitiveType::Void) => continue,
+            TypeDecl::Named { name, .. } => types
+                .iter()
+                .find(|ty| ty.name == *name)
+                .and_then(|ty| match &ty.def {
+                    TypeDef::Enum(enum_def) => Some(&enum_def.variants),
+                    _ => None,
+                })
+                .ok_or(ConversionError::UnsupportedType)?,
+            _ => return Err(ConversionError::UnsupportedType.into()),
+        };
+
+        for enum_variant in variants {
+            let mut args = vec![];
+
+            for struct_field in &enum_variant.def.fields {
+                args.push(ErrorArg {
+                    ty: resolve_type_decl(&struct_field.type_decl, types)?,
+                    name: struct_field
+                        .name
+                        .as_ref()
+                        .map(|name| name.to_case(Case::Camel)),
+                });
+            }
+
+            let error = CustomError {
+                name: enum_variant.name.clone(),
+                args,
+            };
+            if !errors
+                .iter()
+                .any(|other| other.signature() == error.signature())
+            {
+                errors.push(error);
+            }
+        }
+    }
+
+    Ok(errors)
+}
@@ -0,0 +1,473 @@
+//! Import of Solidity ABI JSON and `interface` declarations into Sails IDL services.
+//!
+//! Functions become service functions with the `@codec: ethabi` annotation, keeping their
+//! parameter and return types, and events become service events keeping their name, field
+//! types and `@indexed` flags, so the topic hashes of the events stay the same. The Solidity
+//! functions of the imported service, generated by [`generate_solidity_contract`](crate::generate_solidity_contract),
+//! are named `<service><Function>` and take the `_callReply` flag first, as for any Sails service.
+
+use crate::error::Result;
+use alloc::{
+    borrow::ToOwned,
+    string::{String, ToString},
+    vec::Vec,
+};
+use askama::Template;
+use convert_case::{Case, Casing};
+use sails_idl_parser_v2::ast::{
+    FuncParam, FunctionKind, IdlDoc, PrimitiveType, ServiceEvent, ServiceFunc, ServiceIdent,
+    ServiceUnit, StructDef, StructField, TypeDecl,
+};
+use serde_json::Value;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum ImportError {
+    #[error("Invalid ABI JSON: {0}")]
+    Json(String),
+    #[error("Invalid Solidity interface: {0}")]
+    Interface(String),
+    #[error("Solidity type `{0}` has no Sails equivalent")]
+    UnsupportedType(String),
+    #[error("Not supported: {0}")]
+    Unsupported(String),
+}
+
+struct Param {
+    name: String,
+    ty: String,
+    indexed: bool,
+}
+
+enum Entry {
+    Function {
+        name: String,
+        inputs: Vec<Param>,
+        outputs: Vec<Param>,
+        mutability: String,
+    },
+    Event {
+        name: String,
+        inputs: Vec<Param>,
+        anonymous: bool,
+    },
+}
+
+/// Builds the service `service_name` from a Solidity ABI JSON, either the ABI array
+/// or a compiler artifact holding it under the `abi` key.
+///
+/// Constructors, errors and the `fallback` and `receive` functions are skipped.
+pub fn service_from_abi_json(service_name: &str, abi_json: &str) -> Result<ServiceUnit> {
+    let json: Value =
+        serde_json::from_str(abi_json).map_err(|err| ImportError::Json(err.to_string()))?;
+    let abi = match &json {
+        Value::Object(artifact) => artifact.get("abi"),
+        abi => Some(abi),
+    }
+    .and_then(Value::as_array)
+    .ok_or_else(|| ImportError::Json("expected an array of ABI entries".into()))?;
+
+    let mut entries = vec![];
+    for item in abi {
+        let str_field = |key: &str| item.get(key).and_then(Value::as_str).unwrap_or_default();
+        let name = str_field("name").to_owned();
+        match str_field("type") {
+            "function" => entries.push(Entry::Function {
+                inputs: json_params(item.get("inputs"))?,
+                outputs: json_params(item.get("outputs"))?,
+                mutability: str_field("stateMutability").to_owned(),
+                name,
+            }),
+            "event" => entries.push(Entry::Event {
+                inputs: json_params(item.get("inputs"))?,
+                anonymous: item
+                    .get("anonymous")
+                    .and_then(Value::as_bool)
+                    .unwrap_or_default(),
+                name,
+            }),
+            "constructor" | "error" | "fallback" | "receive" => {}
+            ty => return Err(ImportError::Json(format!("unknown ABI entry type `{ty}`")).into()),
+        }
+    }
+
+    Ok(service_from_entries(service_name, entries)?)
+}
+
+fn json_params(params: Option<&Value>) -> Result<Vec<Param>, ImportError> {
+    let Some(params) = params else {
+        return Ok(vec![]);
+    };
+    let params = params
+        .as_array()
+        .ok_or_else(|| ImportError::Json("expected an array of parameters".into()))?;
+    params
+        .iter()
+        .map(|param| {
+            let ty = param
+                .get("type")
+                .and_then(Value::as_str)
+                .ok_or_else(|| ImportError::Json("parameter without a type".into()))?;
+            Ok(Param {
+                name: param
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_owned(),
+                ty: ty.to_owned(),
+                indexed: param
+                    .get("indexed")
+                    .and_then(Value::as_bool)
+                    .unwrap_or_default(),
+            })
+        })
+        .collect()
+}
+
+/// Builds a service from the first `interface` declared in the Solidity `source`.
+///
+/// The service is named `service_name` or after the interface, without the `I` prefix.
+/// Only `function` and `event` declarations of value types, `string`, `address`
+/// and their arrays are supported, `error` declarations are skipped.
+pub fn service_from_interface(source: &str, service_name: Option<&str>) -> Result<ServiceUnit> {
+    let invalid = |msg: &str| ImportError::Interface(msg.into());
+    let source = strip_comments(source);
+
+    let interface_pos = source
+        .match_indices("interface")
+        .map(|(pos, _)| pos)
+        .find(|pos| {
+            let is_boundary = |ch: char| !ch.is_alphanumeric() && ch != '_';
+            source[..*pos].chars().next_back().is_none_or(is_boundary)
+                && source[pos + "interface".len()..].starts_with(char::is_whitespace)
+        })
+        .ok_or_else(|| invalid("no `interface` declaration"))?;
+    let open = source[interface_pos..]
+        .find('{')
+        .map(|pos| interface_pos + pos + 1)
+        .ok_or_else(|| invalid("missing interface body"))?;
+    let interface_name = source[interface_pos + "interface".len()..open - 1]
+        .split_whitespace()
+        .next()
+        .ok_or_else(|| invalid("missing interface name"))?;
+    let close = matching_close(&source, open, '{', '}')
+        .ok_or_else(|| invalid("unterminated interface body"))?;
+
+    let mut entries = vec![];
+    for statement in source[open..close].split(';') {
+        let statement = statement.trim();
+        let (keyword, rest) = statement
+            .split_once(char::is_whitespace)
+            .unwrap_or((statement, ""));
+        match keyword {
+            "" => {}
+            "function" => entries.push(parse_function(rest)?),
+            "event" => entries.push(parse_event(rest)?),
+            "error" => {}
+            "struct" | "enum" | "type" => {
+                return Err(ImportError::Unsupported(format!(
+                    "`{keyword}` declarations in `{statement}`"
+                ))
+                .into());
+            }
+            _ => return Err(invalid(&format!("unexpected `{statement}`")).into()),
+        }
+    }
+
+    let default_name = match interface_name.strip_prefix('I') {
+        Some(name) if name.starts_with(char::is_uppercase) => name,
+        _ => interface_name,
+    };
+    Ok(service_from_entries(
+        service_name.unwrap_or(default_name),
+        entries,
+    )?)
+}
+
+fn strip_comments(source: &str) -> String {
+    let mut result = String::with_capacity(source.len());
+    let mut rest = source;
+    while !rest.is_empty() {
+        if let Some(tail) = rest.strip_prefix("//") {
+            rest = tail.find('\n').map_or("", |pos| &tail[pos..]);
+        } else if let Some(tail) = rest.strip_prefix("/*") {
+            rest = tail.find("*/").map_or("", |pos| &tail[pos + 2..]);
+            result.push(' ');
+        } else {
+            let ch = rest.chars().next().unwrap_or_default();
+            result.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+    result
+}
+
+/// Returns the position of the delimiter closing the one opened right before `start`.
+fn matching_close(source: &str, start: usize, open: char, close: char) -> Option<usize> {
+    let mut depth = 1;
+    for (pos, ch) in source[start..].char_indices() {
+        if ch == open {
+            depth += 1;
+        } else if ch == close {
+            depth -= 1;
+            if depth == 0 {
+                return Some(start + pos);
+            }
+        }
+    }
+    None
+}
+
+/// Splits `name(params) rest` into its parts.
+fn split_signature(declaration: &str) -> Result<(&str, &str, &str), ImportError> {
+    let invalid = || ImportError::Interface(format!("invalid declaration `{declaration}`"));
+    let open = declaration.find('(').ok_or_else(invalid)?;
+    let close = matching_close(declaration, open + 1, '(', ')').ok_or_else(invalid)?;
+    Ok((
+        declaration[..open].trim(),
+        &declaration[open + 1..close],
+        &declaration[close + 1..],
+    ))
+}
+
+fn parse_function(declaration: &str) -> Result<Entry, ImportError> {
+    let (name, inputs, rest) = split_signature(declaration)?;
+    let (modifiers, outputs) = match rest.split_once("returns") {
+        Some((modifiers, returns)) => {
+            let (_, outputs, _) = split_signature(returns)?;
+            (modifiers, parse_params(outputs)?)
+        }
+        None => (rest, vec![]),
+    };
+    let mutability = modifiers
+        .split_whitespace()
+        .find(|modifier| matches!(*modifier, "view" | "pure" | "payable"))
+        .unwrap_or("nonpayable");
+    Ok(Entry::Function {
+        name: name.to_owned(),
+        inputs: parse_params(inputs)?,
+        outputs,
+        mutability: mutability.to_owned(),
+    })
+}
+
+fn parse_event(declaration: &str) -> Result<Entry, ImportError> {
+    let (name, inputs, rest) = split_signature(declaration)?;
+    Ok(Entry::Event {
+        name: name.to_owned(),
+        inputs: parse_params(inputs)?,
+        anonymous: rest.split_whitespace().any(|token| token == "anonymous"),
+    })
+}
+
+/// Parses `type [indexed] [location] [name]` parameters separated by commas.
+fn parse_params(params: &str) -> Result<Vec<Param>, ImportError> {
+    if params.trim().is_empty() {
+        return Ok(vec![]);
+    }
+    params
+        .split(',')
+        .map(|param| {
+            let mut tokens = param.split_whitespace();
+            let ty = tokens
+                .next()
+                .ok_or_else(|| ImportError::Interface(format!("empty parameter in `{params}`")))?;
+            let mut param = Param {
+                name: String::new(),
+                ty: ty.to_owned(),
+                indexed: false,
+            };
+            for token in tokens {
+                match token {
+                    "indexed" => param.indexed = true,
+                    "memory" | "calldata" | "storage" | "payable" => {}
+                    name => param.name = name.to_owned(),
+                }
+            }
+            Ok(param)
+        })
+        .collect()
+}
+
+fn service_from_entries(name: &str, entries: Vec<Entry>) -> Result<ServiceUnit, ImportError> {
+    let mut funcs: Vec<ServiceFunc> = vec![];
+    let mut events: Vec<ServiceEvent> = vec![];
+
+    for entry in entries {
+        match entry {
+            Entry::Function {
+                name,
+                inputs,
+                outputs,
+                mutability,
+            } => {
+                let func_name = name.to_case(Case::Pascal);
+                if funcs.iter().any(|func| func.name == func_name) {
+                    return Err(ImportError::Unsupported(format!(
+                        "overloaded function `{name}`"
+                    )));
+                }
+                let params = inputs
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, param)| {
+                        Ok(FuncParam {
+                            name: param_name(&param.name, idx),
+                            type_decl: type_decl(&param.ty)?,
+                        })
+                    })
+                    .collect::<Result<_, ImportError>>()?;
+                let output = match outputs.as_slice() {
+                    [] => TypeDecl::Primitive(PrimitiveType::Void),
+                    [output] => type_decl(&output.ty)?,
+                    _ => {
+                        return Err(ImportError::Unsupported(format!(
+                            "multiple return values of `{name}`"
+                        )));
+                    }
+                };
+                let mut annotations = vec![("codec".to_string(), Some("ethabi".to_string()))];
+                let kind = match mutability.as_str() {
+                    "view" | "pure" => {
+                        annotations.push(("query".to_string(), None));
+                        FunctionKind::Query
+                    }
+                    "payable" => {
+                        annotations.push(("payable".to_string(), None));
+                        FunctionKind::Command
+                    }
+                    _ => FunctionKind::Command,
+                };
+                funcs.push(ServiceFunc {
+                    name: func_name,
+                    params,
+                    output,
+                    throws: None,
+                    kind,
+                    entry_id: 0,
+                    docs: vec![format!("Imported from Solidity `{name}`.")],
+                    annotations,
+                });
+            }
+            Entry::Event {
+                name,
+                inputs,
+                anonymous,
+            } => {
+                if anonymous {
+                    return Err(ImportError::Unsupported(format!(
+                        "anonymous event `{name}`"
+                    )));
+                }
+                if events.iter().any(|event| event.name == name) {
+                    return Err(ImportError::Unsupported(format!(
+                        "overloaded event `{name}`"
+                    )));
+                }
+                // Tuple variants cannot have indexed fields, so named fields are used
+                // unless every field is unnamed and not indexed.
+                let positional = inputs
+                    .iter()
+                    .all(|param| param.name.is_empty() && !param.indexed);
+                let fields = inputs
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, param)| {
+                        Ok(StructField {
+                            name: (!positional).then(|| param_name(&param.name, idx)),
+                            type_decl: type_decl(&param.ty)?,
+                            docs: vec![],
+                            annotations: if param.indexed {
+                                vec![("indexed".to_string(), None)]
+                            } else {
+                                vec![]
+                            },
+                        })
+                    })
+                    .collect::<Result<_, ImportError>>()?;
+                events.push(ServiceEvent {
+                    name,
+                    def: StructDef { fields },
+                    entry_id: 0,
+                    docs: vec![],
+                    annotations: vec![("codec".to_string(), Some("ethabi".to_string()))],
+                });
+            }
+        }
+    }
+
+    let mut service = ServiceUnit {
+        name: ServiceIdent {
+            name: name.to_owned(),
+            interface_id: None,
+        },
+        extends: vec![],
+        funcs,
+        events,
+        types: vec![],
+        docs: vec![],
+        annotations: vec![],
+    };
+    service.normalize();
+    Ok(service)
+}
+
+/// Snake case name of a parameter, `arg<idx>` for unnamed ones.
+fn param_name(name: &str, idx: usize) -> String {
+    let name = name.to_case(Case::Snake);
+    if name.is_empty() {
+        format!("arg{idx}")
+    } else {
+        name
+    }
+}
+
+/// Maps a Solidity type to the Sails type `sol-gen` maps back to it.
+fn type_decl(ty: &str) -> Result<TypeDecl, ImportError> {
+    let unsupported = || ImportError::UnsupportedType(ty.to_owned());
+    if let Some(item) = ty.strip_suffix("[]") {
+        return Ok(TypeDecl::Slice {
+            item: type_decl(item)?.into(),
+        });
+    }
+    if let Some((item, len)) = ty.strip_suffix(']').and_then(|ty| ty.rsplit_once('[')) {
+        return Ok(TypeDecl::Array {
+            item: type_decl(item)?.into(),
+            len: len.parse().map_err(|_| unsupported())?,
+        });
+    }
+    let primitive = match ty {
+        "bool" => PrimitiveType::Bool,
+        "uint8" => PrimitiveType::U8,
+        "uint16" => PrimitiveType::U16,
+        "uint32" => PrimitiveType::U32,
+        "uint64" => PrimitiveType::U64,
+        "uint128" => PrimitiveType::U128,
+        "uint256" | "uint" => PrimitiveType::U256,
+        "int8" => PrimitiveType::I8,
+        "int16" => PrimitiveType::I16,
+        "int32" => PrimitiveType::I32,
+        "int64" => PrimitiveType::I64,
+        "int128" => PrimitiveType::I128,
+        "string" => PrimitiveType::String,
+        "address" => PrimitiveType::ActorId,
+        "bytes32" => PrimitiveType::H256,
+        "bytes20" => PrimitiveType::H160,
+        _ => return Err(unsupported()),
+    };
+    Ok(TypeDecl::Primitive(primitive))
+}
+
+/// Renders the IDL of `service`.
+pub fn generate_service_idl(service: ServiceUnit) -> Result<String> {
+    IdlDoc {
+        globals: vec![(
+            "sails".to_string(),
+            Some(env!("CARGO_PKG_VERSION").to_string()),
+        )],
+        program: None,
+        services: vec![service],
+    }
+    .render()
+    .map_err(Into::into)
+}
@@ -7,11 +7,16 @@
 #[cfg(feature = "ffi")]
 pub mod ffi;
 mod generator;
+mod import;
 mod sol_conversion;
 
 pub use error::*;
 pub use generator::{
-    LICENSE_IDENTIFIER, SOLIDITY_VERSION, SolidityFile, generate_solidity_contract,
+    LICENSE_IDENTIFIER, SOLIDITY_VERSION, SolidityFile, generate_abi_json,
+    generate_solidity_contract,
+};
+pub use import::{
+    ImportError, generate_service_idl, service_from_abi_json, service_from_interface,
 };
 pub use sol_conversion::ConversionError;
 
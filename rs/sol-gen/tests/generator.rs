@@ -1,5 +1,5 @@
 use insta::assert_snapshot;
-use sails_sol_gen::{SolidityFile, generate_solidity_contract};
+use sails_sol_gen::{SolidityFile, generate_abi_json, generate_solidity_contract};
 
 const SIMPLE_IDL: &str = r#"
 program TestProgram {
@@ -101,6 +101,26 @@ fn test_generate_contract_w_mixed_indexed_events() {
     assert_snapshot!(String::from_utf8(contract.unwrap()).unwrap());
 }
 
+#[test]
+fn test_generate_abi_json_w_mixed_indexed_events() {
+    let abi = generate_abi_json(IDL_MIXED_INDEXED).expect("generate ABI JSON");
+    let abi = String::from_utf8(abi).unwrap();
+
+    let entries: serde_json::Value = serde_json::from_str(&abi).expect("valid JSON");
+    let event = &entries[0];
+    assert_eq!(event["type"], "event");
+    assert_eq!(event["name"], "MixedEvent");
+    let indexed: Vec<bool> = event["inputs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|input| input["indexed"].as_bool().unwrap())
+        .collect();
+    assert_eq!(indexed, [true, false, true, false]);
+
+    assert_snapshot!(abi);
+}
+
 const PAYABLE_IDL: &str = r#"
 program TestProgram {
     constructors {
@@ -143,6 +163,25 @@ fn test_generate_payable_contract() {
     assert_snapshot!(String::from_utf8(contract.unwrap()).unwrap());
 }
 
+#[test]
+fn test_generate_payable_abi_json() {
+    let abi = generate_abi_json(PAYABLE_IDL).expect("generate ABI JSON");
+    let entries: serde_json::Value = serde_json::from_slice(&abi).expect("valid JSON");
+
+    let mutability = |name: &str| {
+        entries
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|entry| entry["name"] == name)
+            .map(|entry| entry["stateMutability"].clone())
+    };
+    assert_eq!(mutability("create").unwrap(), "payable");
+    assert_eq!(mutability("myServiceDeposit").unwrap(), "payable");
+    assert_eq!(mutability("myServiceWithdraw").unwrap(), "nonpayable");
+    assert_eq!(mutability("myServiceRegularCall").unwrap(), "nonpayable");
+}
+
 const IDL_W_ADDRESS: &str = r#"
 program TokenProgram {
     constructors {
@@ -260,3 +299,90 @@ fn codec_selection() {
 
     assert_snapshot!(generated);
 }
+
+const IDL_W_ERRORS: &str = r#"
+program TokenProgram {
+    constructors {
+        New(limit: u128) throws String;
+    }
+    services {
+        Token
+    }
+}
+
+service Token {
+    functions {
+        Transfer(to: ActorId, amount: u128) -> bool throws TransferError;
+        TransferFrom(from: ActorId, to: ActorId, amount: u128) -> bool throws TransferError;
+        Pause() throws ();
+        @codec: scale
+        Mint(amount: u128) throws MintError;
+    }
+
+    types {
+        enum TransferError {
+            InsufficientBalance { needed: u128 },
+            UnknownAccount(ActorId),
+            Paused,
+        }
+        enum MintError {
+            Overflow,
+        }
+    }
+}
+"#;
+
+#[test]
+fn test_generate_contract_w_errors() {
+    let contract =
+        generate_solidity_contract("TokenContract", IDL_W_ERRORS, SolidityFile::InterfaceFile);
+
+    assert!(
+        contract.is_ok(),
+        "Failed to generate contract: {:?}",
+        contract.err()
+    );
+    let contract = String::from_utf8(contract.unwrap()).unwrap();
+    assert!(
+        !contract.contains("error Overflow"),
+        "expected errors of scale-only functions to be filtered out, got:\n{contract}"
+    );
+    assert_snapshot!(contract);
+}
+
+#[test]
+fn test_generate_abi_json_w_errors() {
+    let abi = generate_abi_json(IDL_W_ERRORS).expect("generate ABI JSON");
+    let abi = String::from_utf8(abi).unwrap();
+
+    let entries: serde_json::Value = serde_json::from_str(&abi).expect("valid JSON");
+    let errors: Vec<&str> = entries
+        .as_array()
+        .unwrap()
+        .iter()
+        .filter(|entry| entry["type"] == "error")
+        .map(|entry| entry["name"].as_str().unwrap())
+        .collect();
+    assert_eq!(errors, ["InsufficientBalance", "UnknownAccount", "Paused"]);
+
+    assert_snapshot!(abi);
+}
+
+#[test]
+fn test_generate_contract_w_unsupported_error() {
+    let idl = r#"
+service Token {
+    functions {
+        Transfer(amount: u128) -> bool throws u32;
+    }
+}
+"#;
+
+    let contract = generate_solidity_contract("TokenContract", idl, SolidityFile::InterfaceFile);
+    assert!(matches!(
+        contract,
+        Err(sails_sol_gen::Error::Conversion(
+            sails_sol_gen::ConversionError::UnsupportedType
+        ))
+    ));
+}
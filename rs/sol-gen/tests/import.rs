@@ -0,0 +1,194 @@
+use insta::assert_snapshot;
+use sails_sol_gen::{
+    Error, ImportError, generate_abi_json, generate_service_idl, service_from_abi_json,
+    service_from_interface,
+};
+use serde_json::Value;
+
+const ERC20_ABI_JSON: &str = r#"
+[
+    { "type": "constructor", "inputs": [{ "name": "name_", "type": "string" }], "stateMutability": "nonpayable" },
+    {
+        "type": "function",
+        "name": "balanceOf",
+        "inputs": [{ "name": "account", "type": "address" }],
+        "outputs": [{ "name": "", "type": "uint256" }],
+        "stateMutability": "view"
+    },
+    {
+        "type": "function",
+        "name": "transfer",
+        "inputs": [{ "name": "to", "type": "address" }, { "name": "value", "type": "uint256" }],
+        "outputs": [{ "name": "", "type": "bool" }],
+        "stateMutability": "nonpayable"
+    },
+    {
+        "type": "function",
+        "name": "deposit",
+        "inputs": [],
+        "outputs": [],
+        "stateMutability": "payable"
+    },
+    {
+        "type": "function",
+        "name": "batchTransfer",
+        "inputs": [{ "name": "recipients", "type": "address[]" }, { "name": "amounts", "type": "uint128[3]" }],
+        "outputs": [],
+        "stateMutability": "nonpayable"
+    },
+    {
+        "type": "event",
+        "name": "Transfer",
+        "inputs": [
+            { "name": "from", "type": "address", "indexed": true },
+            { "name": "to", "type": "address", "indexed": true },
+            { "name": "value", "type": "uint256", "indexed": false }
+        ],
+        "anonymous": false
+    },
+    {
+        "type": "event",
+        "name": "Paused",
+        "inputs": [{ "name": "", "type": "bytes32", "indexed": false }],
+        "anonymous": false
+    },
+    { "type": "error", "name": "InsufficientBalance", "inputs": [{ "name": "needed", "type": "uint256" }] }
+]
+"#;
+
+const ERC20_INTERFACE: &str = r#"
+// SPDX-License-Identifier: MIT
+pragma solidity ^0.8.0;
+
+/// ERC-20 subset.
+interface IERC20 {
+    /* Emitted on transfers. */
+    event Transfer(address indexed from, address indexed to, uint256 value);
+    event Paused(bytes32);
+
+    error InsufficientBalance(uint256 needed);
+
+    function balanceOf(address account) external view returns (uint256);
+    function transfer(address to, uint256 value) external returns (bool);
+    function deposit() external payable;
+    function batchTransfer(address[] calldata recipients, uint128[3] memory amounts) external;
+}
+"#;
+
+fn types(entry: &Value, skip: usize) -> Vec<(String, bool)> {
+    entry["inputs"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .skip(skip)
+        .map(|input| {
+            (
+                input["type"].as_str().unwrap().to_string(),
+                input["indexed"].as_bool().unwrap_or_default(),
+            )
+        })
+        .collect()
+}
+
+fn find<'a>(entries: &'a [Value], name: &str) -> &'a Value {
+    entries
+        .iter()
+        .find(|entry| entry["name"] == name)
+        .unwrap_or_else(|| panic!("`{name}` is missing"))
+}
+
+#[test]
+fn import_abi_json() {
+    let service = service_from_abi_json("Erc20", ERC20_ABI_JSON).unwrap();
+    let idl = generate_service_idl(service).unwrap();
+
+    assert_snapshot!(idl);
+}
+
+#[test]
+fn import_interface_matches_abi_json() {
+    let from_json = service_from_abi_json("ERC20", ERC20_ABI_JSON).unwrap();
+    let from_interface = service_from_interface(ERC20_INTERFACE, None).unwrap();
+
+    assert_eq!(from_interface, from_json);
+}
+
+#[test]
+fn imported_service_keeps_solidity_types() {
+    let service = service_from_abi_json("Erc20", ERC20_ABI_JSON).unwrap();
+    let idl = generate_service_idl(service).unwrap();
+
+    let original: Vec<Value> = serde_json::from_str(ERC20_ABI_JSON).unwrap();
+    let generated: Value = serde_json::from_slice(&generate_abi_json(&idl).unwrap()).unwrap();
+    let generated = generated.as_array().unwrap();
+
+    for (name, sails_name) in [
+        ("balanceOf", "erc20BalanceOf"),
+        ("transfer", "erc20Transfer"),
+        ("deposit", "erc20Deposit"),
+        ("batchTransfer", "erc20BatchTransfer"),
+    ] {
+        let function = find(generated, sails_name);
+        // The first input is the `_callReply` flag.
+        assert_eq!(
+            types(function, 1),
+            types(find(&original, name), 0),
+            "{name}"
+        );
+        let mutability = find(&original, name)["stateMutability"].clone();
+        let payable = if mutability == "payable" {
+            "payable"
+        } else {
+            "nonpayable"
+        };
+        assert_eq!(function["stateMutability"], payable, "{name}");
+    }
+    for name in ["Transfer", "Paused"] {
+        assert_eq!(
+            types(find(generated, name), 0),
+            types(find(&original, name), 0),
+            "{name}"
+        );
+    }
+}
+
+#[test]
+fn import_unsupported_entries() {
+    let import_error = |result: Result<_, Error>| match result {
+        Err(Error::Import(error)) => error,
+        result => panic!("unexpected result {result:?}"),
+    };
+
+    let error = import_error(service_from_interface(
+        "interface IData { function set(bytes calldata data) external; }",
+        None,
+    ));
+    assert!(
+        matches!(&error, ImportError::UnsupportedType(ty) if ty == "bytes"),
+        "{error}"
+    );
+
+    let error = import_error(service_from_interface(
+        "interface IToken { function mint(uint256 value) external; function mint(address to, uint256 value) external; }",
+        None,
+    ));
+    assert!(matches!(error, ImportError::Unsupported(_)), "{error}");
+
+    let error = import_error(service_from_interface(
+        "interface IPair { function reserve() external view returns (uint112); }",
+        None,
+    ));
+    assert!(matches!(error, ImportError::UnsupportedType(_)), "{error}");
+
+    let error = import_error(service_from_interface(
+        "interface IPair { function reserves() external view returns (uint128 a, uint128 b); }",
+        None,
+    ));
+    assert!(matches!(error, ImportError::Unsupported(_)), "{error}");
+
+    let error = import_error(service_from_abi_json("Svc", r#"{ "bytecode": "0x" }"#));
+    assert!(matches!(error, ImportError::Json(_)), "{error}");
+
+    let error = import_error(service_from_interface("contract Token {}", None));
+    assert!(matches!(error, ImportError::Interface(_)), "{error}");
+}
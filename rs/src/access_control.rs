@@ -0,0 +1,334 @@
+//! Role-based access control.
+//!
+//! [`RolesData`] stores role members and the admin role of every role.
+//! Accounts holding the admin role of a role can grant and revoke it;
+//! the admin role of all roles defaults to [`DEFAULT_ADMIN_ROLE`].
+//!
+//! [`AccessControlService`] exposes the roles through the IDL and is meant to be
+//! extended by other services:
+//!
+//! ```rust,ignore
+//! use sails::{access_control::*, cell::RefCell, prelude::*};
+//!
+//! pub struct TokenService<'a> {
+//!     roles: &'a RefCell<RolesData>,
+//! }
+//!
+//! impl<'a> From<TokenService<'a>> for AccessControlService<&'a RefCell<RolesData>> {
+//!     fn from(value: TokenService<'a>) -> Self {
+//!         AccessControlService::new(value.roles)
+//!     }
+//! }
+//!
+//! impl RoleGuard for TokenService<'_> {
+//!     fn has_role(&self, role: &str, account: ActorId) -> bool {
+//!         self.roles.borrow().has_role(role, account)
+//!     }
+//! }
+//!
+//! #[service(extends = AccessControlService<&'a RefCell<RolesData>>)]
+//! impl<'a> TokenService<'a> {
+//!     #[export(requires_role = "MINTER")]
+//!     pub fn mint(&mut self, to: ActorId, amount: u128) {
+//!         // only accounts with the `MINTER` role get here
+//!     }
+//! }
+//! ```
+
+use crate::prelude::{
+    collections::{BTreeMap, BTreeSet},
+    *,
+};
+use core::{cell::RefCell, fmt};
+
+/// The role administering all roles unless another admin role is set.
+pub const DEFAULT_ADMIN_ROLE: &str = "DEFAULT_ADMIN";
+
+/// Errors returned by access control checks.
+#[sails_type(crate = crate)]
+#[cfg_attr(feature = "ethexe", derive(crate::EthError), sails_path(crate = crate))]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccessControlError {
+    /// `account` does not have `role`.
+    MissingRole { role: String, account: ActorId },
+}
+
+impl fmt::Display for AccessControlError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingRole { role, account } => {
+                write!(f, "account {account} is missing role {role}")
+            }
+        }
+    }
+}
+
+impl core::error::Error for AccessControlError {}
+
+/// Events emitted by [`AccessControlService`].
+#[event(crate = crate)]
+#[sails_type(crate = crate)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AccessControlEvents {
+    /// `account` was granted `role` by `sender`.
+    RoleGranted {
+        role: String,
+        #[indexed]
+        account: ActorId,
+        #[indexed]
+        sender: ActorId,
+    },
+    /// `role` was revoked from `account` by `sender`.
+    RoleRevoked {
+        role: String,
+        #[indexed]
+        account: ActorId,
+        #[indexed]
+        sender: ActorId,
+    },
+    /// The admin role of `role` was changed from `previous_admin_role` to `new_admin_role`.
+    RoleAdminChanged {
+        role: String,
+        previous_admin_role: String,
+        new_admin_role: String,
+    },
+}
+
+/// Role members and admin roles.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RolesData {
+    members: BTreeMap<String, BTreeSet<ActorId>>,
+    admin_roles: BTreeMap<String, String>,
+}
+
+impl RolesData {
+    /// Creates roles with `admin` holding [`DEFAULT_ADMIN_ROLE`].
+    pub fn new(admin: ActorId) -> Self {
+        let mut data = Self::default();
+        data.grant_role(DEFAULT_ADMIN_ROLE, admin);
+        data
+    }
+
+    /// Returns whether `account` has `role`.
+    pub fn has_role(&self, role: &str, account: ActorId) -> bool {
+        self.members
+            .get(role)
+            .is_some_and(|members| members.contains(&account))
+    }
+
+    /// Returns `Ok` if `account` has `role`.
+    pub fn check_role(&self, role: &str, account: ActorId) -> Result<(), AccessControlError> {
+        if self.has_role(role, account) {
+            Ok(())
+        } else {
+            Err(AccessControlError::MissingRole {
+                role: role.into(),
+                account,
+            })
+        }
+    }
+
+    /// Returns the role administering `role`.
+    pub fn admin_role(&self, role: &str) -> &str {
+        self.admin_roles
+            .get(role)
+            .map_or(DEFAULT_ADMIN_ROLE, String::as_str)
+    }
+
+    /// Sets the role administering `role`, returning the previous one.
+    pub fn set_admin_role(&mut self, role: &str, admin_role: &str) -> String {
+        let previous = self.admin_role(role).to_owned();
+        self.admin_roles.insert(role.into(), admin_role.into());
+        previous
+    }
+
+    /// Returns the accounts having `role`.
+    pub fn members(&self, role: &str) -> impl Iterator<Item = ActorId> + '_ {
+        self.members.get(role).into_iter().flatten().copied()
+    }
+
+    /// Grants `role` to `account`, returns `false` if the account already had it.
+    pub fn grant_role(&mut self, role: &str, account: ActorId) -> bool {
+        self.members.entry(role.into()).or_default().insert(account)
+    }
+
+    /// Revokes `role` from `account`, returns `false` if the account did not have it.
+    pub fn revoke_role(&mut self, role: &str, account: ActorId) -> bool {
+        let Some(members) = self.members.get_mut(role) else {
+            return false;
+        };
+        let revoked = members.remove(&account);
+        if members.is_empty() {
+            self.members.remove(role);
+        }
+        revoked
+    }
+}
+
+/// Services with methods exported with `#[export(requires_role = "...")]` implement this trait,
+/// the generated code calls [`ensure_role`](Self::ensure_role) before the method body.
+pub trait RoleGuard {
+    /// Returns whether `account` has `role`.
+    fn has_role(&self, role: &str, account: ActorId) -> bool;
+
+    /// Panics unless the message source has `role`.
+    fn ensure_role(&self, role: &str) {
+        let account = Syscall::message_source();
+        if !self.has_role(role, account) {
+            panic!(
+                "{}",
+                AccessControlError::MissingRole {
+                    role: role.into(),
+                    account,
+                }
+            );
+        }
+    }
+}
+
+/// Service exposing role management.
+pub struct AccessControlService<
+    S: StateMut<Item = RolesData, Error = Infallible> = RefCell<RolesData>,
+> {
+    roles: S,
+}
+
+impl<S: StateMut<Item = RolesData, Error = Infallible>> AccessControlService<S> {
+    pub fn new(roles: S) -> Self {
+        Self { roles }
+    }
+
+    fn check_admin_role(&self, role: &str) -> Result<(), AccessControlError> {
+        let roles = self.roles.get();
+        roles.check_role(roles.admin_role(role), Syscall::message_source())
+    }
+}
+
+impl<S: StateMut<Item = RolesData, Error = Infallible>> RoleGuard for AccessControlService<S> {
+    fn has_role(&self, role: &str, account: ActorId) -> bool {
+        self.roles.get().has_role(role, account)
+    }
+}
+
+#[service(crate = crate, events = AccessControlEvents)]
+impl<S: StateMut<Item = RolesData, Error = Infallible>> AccessControlService<S> {
+    /// Returns whether `account` has `role`
+    #[export]
+    pub fn has_role(&self, role: String, account: ActorId) -> bool {
+        self.roles.get().has_role(&role, account)
+    }
+
+    /// Returns the role administering `role`
+    #[export]
+    pub fn role_admin(&self, role: String) -> String {
+        self.roles.get().admin_role(&role).to_owned()
+    }
+
+    /// Returns the accounts having `role`
+    #[export]
+    pub fn role_members(&self, role: String) -> Vec<ActorId> {
+        self.roles.get().members(&role).collect()
+    }
+
+    /// Grants `role` to `account`, the caller must have the admin role of `role`
+    #[export(unwrap_result)]
+    pub fn grant_role(&mut self, role: String, account: ActorId) -> Result<(), AccessControlError> {
+        self.check_admin_role(&role)?;
+        if self.roles.get_mut().grant_role(&role, account) {
+            self.emit_event(AccessControlEvents::RoleGranted {
+                role,
+                account,
+                sender: Syscall::message_source(),
+            })
+            .expect("Failed to emit event");
+        }
+        Ok(())
+    }
+
+    /// Revokes `role` from `account`, the caller must have the admin role of `role`
+    #[export(unwrap_result)]
+    pub fn revoke_role(
+        &mut self,
+        role: String,
+        account: ActorId,
+    ) -> Result<(), AccessControlError> {
+        self.check_admin_role(&role)?;
+        self.revoke(role, account);
+        Ok(())
+    }
+
+    /// Revokes `role` from the caller
+    #[export]
+    pub fn renounce_role(&mut self, role: String) {
+        self.revoke(role, Syscall::message_source());
+    }
+
+    /// Sets the role administering `role`, the caller must have the current admin role of `role`
+    #[export(unwrap_result)]
+    pub fn set_role_admin(
+        &mut self,
+        role: String,
+        admin_role: String,
+    ) -> Result<(), AccessControlError> {
+        self.check_admin_role(&role)?;
+        let previous_admin_role = self.roles.get_mut().set_admin_role(&role, &admin_role);
+        self.emit_event(AccessControlEvents::RoleAdminChanged {
+            role,
+            previous_admin_role,
+            new_admin_role: admin_role,
+        })
+        .expect("Failed to emit event");
+        Ok(())
+    }
+
+    fn revoke(&mut self, role: String, account: ActorId) {
+        if self.roles.get_mut().revoke_role(&role, account) {
+            self.emit_event(AccessControlEvents::RoleRevoked {
+                role,
+                account,
+                sender: Syscall::message_source(),
+            })
+            .expect("Failed to emit event");
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roles_data_grants_and_revokes_roles() {
+        let admin = ActorId::from(1);
+        let minter = ActorId::from(2);
+        let mut roles = RolesData::new(admin);
+
+        assert!(roles.has_role(DEFAULT_ADMIN_ROLE, admin));
+        assert!(roles.grant_role("MINTER", minter));
+        assert!(!roles.grant_role("MINTER", minter));
+        assert!(roles.has_role("MINTER", minter));
+        assert_eq!(roles.members("MINTER").collect::<Vec<_>>(), [minter]);
+
+        assert!(roles.revoke_role("MINTER", minter));
+        assert!(!roles.revoke_role("MINTER", minter));
+        assert_eq!(
+            roles.check_role("MINTER", minter),
+            Err(AccessControlError::MissingRole {
+                role: "MINTER".into(),
+                account: minter,
+            })
+        );
+    }
+
+    #[test]
+    fn roles_data_tracks_admin_roles() {
+        let mut roles = RolesData::default();
+
+        assert_eq!(roles.admin_role("MINTER"), DEFAULT_ADMIN_ROLE);
+        assert_eq!(
+            roles.set_admin_role("MINTER", "MINTER_ADMIN"),
+            DEFAULT_ADMIN_ROLE
+        );
+        assert_eq!(roles.admin_role("MINTER"), "MINTER_ADMIN");
+    }
+}
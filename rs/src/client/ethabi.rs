@@ -0,0 +1,276 @@
+//! Ethereum ABI transport of the client for `ethexe` programs.
+//!
+//! Services exported with the `ethabi` codec are dispatched by the 4-byte Solidity selector of
+//! `<service><Method>(bool,<params>)`, see [`ConstProgramMeta::method_sigs`](crate::solidity::ConstProgramMeta::method_sigs).
+//! A [`Service`] created with [`Actor::service_ethabi`] sends the calls of the IO structs
+//! defined by [`io_struct_impl_ethabi!`](crate::io_struct_impl_ethabi) with this encoding,
+//! decodes their ABI-encoded replies and listens to the `EthEvent` logs of the program,
+//! so the client exercises the same path as Ethereum users.
+
+use super::*;
+use alloy_sol_types::{SolType, SolValue, abi::TokenSeq};
+
+/// `ethexe` Ethereum ABI route: the service name prefixed to method names in Solidity selectors.
+///
+/// The name is the camelCase route of the service in the program, e.g. `svc1` for `Svc1`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RouteEthabi(pub Route);
+impl RouteHeader for RouteEthabi {}
+
+impl<A, E: GearEnv> Actor<A, E> {
+    /// Ethereum ABI: service identified by its camelCase route name.
+    pub fn service_ethabi<S>(&self, name: Route) -> Service<S, E, RouteEthabi> {
+        Service::new(self.env.clone(), self.id, RouteEthabi(name))
+    }
+}
+
+/// Encodes a call of `method` of `service` as `selector ++ abi(params)`.
+///
+/// `params` starts with the `bool` flag asking the program to prefix the reply with
+/// the callback selector and the message id, which is only used by Solidity contracts.
+pub fn encode_ethabi_call<P>(service: Route, method: &str, params: &P) -> Vec<u8>
+where
+    P: SolValue,
+    for<'a> <P::SolType as SolType>::Token<'a>: TokenSeq<'a>,
+{
+    let signature = format!("{service}{method}{}", <P::SolType as SolType>::SOL_NAME);
+    let selector = crate::solidity::selector(signature);
+    [selector.as_slice(), params.abi_encode_params().as_slice()].concat()
+}
+
+/// Decodes the reply of a call encoded by [`encode_ethabi_call`] with the reply flag unset.
+pub fn decode_ethabi_reply<T>(payload: impl AsRef<[u8]>) -> Result<T, parity_scale_codec::Error>
+where
+    T: SolValue + From<<T::SolType as SolType>::RustType>,
+{
+    let (value,) = <(T::SolType,) as SolType>::abi_decode_sequence(payload.as_ref())
+        .map_err(|_| "Failed to decode ABI reply")?;
+    Ok(T::from(value))
+}
+
+/// Ethereum ABI service/ctor IO struct.
+///
+/// `ServiceCall<RouteEthabi>` encodes calls with [`encode_ethabi_call`] using the service name
+/// of the `RouteEthabi` instance passed at call time (set by `Actor::service_ethabi(name)`)
+/// and decodes replies with [`decode_ethabi_reply`].
+///
+/// `throws` values are reverted by the ABI dispatch as Solidity custom errors, which are not decoded
+/// here, so `Throws` is always `()`.
+#[macro_export]
+macro_rules! io_struct_impl_ethabi {
+    (
+        $name:ident ( $( $param:ident : $ty:ty ),* ) -> $reply:ty
+    ) => {
+        pub struct $name(());
+
+        impl $name {
+            /// Encodes the full call with the Solidity selector.
+            pub fn encode_call(route: $crate::client::Route, $( $param: $ty, )* ) -> Vec<u8> {
+                <$name as $crate::client::ServiceCall>::encode_call(
+                    &$crate::client::RouteEthabi(route), &( $( $param, )* )
+                )
+            }
+
+            /// Decodes the ABI-encoded reply.
+            pub fn decode_reply(payload: impl AsRef<[u8]>) -> Result<$reply, $crate::scale_codec::Error> {
+                $crate::client::decode_ethabi_reply::<$reply>(payload)
+            }
+        }
+
+        impl $crate::client::ServiceCall for $name {
+            type Route = $crate::client::RouteEthabi;
+            type Params = ( $( $ty, )* );
+            type Reply = $reply;
+            type Throws = ();
+            type Output = $reply;
+
+            fn encode_call(route: &$crate::client::RouteEthabi, value: &Self::Params) -> Vec<u8> {
+                let ( $( $param, )* ) = Clone::clone(value);
+                $crate::client::encode_ethabi_call(route.0, stringify!($name), &(false, $( $param, )*))
+            }
+
+            fn decode_reply(
+                _route: &$crate::client::RouteEthabi,
+                payload: impl AsRef<[u8]>,
+            ) -> Result<Self::Output, $crate::scale_codec::Error> {
+                $crate::client::decode_ethabi_reply::<$reply>(payload)
+            }
+
+            fn decode_error(
+                _route: &$crate::client::RouteEthabi,
+                _payload: impl AsRef<[u8]>,
+            ) -> Result<Self::Output, $crate::scale_codec::Error> {
+                Err("Throws type is `()` for the Ethereum ABI".into())
+            }
+        }
+    };
+}
+
+/// `EthEvent` log emitted by an `ethexe` program: `u8` count of topics, the topics and
+/// the ABI-encoded non-indexed fields.
+///
+/// Used by generated `impl Event<RouteEthabi>` blocks. Logs do not carry the service route,
+/// so events of the same signature emitted by different services are not told apart.
+#[cfg(not(target_arch = "wasm32"))]
+pub struct EthEventLog<'a> {
+    topics: &'a [u8],
+    data: &'a [u8],
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl<'a> EthEventLog<'a> {
+    pub fn decode(payload: &'a [u8]) -> Result<Self, parity_scale_codec::Error> {
+        let (len, payload) = payload.split_first().ok_or("Empty EthEvent log")?;
+        let topics_len = usize::from(*len) * 32;
+        if *len == 0 || payload.len() < topics_len {
+            return Err("Invalid EthEvent log topics".into());
+        }
+        let (topics, data) = payload.split_at(topics_len);
+        Ok(Self { topics, data })
+    }
+
+    /// Returns true if the first topic is the signature hash of the event `name` with
+    /// the fields `T`, indexed fields included.
+    pub fn is_event<T: SolValue>(&self, name: &str) -> bool {
+        let signature = format!("{name}{}", <T::SolType as SolType>::SOL_NAME);
+        alloy_primitives::keccak256(signature).as_slice() == &self.topics[..32]
+    }
+
+    /// Decodes the indexed field stored in the topic `idx`, the signature being the topic `0`.
+    ///
+    /// Indexed fields of dynamic types are stored as hashes and cannot be decoded.
+    pub fn topic<T>(&self, idx: usize) -> Result<T, parity_scale_codec::Error>
+    where
+        T: SolValue + From<<T::SolType as SolType>::RustType>,
+    {
+        if <T::SolType as SolType>::DYNAMIC {
+            return Err("Indexed field of a dynamic type is hashed".into());
+        }
+        let topic = self
+            .topics
+            .get(idx * 32..(idx + 1) * 32)
+            .ok_or("Missing EthEvent log topic")?;
+        T::abi_decode(topic).map_err(|_| "Failed to decode EthEvent log topic".into())
+    }
+
+    /// Decodes the non-indexed fields `T` of the event.
+    pub fn data<T: SolValue>(
+        &self,
+    ) -> Result<<T::SolType as SolType>::RustType, parity_scale_codec::Error>
+    where
+        for<'de> <T::SolType as SolType>::Token<'de>: TokenSeq<'de>,
+    {
+        <T::SolType as SolType>::abi_decode_sequence(self.data)
+            .map_err(|_| "Failed to decode EthEvent log data".into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{self as sails, EthEvent, TypeInfo, event};
+
+    io_struct_impl_ethabi!(DoThis (p1: u32, p2: String) -> u32);
+    io_struct_impl_ethabi!(Owner () -> ActorId);
+    io_struct_impl_ethabi!(Reset () -> ());
+
+    #[test]
+    fn test_io_struct_impl_ethabi() {
+        let encoded = DoThis::encode_call("svc1", 42, "hello".into());
+
+        let selector = crate::solidity::selector("svc1DoThis(bool,uint32,string)");
+        let params = (false, 42u32, "hello").abi_encode_sequence();
+        assert_eq!(encoded, [selector.as_slice(), params.as_slice()].concat());
+
+        let reply = (84u32,).abi_encode_sequence();
+        assert_eq!(DoThis::decode_reply(&reply).unwrap(), 84);
+        assert!(DoThis::decode_reply([1, 2, 3]).is_err());
+
+        let owner = ActorId::from(42u64);
+        let selector = crate::solidity::selector("svc1Owner(bool)");
+        assert_eq!(
+            Owner::encode_call("svc1"),
+            [selector.as_slice(), (false,).abi_encode_params().as_slice()].concat()
+        );
+        let reply = (owner,).abi_encode_sequence();
+        assert_eq!(Owner::decode_reply(&reply).unwrap(), owner);
+        assert_eq!(
+            <Owner as ServiceCall>::decode_reply(&RouteEthabi("svc1"), &reply).unwrap(),
+            owner
+        );
+
+        let selector = crate::solidity::selector("svc1Reset(bool)");
+        assert_eq!(Reset::encode_call("svc1")[..4], selector);
+        assert!(Reset::decode_reply(((),).abi_encode_sequence()).is_ok());
+    }
+
+    #[event]
+    #[derive(Encode, TypeInfo, PartialEq, Debug)]
+    enum Events {
+        DoThisEvent {
+            #[indexed]
+            p1: u32,
+            p2: String,
+        },
+        Done,
+    }
+
+    // Mirrors the `impl Event<RouteEthabi>` generated by `client-gen`.
+    #[allow(clippy::useless_conversion)]
+    impl Event<RouteEthabi> for Events {
+        fn decode_event(
+            _route: &RouteEthabi,
+            payload: impl AsRef<[u8]>,
+        ) -> Result<Self, parity_scale_codec::Error> {
+            let log = EthEventLog::decode(payload.as_ref())?;
+            if log.is_event::<(u32, String)>("DoThisEvent") {
+                let (p2,) = log.data::<(String,)>()?;
+                return Ok(Self::DoThisEvent {
+                    p1: log.topic::<u32>(1)?,
+                    p2: p2.into(),
+                });
+            }
+            if log.is_event::<()>("Done") {
+                return Ok(Self::Done);
+            }
+            Err("Unknown EthEvent signature".into())
+        }
+    }
+
+    fn log_payload(event: &Events) -> Vec<u8> {
+        let topics = event.topics();
+        let mut payload = vec![topics.len() as u8];
+        for topic in topics {
+            payload.extend_from_slice(topic.as_slice());
+        }
+        payload.extend_from_slice(&event.data());
+        payload
+    }
+
+    #[test]
+    fn test_decode_eth_event() {
+        let route = RouteEthabi("svc1");
+        let event = Events::DoThisEvent {
+            p1: 84,
+            p2: "greetings".into(),
+        };
+        assert_eq!(
+            Events::decode_event(&route, log_payload(&event)).unwrap(),
+            event
+        );
+        assert_eq!(
+            Events::decode_event(&route, log_payload(&Events::Done)).unwrap(),
+            Events::Done
+        );
+
+        let mut unknown = log_payload(&Events::Done);
+        unknown[1] ^= 0xff;
+        assert!(Events::decode_event(&route, unknown).is_err());
+        assert!(Events::decode_event(&route, [2; 33]).is_err());
+
+        let log_payload = log_payload(&event);
+        let log = EthEventLog::decode(&log_payload).unwrap();
+        assert!(log.topic::<String>(1).is_err());
+        assert!(log.topic::<u32>(2).is_err());
+    }
+}
@@ -0,0 +1,300 @@
+//! Gas snapshots guarding the calls recorded by a profiling [`GtestEnv`](super::GtestEnv)
+//! against gas regressions.
+//!
+//! A snapshot is a checked-in text file with the maximal gas burned per call name:
+//!
+//! ```text
+//! counter::Add = 1234567
+//! counter::Sub = 1234000
+//! ```
+//!
+//! [`GasSnapshot::assert`] fails when a call burns more gas than recorded plus the
+//! tolerance, or when a call is not recorded yet. The snapshot file is (re)written instead
+//! when it does not exist or the `SAILS_UPDATE_GAS_SNAPSHOTS` environment variable is set.
+
+use super::*;
+use std::{
+    collections::BTreeMap,
+    fmt, fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Environment variable forcing [`GasSnapshot`] to overwrite the snapshot files.
+pub const UPDATE_GAS_SNAPSHOTS_ENV: &str = "SAILS_UPDATE_GAS_SNAPSHOTS";
+
+#[derive(Debug, thiserror::Error)]
+pub enum GasSnapshotError {
+    #[error("failed to access gas snapshot: {0}")]
+    Io(#[from] io::Error),
+    #[error("invalid gas snapshot line {line}: `{content}`")]
+    InvalidLine { line: usize, content: String },
+    #[error("gas snapshot mismatch:\n{}", format_mismatches(.0))]
+    Mismatch(Vec<GasMismatch>),
+}
+
+/// A call whose gas does not match the snapshot.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum GasMismatch {
+    /// The call burned more gas than allowed by the snapshot and the tolerance.
+    Regression {
+        name: String,
+        expected: GasUnit,
+        actual: GasUnit,
+    },
+    /// The call is not recorded in the snapshot.
+    Unrecorded { name: String, actual: GasUnit },
+}
+
+impl fmt::Display for GasMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Regression {
+                name,
+                expected,
+                actual,
+            } => {
+                let increase = (*actual as f64 / *expected.max(&1) as f64 - 1.0) * 100.0;
+                write!(f, "{name}: {actual} > {expected} (+{increase:.2}%)")
+            }
+            Self::Unrecorded { name, actual } => write!(f, "{name}: {actual} is not recorded"),
+        }
+    }
+}
+
+fn format_mismatches(mismatches: &[GasMismatch]) -> String {
+    let mut lines: Vec<_> = mismatches.iter().map(|m| format!("  {m}")).collect();
+    lines.push(format!(
+        "  set `{UPDATE_GAS_SNAPSHOTS_ENV}=1` to update the snapshot"
+    ));
+    lines.join("\n")
+}
+
+/// Compares the gas burned by the calls of a [`ProfileReport`] with a snapshot file.
+///
+/// ```rust,ignore
+/// let env = GtestEnv::system_default().with_profiling();
+/// // ... deploy the program and call it using the generated client
+/// GasSnapshot::new("tests/gas/counter.gas")
+///     .with_tolerance(2.5)
+///     .assert(&env.profile_report());
+/// ```
+#[derive(Clone, Debug)]
+pub struct GasSnapshot {
+    path: PathBuf,
+    tolerance: f64,
+}
+
+impl GasSnapshot {
+    /// Creates a snapshot stored at `path`, relative paths are resolved against
+    /// the current directory, which is the package root under `cargo test`.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            tolerance: 0.0,
+        }
+    }
+
+    /// Allows the gas of a call to exceed the snapshot by `percent` percent.
+    pub fn with_tolerance(self, percent: f64) -> Self {
+        Self {
+            tolerance: percent,
+            ..self
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Checks `report` against the snapshot, writes the snapshot instead
+    /// if it does not exist or updating is requested.
+    pub fn check(&self, report: &ProfileReport) -> Result<(), GasSnapshotError> {
+        let actual = snapshot_of(report);
+        if std::env::var_os(UPDATE_GAS_SNAPSHOTS_ENV).is_some() || !self.path.exists() {
+            return self.write(&actual);
+        }
+        let expected = self.read()?;
+        let mismatches = self.compare(&expected, &actual);
+        if mismatches.is_empty() {
+            Ok(())
+        } else {
+            Err(GasSnapshotError::Mismatch(mismatches))
+        }
+    }
+
+    /// Same as [`GasSnapshot::check`], but panics on error.
+    #[track_caller]
+    pub fn assert(&self, report: &ProfileReport) {
+        if let Err(err) = self.check(report) {
+            panic!("{}: {err}", self.path.display());
+        }
+    }
+
+    fn compare(
+        &self,
+        expected: &BTreeMap<String, GasUnit>,
+        actual: &BTreeMap<String, GasUnit>,
+    ) -> Vec<GasMismatch> {
+        actual
+            .iter()
+            .filter_map(|(name, &actual)| match expected.get(name) {
+                None => Some(GasMismatch::Unrecorded {
+                    name: name.clone(),
+                    actual,
+                }),
+                Some(&expected)
+                    if actual as f64 > expected as f64 * (1.0 + self.tolerance / 100.0) =>
+                {
+                    Some(GasMismatch::Regression {
+                        name: name.clone(),
+                        expected,
+                        actual,
+                    })
+                }
+                Some(_) => None,
+            })
+            .collect()
+    }
+
+    fn read(&self) -> Result<BTreeMap<String, GasUnit>, GasSnapshotError> {
+        let content = fs::read_to_string(&self.path)?;
+        let mut snapshot = BTreeMap::new();
+        for (idx, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let entry = line
+                .split_once('=')
+                .and_then(|(name, gas)| Some((name.trim(), gas.trim().parse().ok()?)));
+            let Some((name, gas)) = entry else {
+                return Err(GasSnapshotError::InvalidLine {
+                    line: idx + 1,
+                    content: line.to_string(),
+                });
+            };
+            snapshot.insert(name.to_string(), gas);
+        }
+        Ok(snapshot)
+    }
+
+    fn write(&self, snapshot: &BTreeMap<String, GasUnit>) -> Result<(), GasSnapshotError> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let content: String = snapshot
+            .iter()
+            .map(|(name, gas)| format!("{name} = {gas}\n"))
+            .collect();
+        fs::write(&self.path, content)?;
+        Ok(())
+    }
+}
+
+impl ProfileReport {
+    /// Asserts the gas burned by the calls does not exceed the snapshot at `path`,
+    /// see [`GasSnapshot`].
+    #[track_caller]
+    pub fn assert_gas_snapshot(&self, path: impl Into<PathBuf>) {
+        GasSnapshot::new(path).assert(self);
+    }
+}
+
+/// Maximal gas burned per call name.
+fn snapshot_of(report: &ProfileReport) -> BTreeMap<String, GasUnit> {
+    report
+        .summary()
+        .into_iter()
+        .map(|summary| (summary.name, summary.gas_max))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report(calls: &[(&str, GasUnit)]) -> ProfileReport {
+        ProfileReport::new(
+            calls
+                .iter()
+                .map(|&(name, gas_burned)| CallProfile {
+                    name: name.into(),
+                    message_id: MessageId::zero(),
+                    gas_burned,
+                    blocks: 1,
+                    spawned_messages: 0,
+                    reply_code: None,
+                })
+                .collect(),
+        )
+    }
+
+    fn snapshot_path(name: &str) -> PathBuf {
+        let path = std::env::temp_dir()
+            .join(format!("sails-gas-snapshot-{}", std::process::id()))
+            .join(name);
+        _ = fs::remove_file(&path);
+        path
+    }
+
+    #[test]
+    fn snapshot_is_written_when_missing() {
+        let path = snapshot_path("written.gas");
+        let snapshot = GasSnapshot::new(&path);
+
+        snapshot
+            .check(&report(&[("svc::B", 20), ("svc::A", 10), ("svc::A", 15)]))
+            .unwrap();
+
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            "svc::A = 15\nsvc::B = 20\n"
+        );
+    }
+
+    #[test]
+    fn snapshot_detects_regressions_beyond_tolerance() {
+        let path = snapshot_path("regression.gas");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "# gas\nsvc::A = 1000\nsvc::B = 1000\n").unwrap();
+
+        let snapshot = GasSnapshot::new(&path).with_tolerance(5.0);
+        snapshot
+            .check(&report(&[("svc::A", 1050), ("svc::B", 900)]))
+            .unwrap();
+
+        let err = snapshot
+            .check(&report(&[("svc::A", 1051), ("svc::C", 1)]))
+            .unwrap_err();
+        let GasSnapshotError::Mismatch(mismatches) = &err else {
+            panic!("unexpected error: {err}");
+        };
+        assert_eq!(
+            mismatches,
+            &[
+                GasMismatch::Regression {
+                    name: "svc::A".into(),
+                    expected: 1000,
+                    actual: 1051,
+                },
+                GasMismatch::Unrecorded {
+                    name: "svc::C".into(),
+                    actual: 1,
+                },
+            ]
+        );
+        assert!(err.to_string().contains("svc::A: 1051 > 1000 (+5.10%)"));
+    }
+
+    #[test]
+    fn snapshot_rejects_invalid_lines() {
+        let path = snapshot_path("invalid.gas");
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "svc::A = 1000\nsvc::B\n").unwrap();
+
+        let err = GasSnapshot::new(&path)
+            .check(&report(&[("svc::A", 1)]))
+            .unwrap_err();
+        assert!(matches!(err, GasSnapshotError::InvalidLine { line: 2, .. }));
+    }
+}
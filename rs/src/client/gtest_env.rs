@@ -1,10 +1,16 @@
-use super::*;
+use super::{
+    gtest_events::EventLog,
+    gtest_profile::Profiler,
+    gtest_snapshot::{self, Journal, Message},
+    gtest_trace::MessageTrace,
+    *,
+};
 pub use ::gtest::constants::{
-    DEFAULT_USER_ALICE, DEFAULT_USER_BOB, DEFAULT_USER_CHARLIE, DEFAULT_USER_EVE,
-    DEFAULT_USERS_INITIAL_BALANCE, EPOCH_DURATION_IN_BLOCKS, MAX_USER_GAS_LIMIT,
+    BLOCK_DURATION_IN_MSECS, DEFAULT_USER_ALICE, DEFAULT_USER_BOB, DEFAULT_USER_CHARLIE,
+    DEFAULT_USER_EVE, DEFAULT_USERS_INITIAL_BALANCE, EPOCH_DURATION_IN_BLOCKS, MAX_USER_GAS_LIMIT,
 };
 #[cfg(feature = "ethexe")]
-use ::gtest::ethexe::{Program, System};
+use ::gtest::ethexe::System;
 use ::gtest::{BlockRunResult, TestError};
 #[cfg(not(feature = "ethexe"))]
 use ::gtest::{Program, System};
@@ -58,8 +64,14 @@ pub struct GtestEnv {
     system: Rc<System>,
     actor_id: ActorId,
     event_senders: Rc<RefCell<Vec<EventSender>>>,
-    block_run_mode: BlockRunMode,
+    pub(crate) event_log: Rc<RefCell<EventLog>>,
+    pub(crate) journal: Rc<RefCell<Journal>>,
+    #[cfg(all(feature = "mockall", not(feature = "ethexe")))]
+    mock_events: Rc<RefCell<Vec<(ActorId, super::mock_program::MockEvents)>>>,
+    pub(crate) block_run_mode: BlockRunMode,
     block_reply_senders: Rc<RefCell<HashMap<MessageId, ReplySender>>>,
+    profiler: Option<Rc<RefCell<Profiler>>>,
+    trace: Option<Rc<RefCell<MessageTrace>>>,
 }
 
 crate::params_struct_impl!(
@@ -81,8 +93,14 @@ pub fn new(system: System, actor_id: ActorId) -> Self {
             system,
             actor_id,
             event_senders: Default::default(),
+            event_log: Default::default(),
+            journal: Default::default(),
+            #[cfg(all(feature = "mockall", not(feature = "ethexe")))]
+            mock_events: Default::default(),
             block_run_mode: BlockRunMode::UpTo(EPOCH_DURATION_IN_BLOCKS),
             block_reply_senders: Default::default(),
+            profiler: None,
+            trace: None,
         }
     }
 
@@ -90,9 +108,10 @@ pub fn new(system: System, actor_id: ActorId) -> Self {
     pub fn system_default() -> Self {
         let system = System::new();
         system.init_logger_with_default_filter("gwasm=debug,gtest=info,sails=debug");
-        system.mint_to(DEFAULT_USER_ALICE, DEFAULT_USERS_INITIAL_BALANCE);
 
-        GtestEnv::new(system, DEFAULT_USER_ALICE.into())
+        let env = GtestEnv::new(system, DEFAULT_USER_ALICE.into());
+        env.mint_to(DEFAULT_USER_ALICE.into(), DEFAULT_USERS_INITIAL_BALANCE);
+        env
     }
 
     /// Avoid calling methods of `System` related to block execution.
@@ -102,6 +121,30 @@ pub fn system(&self) -> &System {
         &self.system
     }
 
+    /// Mint `value` to `actor_id`, unlike minting through `GtestEnv::system`
+    /// it is captured by `GtestEnv::snapshot`.
+    pub fn mint_to(&self, actor_id: ActorId, value: ValueUnit) {
+        self.system.mint_to(actor_id, value);
+        self.journal.borrow_mut().mint(actor_id, value);
+    }
+
+    /// Submit the code `binary`, unlike submitting through `GtestEnv::system`
+    /// it is captured by `GtestEnv::snapshot`.
+    pub fn submit_code(&self, binary: impl Into<Vec<u8>>) -> CodeId {
+        let binary = binary.into();
+        let code_id = self.system.submit_code(binary.clone());
+        self.journal.borrow_mut().submit_code(code_id, || binary);
+        code_id
+    }
+
+    /// Submit the code from the file at `path`, see `GtestEnv::submit_code`.
+    pub fn submit_code_file(&self, path: impl AsRef<std::path::Path>) -> CodeId {
+        let path = path.as_ref();
+        let binary = std::fs::read(path)
+            .unwrap_or_else(|err| panic!("failed to read file {}: {err}", path.display()));
+        self.submit_code(binary)
+    }
+
     pub fn with_block_run_mode(self, block_run_mode: BlockRunMode) -> Self {
         Self {
             block_run_mode,
@@ -117,9 +160,157 @@ pub fn actor_id(&self) -> ActorId {
         self.actor_id
     }
 
+    /// Enable recording of gas burned, blocks elapsed, messages spawned and reply code
+    /// of every `PendingCall`/`PendingCtor` sent through this env and its clones.
+    /// Use `GtestEnv::profile_report` to obtain the recorded profiles.
+    pub fn with_profiling(self) -> Self {
+        Self {
+            profiler: Some(Default::default()),
+            ..self
+        }
+    }
+
+    /// Returns the profiles of the calls replied so far,
+    /// empty if profiling is not enabled.
+    pub fn profile_report(&self) -> ProfileReport {
+        self.profiler
+            .as_ref()
+            .map(|profiler| profiler.borrow().report())
+            .unwrap_or_default()
+    }
+
+    /// Discards the profiles of the calls replied so far.
+    pub fn reset_profile(&self) {
+        if let Some(profiler) = &self.profiler {
+            profiler.borrow_mut().reset();
+        }
+    }
+
+    /// Enable tracing of the calls sent through this env and its clones, the replies
+    /// to them and the events emitted by programs.
+    /// Use `GtestEnv::message_trace` to obtain the traced messages.
+    pub fn with_message_trace(self) -> Self {
+        Self {
+            trace: Some(Default::default()),
+            ..self
+        }
+    }
+
+    /// Returns the messages traced so far, empty if tracing is not enabled.
+    pub fn message_trace(&self) -> Vec<TracedMessage> {
+        self.trace
+            .as_ref()
+            .map(|trace| trace.borrow().messages().to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Discards the messages traced so far.
+    pub fn reset_message_trace(&self) {
+        if let Some(trace) = &self.trace {
+            trace.borrow_mut().reset();
+        }
+    }
+
     pub fn run_next_block(&self) {
         _ = self.run_next_block_and_extract();
     }
+
+    /// Returns the height of the last block run.
+    pub fn block_height(&self) -> u32 {
+        self.system.block_height()
+    }
+
+    /// Returns the timestamp of the last block run, in milliseconds.
+    pub fn block_timestamp(&self) -> u64 {
+        self.system.block_timestamp()
+    }
+
+    /// Run `count` blocks, extracting the events and the replies produced in them.
+    ///
+    /// Every block is run in full, so delayed messages, wakes and `wait_up_to` timeouts
+    /// scheduled for the skipped blocks happen in their blocks. Calls awaited in the
+    /// `BlockRunMode::Manual` mode resolve once their replies are produced, the others
+    /// stay pending. In the other modes the awaited calls are resolved when sent.
+    pub fn run_blocks(&self, count: BlockCount) {
+        for _ in 0..count {
+            self.run_next_block();
+        }
+    }
+
+    /// Run blocks up to and including the block at `height`, see `GtestEnv::run_blocks`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `height` is below the current block height.
+    pub fn run_to_block(&self, height: u32) {
+        let current = self.block_height();
+        assert!(
+            height >= current,
+            "cannot run to block #{height}, the current block is #{current}"
+        );
+        self.run_blocks(height - current);
+    }
+
+    /// Run blocks until the block timestamp reaches `timestamp`, in milliseconds,
+    /// see `GtestEnv::run_blocks`. A block lasts `BLOCK_DURATION_IN_MSECS`, so the
+    /// timestamp of the last block run may exceed `timestamp` by less than a block.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `timestamp` is below the current block timestamp.
+    pub fn run_to_timestamp(&self, timestamp: u64) {
+        let current = self.block_timestamp();
+        assert!(
+            timestamp >= current,
+            "cannot run to timestamp {timestamp}, the current block timestamp is {current}"
+        );
+        let blocks = (timestamp - current).div_ceil(BLOCK_DURATION_IN_MSECS);
+        self.run_blocks(BlockCount::try_from(blocks).expect("block count exceeds u32"));
+    }
+
+    /// Run blocks until `duration` has elapsed, see `GtestEnv::run_to_timestamp`.
+    pub fn run_for(&self, duration: core::time::Duration) {
+        let millis = u64::try_from(duration.as_millis()).expect("duration exceeds u64 millis");
+        self.run_to_timestamp(self.block_timestamp().saturating_add(millis));
+    }
+
+    /// Install `program` at `destination` in place of a callee of the program under test,
+    /// so the calls it receives fail as configured. See [`FaultProgram`].
+    #[cfg(not(feature = "ethexe"))]
+    pub fn inject_faults(&self, destination: ActorId, program: FaultProgram) {
+        self.install_mock(destination, program);
+    }
+
+    /// Install a program failing its initialization at `destination`, so every call to it
+    /// is rejected with `ErrorReplyReason::UnavailableActor`.
+    #[cfg(not(feature = "ethexe"))]
+    pub fn inject_unavailable(&self, destination: ActorId) {
+        self.install_mock(destination, super::gtest_fault::UnavailableProgram);
+    }
+
+    /// Install `mock` at `program_id` in place of a program called by the program under
+    /// test, returns the client of the mocked program. See [`MockProgram`].
+    #[cfg(all(feature = "mockall", not(feature = "ethexe")))]
+    pub fn mock_program<P: 'static>(
+        &self,
+        program_id: ActorId,
+        mock: MockProgram<P>,
+    ) -> Actor<P, GtestEnv> {
+        self.mock_events
+            .borrow_mut()
+            .push((program_id, mock.events()));
+        self.install_mock(program_id, mock);
+        Actor::new(self.clone(), program_id)
+    }
+
+    #[cfg(not(feature = "ethexe"))]
+    fn install_mock(&self, destination: ActorId, program: impl ::gtest::WasmProgram + 'static) {
+        self.journal.borrow_mut().mock(destination);
+        let program = Program::mock_with_id(&self.system, destination, program);
+        // The init message is processed before any call sent to the program afterwards.
+        let message_id = program.send_bytes(self.actor_id, []);
+        log::debug!("Send mock activation id: {message_id}, to program: {destination}");
+    }
 }
 
 impl GtestEnv {
@@ -129,25 +320,50 @@ fn extract_events_and_replies(&self, run_result: &BlockRunResult) {
             run_result.block_info.height,
             self.block_run_mode
         );
+        let block = run_result.block_info.height;
         let mut event_senders = self.event_senders.borrow_mut();
-        let mut reply_senders = self.block_reply_senders.borrow_mut();
         event_senders.retain(|c| !c.is_closed());
+        let mut event_log = self.event_log.borrow_mut();
+        let trace = self.trace.as_deref();
+        let mut emit = |source: ActorId, payload: Vec<u8>| {
+            for sender in event_senders.iter() {
+                _ = sender.unbounded_send((source, payload.clone()));
+            }
+            if let Some(trace) = trace {
+                trace.borrow_mut().event(source, &payload);
+            }
+            event_log.record(source, payload, block);
+        };
+        let mut reply_senders = self.block_reply_senders.borrow_mut();
+        let mut profiler = self.profiler.as_ref().map(|p| p.borrow_mut());
+        if let Some(profiler) = profiler.as_mut() {
+            profiler.record_block(run_result);
+        }
         for entry in run_result.log().iter() {
             if entry.destination() == ActorId::zero() {
                 log::debug!("Extract event from entry {entry:?}");
-                for sender in event_senders.iter() {
-                    _ = sender.unbounded_send((entry.source(), entry.payload().to_vec()));
-                }
+                emit(entry.source(), entry.payload().to_vec());
                 continue;
             }
             #[cfg(feature = "ethexe")]
             if entry.destination() == crate::solidity::ETH_EVENT_ADDR {
                 log::debug!("Extract event from entry {:?}", entry);
-                for sender in event_senders.iter() {
-                    _ = sender.unbounded_send((entry.source(), entry.payload().to_vec()));
-                }
+                emit(entry.source(), entry.payload().to_vec());
                 continue;
             }
+            if let Some(message_id) = entry.reply_to()
+                && let Some(code) = entry.reply_code()
+                && let Some(trace) = trace
+            {
+                trace
+                    .borrow_mut()
+                    .reply(Some(message_id), entry.source(), code, entry.payload());
+            }
+            if let Some(message_id) = entry.reply_to()
+                && let Some(profiler) = profiler.as_mut()
+            {
+                profiler.complete_call(message_id, entry.reply_code());
+            }
             if let Some(message_id) = entry.reply_to()
                 && let Some(sender) = reply_senders.remove(&message_id)
             {
@@ -162,6 +378,14 @@ fn extract_events_and_replies(&self, run_result: &BlockRunResult) {
                 _ = sender.send(reply);
             }
         }
+        // Mocked programs cannot send messages, their events are delivered after the block log.
+        #[cfg(all(feature = "mockall", not(feature = "ethexe")))]
+        for (program_id, events) in self.mock_events.borrow().iter() {
+            for payload in core::mem::take(&mut *events.borrow_mut()) {
+                log::debug!("Extract event of mock program {program_id}");
+                emit(*program_id, payload);
+            }
+        }
     }
 
     pub fn create_program(
@@ -176,18 +400,28 @@ pub fn create_program(
         let gas_limit = params.gas_limit.unwrap_or(GAS_LIMIT_DEFAULT);
         #[cfg(feature = "ethexe")]
         let gas_limit = GAS_LIMIT_DEFAULT;
-        let code = self
-            .system
-            .submitted_code(code_id)
-            .ok_or(TestError::Instrumentation)?;
         let program_id = ::gtest::calculate_program_id(code_id, salt.as_ref(), None);
-        let program = Program::from_binary_with_id(&self.system, program_id, code);
-        // On `ethexe`, execution gas is drawn from the program's executable balance.
-        #[cfg(feature = "ethexe")]
-        self.system
-            .top_up_executable_balance(program_id, ETHEXE_EXECUTABLE_BALANCE);
-        let actor_id = params.actor_id.unwrap_or(self.actor_id);
-        let message_id = program.send_bytes_with_gas(actor_id, payload.as_ref(), gas_limit, value);
+        let message = Message {
+            source: params.actor_id.unwrap_or(self.actor_id),
+            payload: payload.as_ref().to_vec(),
+            gas_limit,
+            value,
+        };
+        let message_id =
+            gtest_snapshot::create_program(&self.system, code_id, program_id, &message)?;
+        let mut journal = self.journal.borrow_mut();
+        // The code submitted through `System` is captured once it is used.
+        journal.submit_code(code_id, || {
+            self.system
+                .submitted_code(code_id)
+                .expect("program is created from the submitted code")
+        });
+        if let Some(trace) = &self.trace {
+            trace
+                .borrow_mut()
+                .call(Some(message_id), program_id, &message.payload);
+        }
+        journal.create_program(code_id, program_id, message);
         log::debug!("Send activation id: {message_id}, to program: {program_id}");
         Ok((program_id, message_id))
     }
@@ -203,12 +437,19 @@ pub fn send_one_way(
         let gas_limit = params.gas_limit.unwrap_or(GAS_LIMIT_DEFAULT);
         #[cfg(feature = "ethexe")]
         let gas_limit = GAS_LIMIT_DEFAULT;
-        let program = self
-            .system
-            .get_program(destination)
-            .ok_or(TestError::ActorNotFound(destination))?;
-        let actor_id = params.actor_id.unwrap_or(self.actor_id);
-        let message_id = program.send_bytes_with_gas(actor_id, payload.as_ref(), gas_limit, value);
+        let message = Message {
+            source: params.actor_id.unwrap_or(self.actor_id),
+            payload: payload.as_ref().to_vec(),
+            gas_limit,
+            value,
+        };
+        let message_id = gtest_snapshot::send(&self.system, destination, &message)?;
+        if let Some(trace) = &self.trace {
+            trace
+                .borrow_mut()
+                .call(Some(message_id), destination, &message.payload);
+        }
+        self.journal.borrow_mut().send(destination, message);
         log::debug!(
             "Send message id: {message_id}, to: {destination}, payload: {}",
             hex::encode(payload.as_ref())
@@ -266,6 +507,11 @@ pub fn query(
             .system
             .calculate_reply_for_handle(actor_id, destination, payload.as_ref(), gas_limit, value)
             .map_err(|_s| GtestError::ReplyIsMissing)?;
+        if let Some(trace) = &self.trace {
+            let mut trace = trace.borrow_mut();
+            trace.call(None, destination, payload.as_ref());
+            trace.reply(None, destination, reply_info.code, &reply_info.payload);
+        }
 
         match reply_info.code {
             ReplyCode::Success(_) => Ok(reply_info.payload),
@@ -277,8 +523,17 @@ pub fn query(
         }
     }
 
+    fn start_profiled_call<T: ?Sized>(&self, message_id: MessageId) {
+        if let Some(profiler) = &self.profiler {
+            profiler
+                .borrow_mut()
+                .start_call(call_name::<T>(), message_id);
+        }
+    }
+
     fn run_next_block_and_extract(&self) -> BlockRunResult {
         let run_result = self.system.run_next_block();
+        self.journal.borrow_mut().run_block();
         self.extract_events_and_replies(&run_result);
         run_result
     }
@@ -301,8 +556,12 @@ fn run_until_extract_replies_up_to(&self, block_limit: BlockCount) {
 
     fn drain_reply_senders(&self) {
         let mut reply_senders = self.block_reply_senders.borrow_mut();
+        let mut profiler = self.profiler.as_ref().map(|p| p.borrow_mut());
         for (message_id, sender) in reply_senders.drain() {
             log::debug!("Reply is missing in block for message {message_id}");
+            if let Some(profiler) = profiler.as_mut() {
+                profiler.complete_call(message_id, None);
+            }
             _ = sender.send(Err(GtestError::ReplyIsMissing));
         }
     }
@@ -340,6 +599,7 @@ pub fn send_one_way(&mut self) -> Result<MessageId, GtestError> {
         let (payload, params) = self.take_encoded_args_and_params();
         let message_id = self.env.send_one_way(self.destination, payload, params)?;
         log::debug!("PendingCall: send message {message_id:?}");
+        self.env.start_profiled_call::<T>(message_id);
         Ok(message_id)
     }
 
@@ -367,6 +627,7 @@ fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
             match send_res {
                 Ok(message_id) => {
                     log::debug!("PendingCall: send message {message_id:?}");
+                    self.env.start_profiled_call::<T>(message_id);
                     self.state = Some(self.env.message_reply_from_next_blocks(message_id));
                 }
                 Err(err) => {
@@ -405,6 +666,7 @@ pub fn create_program(mut self) -> Result<Self, GtestError> {
         match send_res {
             Ok((program_id, message_id)) => {
                 log::debug!("PendingCtor: send message {message_id:?}");
+                self.env.start_profiled_call::<T>(message_id);
                 self.state = Some(self.env.message_reply_from_next_blocks(message_id));
                 self.program_id = Some(program_id);
                 Ok(self)
@@ -440,6 +702,7 @@ fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
             match send_res {
                 Ok((program_id, message_id)) => {
                     log::debug!("PendingCtor: send message {message_id:?}");
+                    self.env.start_profiled_call::<T>(message_id);
                     self.state = Some(self.env.message_reply_from_next_blocks(message_id));
                     self.program_id = Some(program_id);
                 }
@@ -489,3 +752,66 @@ pub fn balance(&self) -> ValueUnit {
         self.env.system().balance_of(self.id)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::time::Duration;
+
+    fn env() -> GtestEnv {
+        let system = System::new();
+        system.mint_to(DEFAULT_USER_ALICE, DEFAULT_USERS_INITIAL_BALANCE);
+        GtestEnv::new(system, DEFAULT_USER_ALICE.into())
+    }
+
+    #[test]
+    fn runs_to_block_and_timestamp() {
+        let env = env();
+        let height = env.block_height();
+
+        env.run_blocks(3);
+        assert_eq!(env.block_height(), height + 3);
+
+        env.run_to_block(height + 5);
+        assert_eq!(env.block_height(), height + 5);
+
+        // Partial blocks are rounded up.
+        env.run_to_timestamp(env.block_timestamp() + BLOCK_DURATION_IN_MSECS + 1);
+        assert_eq!(env.block_height(), height + 7);
+
+        env.run_for(Duration::from_millis(10 * BLOCK_DURATION_IN_MSECS));
+        assert_eq!(env.block_height(), height + 17);
+
+        env.run_to_timestamp(env.block_timestamp());
+        assert_eq!(env.block_height(), height + 17);
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot run to block")]
+    fn running_to_past_block_panics() {
+        let env = env();
+        env.run_blocks(2);
+        env.run_to_block(1);
+    }
+
+    #[cfg(not(feature = "ethexe"))]
+    #[tokio::test]
+    async fn manual_mode_replies_resolve_in_skipped_blocks() {
+        const TARGET: ActorId = ActorId::new([7; 32]);
+
+        let env = env().with_block_run_mode(BlockRunMode::Manual);
+        env.inject_faults(
+            TARGET,
+            FaultProgram::new().with_handler(|payload| Some(payload.to_vec())),
+        );
+        let message_id = env
+            .send_one_way(TARGET, [1, 2, 3], Default::default())
+            .unwrap();
+        let mut reply = env.message_reply_from_next_blocks(message_id);
+        assert_eq!(reply.try_recv(), Ok(None));
+
+        env.run_blocks(2);
+
+        assert_eq!(reply.await.unwrap(), Ok(vec![1, 2, 3]));
+    }
+}
@@ -0,0 +1,193 @@
+//! Log of the events emitted by programs under test with [`GtestEnv`](super::GtestEnv).
+//!
+//! Every event extracted from a block run is recorded with its source and the height of
+//! the block it was emitted in, so tests can check events synchronously after a call
+//! instead of listening to them. The helpers on [`Service`] decode the recorded events with
+//! [`Service::decode_event`] and skip the events of other programs and services.
+//!
+//! ```rust,ignore
+//! let checkpoint = env.checkpoint();
+//! counter.add(10).await.unwrap();
+//! counter.expect_event(CounterEvents::Added(10));
+//! counter.expect_no_events();
+//! assert_eq!(counter.events_since(checkpoint).len(), 1);
+//! ```
+
+use super::*;
+use core::fmt::Debug;
+
+/// Event recorded by [`GtestEnv`], either the raw payload or the event decoded by a [`Service`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecordedEvent<E = Vec<u8>> {
+    pub source: ActorId,
+    pub event: E,
+    /// Height of the block the event was emitted in.
+    pub block: u32,
+}
+
+/// Position in the event log of a [`GtestEnv`], see [`GtestEnv::checkpoint`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct EventCheckpoint(usize);
+
+#[derive(Clone, Default)]
+pub(crate) struct EventLog {
+    events: Vec<RecordedEvent>,
+    /// Whether the event at the same index was matched by `Service::expect_event`.
+    matched: Vec<bool>,
+}
+
+impl EventLog {
+    pub(crate) fn record(&mut self, source: ActorId, payload: Vec<u8>, block: u32) {
+        self.events.push(RecordedEvent {
+            source,
+            event: payload,
+            block,
+        });
+        self.matched.push(false);
+    }
+
+    fn checkpoint(&self) -> EventCheckpoint {
+        EventCheckpoint(self.events.len())
+    }
+
+    fn since(&self, checkpoint: EventCheckpoint) -> &[RecordedEvent] {
+        self.events.get(checkpoint.0..).unwrap_or_default()
+    }
+
+    /// Returns the indexes and the decoded events of `source` not matched by an expectation.
+    fn unmatched<E>(
+        &self,
+        source: ActorId,
+        decode: impl Fn(&[u8]) -> Option<E>,
+    ) -> impl Iterator<Item = (usize, E)> {
+        self.events
+            .iter()
+            .zip(&self.matched)
+            .enumerate()
+            .filter(move |(_, (recorded, matched))| !**matched && recorded.source == source)
+            .filter_map(move |(idx, (recorded, _))| decode(&recorded.event).map(|e| (idx, e)))
+    }
+}
+
+impl GtestEnv {
+    /// Returns the position of the next event in the log,
+    /// pass it to `events_since` to obtain the events recorded afterwards.
+    pub fn checkpoint(&self) -> EventCheckpoint {
+        self.event_log.borrow().checkpoint()
+    }
+
+    /// Returns the events of all programs recorded since `checkpoint`.
+    pub fn events_since(&self, checkpoint: EventCheckpoint) -> Vec<RecordedEvent> {
+        self.event_log.borrow().since(checkpoint).to_vec()
+    }
+}
+
+impl<S: ServiceWithEvents<R>, R: RouteHeader> Service<S, GtestEnv, R> {
+    /// Returns the events of this service recorded since `checkpoint`.
+    pub fn events_since(&self, checkpoint: EventCheckpoint) -> Vec<RecordedEvent<S::Event>> {
+        self.env
+            .event_log
+            .borrow()
+            .since(checkpoint)
+            .iter()
+            .filter(|recorded| recorded.source == self.actor_id)
+            .filter_map(|recorded| {
+                Some(RecordedEvent {
+                    source: recorded.source,
+                    event: self.decode_event(&recorded.event).ok()?,
+                    block: recorded.block,
+                })
+            })
+            .collect()
+    }
+
+    /// Asserts that the first event of this service not matched by a previous expectation
+    /// is `expected`, and marks it as matched.
+    #[track_caller]
+    pub fn expect_event(&self, expected: S::Event)
+    where
+        S::Event: PartialEq + Debug,
+    {
+        let mut log = self.env.event_log.borrow_mut();
+        let next = log
+            .unmatched(self.actor_id, |payload| {
+                self.decode_event::<S::Event>(payload).ok()
+            })
+            .next();
+        match next {
+            Some((idx, event)) if event == expected => log.matched[idx] = true,
+            Some((_, event)) => panic!(
+                "expected event {expected:?} from program {}, got {event:?}",
+                self.actor_id
+            ),
+            None => panic!(
+                "expected event {expected:?} from program {}, got no events",
+                self.actor_id
+            ),
+        }
+    }
+
+    /// Asserts that every event of this service was matched by `expect_event`.
+    #[track_caller]
+    pub fn expect_no_events(&self)
+    where
+        S::Event: Debug,
+    {
+        let log = self.env.event_log.borrow();
+        let unexpected: Vec<_> = log
+            .unmatched(self.actor_id, |payload| {
+                self.decode_event::<S::Event>(payload).ok()
+            })
+            .map(|(_, event)| event)
+            .collect();
+        assert!(
+            unexpected.is_empty(),
+            "unexpected events from program {}: {unexpected:?}",
+            self.actor_id
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: ActorId = ActorId::new([1; 32]);
+    const OTHER: ActorId = ActorId::new([2; 32]);
+
+    fn decode(payload: &[u8]) -> Option<u8> {
+        payload.first().copied()
+    }
+
+    #[test]
+    fn checkpoint_splits_log() {
+        let mut log = EventLog::default();
+        log.record(SOURCE, vec![1], 1);
+        let checkpoint = log.checkpoint();
+        log.record(OTHER, vec![2], 2);
+
+        assert_eq!(log.since(EventCheckpoint::default()).len(), 2);
+        assert_eq!(
+            log.since(checkpoint),
+            [RecordedEvent {
+                source: OTHER,
+                event: vec![2],
+                block: 2
+            }]
+        );
+        assert!(log.since(log.checkpoint()).is_empty());
+    }
+
+    #[test]
+    fn unmatched_skips_other_sources_and_undecodable_events() {
+        let mut log = EventLog::default();
+        log.record(SOURCE, vec![1], 1);
+        log.record(OTHER, vec![2], 1);
+        log.record(SOURCE, vec![], 1);
+        log.record(SOURCE, vec![3], 2);
+        log.matched[0] = true;
+
+        let unmatched: Vec<_> = log.unmatched(SOURCE, decode).collect();
+        assert_eq!(unmatched, [(3, 3)]);
+    }
+}
@@ -0,0 +1,283 @@
+//! Fault injection for the calls a program under test sends to other programs.
+//!
+//! A [`FaultProgram`] stands in for a callee: it is installed at the callee address with
+//! [`GtestEnv::inject_faults`](super::GtestEnv::inject_faults) and answers the calls it
+//! receives with the configured [`Fault`], per method or for all of them, or with canned
+//! replies. [`GtestEnv::inject_unavailable`](super::GtestEnv::inject_unavailable) makes
+//! the callee reject every call as an unavailable actor.
+//!
+//! The faults are limited to what `gtest` can simulate for mocked programs: error replies
+//! are reported as userspace panics, every call is replied, and gas exhaustion of the callee
+//! cannot be simulated. Calls sent to an address without a program are never replied, which
+//! exercises the `wait_up_to` timeouts of the caller.
+
+use super::*;
+use crate::meta::SailsMessageHeader;
+use ::gtest::WasmProgram;
+use core::fmt;
+use std::rc::Rc;
+
+const UNEXPECTED_CALL: &str = "FaultProgram: unexpected call";
+
+/// Failure simulated by a [`FaultProgram`] for a call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Fault {
+    /// Replies with `ErrorReplyReason::Execution(SimpleExecutionError::UserspacePanic)`,
+    /// the message is the reply payload.
+    Panic(&'static str),
+    /// Replies with an empty payload, as the auto reply of a program which did not reply
+    /// explicitly, so the caller fails to decode the reply.
+    EmptyReply,
+}
+
+#[derive(Clone, Debug)]
+enum Response {
+    Fault(Fault),
+    /// SCALE-encoded reply value, sent after the Sails header of the call.
+    Reply(Vec<u8>),
+}
+
+type Handler = Rc<dyn Fn(&[u8]) -> Option<Vec<u8>>>;
+
+/// Program answering the calls it receives with faults or canned replies.
+///
+/// Method rules take precedence over the rule set by [`FaultProgram::with_fault`],
+/// calls matching no rule are passed to the handler set by [`FaultProgram::with_handler`]
+/// or fail with [`Fault::Panic`].
+///
+/// ```rust,ignore
+/// let faults = FaultProgram::new()
+///     .with_method_fault::<redirect::io::GetProgramId>(Fault::Panic("unavailable"))
+///     .with_reply::<redirect::io::Exit>(());
+/// env.inject_faults(target_id, faults);
+/// ```
+#[derive(Clone, Default)]
+pub struct FaultProgram {
+    methods: Vec<((InterfaceId, u16), Response)>,
+    fallback: Option<Fault>,
+    handler: Option<Handler>,
+}
+
+impl FaultProgram {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fails every call not matching a method rule with `fault`.
+    pub fn with_fault(self, fault: Fault) -> Self {
+        Self {
+            fallback: Some(fault),
+            ..self
+        }
+    }
+
+    /// Fails the calls of the method `M` of a generated client with `fault`.
+    pub fn with_method_fault<M: MethodMeta>(self, fault: Fault) -> Self {
+        self.with_entry_fault(M::INTERFACE_ID, M::ENTRY_ID, fault)
+    }
+
+    /// Fails the calls of the entry `entry_id` of the interface `interface_id` with `fault`.
+    pub fn with_entry_fault(
+        mut self,
+        interface_id: InterfaceId,
+        entry_id: u16,
+        fault: Fault,
+    ) -> Self {
+        self.methods
+            .push(((interface_id, entry_id), Response::Fault(fault)));
+        self
+    }
+
+    /// Replies to the calls of the method `M` of a generated client with `reply`.
+    pub fn with_reply<M: MethodMeta>(mut self, reply: impl Encode) -> Self {
+        self.methods.push((
+            (M::INTERFACE_ID, M::ENTRY_ID),
+            Response::Reply(reply.encode()),
+        ));
+        self
+    }
+
+    /// Answers the calls matching no rule with the raw reply payload returned by `handler`,
+    /// `None` results in an empty auto reply.
+    pub fn with_handler(self, handler: impl Fn(&[u8]) -> Option<Vec<u8>> + 'static) -> Self {
+        Self {
+            handler: Some(Rc::new(handler)),
+            ..self
+        }
+    }
+
+    fn respond(&self, payload: &[u8]) -> Result<Option<Vec<u8>>, &'static str> {
+        let header = SailsMessageHeader::try_from_bytes(payload).ok();
+        let method = header.and_then(|header| {
+            self.methods
+                .iter()
+                .find(|(key, _)| *key == (header.interface_id(), header.entry_id()))
+                .map(|(_, response)| (header, response))
+        });
+        match method {
+            Some((_, Response::Fault(fault))) => fail(*fault),
+            Some((header, Response::Reply(reply))) => {
+                let mut payload = SailsMessageHeader::v1(
+                    header.interface_id(),
+                    header.entry_id(),
+                    header.route_id(),
+                )
+                .to_bytes();
+                payload.extend_from_slice(reply);
+                Ok(Some(payload))
+            }
+            None => match (self.fallback, &self.handler) {
+                (Some(fault), _) => fail(fault),
+                (None, Some(handler)) => Ok(handler(payload)),
+                (None, None) => Err(UNEXPECTED_CALL),
+            },
+        }
+    }
+}
+
+fn fail(fault: Fault) -> Result<Option<Vec<u8>>, &'static str> {
+    match fault {
+        Fault::Panic(message) => Err(message),
+        Fault::EmptyReply => Ok(None),
+    }
+}
+
+impl fmt::Debug for FaultProgram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FaultProgram")
+            .field("methods", &self.methods)
+            .field("fallback", &self.fallback)
+            .field("handler", &self.handler.as_ref().map(|_| "Fn"))
+            .finish()
+    }
+}
+
+impl WasmProgram for FaultProgram {
+    fn init(&mut self, _payload: Vec<u8>) -> Result<Option<Vec<u8>>, &'static str> {
+        Ok(Some(Vec::new()))
+    }
+
+    fn handle(&mut self, payload: Vec<u8>) -> Result<Option<Vec<u8>>, &'static str> {
+        self.respond(&payload)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn WasmProgram> {
+        Box::new(self.clone())
+    }
+
+    fn state(&mut self) -> Result<Vec<u8>, &'static str> {
+        Ok(Vec::new())
+    }
+}
+
+/// Program failing its initialization, so every call to it is rejected with
+/// `ErrorReplyReason::UnavailableActor`.
+#[derive(Clone, Debug)]
+pub(crate) struct UnavailableProgram;
+
+impl WasmProgram for UnavailableProgram {
+    fn init(&mut self, _payload: Vec<u8>) -> Result<Option<Vec<u8>>, &'static str> {
+        Err("FaultProgram: unavailable")
+    }
+
+    fn handle(&mut self, _payload: Vec<u8>) -> Result<Option<Vec<u8>>, &'static str> {
+        Err("FaultProgram: unavailable")
+    }
+
+    fn clone_boxed(&self) -> Box<dyn WasmProgram> {
+        Box::new(self.clone())
+    }
+
+    fn state(&mut self) -> Result<Vec<u8>, &'static str> {
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gtest::System;
+
+    struct Ping(());
+
+    impl Identifiable for Ping {
+        const INTERFACE_ID: InterfaceId = InterfaceId::from_u64(1);
+    }
+
+    impl MethodMeta for Ping {
+        const ENTRY_ID: u16 = 3;
+    }
+
+    const TARGET: ActorId = ActorId::new([7; 32]);
+
+    fn env() -> GtestEnv {
+        let system = System::new();
+        system.mint_to(DEFAULT_USER_ALICE, DEFAULT_USERS_INITIAL_BALANCE);
+        GtestEnv::new(system, DEFAULT_USER_ALICE.into())
+    }
+
+    fn ping(route_id: u8) -> Vec<u8> {
+        SailsMessageHeader::v1(Ping::INTERFACE_ID, Ping::ENTRY_ID, route_id).to_bytes()
+    }
+
+    #[tokio::test]
+    async fn method_rules_take_precedence() {
+        let env = env();
+        env.inject_faults(
+            TARGET,
+            FaultProgram::new()
+                .with_reply::<Ping>(42u32)
+                .with_fault(Fault::Panic("boom")),
+        );
+
+        let reply = env
+            .send_for_reply(TARGET, ping(2), Default::default())
+            .await
+            .unwrap();
+        assert_eq!(reply, [ping(2), 42u32.encode()].concat());
+
+        let err = env
+            .send_for_reply(TARGET, [1, 2, 3], Default::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.userspace_panic_payload(), Some(b"boom".as_slice()));
+    }
+
+    #[tokio::test]
+    async fn handler_answers_unmatched_calls() {
+        let env = env();
+        env.inject_faults(
+            TARGET,
+            FaultProgram::new()
+                .with_method_fault::<Ping>(Fault::EmptyReply)
+                .with_handler(|payload| Some(payload.to_vec())),
+        );
+
+        let reply = env
+            .send_for_reply(TARGET, ping(1), Default::default())
+            .await
+            .unwrap();
+        assert!(reply.is_empty());
+
+        let reply = env
+            .send_for_reply(TARGET, [1, 2, 3], Default::default())
+            .await
+            .unwrap();
+        assert_eq!(reply, [1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn unavailable_program_rejects_calls() {
+        let env = env();
+        env.inject_unavailable(TARGET);
+
+        let err = env
+            .send_for_reply(TARGET, ping(1), Default::default())
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            GtestError::ReplyHasError(ErrorReplyReason::UnavailableActor(_), _)
+        ));
+    }
+}
@@ -0,0 +1,415 @@
+//! Gas and execution profiling of calls sent through [`GtestEnv`](super::GtestEnv).
+//!
+//! Profiling is opt-in via [`GtestEnv::with_profiling`](super::GtestEnv::with_profiling).
+//! Every `PendingCall`/`PendingCtor` sent through a profiling env is recorded as a
+//! [`CallProfile`] once its reply is received, and [`ProfileReport`] aggregates them per
+//! call name.
+
+use super::*;
+use ::gtest::BlockRunResult;
+use core::fmt::{self, Write as _};
+use std::{collections::BTreeMap, io, path::Path};
+
+/// Execution details of a single call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CallProfile {
+    /// Name of the call, `<service>::<Method>`, see [`call_name`].
+    pub name: String,
+    /// Id of the message sent for the call.
+    pub message_id: MessageId,
+    /// Gas burned by the message and the messages it spawned.
+    pub gas_burned: GasUnit,
+    /// Number of blocks run while the reply was awaited.
+    pub blocks: u32,
+    /// Number of messages executed on behalf of the call, except the call itself.
+    pub spawned_messages: u32,
+    /// Code of the reply, `None` if the reply is missing.
+    pub reply_code: Option<ReplyCode>,
+}
+
+impl CallProfile {
+    fn new(name: String, message_id: MessageId) -> Self {
+        Self {
+            name,
+            message_id,
+            gas_burned: 0,
+            blocks: 0,
+            spawned_messages: 0,
+            reply_code: None,
+        }
+    }
+
+    /// Returns whether the call was replied successfully.
+    pub fn is_success(&self) -> bool {
+        matches!(self.reply_code, Some(ReplyCode::Success(_)))
+    }
+}
+
+/// Aggregated profile of all calls with the same name.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CallSummary {
+    pub name: String,
+    pub calls: u32,
+    /// Calls without a successful reply.
+    pub failures: u32,
+    pub gas_total: GasUnit,
+    pub gas_min: GasUnit,
+    pub gas_max: GasUnit,
+    pub blocks_total: u32,
+    pub spawned_messages_total: u32,
+}
+
+impl CallSummary {
+    /// Average gas burned per call.
+    pub fn gas_avg(&self) -> GasUnit {
+        self.gas_total / GasUnit::from(self.calls.max(1))
+    }
+
+    fn add(&mut self, call: &CallProfile) {
+        if self.calls == 0 {
+            self.gas_min = call.gas_burned;
+        }
+        self.calls += 1;
+        if !call.is_success() {
+            self.failures += 1;
+        }
+        self.gas_total += call.gas_burned;
+        self.gas_min = self.gas_min.min(call.gas_burned);
+        self.gas_max = self.gas_max.max(call.gas_burned);
+        self.blocks_total += call.blocks;
+        self.spawned_messages_total += call.spawned_messages;
+    }
+}
+
+/// Profiles of the calls recorded by a profiling [`GtestEnv`](super::GtestEnv),
+/// in the order their replies were received.
+///
+/// `Display` renders the per-name summary as a table, [`ProfileReport::to_json`]
+/// renders both the summary and the individual calls.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ProfileReport {
+    calls: Vec<CallProfile>,
+}
+
+impl ProfileReport {
+    pub fn new(calls: Vec<CallProfile>) -> Self {
+        Self { calls }
+    }
+
+    pub fn calls(&self) -> &[CallProfile] {
+        &self.calls
+    }
+
+    /// Returns the summary of the calls grouped by name, sorted by name.
+    pub fn summary(&self) -> Vec<CallSummary> {
+        let mut summary: BTreeMap<&str, CallSummary> = BTreeMap::new();
+        for call in &self.calls {
+            summary
+                .entry(&call.name)
+                .or_insert_with(|| CallSummary {
+                    name: call.name.clone(),
+                    ..Default::default()
+                })
+                .add(call);
+        }
+        summary.into_values().collect()
+    }
+
+    /// Returns the summary of the calls named `name`.
+    pub fn call_summary(&self, name: &str) -> Option<CallSummary> {
+        self.summary().into_iter().find(|s| s.name == name)
+    }
+
+    /// Renders the report as a JSON object with `summary` and `calls` arrays.
+    pub fn to_json(&self) -> String {
+        let mut json = String::from("{\n  \"summary\": [");
+        for (idx, s) in self.summary().iter().enumerate() {
+            let sep = if idx == 0 { "" } else { "," };
+            _ = write!(
+                json,
+                "{sep}\n    {{\"name\": {}, \"calls\": {}, \"failures\": {}, \"gas_total\": {}, \
+                 \"gas_min\": {}, \"gas_avg\": {}, \"gas_max\": {}, \"blocks_total\": {}, \
+                 \"spawned_messages_total\": {}}}",
+                json_string(&s.name),
+                s.calls,
+                s.failures,
+                s.gas_total,
+                s.gas_min,
+                s.gas_avg(),
+                s.gas_max,
+                s.blocks_total,
+                s.spawned_messages_total,
+            );
+        }
+        json.push_str("\n  ],\n  \"calls\": [");
+        for (idx, c) in self.calls.iter().enumerate() {
+            let sep = if idx == 0 { "" } else { "," };
+            _ = write!(
+                json,
+                "{sep}\n    {{\"name\": {}, \"message_id\": \"{}\", \"gas_burned\": {}, \
+                 \"blocks\": {}, \"spawned_messages\": {}, \"reply_code\": {}}}",
+                json_string(&c.name),
+                c.message_id,
+                c.gas_burned,
+                c.blocks,
+                c.spawned_messages,
+                c.reply_code
+                    .map(|code| json_string(&reply_code_str(code)))
+                    .unwrap_or_else(|| "null".into()),
+            );
+        }
+        json.push_str("\n  ]\n}\n");
+        json
+    }
+
+    /// Writes [`ProfileReport::to_json`] to `path`, e.g. at the end of a test run.
+    pub fn save_json(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        std::fs::write(path, self.to_json())
+    }
+}
+
+impl fmt::Display for ProfileReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        const HEADERS: [&str; 8] = [
+            "call", "calls", "failures", "gas min", "gas avg", "gas max", "blocks", "spawned",
+        ];
+        let rows: Vec<[String; 8]> = self
+            .summary()
+            .into_iter()
+            .map(|s| {
+                [
+                    s.name.clone(),
+                    s.calls.to_string(),
+                    s.failures.to_string(),
+                    s.gas_min.to_string(),
+                    s.gas_avg().to_string(),
+                    s.gas_max.to_string(),
+                    s.blocks_total.to_string(),
+                    s.spawned_messages_total.to_string(),
+                ]
+            })
+            .collect();
+        let mut widths = HEADERS.map(str::len);
+        for row in &rows {
+            for (width, cell) in widths.iter_mut().zip(row) {
+                *width = (*width).max(cell.len());
+            }
+        }
+        let write_row = |f: &mut fmt::Formatter<'_>, row: &[&str]| {
+            for (idx, (cell, width)) in row.iter().zip(widths).enumerate() {
+                if idx == 0 {
+                    write!(f, "{cell:<width$}")?;
+                } else {
+                    write!(f, " | {cell:>width$}")?;
+                }
+            }
+            writeln!(f)
+        };
+        write_row(f, &HEADERS)?;
+        let total_width = widths.iter().sum::<usize>() + 3 * (widths.len() - 1);
+        writeln!(f, "{}", "-".repeat(total_width))?;
+        for row in &rows {
+            write_row(f, &row.each_ref().map(String::as_str))?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the name a call of `T` is recorded under: the module of the service
+/// in the generated client followed by the call type, e.g. `counter::Add`.
+pub fn call_name<T: ?Sized>() -> String {
+    let type_name = core::any::type_name::<T>();
+    let mut segments = type_name.rsplit("::");
+    let method = segments.next().unwrap_or(type_name);
+    match (segments.next(), segments.next()) {
+        (Some("io"), Some(service)) => format!("{service}::{method}"),
+        (Some(service), _) => format!("{service}::{method}"),
+        _ => method.to_string(),
+    }
+}
+
+fn reply_code_str(code: ReplyCode) -> String {
+    match code {
+        ReplyCode::Success(_) => "success".into(),
+        ReplyCode::Error(reason) => reason.to_string(),
+        ReplyCode::Unsupported => "unsupported".into(),
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut json = String::with_capacity(value.len() + 2);
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            c if c.is_control() => _ = write!(json, "\\u{:04x}", c as u32),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+/// Collects the profiles of calls sent through a profiling env.
+#[derive(Default)]
+pub(crate) struct Profiler {
+    /// Calls awaiting their reply, in the order they were sent.
+    pending: Vec<CallProfile>,
+    completed: Vec<CallProfile>,
+}
+
+impl Profiler {
+    pub(crate) fn start_call(&mut self, name: String, message_id: MessageId) {
+        self.pending.push(CallProfile::new(name, message_id));
+    }
+
+    /// Attributes the messages executed in the block to the pending calls.
+    ///
+    /// Messages other than the calls themselves are attributed to the earliest sent
+    /// pending call, as the block run result does not track where they originate from.
+    pub(crate) fn record_block(&mut self, run_result: &BlockRunResult) {
+        let Some((first, _)) = self.pending.split_first() else {
+            return;
+        };
+        let first = first.message_id;
+        let mut spawned_gas = 0;
+        let mut spawned_messages = 0;
+        for (message_id, gas) in &run_result.gas_burned {
+            match self
+                .pending
+                .iter_mut()
+                .find(|c| c.message_id == *message_id)
+            {
+                Some(call) => call.gas_burned += gas,
+                None => {
+                    spawned_gas += gas;
+                    spawned_messages += 1;
+                }
+            }
+        }
+        for call in self.pending.iter_mut() {
+            call.blocks += 1;
+            if call.message_id == first {
+                call.gas_burned += spawned_gas;
+                call.spawned_messages += spawned_messages;
+            }
+        }
+    }
+
+    /// Completes the call of `message_id`, `reply_code` is `None` if the reply is missing.
+    pub(crate) fn complete_call(&mut self, message_id: MessageId, reply_code: Option<ReplyCode>) {
+        if let Some(idx) = self.pending.iter().position(|c| c.message_id == message_id) {
+            let mut call = self.pending.remove(idx);
+            call.reply_code = reply_code;
+            self.completed.push(call);
+        }
+    }
+
+    pub(crate) fn report(&self) -> ProfileReport {
+        ProfileReport::new(self.completed.clone())
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.completed.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gear_core_errors::{ErrorReplyReason, SimpleExecutionError, SuccessReplyReason};
+
+    mod counter {
+        pub mod io {
+            pub struct Add;
+        }
+    }
+
+    fn block(gas_burned: &[(MessageId, GasUnit)]) -> BlockRunResult {
+        BlockRunResult {
+            gas_burned: gas_burned.iter().copied().collect(),
+            ..Default::default()
+        }
+    }
+
+    fn profile(name: &str, gas_burned: GasUnit, success: bool) -> CallProfile {
+        CallProfile {
+            name: name.into(),
+            message_id: MessageId::zero(),
+            gas_burned,
+            blocks: 1,
+            spawned_messages: 0,
+            reply_code: Some(if success {
+                ReplyCode::Success(SuccessReplyReason::Manual)
+            } else {
+                ReplyCode::Error(ErrorReplyReason::Execution(
+                    SimpleExecutionError::UserspacePanic,
+                ))
+            }),
+        }
+    }
+
+    #[test]
+    fn call_name_uses_service_module() {
+        assert_eq!(call_name::<counter::io::Add>(), "counter::Add");
+        assert_eq!(call_name::<CallProfile>(), "gtest_profile::CallProfile");
+    }
+
+    #[test]
+    fn profiler_attributes_spawned_messages_to_earliest_call() {
+        let first = MessageId::from(1);
+        let second = MessageId::from(2);
+        let spawned = MessageId::from(3);
+        let mut profiler = Profiler::default();
+        profiler.start_call("svc::First".into(), first);
+        profiler.start_call("svc::Second".into(), second);
+
+        profiler.record_block(&block(&[(first, 10), (second, 20), (spawned, 5)]));
+        profiler.complete_call(second, None);
+        profiler.record_block(&block(&[(first, 1)]));
+        profiler.complete_call(first, Some(ReplyCode::Success(SuccessReplyReason::Auto)));
+
+        let report = profiler.report();
+        let [second, first] = report.calls() else {
+            panic!("unexpected calls: {report:?}");
+        };
+        assert_eq!(
+            (first.gas_burned, first.blocks, first.spawned_messages),
+            (16, 2, 1)
+        );
+        assert!(first.is_success());
+        assert_eq!(
+            (second.gas_burned, second.blocks, second.spawned_messages),
+            (20, 1, 0)
+        );
+        assert_eq!(second.reply_code, None);
+    }
+
+    #[test]
+    fn report_aggregates_calls_by_name() {
+        let report = ProfileReport::new(vec![
+            profile("counter::Add", 100, true),
+            profile("counter::Sub", 50, true),
+            profile("counter::Add", 300, false),
+        ]);
+
+        let add = report.call_summary("counter::Add").unwrap();
+        assert_eq!((add.calls, add.failures), (2, 1));
+        assert_eq!((add.gas_min, add.gas_avg(), add.gas_max), (100, 200, 300));
+        assert_eq!(report.summary().len(), 2);
+
+        let table = report.to_string();
+        let lines: Vec<_> = table.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("call         | calls | failures"));
+        assert!(lines[2].starts_with("counter::Add |     2 |        1 |     100 |     200"));
+
+        let json = report.to_json();
+        assert!(json.contains(
+            "{\"name\": \"counter::Add\", \"calls\": 2, \"failures\": 1, \"gas_total\": 400"
+        ));
+        assert!(json.contains("\"reply_code\": \"execution error (Message panicked)\""));
+        assert!(json.contains("\"reply_code\": \"success\""));
+    }
+}
@@ -0,0 +1,324 @@
+//! Snapshots of the state of a [`GtestEnv`](super::GtestEnv).
+//!
+//! `gtest` keeps the state of a `System` in thread-local storages and allows a single
+//! `System` per thread, so the state cannot be copied. Instead, the env journals the
+//! operations performed through it: minting, code submission, program creation, messages
+//! and block runs. A [`GtestSnapshot`] holds the journal, and restoring it replays the
+//! operations on a new `System`, which reproduces the programs, their memory, balances,
+//! mailboxes and block height deterministically.
+//!
+//! A snapshot is `Send + Sync`, so a fixture can be built once and restored in each test,
+//! every restored env being independent of the others:
+//!
+//! ```rust,ignore
+//! static FIXTURE: LazyLock<GtestSnapshot> = LazyLock::new(|| {
+//!     let env = GtestEnv::system_default();
+//!     let code_id = env.submit_code_file(DEMO_WASM_PATH);
+//!     futures::executor::block_on(env.deploy(code_id, vec![]).new(Some(42), None)).unwrap();
+//!     env.snapshot()
+//! });
+//!
+//! let env = FIXTURE.restore();
+//! ```
+//!
+//! Operations performed directly on `GtestEnv::system`, such as replies to the mailbox, or
+//! before the `System` was passed to `GtestEnv::new` are not journaled, except the codes of
+//! the programs created through the env. Mocked programs cannot be replayed, so they have to
+//! be installed after the snapshot is restored.
+
+use super::{gtest_events::EventLog, *};
+use ::gtest::TestError;
+#[cfg(feature = "ethexe")]
+use ::gtest::ethexe::{Program, System};
+#[cfg(not(feature = "ethexe"))]
+use ::gtest::{Program, System};
+use std::collections::BTreeSet;
+
+/// Message sent by a journaled operation.
+#[derive(Clone, Debug)]
+pub(crate) struct Message {
+    pub(crate) source: ActorId,
+    pub(crate) payload: Vec<u8>,
+    pub(crate) gas_limit: GasUnit,
+    pub(crate) value: ValueUnit,
+}
+
+#[derive(Clone, Debug)]
+enum Op {
+    Mint(ActorId, ValueUnit),
+    SubmitCode(Vec<u8>),
+    CreateProgram {
+        code_id: CodeId,
+        program_id: ActorId,
+        message: Message,
+    },
+    Send {
+        destination: ActorId,
+        message: Message,
+    },
+    RunBlock,
+}
+
+impl Op {
+    fn replay(&self, system: &System) {
+        match self {
+            Op::Mint(actor_id, value) => system.mint_to(*actor_id, *value),
+            Op::SubmitCode(code) => _ = system.submit_code(code.clone()),
+            Op::CreateProgram {
+                code_id,
+                program_id,
+                message,
+            } => _ = create_program(system, *code_id, *program_id, message),
+            Op::Send {
+                destination,
+                message,
+            } => _ = send(system, *destination, message),
+            Op::RunBlock => _ = system.run_next_block(),
+        }
+    }
+}
+
+/// Creates the program `program_id` from the submitted code `code_id`,
+/// returns the id of the init message.
+pub(crate) fn create_program(
+    system: &System,
+    code_id: CodeId,
+    program_id: ActorId,
+    message: &Message,
+) -> Result<MessageId, GtestError> {
+    let code = system
+        .submitted_code(code_id)
+        .ok_or(TestError::Instrumentation)?;
+    let program = Program::from_binary_with_id(system, program_id, code);
+    // On `ethexe`, execution gas is drawn from the program's executable balance.
+    #[cfg(feature = "ethexe")]
+    system.top_up_executable_balance(program_id, ETHEXE_EXECUTABLE_BALANCE);
+    Ok(program.send_bytes_with_gas(
+        message.source,
+        message.payload.as_slice(),
+        message.gas_limit,
+        message.value,
+    ))
+}
+
+/// Sends `message` to the program `destination`, returns the id of the message.
+pub(crate) fn send(
+    system: &System,
+    destination: ActorId,
+    message: &Message,
+) -> Result<MessageId, GtestError> {
+    let program = system
+        .get_program(destination)
+        .ok_or(TestError::ActorNotFound(destination))?;
+    Ok(program.send_bytes_with_gas(
+        message.source,
+        message.payload.as_slice(),
+        message.gas_limit,
+        message.value,
+    ))
+}
+
+/// Operations performed through a [`GtestEnv`] and its clones.
+#[derive(Default)]
+pub(crate) struct Journal {
+    ops: Vec<Op>,
+    codes: BTreeSet<CodeId>,
+    /// Address of the first mocked program, which cannot be replayed.
+    mock: Option<ActorId>,
+}
+
+impl Journal {
+    pub(crate) fn mint(&mut self, actor_id: ActorId, value: ValueUnit) {
+        self.ops.push(Op::Mint(actor_id, value));
+    }
+
+    pub(crate) fn submit_code(&mut self, code_id: CodeId, code: impl FnOnce() -> Vec<u8>) {
+        if self.codes.insert(code_id) {
+            self.ops.push(Op::SubmitCode(code()));
+        }
+    }
+
+    pub(crate) fn create_program(
+        &mut self,
+        code_id: CodeId,
+        program_id: ActorId,
+        message: Message,
+    ) {
+        self.ops.push(Op::CreateProgram {
+            code_id,
+            program_id,
+            message,
+        });
+    }
+
+    pub(crate) fn send(&mut self, destination: ActorId, message: Message) {
+        self.ops.push(Op::Send {
+            destination,
+            message,
+        });
+    }
+
+    pub(crate) fn run_block(&mut self) {
+        self.ops.push(Op::RunBlock);
+    }
+
+    #[cfg(not(feature = "ethexe"))]
+    pub(crate) fn mock(&mut self, destination: ActorId) {
+        self.mock.get_or_insert(destination);
+    }
+}
+
+/// State of a [`GtestEnv`] captured by [`GtestEnv::snapshot`].
+#[derive(Clone)]
+pub struct GtestSnapshot {
+    ops: Vec<Op>,
+    codes: BTreeSet<CodeId>,
+    actor_id: ActorId,
+    block_run_mode: BlockRunMode,
+    event_log: EventLog,
+}
+
+impl GtestSnapshot {
+    /// Replays the snapshot on a new `System`, returns an env with the actor id,
+    /// the block run mode and the recorded events of the env the snapshot was taken of.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a `System` exists in the current thread, so the env the snapshot was
+    /// taken of and its clones held by clients have to be dropped before it is restored
+    /// in the same thread.
+    pub fn restore(&self) -> GtestEnv {
+        let system = System::new();
+        for op in &self.ops {
+            op.replay(&system);
+        }
+        let env =
+            GtestEnv::new(system, self.actor_id).with_block_run_mode(self.block_run_mode.clone());
+        *env.journal.borrow_mut() = Journal {
+            ops: self.ops.clone(),
+            codes: self.codes.clone(),
+            mock: None,
+        };
+        *env.event_log.borrow_mut() = self.event_log.clone();
+        env
+    }
+}
+
+impl GtestEnv {
+    /// Captures the state of the env, see [`GtestSnapshot`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if a mocked program was installed through the env, as it cannot be replayed.
+    pub fn snapshot(&self) -> GtestSnapshot {
+        let journal = self.journal.borrow();
+        if let Some(destination) = journal.mock {
+            panic!(
+                "cannot snapshot the env with the mocked program {destination}, \
+                install mocks after restoring the snapshot"
+            );
+        }
+        GtestSnapshot {
+            ops: journal.ops.clone(),
+            codes: journal.codes.clone(),
+            actor_id: self.actor_id(),
+            block_run_mode: self.block_run_mode.clone(),
+            event_log: self.event_log.borrow().clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ::gtest::constants::DEFAULT_USER_BOB;
+
+    /// `(module (import "env" "memory" (memory 1)) (func $init) (func $handle)
+    /// (export "init" (func $init)) (export "handle" (func $handle)))`
+    const NOOP_WASM: &[u8] = &[
+        0x00, 0x61, 0x73, 0x6d, 0x01, 0x00, 0x00, 0x00, 0x01, 0x04, 0x01, 0x60, 0x00, 0x00, 0x02,
+        0x0f, 0x01, 0x03, 0x65, 0x6e, 0x76, 0x06, 0x6d, 0x65, 0x6d, 0x6f, 0x72, 0x79, 0x02, 0x00,
+        0x01, 0x03, 0x03, 0x02, 0x00, 0x00, 0x07, 0x11, 0x02, 0x04, 0x69, 0x6e, 0x69, 0x74, 0x00,
+        0x00, 0x06, 0x68, 0x61, 0x6e, 0x64, 0x6c, 0x65, 0x00, 0x01, 0x0a, 0x07, 0x02, 0x02, 0x00,
+        0x0b, 0x02, 0x00, 0x0b,
+    ];
+
+    fn env() -> GtestEnv {
+        let env = GtestEnv::new(System::new(), DEFAULT_USER_ALICE.into());
+        env.mint_to(DEFAULT_USER_ALICE.into(), DEFAULT_USERS_INITIAL_BALANCE);
+        env
+    }
+
+    fn state(env: &GtestEnv) -> (u32, ValueUnit, ValueUnit) {
+        (
+            env.block_height(),
+            env.system().balance_of(DEFAULT_USER_ALICE),
+            env.system().balance_of(DEFAULT_USER_BOB),
+        )
+    }
+
+    #[tokio::test]
+    async fn restore_replays_programs_balances_and_blocks() {
+        let env = env();
+        env.mint_to(DEFAULT_USER_BOB.into(), DEFAULT_USERS_INITIAL_BALANCE);
+        let code_id = env.submit_code(NOOP_WASM);
+        let (program_id, _) = env
+            .create_program(code_id, b"salt", [], Default::default())
+            .unwrap();
+        env.send_for_reply(program_id, [1, 2, 3], Default::default())
+            .await
+            .unwrap();
+        env.run_blocks(3);
+        let snapshot = env.snapshot();
+        let expected = state(&env);
+
+        // Changes made after the snapshot are not restored.
+        env.mint_to(DEFAULT_USER_BOB.into(), 1_000);
+        env.send_for_reply(program_id, [4], Default::default())
+            .await
+            .unwrap();
+        assert_ne!(state(&env), expected);
+        drop(env);
+
+        for _ in 0..2 {
+            let env = snapshot.restore();
+            assert_eq!(state(&env), expected);
+            assert!(env.system().is_active_program(program_id));
+            let reply = env
+                .send_for_reply(program_id, [5], Default::default())
+                .await
+                .unwrap();
+            assert!(reply.is_empty());
+        }
+    }
+
+    #[test]
+    fn snapshot_forks_in_other_threads() {
+        let env = env();
+        env.run_blocks(2);
+        let snapshot = env.snapshot();
+
+        let forks: Vec<_> = (0..2)
+            .map(|blocks| {
+                let snapshot = snapshot.clone();
+                std::thread::spawn(move || {
+                    let env = snapshot.restore();
+                    env.run_blocks(blocks);
+                    env.block_height()
+                })
+            })
+            .collect();
+        let heights: Vec<_> = forks.into_iter().map(|fork| fork.join().unwrap()).collect();
+
+        assert_eq!(heights, [env.block_height(), env.block_height() + 1]);
+    }
+
+    #[cfg(not(feature = "ethexe"))]
+    #[test]
+    #[should_panic(expected = "cannot snapshot the env with the mocked program")]
+    fn snapshot_with_mocks_panics() {
+        let env = env();
+        env.inject_unavailable(ActorId::from(1000));
+        _ = env.snapshot();
+    }
+}
@@ -0,0 +1,131 @@
+//! Trace of the messages dispatched through [`GtestEnv`](super::GtestEnv).
+//!
+//! Tracing is opt-in via [`GtestEnv::with_message_trace`](super::GtestEnv::with_message_trace).
+//! The trace holds the calls sent through the env, the replies to them and the events emitted
+//! by programs, so tools such as coverage reporting can map them back to the program interface
+//! by their `SailsMessageHeader`. Messages exchanged between programs are not visible to
+//! `gtest` and are not traced.
+
+use super::*;
+use hashbrown::HashSet;
+
+/// Message traced by [`GtestEnv`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TracedMessage {
+    /// Constructor or service call sent to `destination`, queries included.
+    Call {
+        destination: ActorId,
+        payload: Vec<u8>,
+    },
+    /// Reply of `source` to a traced call.
+    Reply {
+        source: ActorId,
+        code: ReplyCode,
+        payload: Vec<u8>,
+    },
+    /// Event emitted by `source`.
+    Event { source: ActorId, payload: Vec<u8> },
+}
+
+#[derive(Default)]
+pub(crate) struct MessageTrace {
+    messages: Vec<TracedMessage>,
+    /// Ids of the traced calls awaiting a reply.
+    calls: HashSet<MessageId>,
+}
+
+impl MessageTrace {
+    pub(crate) fn call(
+        &mut self,
+        message_id: Option<MessageId>,
+        destination: ActorId,
+        payload: &[u8],
+    ) {
+        if let Some(message_id) = message_id {
+            self.calls.insert(message_id);
+        }
+        self.messages.push(TracedMessage::Call {
+            destination,
+            payload: payload.to_vec(),
+        });
+    }
+
+    /// Traces the reply if it is a reply to a traced call, `reply_to` is `None` for queries.
+    pub(crate) fn reply(
+        &mut self,
+        reply_to: Option<MessageId>,
+        source: ActorId,
+        code: ReplyCode,
+        payload: &[u8],
+    ) {
+        if reply_to.is_none_or(|message_id| self.calls.remove(&message_id)) {
+            self.messages.push(TracedMessage::Reply {
+                source,
+                code,
+                payload: payload.to_vec(),
+            });
+        }
+    }
+
+    pub(crate) fn event(&mut self, source: ActorId, payload: &[u8]) {
+        self.messages.push(TracedMessage::Event {
+            source,
+            payload: payload.to_vec(),
+        });
+    }
+
+    pub(crate) fn messages(&self) -> &[TracedMessage] {
+        &self.messages
+    }
+
+    pub(crate) fn reset(&mut self) {
+        self.messages.clear();
+    }
+}
+
+#[cfg(all(test, not(feature = "ethexe")))]
+mod tests {
+    use super::*;
+    use crate::gtest::System;
+
+    const TARGET: ActorId = ActorId::new([7; 32]);
+
+    #[tokio::test]
+    async fn traces_calls_and_their_replies() {
+        let system = System::new();
+        system.mint_to(DEFAULT_USER_ALICE, DEFAULT_USERS_INITIAL_BALANCE);
+        let env = GtestEnv::new(system, DEFAULT_USER_ALICE.into()).with_message_trace();
+        env.inject_faults(
+            TARGET,
+            FaultProgram::new().with_handler(|payload| Some(payload.to_vec())),
+        );
+
+        env.send_for_reply(TARGET, [1, 2], Default::default())
+            .await
+            .unwrap();
+        let query = env.query(TARGET, [3], Default::default()).unwrap();
+
+        assert_eq!(query, [3]);
+        // The activation of the mocked program is not sent through the env, so its reply is not traced.
+        let trace = env.message_trace();
+        assert_eq!(trace.len(), 4);
+        assert_eq!(
+            trace[0],
+            TracedMessage::Call {
+                destination: TARGET,
+                payload: vec![1, 2]
+            }
+        );
+        assert!(matches!(
+            &trace[1],
+            TracedMessage::Reply { source: TARGET, code: ReplyCode::Success(_), payload } if payload == &[1, 2]
+        ));
+        assert!(matches!(
+            &trace[3],
+            TracedMessage::Reply { payload, .. } if payload == &[3]
+        ));
+
+        env.reset_message_trace();
+        assert!(env.message_trace().is_empty());
+    }
+}
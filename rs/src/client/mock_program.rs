@@ -0,0 +1,341 @@
+//! Mock programs standing in for the programs called by a program under test.
+//!
+//! A [`MockProgram`] answers the calls it receives with canned replies and emits events
+//! per method, so integration tests of a program calling another program can stub the
+//! dependency at the message level instead of deploying the real one. It is installed
+//! with [`GtestEnv::mock_program`](super::GtestEnv::mock_program).
+//!
+//! Clients generated with mocks provide typed accessors for the services and methods
+//! of the mocked program:
+//!
+//! ```rust,ignore
+//! use demo_client::{DemoClientMock as _, counter::mock_program::CounterMock as _};
+//!
+//! let mut mock = MockProgram::<DemoClientProgram>::new();
+//! mock.counter()
+//!     .add()
+//!     .returns_with(|(value,)| value + 1)
+//!     .emits(CounterEvents::Added(1));
+//! let demo_program = env.mock_program(demo_id, mock);
+//! ```
+//!
+//! Calls of a configured method without a reply are replied with the Sails header only,
+//! calls of methods which are not configured fail with a userspace panic. The events of
+//! a method are emitted only if the call does not fail.
+
+use super::*;
+use crate::meta::SailsMessageHeader;
+use core::{cell::RefCell, fmt};
+use std::rc::Rc;
+
+const UNEXPECTED_CALL: &str = "MockProgram: unexpected call";
+const INVALID_PARAMS: &str = "MockProgram: failed to decode call params";
+
+type Handler = Rc<dyn Fn(&[u8]) -> Result<Vec<u8>, &'static str>>;
+/// Encoded events emitted by a [`MockProgram`], shared by its clones.
+pub(crate) type MockEvents = Rc<RefCell<Vec<Vec<u8>>>>;
+
+#[derive(Clone)]
+struct MockEntry {
+    interface_id: InterfaceId,
+    route_idx: u8,
+    entry_id: u16,
+    handler: Option<Handler>,
+    events: Vec<Vec<u8>>,
+}
+
+impl MockEntry {
+    fn key(&self) -> (InterfaceId, u8, u16) {
+        (self.interface_id, self.route_idx, self.entry_id)
+    }
+}
+
+/// Program answering the calls of the client of the program `P` with canned replies
+/// and events.
+pub struct MockProgram<P = ()> {
+    entries: Vec<MockEntry>,
+    events: MockEvents,
+    _program: PhantomData<P>,
+}
+
+impl<P> MockProgram<P> {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            events: Default::default(),
+            _program: PhantomData,
+        }
+    }
+
+    /// Configures the service `S` exposed at `route_idx`.
+    pub fn service<S>(&mut self, route_idx: u8) -> MockService<'_, S> {
+        MockService {
+            entries: &mut self.entries,
+            route_idx,
+            _service: PhantomData,
+        }
+    }
+
+    /// Handles the encoded call `payload`, returns the reply payload.
+    pub fn handle(&self, payload: &[u8]) -> Result<Vec<u8>, &'static str> {
+        let mut params = payload;
+        let header =
+            SailsMessageHeader::try_read_bytes(&mut params).map_err(|_| UNEXPECTED_CALL)?;
+        let key = (header.interface_id(), header.route_id(), header.entry_id());
+        let entry = self
+            .entries
+            .iter()
+            .find(|entry| entry.key() == key)
+            .ok_or(UNEXPECTED_CALL)?;
+        let reply = match &entry.handler {
+            Some(handler) => handler(params)?,
+            None => Vec::new(),
+        };
+        self.events
+            .borrow_mut()
+            .extend(entry.events.iter().cloned());
+        let mut payload = header.to_bytes();
+        payload.extend(reply);
+        Ok(payload)
+    }
+}
+
+impl<P> Default for MockProgram<P> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<P> Clone for MockProgram<P> {
+    fn clone(&self) -> Self {
+        Self {
+            entries: self.entries.clone(),
+            events: self.events.clone(),
+            _program: PhantomData,
+        }
+    }
+}
+
+impl<P> fmt::Debug for MockProgram<P> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let methods: Vec<_> = self.entries.iter().map(MockEntry::key).collect();
+        f.debug_struct("MockProgram")
+            .field("methods", &methods)
+            .finish()
+    }
+}
+
+/// Configures the methods of the service `S` of a [`MockProgram`].
+pub struct MockService<'a, S> {
+    entries: &'a mut Vec<MockEntry>,
+    route_idx: u8,
+    _service: PhantomData<S>,
+}
+
+impl<S> MockService<'_, S> {
+    /// Configures the method `M` of the service.
+    pub fn method<M: MethodMeta>(&mut self) -> MockMethod<'_, M> {
+        let key = (M::INTERFACE_ID, self.route_idx, M::ENTRY_ID);
+        let idx = match self.entries.iter().position(|entry| entry.key() == key) {
+            Some(idx) => idx,
+            None => {
+                self.entries.push(MockEntry {
+                    interface_id: M::INTERFACE_ID,
+                    route_idx: self.route_idx,
+                    entry_id: M::ENTRY_ID,
+                    handler: None,
+                    events: Vec::new(),
+                });
+                self.entries.len() - 1
+            }
+        };
+        MockMethod {
+            entry: &mut self.entries[idx],
+            _method: PhantomData,
+        }
+    }
+
+    /// Configures the base service `B` extended by the service.
+    pub fn base_service<B>(&mut self) -> MockService<'_, B> {
+        MockService {
+            entries: self.entries,
+            route_idx: self.route_idx,
+            _service: PhantomData,
+        }
+    }
+}
+
+/// Configures the reply and events of the method `M` of a [`MockProgram`].
+pub struct MockMethod<'a, M> {
+    entry: &'a mut MockEntry,
+    _method: PhantomData<M>,
+}
+
+impl<M: ServiceCall> MockMethod<'_, M> {
+    /// Replies to the calls with `reply`.
+    pub fn returns(self, reply: M::Reply) -> Self
+    where
+        M::Reply: Encode,
+    {
+        let reply = reply.encode();
+        self.entry.handler = Some(Rc::new(move |_| Ok(reply.clone())));
+        self
+    }
+
+    /// Replies to the calls with the result of `f` for the decoded call params.
+    pub fn returns_with(self, f: impl Fn(M::Params) -> M::Reply + 'static) -> Self
+    where
+        M::Params: Decode,
+        M::Reply: Encode,
+    {
+        self.entry.handler = Some(Rc::new(move |mut params| {
+            let params = M::Params::decode(&mut params).map_err(|_| INVALID_PARAMS)?;
+            Ok(f(params).encode())
+        }));
+        self
+    }
+
+    /// Fails the calls with a userspace panic, `message` is the error reply payload.
+    pub fn panics(self, message: &'static str) -> Self {
+        self.entry.handler = Some(Rc::new(move |_| Err(message)));
+        self
+    }
+
+    /// Emits `event` from the service route when a call is handled.
+    pub fn emits<E: Encode + Identifiable>(self, event: E) -> Self {
+        let encoded = event.encode();
+        // The variant index of the event is carried by the entry id of the header.
+        if let Some((&variant, fields)) = encoded.split_first() {
+            let mut payload =
+                SailsMessageHeader::v1(E::INTERFACE_ID, variant as u16, self.entry.route_idx)
+                    .to_bytes();
+            payload.extend_from_slice(fields);
+            self.entry.events.push(payload);
+        }
+        self
+    }
+}
+
+#[cfg(all(feature = "gtest", not(feature = "ethexe")))]
+impl<P> MockProgram<P> {
+    pub(crate) fn events(&self) -> MockEvents {
+        self.events.clone()
+    }
+}
+
+#[cfg(all(feature = "gtest", not(feature = "ethexe")))]
+impl<P: 'static> ::gtest::WasmProgram for MockProgram<P> {
+    fn init(&mut self, _payload: Vec<u8>) -> Result<Option<Vec<u8>>, &'static str> {
+        Ok(Some(Vec::new()))
+    }
+
+    fn handle(&mut self, payload: Vec<u8>) -> Result<Option<Vec<u8>>, &'static str> {
+        MockProgram::handle(self, &payload).map(Some)
+    }
+
+    fn clone_boxed(&self) -> Box<dyn ::gtest::WasmProgram> {
+        Box::new(self.clone())
+    }
+
+    fn state(&mut self) -> Result<Vec<u8>, &'static str> {
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(all(test, feature = "gtest", not(feature = "ethexe")))]
+mod tests {
+    use super::*;
+    use crate::gtest::System;
+    use futures::StreamExt as _;
+
+    struct CounterImpl;
+
+    impl Identifiable for CounterImpl {
+        const INTERFACE_ID: InterfaceId = InterfaceId::from_u64(1);
+    }
+
+    #[allow(dead_code)]
+    mod io {
+        use super::*;
+        crate::io_struct_impl!(Add (value: u32) -> u32, 0, <CounterImpl as Identifiable>::INTERFACE_ID);
+        crate::io_struct_impl!(Reset () -> (), 1, <CounterImpl as Identifiable>::INTERFACE_ID);
+    }
+
+    #[derive(Encode, Decode, PartialEq, Debug)]
+    #[codec(crate = crate::scale_codec)]
+    enum CounterEvents {
+        Added(u32),
+        Reset,
+    }
+
+    impl Identifiable for CounterEvents {
+        const INTERFACE_ID: InterfaceId = CounterImpl::INTERFACE_ID;
+    }
+
+    const ROUTE: u8 = 2;
+    const TARGET: ActorId = ActorId::new([7; 32]);
+
+    fn env() -> GtestEnv {
+        let system = System::new();
+        system.mint_to(DEFAULT_USER_ALICE, DEFAULT_USERS_INITIAL_BALANCE);
+        GtestEnv::new(system, DEFAULT_USER_ALICE.into())
+    }
+
+    #[tokio::test]
+    async fn replies_and_emits_events() {
+        let env = env();
+        let mut mock = MockProgram::<()>::new();
+        mock.service::<CounterImpl>(ROUTE)
+            .method::<io::Add>()
+            .returns_with(|(value,)| value + 1)
+            .emits(CounterEvents::Added(1));
+        mock.service::<CounterImpl>(ROUTE)
+            .method::<io::Reset>()
+            .emits(CounterEvents::Reset);
+        let program = env.mock_program(TARGET, mock);
+        let counter = program.service::<CounterImpl>(ROUTE);
+        let mut events = env
+            .listen(|(source, payload)| {
+                decode_event_v2::<CounterEvents>(ROUTE, payload)
+                    .ok()
+                    .map(|event| (source, event))
+            })
+            .await
+            .unwrap();
+
+        let sum = counter.pending_call::<io::Add>((41,)).await.unwrap();
+        counter.pending_call::<io::Reset>(()).await.unwrap();
+
+        assert_eq!(sum, 42);
+        assert_eq!(events.next().await, Some((TARGET, CounterEvents::Added(1))));
+        assert_eq!(events.next().await, Some((TARGET, CounterEvents::Reset)));
+    }
+
+    #[tokio::test]
+    async fn fails_unexpected_and_panicking_calls() {
+        let env = env();
+        let mut mock = MockProgram::<()>::new();
+        mock.service::<CounterImpl>(ROUTE)
+            .method::<io::Add>()
+            .panics("overflow")
+            .emits(CounterEvents::Added(1));
+        let program = env.mock_program(TARGET, mock);
+
+        let err = program
+            .service::<CounterImpl>(ROUTE)
+            .pending_call::<io::Add>((1,))
+            .await
+            .unwrap_err();
+        assert_eq!(err.userspace_panic_payload(), Some(b"overflow".as_slice()));
+
+        let err = program
+            .service::<CounterImpl>(ROUTE + 1)
+            .pending_call::<io::Add>((1,))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.userspace_panic_payload(),
+            Some(UNEXPECTED_CALL.as_bytes())
+        );
+    }
+}
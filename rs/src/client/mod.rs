@@ -14,6 +14,42 @@
 mod gtest_env;
 #[cfg(all(feature = "gtest", not(target_arch = "wasm32")))]
 pub use gtest_env::*;
+#[cfg(all(feature = "gtest", not(target_arch = "wasm32")))]
+mod gtest_events;
+#[cfg(all(feature = "gtest", not(target_arch = "wasm32")))]
+pub use gtest_events::{EventCheckpoint, RecordedEvent};
+#[cfg(all(feature = "gtest", not(target_arch = "wasm32")))]
+mod gtest_snapshot;
+#[cfg(all(feature = "gtest", not(target_arch = "wasm32")))]
+pub use gtest_snapshot::GtestSnapshot;
+#[cfg(all(feature = "gtest", not(target_arch = "wasm32")))]
+mod gtest_trace;
+#[cfg(all(feature = "gtest", not(target_arch = "wasm32")))]
+pub use gtest_trace::TracedMessage;
+#[cfg(all(feature = "gtest", not(target_arch = "wasm32")))]
+mod gtest_profile;
+#[cfg(all(feature = "gtest", not(target_arch = "wasm32")))]
+pub use gtest_profile::{CallProfile, CallSummary, ProfileReport, call_name};
+#[cfg(all(feature = "gtest", not(target_arch = "wasm32")))]
+mod gas_snapshot;
+#[cfg(all(feature = "gtest", not(target_arch = "wasm32")))]
+pub use gas_snapshot::{GasMismatch, GasSnapshot, GasSnapshotError, UPDATE_GAS_SNAPSHOTS_ENV};
+#[cfg(all(
+    feature = "gtest",
+    not(feature = "ethexe"),
+    not(target_arch = "wasm32")
+))]
+mod gtest_fault;
+#[cfg(all(
+    feature = "gtest",
+    not(feature = "ethexe"),
+    not(target_arch = "wasm32")
+))]
+pub use gtest_fault::{Fault, FaultProgram};
+#[cfg(all(feature = "mockall", not(target_arch = "wasm32")))]
+mod mock_program;
+#[cfg(all(feature = "mockall", not(target_arch = "wasm32")))]
+pub use mock_program::{MockMethod, MockProgram, MockService};
 
 #[cfg(all(feature = "gsdk", not(target_arch = "wasm32")))]
 mod gsdk_env;
@@ -23,6 +59,18 @@
 mod gstd_env;
 pub use gstd_env::{GstdEnv, GstdParams};
 
+#[cfg(feature = "ethexe")]
+mod ethabi;
+#[cfg(all(feature = "ethexe", not(target_arch = "wasm32")))]
+pub use ethabi::EthEventLog;
+#[cfg(feature = "ethexe")]
+pub use ethabi::{RouteEthabi, decode_ethabi_reply, encode_ethabi_call};
+
+#[cfg(all(feature = "gstd", feature = "std", not(target_arch = "wasm32")))]
+mod native_env;
+#[cfg(all(feature = "gstd", feature = "std", not(target_arch = "wasm32")))]
+pub use native_env::{NativeEnv, NativeError, NativeParams};
+
 pub(crate) const PENDING_CALL_INVALID_STATE: &str =
     "PendingCall polled after completion or invalid state";
 pub(crate) const PENDING_CTOR_INVALID_STATE: &str =
@@ -415,6 +463,36 @@ pub fn decode_with_header<T, M>(
     route_idx: u8,
     payload: impl AsRef<[u8]>,
 ) -> Result<T, parity_scale_codec::Error>
+where
+    T: Decode + 'static,
+    M: MethodMeta + Identifiable,
+{
+    decode_with_header_impl::<T, M>(route_idx, payload, true)
+}
+
+/// v2-specific: lenient counterpart of [`decode_with_header`] for clients generated
+/// with lenient decoding.
+///
+/// The `interface_id` of the header is not checked, so replies of an upgraded service
+/// with a different `InterfaceId` are accepted as long as the route and entry match.
+/// Appended fields of the reply are ignored and unknown variants of `lenient` enums
+/// are decoded into their `Unknown` variant.
+pub fn decode_with_header_lenient<T, M>(
+    route_idx: u8,
+    payload: impl AsRef<[u8]>,
+) -> Result<T, parity_scale_codec::Error>
+where
+    T: Decode + 'static,
+    M: MethodMeta + Identifiable,
+{
+    decode_with_header_impl::<T, M>(route_idx, payload, false)
+}
+
+fn decode_with_header_impl<T, M>(
+    route_idx: u8,
+    payload: impl AsRef<[u8]>,
+    check_interface_id: bool,
+) -> Result<T, parity_scale_codec::Error>
 where
     T: Decode + 'static,
     M: MethodMeta + Identifiable,
@@ -424,7 +502,7 @@ pub fn decode_with_header<T, M>(
         return Decode::decode(&mut value);
     }
     let header = SailsMessageHeader::decode(&mut value)?;
-    if header.interface_id() != M::INTERFACE_ID {
+    if check_interface_id && header.interface_id() != M::INTERFACE_ID {
         return Err("Invalid reply interface_id".into());
     }
     if header.route_id() != route_idx {
@@ -608,13 +686,42 @@ pub fn [<with_ $field>](self, $field: $ty) -> Self {
     };
 }
 
+/// v2 service/ctor IO struct.
+///
+/// Replies are decoded with [`decode_with_header`](crate::client::decode_with_header).
+/// The `lenient` form decodes them with
+/// [`decode_with_header_lenient`](crate::client::decode_with_header_lenient) instead.
 #[macro_export]
 macro_rules! io_struct_impl {
+    // lenient form: service method decoding replies with `decode_with_header_lenient`
+    (
+        lenient $name:ident ( $( $param:ident : $ty:ty ),* ) -> $reply:ty $( | $throws:ty )?, $entry_id:expr, $interface_id:expr
+    ) => {
+        $crate::io_struct_impl!(@impl decode_with_header_lenient; $name ( $( $param : $ty ),* ) -> $reply $( | $throws )?, $entry_id, $interface_id);
+    };
     // 3-arg form: service method with entry_id + interface_id (no throws)
     (
         $name:ident ( $( $param:ident : $ty:ty ),* ) -> $reply:ty, $entry_id:expr, $interface_id:expr
     ) => {
-        $crate::io_struct_impl!(@impl_base $name ( $( $param : $ty ),* ) -> $reply, $entry_id, $interface_id);
+        $crate::io_struct_impl!(@impl decode_with_header; $name ( $( $param : $ty ),* ) -> $reply, $entry_id, $interface_id);
+    };
+    // 3-arg form with throws
+    (
+        $name:ident ( $( $param:ident : $ty:ty ),* ) -> $reply:ty | $throws:ty, $entry_id:expr, $interface_id:expr
+    ) => {
+        $crate::io_struct_impl!(@impl decode_with_header; $name ( $( $param : $ty ),* ) -> $reply | $throws, $entry_id, $interface_id);
+    };
+    // 2-arg form: ctor method (InterfaceId::zero)
+    (
+        $name:ident ( $( $param:ident : $ty:ty ),* ) -> $reply:ty $( | $throws:ty )?, $entry_id:expr
+    ) => {
+        $crate::io_struct_impl!($name ( $( $param : $ty ),* ) -> $reply $( | $throws )?, $entry_id, $crate::meta::InterfaceId::zero());
+    };
+    // @impl: no throws, replies decoded with `$decode`
+    (
+        @impl $decode:ident; $name:ident ( $( $param:ident : $ty:ty ),* ) -> $reply:ty, $entry_id:expr, $interface_id:expr
+    ) => {
+        $crate::io_struct_impl!(@impl_base $decode; $name ( $( $param : $ty ),* ) -> $reply, $entry_id, $interface_id);
 
         impl $crate::client::ServiceCall for $name {
             type Route = $crate::client::RouteIdx;
@@ -640,7 +747,7 @@ fn decode_reply(
                 route: &$crate::client::RouteIdx,
                 payload: impl AsRef<[u8]>,
             ) -> Result<Self::Output, $crate::scale_codec::Error> {
-                $crate::client::decode_with_header::<Self::Reply, $name>(route.0, payload)
+                $crate::client::$decode::<Self::Reply, $name>(route.0, payload)
             }
 
             fn decode_error(
@@ -651,11 +758,11 @@ fn decode_error(
             }
         }
     };
-    // 3-arg form with throws
+    // @impl: with throws, replies and errors decoded with `$decode`
     (
-        $name:ident ( $( $param:ident : $ty:ty ),* ) -> $reply:ty | $throws:ty, $entry_id:expr, $interface_id:expr
+        @impl $decode:ident; $name:ident ( $( $param:ident : $ty:ty ),* ) -> $reply:ty | $throws:ty, $entry_id:expr, $interface_id:expr
     ) => {
-        $crate::io_struct_impl!(@impl_base $name ( $( $param : $ty ),* ) -> $reply, $entry_id, $interface_id);
+        $crate::io_struct_impl!(@impl_base $decode; $name ( $( $param : $ty ),* ) -> $reply, $entry_id, $interface_id);
 
         impl $crate::client::ServiceCall for $name {
             type Route = $crate::client::RouteIdx;
@@ -681,26 +788,20 @@ fn decode_reply(
                 route: &$crate::client::RouteIdx,
                 payload: impl AsRef<[u8]>,
             ) -> Result<Self::Output, $crate::scale_codec::Error> {
-                Ok(Ok($crate::client::decode_with_header::<Self::Reply, $name>(route.0, payload)?))
+                Ok(Ok($crate::client::$decode::<Self::Reply, $name>(route.0, payload)?))
             }
 
             fn decode_error(
                 route: &$crate::client::RouteIdx,
                 payload: impl AsRef<[u8]>,
             ) -> Result<Self::Output, $crate::scale_codec::Error> {
-                Ok(Err($crate::client::decode_with_header::<Self::Throws, $name>(route.0, payload)?))
+                Ok(Err($crate::client::$decode::<Self::Throws, $name>(route.0, payload)?))
             }
         }
     };
-    // 2-arg form: ctor method (InterfaceId::zero)
-    (
-        $name:ident ( $( $param:ident : $ty:ty ),* ) -> $reply:ty $( | $throws:ty )?, $entry_id:expr
-    ) => {
-        $crate::io_struct_impl!($name ( $( $param : $ty ),* ) -> $reply $( | $throws )?, $entry_id, $crate::meta::InterfaceId::zero());
-    };
     // @impl_base: struct + Identifiable + MethodMeta + convenience inherent methods
     (
-        @impl_base $name:ident ( $( $param:ident : $ty:ty ),* ) -> $reply:ty, $entry_id:expr, $interface_id:expr
+        @impl_base $decode:ident; $name:ident ( $( $param:ident : $ty:ty ),* ) -> $reply:ty, $entry_id:expr, $interface_id:expr
     ) => {
         pub struct $name(());
         impl $name {
@@ -712,7 +813,7 @@ pub fn encode_call(route_idx: u8, $( $param: $ty, )* ) -> Vec<u8> {
             }
             /// Decodes the reply checking against the correct Sails header.
             pub fn decode_reply(route_idx: u8, payload: impl AsRef<[u8]>) -> Result<$reply, $crate::scale_codec::Error> {
-                $crate::client::decode_with_header::<$reply, $name>(route_idx, payload)
+                $crate::client::$decode::<$reply, $name>(route_idx, payload)
             }
         }
         impl $crate::client::Identifiable for $name {
@@ -855,7 +956,7 @@ struct EventInput<'a> {
 #[cfg(not(target_arch = "wasm32"))]
 impl<'a> parity_scale_codec::Input for EventInput<'a> {
     fn remaining_len(&mut self) -> Result<Option<usize>, parity_scale_codec::Error> {
-        Ok(Some(1 + self.payload.len()))
+        Ok(Some(usize::from(self.first) + self.payload.len()))
     }
 
     fn read(&mut self, into: &mut [u8]) -> Result<(), parity_scale_codec::Error> {
@@ -906,13 +1007,42 @@ pub fn decode_event_v2<E>(
     route_idx: u8,
     payload: impl AsRef<[u8]>,
 ) -> Result<E, parity_scale_codec::Error>
+where
+    E: Decode + Identifiable,
+{
+    decode_event_v2_impl(route_idx, payload, true)
+}
+
+/// v2-specific: lenient counterpart of [`decode_event_v2`] for clients generated
+/// with lenient decoding.
+///
+/// The `interface_id` of the header is not checked, appended fields of the event payload
+/// are ignored and events unknown to the client are decoded into the `Unknown` variant
+/// of the `lenient` events enum.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn decode_event_v2_lenient<E>(
+    route_idx: u8,
+    payload: impl AsRef<[u8]>,
+) -> Result<E, parity_scale_codec::Error>
+where
+    E: Decode + Identifiable,
+{
+    decode_event_v2_impl(route_idx, payload, false)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn decode_event_v2_impl<E>(
+    route_idx: u8,
+    payload: impl AsRef<[u8]>,
+    check_interface_id: bool,
+) -> Result<E, parity_scale_codec::Error>
 where
     E: Decode + Identifiable,
 {
     let mut payload = payload.as_ref();
 
     let header = SailsMessageHeader::decode(&mut payload)?;
-    if header.interface_id() != E::INTERFACE_ID {
+    if check_interface_id && header.interface_id() != E::INTERFACE_ID {
         return Err("Invalid event interface_id".into());
     }
     if header.route_id() != route_idx {
@@ -963,6 +1093,7 @@ pub fn decode_event_v1<E>(
 /// Event codec parameterized by route header.
 /// `R = RouteIdx` → v2 SailsHeader-based decoding.
 /// `R = RouteName` → v1 SCALE-string variant-name decoding.
+/// `R = RouteEthabi` → `EthEvent` log decoding (`ethexe` only).
 #[cfg(not(target_arch = "wasm32"))]
 pub trait Event<R = RouteIdx>: Sized
 where
@@ -978,11 +1109,17 @@ fn decode_event(
 mod tests {
     use super::*;
     // Define Add with InterfaceId to test 3-arg macro (Service mode)
-    io_struct_impl!(Add (value: u32) -> u32, 0, InterfaceId::from_bytes_8([1, 2, 3, 4, 5, 6, 7, 8]));
+    io_struct_impl!(
+        Add(value: u32) -> u32,
+        0,
+        InterfaceId::from_bytes_8([1, 2, 3, 4, 5, 6, 7, 8])
+    );
     // Define Value with 2-arg macro (Ctor/Legacy mode)
     io_struct_impl!(Value () -> u32, 1);
     // Define Sub with `throws` type
     io_struct_impl!(Sub (value: u32) -> u32 | String, 0, InterfaceId::from_bytes_8([1, 2, 3, 4, 5, 6, 7, 8]));
+    // Define Mul with lenient reply decoding
+    io_struct_impl!(lenient Mul (value: u32) -> u32 | String, 1, InterfaceId::from_bytes_8([1, 2, 3, 4, 5, 6, 7, 8]));
 
     #[test]
     fn test_io_struct_impl() {
@@ -1070,9 +1207,85 @@ fn test_io_struct_impl_throws() {
         assert_eq!(decoded_error, Err(error_message));
     }
 
+    #[test]
+    fn test_io_struct_impl_lenient() {
+        let route_idx = RouteIdx(5u8);
+        // Reply of an upgraded service: another interface_id and an appended field
+        let upgraded_header = SailsMessageHeader::new(
+            crate::meta::Version::v1(),
+            crate::meta::HeaderLength::new(crate::meta::MINIMAL_HLEN).unwrap(),
+            InterfaceId::from_bytes_8([9; 8]),
+            route_idx.0,
+            1,
+        );
+        let mut reply = upgraded_header.to_bytes();
+        reply.extend_from_slice(&[42, 0, 0, 0, 0xff]);
+
+        assert_eq!(
+            <Mul as ServiceCall>::decode_reply(&route_idx, &reply).unwrap(),
+            Ok(42)
+        );
+        assert_eq!(Mul::decode_reply(route_idx.0, &reply).unwrap(), 42);
+        assert!(decode_with_header::<u32, Mul>(route_idx.0, &reply).is_err());
+
+        // Route and entry are still checked
+        assert!(Mul::decode_reply(4, &reply).is_err());
+        assert!(Sub::decode_reply(route_idx.0, &reply).is_err());
+
+        // Calls are encoded with the interface_id known to the client
+        assert_eq!(
+            Mul::encode_call(route_idx.0, 42)[4..12],
+            [1, 2, 3, 4, 5, 6, 7, 8]
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    #[test]
+    fn test_decode_event_v2_lenient() {
+        #[crate::sails_type(crate = crate, lenient)]
+        #[derive(PartialEq, Debug)]
+        enum CounterEvents {
+            #[codec(index = 0)]
+            Added(u32),
+        }
+
+        impl Identifiable for CounterEvents {
+            const INTERFACE_ID: InterfaceId = InterfaceId::from_bytes_8([1, 2, 3, 4, 5, 6, 7, 8]);
+        }
+
+        let event = |entry_id: u16, data: &[u8]| {
+            let mut payload = SailsMessageHeader::new(
+                crate::meta::Version::v1(),
+                crate::meta::HeaderLength::new(crate::meta::MINIMAL_HLEN).unwrap(),
+                InterfaceId::from_bytes_8([9; 8]),
+                2,
+                entry_id,
+            )
+            .to_bytes();
+            payload.extend_from_slice(data);
+            payload
+        };
+
+        // Known event with an appended field
+        let added = event(0, &[5, 0, 0, 0, 1]);
+        assert_eq!(
+            decode_event_v2_lenient::<CounterEvents>(2, &added).unwrap(),
+            CounterEvents::Added(5)
+        );
+        assert!(decode_event_v2::<CounterEvents>(2, &added).is_err());
+
+        // Event unknown to the client
+        let removed = event(1, &[7, 0, 0, 0]);
+        assert_eq!(
+            decode_event_v2_lenient::<CounterEvents>(2, &removed).unwrap(),
+            CounterEvents::Unknown(vec![1, 7, 0, 0, 0])
+        );
+        assert!(decode_event_v2_lenient::<CounterEvents>(3, &removed).is_err());
+    }
+
     #[test]
     fn test_io_struct_impl_v1() {
-        io_struct_impl_v1!(DoThis (value: u32) -> u32);
+        io_struct_impl_v1!(DoThis(value: u32) -> u32);
 
         // Encoding: SCALE("MyService") + SCALE("DoThis") + SCALE(42u32)
         let encoded = DoThis::encode_call("MyService", 42u32);
@@ -0,0 +1,283 @@
+//! Environment running programs natively, without compiling them to WASM.
+//!
+//! [`NativeEnv`] creates programs from the types annotated with `#[program]` and dispatches
+//! the messages sent by generated clients through the same Sails header routing as the
+//! WASM entry points. [`Syscall`] values of a message (`message_id`, `message_source`,
+//! `message_value`, `message_size` and `program_id`) are set before it is dispatched,
+//! others can be injected with the `Syscall::with_*` setters.
+//!
+//! Messages are handled synchronously and to completion: a program awaiting a reply of
+//! another program fails with [`NativeError::Pending`], as cross-program calls, events,
+//! gas and balances are not simulated. Unlike on-chain execution, state changes made
+//! before a panic are not reverted.
+
+use super::*;
+use crate::gstd::{NativeProgram, Syscall, native::take_panic_payload};
+use core::{cell::RefCell, task::Waker};
+use futures::{FutureExt as _, future::LocalBoxFuture};
+use hashbrown::HashMap;
+use std::{any::type_name, panic::AssertUnwindSafe, rc::Rc};
+
+type Handler = Rc<dyn Fn(Vec<u8>) -> LocalBoxFuture<'static, (Vec<u8>, ValueUnit)>>;
+type Constructor = Rc<dyn Fn(Vec<u8>) -> LocalBoxFuture<'static, Handler>>;
+
+#[derive(Clone, Debug, PartialEq, Eq, thiserror::Error)]
+pub enum NativeError {
+    #[error("code {0} is not submitted")]
+    CodeNotFound(CodeId),
+    #[error("program {0} is not found")]
+    ProgramNotFound(ActorId),
+    #[error("program {0} already exists")]
+    ProgramExists(ActorId),
+    #[error("program panicked: {}", String::from_utf8_lossy(.0))]
+    Panic(Vec<u8>),
+    #[error("program awaits a reply, cross-program calls are not supported")]
+    Pending,
+    #[error("failed to decode reply: {0}")]
+    Codec(parity_scale_codec::Error),
+}
+
+#[derive(Default)]
+struct NativeState {
+    codes: HashMap<CodeId, Constructor>,
+    programs: HashMap<ActorId, Handler>,
+    nonce: u64,
+}
+
+#[derive(Clone)]
+pub struct NativeEnv {
+    state: Rc<RefCell<NativeState>>,
+    actor_id: ActorId,
+}
+
+crate::params_struct_impl!(
+    NativeEnv,
+    NativeParams {
+        actor_id: ActorId,
+        value: ValueUnit,
+    }
+);
+
+impl NativeEnv {
+    /// Create new `NativeEnv` instance sending messages from `actor_id`.
+    pub fn new(actor_id: ActorId) -> Self {
+        Self {
+            state: Default::default(),
+            actor_id,
+        }
+    }
+
+    pub fn with_actor_id(self, actor_id: ActorId) -> Self {
+        Self { actor_id, ..self }
+    }
+
+    pub fn actor_id(&self) -> ActorId {
+        self.actor_id
+    }
+
+    /// Submits the program `P` as a code, returns the id to deploy it with.
+    ///
+    /// The code id is derived from the type name of `P`, so submitting it again
+    /// returns the same id.
+    pub fn submit_code<P: NativeProgram + 'static>(&self) -> CodeId {
+        let code_id = CodeId::new(hash(&[type_name::<P>().as_bytes()]));
+        let constructor: Constructor = Rc::new(|payload: Vec<u8>| {
+            async move {
+                let program = Rc::new(P::init(&payload).await);
+                let handler: Handler = Rc::new(move |payload: Vec<u8>| {
+                    let program = program.clone();
+                    async move { program.handle(&payload).await }.boxed_local()
+                });
+                handler
+            }
+            .boxed_local()
+        });
+        self.state.borrow_mut().codes.insert(code_id, constructor);
+        code_id
+    }
+
+    /// Creates a program from the code `code_id` with the encoded constructor call `payload`,
+    /// returns the program id and the reply payload.
+    pub fn create_program(
+        &self,
+        code_id: CodeId,
+        salt: impl AsRef<[u8]>,
+        payload: impl AsRef<[u8]>,
+        params: NativeParams,
+    ) -> Result<(ActorId, Vec<u8>), NativeError> {
+        let program_id = program_id_of(code_id, salt.as_ref());
+        let constructor = {
+            let state = self.state.borrow();
+            if state.programs.contains_key(&program_id) {
+                return Err(NativeError::ProgramExists(program_id));
+            }
+            state
+                .codes
+                .get(&code_id)
+                .cloned()
+                .ok_or(NativeError::CodeNotFound(code_id))?
+        };
+        let payload = payload.as_ref().to_vec();
+        self.set_message_context(program_id, &payload, &params);
+        let handler = execute(constructor(payload))?;
+        self.state.borrow_mut().programs.insert(program_id, handler);
+        Ok((program_id, Vec::new()))
+    }
+
+    /// Sends the encoded message `payload` to the program `destination`,
+    /// returns the reply payload.
+    pub fn send_for_reply(
+        &self,
+        destination: ActorId,
+        payload: impl AsRef<[u8]>,
+        params: NativeParams,
+    ) -> Result<Vec<u8>, NativeError> {
+        let handler = self
+            .state
+            .borrow()
+            .programs
+            .get(&destination)
+            .cloned()
+            .ok_or(NativeError::ProgramNotFound(destination))?;
+        let payload = payload.as_ref().to_vec();
+        self.set_message_context(destination, &payload, &params);
+        let (reply, _value) = execute(handler(payload))?;
+        Ok(reply)
+    }
+
+    fn set_message_context(&self, program_id: ActorId, payload: &[u8], params: &NativeParams) {
+        let nonce = {
+            let mut state = self.state.borrow_mut();
+            state.nonce += 1;
+            state.nonce
+        };
+        let source = params.actor_id.unwrap_or(self.actor_id);
+        Syscall::with_message_id(MessageId::new(hash(&[
+            &source.into_bytes(),
+            &nonce.to_le_bytes(),
+        ])));
+        Syscall::with_message_source(source);
+        Syscall::with_message_value(params.value.unwrap_or_default());
+        Syscall::with_message_size(payload.len());
+        Syscall::with_program_id(program_id);
+    }
+}
+
+fn program_id_of(code_id: CodeId, salt: &[u8]) -> ActorId {
+    ActorId::new(hash(&[&code_id.into_bytes(), salt]))
+}
+
+fn hash(parts: &[&[u8]]) -> [u8; 32] {
+    parts
+        .iter()
+        .fold(keccak_const::Keccak256::new(), |hasher, part| {
+            hasher.update(part)
+        })
+        .finalize()
+}
+
+/// Polls the message handling future once, it completes unless the program awaits a reply.
+fn execute<T>(mut future: LocalBoxFuture<'static, T>) -> Result<T, NativeError> {
+    let mut cx = Context::from_waker(Waker::noop());
+    _ = take_panic_payload();
+    match std::panic::catch_unwind(AssertUnwindSafe(|| future.as_mut().poll(&mut cx))) {
+        Ok(Poll::Ready(output)) => Ok(output),
+        Ok(Poll::Pending) => Err(NativeError::Pending),
+        Err(panic) => {
+            let payload = take_panic_payload()
+                .or_else(|| {
+                    panic
+                        .downcast_ref::<String>()
+                        .map(|s| s.clone().into_bytes())
+                })
+                .or_else(|| panic.downcast_ref::<&str>().map(|s| s.as_bytes().to_vec()))
+                .unwrap_or_default();
+            Err(NativeError::Panic(payload))
+        }
+    }
+}
+
+impl ReplyError for NativeError {
+    fn from_codec_error(err: parity_scale_codec::Error) -> Self {
+        NativeError::Codec(err)
+    }
+
+    fn userspace_panic_payload(&self) -> Option<&[u8]> {
+        match self {
+            NativeError::Panic(payload) => Some(payload),
+            _ => None,
+        }
+    }
+}
+
+impl GearEnv for NativeEnv {
+    type Params = NativeParams;
+    type Error = NativeError;
+    type MessageState = core::future::Ready<Result<Vec<u8>, NativeError>>;
+}
+
+impl EnvWithCtor for NativeEnv {}
+
+impl<T: ServiceCall> Future for PendingCall<T, NativeEnv> {
+    type Output = Result<T::Output, <NativeEnv as GearEnv>::Error>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.state.is_none() {
+            let (payload, params) = self.take_encoded_args_and_params();
+            let reply = self.env.send_for_reply(self.destination, payload, params);
+            self.state = Some(core::future::ready(reply));
+        }
+        let this = self.as_mut().project();
+        let reply = this
+            .state
+            .as_pin_mut()
+            .unwrap_or_else(|| panic!("{PENDING_CALL_INVALID_STATE}"));
+        reply
+            .poll(cx)
+            .map(|reply| decode_reply_or_throw::<T, _>(this.route, reply))
+    }
+}
+
+impl<A, T> Future for PendingCtor<A, T, NativeEnv>
+where
+    T: ServiceCall,
+    T::Output: PendingCtorOutput<A, NativeEnv>,
+{
+    type Output = Result<
+        <T::Output as PendingCtorOutput<A, NativeEnv>>::Output,
+        <NativeEnv as GearEnv>::Error,
+    >;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.state.is_none() {
+            let args = self
+                .args
+                .take()
+                .unwrap_or_else(|| panic!("{PENDING_CTOR_INVALID_STATE}"));
+            let payload = T::encode_call(&self.route, &args);
+            let params = self.params.take().unwrap_or_default();
+            let salt = self.salt.take().unwrap_or_default();
+            self.program_id = Some(program_id_of(self.code_id, &salt));
+            let reply = self
+                .env
+                .create_program(self.code_id, salt, payload, params)
+                .map(|(_, reply)| reply);
+            self.state = Some(core::future::ready(reply));
+        }
+        let route = self.route.clone();
+        let this = self.as_mut().project();
+        let reply = this
+            .state
+            .as_pin_mut()
+            .unwrap_or_else(|| panic!("{PENDING_CTOR_INVALID_STATE}"));
+        reply.poll(cx).map(|reply| {
+            decode_reply_or_throw::<T, _>(&route, reply).map(|output| {
+                let program_id = this
+                    .program_id
+                    .take()
+                    .unwrap_or_else(|| panic!("{PENDING_CTOR_INVALID_STATE}"));
+                output.map_result(this.env.clone(), program_id)
+            })
+        })
+    }
+}
@@ -1,5 +1,5 @@
 use crate::prelude::*;
-use alloy_sol_types::{SolType, SolValue, abi::TokenSeq};
+use alloy_sol_types::{Revert, SolError, SolType, SolValue, abi::TokenSeq};
 
 #[doc(hidden)]
 #[cfg(target_arch = "wasm32")]
@@ -152,6 +152,67 @@ fn encode_sequence<T: SolValue>(value: &T) -> Vec<u8>
     }
 }
 
+pub type EthErrorExpo = (
+    &'static str, // Error name
+    &'static str, // Error parameters types
+    [u8; 4],      // Selector
+);
+
+/// Trait for encoding errors of `ethabi` methods as Solidity reverts.
+///
+/// Each variant of the error enum is a Solidity custom error, e.g. `InsufficientBalance { needed: u128 }`
+/// is reverted as `error InsufficientBalance(uint128 needed)`. The revert data is the 4-byte selector
+/// of the error signature followed by the ABI-encoded fields, so EVM tooling can decode it with
+/// the interface generated by `sails-sol-gen`.
+///
+/// This trait is intended to be implemented with `#[derive(sails::EthError)]` for the error type
+/// of methods exported with `unwrap_result`. `String` errors are reverted as the built-in
+/// `Error(string)` and `()` errors without data.
+///
+/// # Examples
+///
+/// ```rust,ignore
+/// #[derive(sails::EthError)]
+/// pub enum TransferError {
+///     InsufficientBalance { needed: u128 },
+///     Paused,
+/// }
+///
+/// let data = TransferError::InsufficientBalance { needed: 42 }.revert_data();
+/// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` cannot be reverted as a Solidity error",
+    label = "the error type of an `ethabi` method",
+    note = "derive `sails::EthError` for the error enum, or use `String` as the error type"
+)]
+pub trait EthError {
+    /// The signature(s) associated with the error variants.
+    ///
+    /// The signature is the error name and its parameter types, e.g. `InsufficientBalance` and `(uint128)`,
+    /// the selector being the first 4 bytes of the keccak256 hash of them.
+    const SIGNATURES: &'static [EthErrorExpo];
+
+    /// Returns the revert data of the error, i.e. the selector followed by the ABI-encoded fields.
+    fn revert_data(&self) -> Vec<u8>;
+}
+
+impl EthError for String {
+    const SIGNATURES: &'static [EthErrorExpo] = &[("Error", "(string)", Revert::SELECTOR)];
+
+    fn revert_data(&self) -> Vec<u8> {
+        Revert::from(self.as_str()).abi_encode()
+    }
+}
+
+impl EthError for () {
+    const SIGNATURES: &'static [EthErrorExpo] = &[];
+
+    /// Reverts without data, the same as `revert()` in Solidity.
+    fn revert_data(&self) -> Vec<u8> {
+        Vec::new()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(unused_assignments)]
@@ -217,4 +278,12 @@ fn eth_event_data() {
         };
         assert_eq!(DATA, ev.data().as_slice());
     }
+
+    #[test]
+    fn eth_error_string_revert_data() {
+        let data = "not enough".to_string().revert_data();
+        assert_eq!(data[..4], Revert::SELECTOR);
+        let revert = Revert::abi_decode(&data).unwrap();
+        assert_eq!(revert.reason, "not enough");
+    }
 }
@@ -324,6 +324,50 @@ macro_rules! ok_or_throws {
     };
 }
 
+/// Unwraps a `Result` or reverts with its error encoded as a Solidity error.
+///
+/// The revert data of [`crate::gstd::EthError`] is sent through [`crate::gstd::Syscall::panic`]
+/// when it fits within [`crate::gstd::MAX_PANIC_PAYLOAD_SIZE`].
+#[cfg(feature = "ethexe")]
+#[macro_export]
+macro_rules! ok_or_revert {
+    ($res: expr) => {
+        match $res {
+            Ok(r) => r,
+            Err(e) => {
+                let encoded = $crate::gstd::EthError::revert_data(&e);
+                if encoded.len() <= $crate::gstd::MAX_PANIC_PAYLOAD_SIZE {
+                    $crate::gstd::Syscall::panic(&encoded)
+                } else {
+                    ::core::panic!("Error payload is too large to panic")
+                }
+            }
+        }
+    };
+}
+
+/// Emits the [`crate::gstd::native::NativeProgram`] implementation generated by the
+/// `#[program]` macro when `sails` is built with the `std` feature for a non-WASM target,
+/// and nothing otherwise.
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! native_program {
+    ($($item:tt)*) => {
+        $($item)*
+    };
+}
+
+/// Emits the [`crate::gstd::native::NativeProgram`] implementation generated by the
+/// `#[program]` macro when `sails` is built with the `std` feature for a non-WASM target,
+/// and nothing otherwise.
+#[cfg(not(all(feature = "std", not(target_arch = "wasm32"))))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! native_program {
+    ($($item:tt)*) => {};
+}
+
 #[cfg(test)]
 mod tests {
     use crate::prelude::*;
@@ -1,6 +1,6 @@
 #[doc(hidden)]
 #[cfg(feature = "ethexe")]
-pub use ethexe::{EthEvent, EthEventExpo};
+pub use ethexe::{EthError, EthErrorExpo, EthEvent, EthEventExpo};
 #[doc(hidden)]
 pub use events::{EventEmitter, SailsEvent};
 #[cfg(not(feature = "ethexe"))]
@@ -9,7 +9,12 @@
 #[doc(hidden)]
 pub use gstd::{async_init, async_main, handle_reply_with_hook, message_loop};
 pub use gstd::{debug, exec, msg};
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub use native::NativeProgram;
 use sails_idl_meta::{InterfaceId, MethodMeta};
+#[cfg(feature = "ethexe")]
+#[doc(hidden)]
+pub use sails_macros::EthError;
 #[doc(hidden)]
 pub use sails_macros::{event, export, program, service};
 pub use syscalls::Syscall;
@@ -31,6 +36,8 @@
 mod ethexe;
 mod events;
 mod macros;
+#[cfg(all(feature = "std", not(target_arch = "wasm32")))]
+pub mod native;
 pub mod services;
 mod syscalls;
 
@@ -0,0 +1,89 @@
+//! Native execution of programs, used by [`NativeEnv`](crate::client::NativeEnv) to run
+//! a `#[program]` without compiling it to WASM.
+//!
+//! The `#[program]` macro implements [`NativeProgram`] for the program type, dispatching
+//! messages through the same Sails header routing as the WASM entry points. The
+//! implementation is only emitted when `sails` is built with the `std` feature for a
+//! non-WASM target.
+
+use crate::prelude::*;
+use core::cell::RefCell;
+use std::thread_local;
+
+thread_local! {
+    static REPLY: RefCell<Option<(Vec<u8>, ValueUnit)>> = const { RefCell::new(None) };
+    static PANIC_PAYLOAD: RefCell<Option<Vec<u8>>> = const { RefCell::new(None) };
+}
+
+/// Program executed natively by dispatching encoded messages to its services.
+pub trait NativeProgram: Sized {
+    /// Creates the program from an encoded constructor call.
+    ///
+    /// Panics as the WASM `init` does if the payload does not match any constructor.
+    fn init(input: &[u8]) -> impl Future<Output = Self>;
+
+    /// Handles an encoded service call, returns the reply payload and value.
+    ///
+    /// The reply payload is empty if the call is not replied explicitly, as the auto reply
+    /// of a WASM program.
+    fn handle(&self, input: &[u8]) -> impl Future<Output = (Vec<u8>, ValueUnit)>;
+}
+
+/// Stores the reply of a service call, used as the result handler of service exposures.
+#[doc(hidden)]
+pub fn store_reply(payload: &[u8], value: ValueUnit) {
+    REPLY.with_borrow_mut(|reply| *reply = Some((payload.to_vec(), value)));
+}
+
+/// Takes the reply stored by [`store_reply`], if any.
+#[doc(hidden)]
+pub fn take_reply() -> Option<(Vec<u8>, ValueUnit)> {
+    REPLY.with_borrow_mut(Option::take)
+}
+
+pub(crate) fn store_panic_payload(payload: &[u8]) {
+    PANIC_PAYLOAD.with_borrow_mut(|stored| *stored = Some(payload.to_vec()));
+}
+
+/// Takes the payload passed to the last [`Syscall::panic`](super::Syscall::panic), if any.
+pub fn take_panic_payload() -> Option<Vec<u8>> {
+    PANIC_PAYLOAD.with_borrow_mut(Option::take)
+}
+
+/// Dispatches a service exposure natively, storing the encoded result with [`store_reply`].
+///
+/// The service exposure value must already be bound in the local scope, and the macro
+/// must be used within an async context.
+#[macro_export]
+macro_rules! native_route_dispatch {
+    (
+        $svc:ident : $service_ty:ty,
+        interface_id = $interface_id:expr,
+        entry_id = $entry_id:expr,
+        input = $input:expr $(,)?
+    ) => {{
+        let is_async = <<$service_ty as $crate::gstd::services::Service>::Exposure as $crate::gstd::services::Exposure>::check_asyncness(
+            $interface_id,
+            $entry_id,
+        )
+        .unwrap_or_else(|| $crate::gstd::unknown_input_panic("Unknown call", &[]));
+
+        if is_async {
+            $svc.try_handle_async(
+                $interface_id,
+                $entry_id,
+                $input,
+                $crate::gstd::native::store_reply,
+            )
+            .await
+        } else {
+            $svc.try_handle(
+                $interface_id,
+                $entry_id,
+                $input,
+                $crate::gstd::native::store_reply,
+            )
+        }
+        .unwrap_or_else(|| $crate::gstd::unknown_input_panic("Unknown request", &[]));
+    }};
+}
@@ -233,12 +233,19 @@ pub fn exit(inheritor_id: ActorId) -> ! {
         }
 
         pub fn panic(data: &[u8]) -> ! {
+            crate::gstd::native::store_panic_payload(data);
             if data.starts_with(b"GM") && data.len() >= 16 {
                 let mut payload = &data[16..];
                 if let Ok(s) = <String as parity_scale_codec::Decode>::decode(&mut payload) {
                     panic!("{}", s);
                 }
             }
+            #[cfg(feature = "ethexe")]
+            if let Ok(revert) =
+                <alloy_sol_types::Revert as alloy_sol_types::SolError>::abi_decode(data)
+            {
+                panic!("{}", revert.reason);
+            }
             panic!("{:?}", data);
         }
 
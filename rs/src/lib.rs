@@ -21,6 +21,8 @@
 pub use sails_idl_meta::{self as meta};
 pub use spin;
 
+#[cfg(feature = "gstd")]
+pub mod access_control;
 #[cfg(feature = "client-builder")]
 mod builder;
 #[cfg(any(
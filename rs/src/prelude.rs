@@ -62,7 +62,7 @@ pub mod ffi {
 #[cfg(feature = "ethexe")]
 pub use crate::address::Address;
 #[cfg(all(feature = "gstd", feature = "ethexe"))]
-pub use crate::gstd::EthEvent;
+pub use crate::gstd::{EthError, EthEvent};
 #[cfg(feature = "ethexe")]
 pub use crate::solidity;
 #[cfg(feature = "ethexe")]
@@ -120,6 +120,57 @@ const fn copy_slice(output: &mut [MaybeUninit<$T>], input: &[$T], offset: usize)
     }
 }
 
+/// Rust types with a Solidity counterpart in the interfaces generated by `sails-sol-gen`.
+///
+/// Parameters and results of `#[export(ethabi)]` methods are checked against this trait
+/// by the `service` macro, so a type which cannot be encoded by the Ethereum ABI dispatch
+/// or has no Solidity mapping is reported at the offending argument.
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` has no Solidity type mapping and cannot be used in an `ethabi` method",
+    label = "unsupported type of an `ethabi` method",
+    note = "supported types are `bool`, `u8`..`u128`, `i8`..`i128`, `String`, `ActorId`, `Address`, `alloy_primitives::Address`, `alloy_primitives::B256`, and `Vec`s and arrays of them",
+    note = "export the method with `#[export(scale)]` to keep it out of the Solidity interface"
+)]
+pub trait SolTypeMapping {}
+
+macro_rules! impl_sol_type_mapping {
+    ($($ty:ty),* $(,)?) => {
+        $(impl SolTypeMapping for $ty {})*
+    };
+}
+
+impl_sol_type_mapping!(
+    (),
+    bool,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    str,
+    String,
+    ActorId,
+    Address,
+    alloy_primitives::Address,
+    alloy_primitives::B256,
+);
+
+impl<T: SolTypeMapping> SolTypeMapping for Vec<T> {}
+
+impl<T: SolTypeMapping> SolTypeMapping for [T] {}
+
+impl<T: SolTypeMapping, const N: usize> SolTypeMapping for [T; N] {}
+
+impl<T: SolTypeMapping + ?Sized> SolTypeMapping for &T {}
+
+#[doc(hidden)]
+pub const fn assert_sol_type_mapping<T: SolTypeMapping + ?Sized>() {}
+
 pub struct ConstProgramMeta<T>(marker::PhantomData<T>);
 
 impl<T> ConstProgramMeta<T>
@@ -0,0 +1,122 @@
+//! Service managing the control values shared with [`Paused`](super::Paused) and
+//! [`Owned`](super::Owned) states.
+
+use crate::prelude::*;
+use core::{cell::Cell, fmt};
+
+/// Errors returned by [`AdminService`].
+#[sails_type(crate = crate)]
+#[cfg_attr(feature = "ethexe", derive(crate::EthError), sails_path(crate = crate))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AdminError {
+    /// The caller is not the owner.
+    NotOwner,
+}
+
+impl fmt::Display for AdminError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotOwner => f.write_str("caller is not the owner"),
+        }
+    }
+}
+
+impl core::error::Error for AdminError {}
+
+/// Events emitted by [`AdminService`].
+#[event(crate = crate)]
+#[sails_type(crate = crate)]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AdminEvents {
+    /// Writes were paused by `account`.
+    Paused {
+        #[indexed]
+        account: ActorId,
+    },
+    /// Writes were unpaused by `account`.
+    Unpaused {
+        #[indexed]
+        account: ActorId,
+    },
+    /// The ownership was transferred from `previous_owner` to `new_owner`.
+    OwnershipTransferred {
+        #[indexed]
+        previous_owner: ActorId,
+        #[indexed]
+        new_owner: ActorId,
+    },
+}
+
+/// Service exposing the owner and the pause flag, only the owner can change them.
+pub struct AdminService<'a> {
+    owner: &'a Cell<ActorId>,
+    paused: &'a Cell<bool>,
+}
+
+impl<'a> AdminService<'a> {
+    pub fn new(owner: &'a Cell<ActorId>, paused: &'a Cell<bool>) -> Self {
+        Self { owner, paused }
+    }
+
+    fn check_owner(&self) -> Result<(), AdminError> {
+        if Syscall::message_source() == self.owner.get() {
+            Ok(())
+        } else {
+            Err(AdminError::NotOwner)
+        }
+    }
+}
+
+#[service(crate = crate, events = AdminEvents)]
+impl AdminService<'_> {
+    /// Returns the owner
+    #[export]
+    pub fn owner(&self) -> ActorId {
+        self.owner.get()
+    }
+
+    /// Returns whether writes are paused
+    #[export]
+    pub fn paused(&self) -> bool {
+        self.paused.get()
+    }
+
+    /// Pauses writes, the caller must be the owner
+    #[export(unwrap_result)]
+    pub fn pause(&mut self) -> Result<(), AdminError> {
+        self.set_paused(true)
+    }
+
+    /// Unpauses writes, the caller must be the owner
+    #[export(unwrap_result)]
+    pub fn unpause(&mut self) -> Result<(), AdminError> {
+        self.set_paused(false)
+    }
+
+    /// Transfers the ownership to `new_owner`, the caller must be the owner
+    #[export(unwrap_result)]
+    pub fn transfer_ownership(&mut self, new_owner: ActorId) -> Result<(), AdminError> {
+        self.check_owner()?;
+        let previous_owner = self.owner.replace(new_owner);
+        self.emit_event(AdminEvents::OwnershipTransferred {
+            previous_owner,
+            new_owner,
+        })
+        .expect("Failed to emit event");
+        Ok(())
+    }
+
+    fn set_paused(&mut self, paused: bool) -> Result<(), AdminError> {
+        self.check_owner()?;
+        if self.paused.replace(paused) != paused {
+            let account = Syscall::message_source();
+            let event = if paused {
+                AdminEvents::Paused { account }
+            } else {
+                AdminEvents::Unpaused { account }
+            };
+            self.emit_event(event).expect("Failed to emit event");
+        }
+        Ok(())
+    }
+}
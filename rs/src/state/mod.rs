@@ -26,10 +26,24 @@
 //!
 //! Cross-cutting concerns (pausing, rate-limiting, metering) are expressed
 //! as wrappers that themselves implement `State`/`StateMut`, not as parallel
-//! trait hierarchies.
+//! trait hierarchies. [`Paused`], [`NonReentrant`] and, with the `gstd` feature,
+//! `Owned` and `RateLimited` are provided; `AdminService` exposes their
+//! pause flag and owner through the IDL.
 
 extern crate alloc;
 
+#[cfg(feature = "gstd")]
+pub use admin::{AdminError, AdminEvents, AdminService};
+pub use wrappers::{
+    NonReentrant, NonReentrantError, OwnedError, Paused, PausedError, RateLimit, RateLimitedError,
+};
+#[cfg(feature = "gstd")]
+pub use wrappers::{Owned, RateLimited};
+
+#[cfg(feature = "gstd")]
+mod admin;
+mod wrappers;
+
 use alloc::rc::Rc;
 use core::{
     cell::RefCell,
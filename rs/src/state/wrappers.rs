@@ -0,0 +1,389 @@
+//! Wrappers guarding access to a [`State`]/[`StateMut`] backend.
+//!
+//! Each wrapper borrows its control value (a flag, the owner, the rate limit) from the
+//! program, so several states and the [`AdminService`](super::AdminService) can share it.
+//! Wrappers compose: `Paused<Owned<&RefCell<T>>>` rejects writes while paused and writes
+//! by anyone but the owner.
+
+use super::{State, StateMut};
+use core::{
+    cell::Cell,
+    fmt,
+    ops::{Deref, DerefMut},
+};
+#[cfg(feature = "gstd")]
+use {crate::gstd::Syscall, gprimitives::ActorId};
+
+macro_rules! wrapper_error {
+    ($(#[$meta:meta])* $name:ident { $variant:ident => $message:literal }) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+        pub enum $name<E> {
+            #[doc = concat!("Access was rejected: ", $message, ".")]
+            $variant,
+            /// The wrapped state failed.
+            Inner(E),
+        }
+
+        impl<E: fmt::Display> fmt::Display for $name<E> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    Self::$variant => f.write_str($message),
+                    Self::Inner(e) => e.fmt(f),
+                }
+            }
+        }
+
+        impl<E: core::error::Error> core::error::Error for $name<E> {}
+    };
+}
+
+wrapper_error!(
+    /// Error of [`Paused`] state.
+    PausedError { Paused => "state is paused" }
+);
+
+wrapper_error!(
+    /// Error of [`Owned`] state.
+    OwnedError { NotOwner => "caller is not the owner" }
+);
+
+wrapper_error!(
+    /// Error of [`RateLimited`] state.
+    RateLimitedError { RateLimitExceeded => "rate limit exceeded" }
+);
+
+wrapper_error!(
+    /// Error of [`NonReentrant`] state.
+    NonReentrantError { Reentrant => "reentrant access" }
+);
+
+// ---- Paused ----
+
+/// Rejects writes while the shared `paused` flag is set, reads are always allowed.
+pub struct Paused<'a, S> {
+    inner: S,
+    paused: &'a Cell<bool>,
+}
+
+impl<'a, S> Paused<'a, S> {
+    pub fn new(inner: S, paused: &'a Cell<bool>) -> Self {
+        Self { inner, paused }
+    }
+
+    /// Returns whether writes are rejected.
+    pub fn is_paused(&self) -> bool {
+        self.paused.get()
+    }
+}
+
+impl<S: State> State for Paused<'_, S> {
+    type Item = S::Item;
+    type Error = PausedError<S::Error>;
+
+    fn read(&self) -> Result<impl Deref<Target = Self::Item>, Self::Error> {
+        self.inner.read().map_err(PausedError::Inner)
+    }
+}
+
+impl<S: StateMut> StateMut for Paused<'_, S> {
+    fn write(&mut self) -> Result<impl DerefMut<Target = Self::Item>, Self::Error> {
+        if self.paused.get() {
+            return Err(PausedError::Paused);
+        }
+        self.inner.write().map_err(PausedError::Inner)
+    }
+}
+
+// ---- Owned ----
+
+/// Rejects writes unless the message source is the shared `owner`, reads are always allowed.
+#[cfg(feature = "gstd")]
+pub struct Owned<'a, S> {
+    inner: S,
+    owner: &'a Cell<ActorId>,
+}
+
+#[cfg(feature = "gstd")]
+impl<'a, S> Owned<'a, S> {
+    pub fn new(inner: S, owner: &'a Cell<ActorId>) -> Self {
+        Self { inner, owner }
+    }
+
+    /// Returns the only account allowed to write.
+    pub fn owner(&self) -> ActorId {
+        self.owner.get()
+    }
+}
+
+#[cfg(feature = "gstd")]
+impl<S: State> State for Owned<'_, S> {
+    type Item = S::Item;
+    type Error = OwnedError<S::Error>;
+
+    fn read(&self) -> Result<impl Deref<Target = Self::Item>, Self::Error> {
+        self.inner.read().map_err(OwnedError::Inner)
+    }
+}
+
+#[cfg(feature = "gstd")]
+impl<S: StateMut> StateMut for Owned<'_, S> {
+    fn write(&mut self) -> Result<impl DerefMut<Target = Self::Item>, Self::Error> {
+        if Syscall::message_source() != self.owner.get() {
+            return Err(OwnedError::NotOwner);
+        }
+        self.inner.write().map_err(OwnedError::Inner)
+    }
+}
+
+// ---- RateLimited ----
+
+/// Number of writes allowed within a window of blocks.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimit {
+    max_writes: u32,
+    period: u32,
+    window_start: u32,
+    writes: u32,
+}
+
+impl RateLimit {
+    /// Allows `max_writes` writes every `period` blocks.
+    pub const fn new(max_writes: u32, period: u32) -> Self {
+        Self {
+            max_writes,
+            period,
+            window_start: 0,
+            writes: 0,
+        }
+    }
+
+    /// Counts a write at `block_height`, returns `false` if the limit of the window is reached.
+    pub fn try_acquire(&mut self, block_height: u32) -> bool {
+        if block_height.saturating_sub(self.window_start) >= self.period {
+            self.window_start = block_height;
+            self.writes = 0;
+        }
+        if self.writes >= self.max_writes {
+            return false;
+        }
+        self.writes += 1;
+        true
+    }
+
+    /// Returns the writes left in the window containing `block_height`.
+    pub fn remaining(&self, block_height: u32) -> u32 {
+        if block_height.saturating_sub(self.window_start) >= self.period {
+            self.max_writes
+        } else {
+            self.max_writes.saturating_sub(self.writes)
+        }
+    }
+}
+
+/// Rejects writes exceeding the shared [`RateLimit`] within the current block window,
+/// reads are always allowed.
+#[cfg(feature = "gstd")]
+pub struct RateLimited<'a, S> {
+    inner: S,
+    limit: &'a Cell<RateLimit>,
+}
+
+#[cfg(feature = "gstd")]
+impl<'a, S> RateLimited<'a, S> {
+    pub fn new(inner: S, limit: &'a Cell<RateLimit>) -> Self {
+        Self { inner, limit }
+    }
+
+    /// Returns the writes left in the current window.
+    pub fn remaining(&self) -> u32 {
+        self.limit.get().remaining(Syscall::block_height())
+    }
+}
+
+#[cfg(feature = "gstd")]
+impl<S: State> State for RateLimited<'_, S> {
+    type Item = S::Item;
+    type Error = RateLimitedError<S::Error>;
+
+    fn read(&self) -> Result<impl Deref<Target = Self::Item>, Self::Error> {
+        self.inner.read().map_err(RateLimitedError::Inner)
+    }
+}
+
+#[cfg(feature = "gstd")]
+impl<S: StateMut> StateMut for RateLimited<'_, S> {
+    fn write(&mut self) -> Result<impl DerefMut<Target = Self::Item>, Self::Error> {
+        let mut limit = self.limit.get();
+        if !limit.try_acquire(Syscall::block_height()) {
+            return Err(RateLimitedError::RateLimitExceeded);
+        }
+        let guard = self.inner.write().map_err(RateLimitedError::Inner)?;
+        // Only successful writes count against the limit.
+        self.limit.set(limit);
+        Ok(guard)
+    }
+}
+
+// ---- NonReentrant ----
+
+/// Rejects access while a write guard obtained through the shared `locked` flag is alive,
+/// e.g. when another message is handled while an async method holding the guard awaits.
+pub struct NonReentrant<'a, S> {
+    inner: S,
+    locked: &'a Cell<bool>,
+}
+
+impl<'a, S> NonReentrant<'a, S> {
+    pub fn new(inner: S, locked: &'a Cell<bool>) -> Self {
+        Self { inner, locked }
+    }
+}
+
+impl<S: State> State for NonReentrant<'_, S> {
+    type Item = S::Item;
+    type Error = NonReentrantError<S::Error>;
+
+    fn read(&self) -> Result<impl Deref<Target = Self::Item>, Self::Error> {
+        if self.locked.get() {
+            return Err(NonReentrantError::Reentrant);
+        }
+        self.inner.read().map_err(NonReentrantError::Inner)
+    }
+}
+
+impl<S: StateMut> StateMut for NonReentrant<'_, S> {
+    fn write(&mut self) -> Result<impl DerefMut<Target = Self::Item>, Self::Error> {
+        if self.locked.get() {
+            return Err(NonReentrantError::Reentrant);
+        }
+        let inner = self.inner.write().map_err(NonReentrantError::Inner)?;
+        self.locked.set(true);
+        Ok(LockGuard {
+            inner,
+            locked: self.locked,
+        })
+    }
+}
+
+/// Write guard releasing the lock of [`NonReentrant`] on drop.
+struct LockGuard<'a, G> {
+    inner: G,
+    locked: &'a Cell<bool>,
+}
+
+impl<G: Deref> Deref for LockGuard<'_, G> {
+    type Target = G::Target;
+
+    fn deref(&self) -> &Self::Target {
+        &self.inner
+    }
+}
+
+impl<G: DerefMut> DerefMut for LockGuard<'_, G> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.inner
+    }
+}
+
+impl<G> Drop for LockGuard<'_, G> {
+    fn drop(&mut self) {
+        self.locked.set(false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::RefCell;
+
+    #[test]
+    fn paused_blocks_writes_only() {
+        let cell = RefCell::new(0u32);
+        let paused = Cell::new(false);
+        let mut state = Paused::new(&cell, &paused);
+
+        *state.write().unwrap() = 5;
+        paused.set(true);
+        assert!(matches!(state.write(), Err(PausedError::Paused)));
+        assert_eq!(*state.read().unwrap(), 5);
+    }
+
+    #[test]
+    fn rate_limit_resets_every_period() {
+        let mut limit = RateLimit::new(2, 10);
+
+        assert!(limit.try_acquire(1));
+        assert!(limit.try_acquire(5));
+        assert!(!limit.try_acquire(9));
+        assert_eq!(limit.remaining(9), 0);
+        assert_eq!(limit.remaining(10), 2);
+        assert!(limit.try_acquire(10));
+        assert_eq!(limit.remaining(12), 1);
+    }
+
+    #[test]
+    fn non_reentrant_rejects_access_while_guard_is_alive() {
+        let cell = RefCell::new(0u32);
+        let locked = Cell::new(false);
+        let mut state = NonReentrant::new(&cell, &locked);
+        let mut other = NonReentrant::new(&cell, &locked);
+
+        let mut guard = state.write().unwrap();
+        *guard = 1;
+        assert!(matches!(other.write(), Err(NonReentrantError::Reentrant)));
+        assert!(matches!(other.read(), Err(NonReentrantError::Reentrant)));
+        drop(guard);
+
+        *other.write().unwrap() = 2;
+        assert_eq!(*state.read().unwrap(), 2);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn owned_blocks_writes_by_others() {
+        let cell = RefCell::new(0u32);
+        let owner = Cell::new(ActorId::from(1));
+        let mut state = Owned::new(&cell, &owner);
+
+        Syscall::with_message_source(ActorId::from(2));
+        assert!(matches!(state.write(), Err(OwnedError::NotOwner)));
+
+        Syscall::with_message_source(ActorId::from(1));
+        *state.write().unwrap() = 3;
+        assert_eq!(*state.read().unwrap(), 3);
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn rate_limited_counts_writes() {
+        let cell = RefCell::new(0u32);
+        let limit = Cell::new(RateLimit::new(1, 100));
+        let mut state = RateLimited::new(&cell, &limit);
+
+        Syscall::with_block_height(200);
+        *state.write().unwrap() = 1;
+        assert_eq!(state.remaining(), 0);
+        assert!(matches!(
+            state.write(),
+            Err(RateLimitedError::RateLimitExceeded)
+        ));
+
+        Syscall::with_block_height(300);
+        *state.write().unwrap() = 2;
+        assert_eq!(*state.read().unwrap(), 2);
+    }
+
+    #[test]
+    fn wrappers_compose() {
+        let cell = RefCell::new(0u32);
+        let paused = Cell::new(false);
+        let locked = Cell::new(false);
+        let mut state = Paused::new(NonReentrant::new(&cell, &locked), &paused);
+
+        *state.write().unwrap() = 1;
+        paused.set(true);
+        assert!(matches!(state.write(), Err(PausedError::Paused)));
+        assert_eq!(*state.read().unwrap(), 1);
+    }
+}
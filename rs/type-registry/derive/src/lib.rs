@@ -160,6 +160,21 @@ fn resolve_registry_path(input: &DeriveInput) -> syn::Result<TokenStream2> {
     Ok(quote!(::sails_type_registry))
 }
 
+/// Returns `true` for variants marked with `#[type_info(skip)]`.
+fn is_skipped(attrs: &[syn::Attribute]) -> bool {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("type_info"))
+        .any(|attr| {
+            let mut skip = false;
+            let _ = attr.parse_nested_meta(|meta| {
+                skip |= meta.path.is_ident("skip");
+                Ok(())
+            });
+            skip
+        })
+}
+
 fn extract_docs(attrs: &[syn::Attribute]) -> Vec<TokenStream2> {
     attrs
         .iter()
@@ -256,6 +271,7 @@ fn build_dep_hook(data: &Data, registry: &TokenStream2) -> syn::Result<TokenStre
         Data::Enum(e) => e
             .variants
             .iter()
+            .filter(|v| !is_skipped(&v.attrs))
             .flat_map(|v| {
                 v.fields.iter().map(|f| {
                     let ty = &f.ty;
@@ -625,6 +641,7 @@ fn type_def_uses_registry(data: &Data, lower: &LowerContext<'_>) -> bool {
         Data::Enum(e) => e
             .variants
             .iter()
+            .filter(|v| !is_skipped(&v.attrs))
             .any(|v| v.fields.iter().any(|f| field_uses_registry(&f.ty, lower))),
         Data::Union(_) => false,
     }
@@ -717,6 +734,7 @@ fn build_type_def_body(data: &Data, ctx: &TypeDefContext<'_>) -> syn::Result<Tok
             let variant_blocks = e
                 .variants
                 .iter()
+                .filter(|v| !is_skipped(&v.attrs))
                 .map(|v| {
                     let vname = v.ident.to_string();
                     let vdocs = extract_docs(&v.attrs);
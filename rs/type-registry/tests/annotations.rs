@@ -140,3 +140,24 @@ struct Versioned {
         [("version".to_string(), Some("2".to_string()))]
     );
 }
+
+#[test]
+fn derive_omits_skipped_variants() {
+    #[allow(dead_code)]
+    #[derive(TypeInfo)]
+    enum WithHidden {
+        _Known(u32),
+        #[type_info(skip)]
+        _Hidden(alloc::vec::Vec<u8>),
+    }
+
+    let mut registry = Registry::new();
+    let type_ref = registry.register_type::<WithHidden>().unwrap();
+    let ty = registry.get_type(type_ref).unwrap();
+
+    let TypeDef::Enum(enum_def) = &ty.def else {
+        panic!("Expected Enum, got {:?}", ty.def);
+    };
+    let names: alloc::vec::Vec<_> = enum_def.variants.iter().map(|v| &v.name).collect();
+    assert_eq!(names, ["_Known"]);
+}
@@ -0,0 +1,59 @@
+use crate::{VftData, VftError};
+use sails::{cell::RefCell, prelude::*};
+
+/// Events emitted by [`VftBurnableService`].
+#[event]
+#[sails_type]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VftBurnableEvents {
+    /// `value` tokens of `from` were destroyed.
+    Burned {
+        #[indexed]
+        from: ActorId,
+        value: U256,
+    },
+}
+
+/// Fungible token extension burning tokens of the caller or tokens it is allowed to spend.
+pub struct VftBurnableService<S: StateMut<Item = VftData, Error: Into<VftError>> = RefCell<VftData>>
+{
+    data: S,
+}
+
+impl<S: StateMut<Item = VftData, Error: Into<VftError>>> VftBurnableService<S> {
+    pub fn new(data: S) -> Self {
+        Self { data }
+    }
+}
+
+#[service(events = VftBurnableEvents)]
+impl<S: StateMut<Item = VftData, Error: Into<VftError>>> VftBurnableService<S> {
+    /// Destroys `value` tokens of the caller, returns `false` if nothing is destroyed
+    #[export(unwrap_result)]
+    pub fn burn(&mut self, value: U256) -> Result<bool, VftError> {
+        let from = Syscall::message_source();
+        let burned = self.data.write().map_err(Into::into)?.burn(from, value)?;
+        self.burned(from, value, burned)
+    }
+
+    /// Destroys `value` tokens of `from` spending the allowance of the caller,
+    /// returns `false` if nothing is destroyed
+    #[export(unwrap_result)]
+    pub fn burn_from(&mut self, from: ActorId, value: U256) -> Result<bool, VftError> {
+        let spender = Syscall::message_source();
+        let burned = self
+            .data
+            .write()
+            .map_err(Into::into)?
+            .burn_from(spender, from, value)?;
+        self.burned(from, value, burned)
+    }
+
+    fn burned(&mut self, from: ActorId, value: U256, burned: bool) -> Result<bool, VftError> {
+        if burned {
+            self.emit_event(VftBurnableEvents::Burned { from, value })
+                .expect("Failed to emit event");
+        }
+        Ok(burned)
+    }
+}
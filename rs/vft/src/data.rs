@@ -0,0 +1,244 @@
+use sails::prelude::{collections::BTreeMap, *};
+
+/// Errors of fungible token operations.
+#[sails_type]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VftError {
+    /// The balance of the account is lower than the requested amount.
+    InsufficientBalance,
+    /// The allowance of the spender is lower than the requested amount.
+    InsufficientAllowance,
+    /// The total supply would overflow.
+    Overflow,
+    /// The token is paused.
+    Paused,
+}
+
+impl fmt::Display for VftError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::InsufficientBalance => "insufficient balance",
+            Self::InsufficientAllowance => "insufficient allowance",
+            Self::Overflow => "total supply overflow",
+            Self::Paused => "token is paused",
+        })
+    }
+}
+
+impl core::error::Error for VftError {}
+
+impl From<Infallible> for VftError {
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
+}
+
+impl<E: Into<VftError>> From<sails::state::PausedError<E>> for VftError {
+    fn from(value: sails::state::PausedError<E>) -> Self {
+        match value {
+            sails::state::PausedError::Paused => Self::Paused,
+            sails::state::PausedError::Inner(err) => err.into(),
+        }
+    }
+}
+
+/// Name, symbol and decimals of a token.
+#[sails_type]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Metadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+/// Balances, allowances and metadata of a fungible token.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VftData {
+    metadata: Metadata,
+    balances: BTreeMap<ActorId, U256>,
+    allowances: BTreeMap<(ActorId, ActorId), U256>,
+    total_supply: U256,
+}
+
+impl VftData {
+    pub fn new(metadata: Metadata) -> Self {
+        Self {
+            metadata,
+            ..Default::default()
+        }
+    }
+
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    pub fn total_supply(&self) -> U256 {
+        self.total_supply
+    }
+
+    pub fn balance_of(&self, account: ActorId) -> U256 {
+        self.balances.get(&account).copied().unwrap_or_default()
+    }
+
+    pub fn allowance(&self, owner: ActorId, spender: ActorId) -> U256 {
+        self.allowances
+            .get(&(owner, spender))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Sets the allowance of `spender` over the tokens of `owner`, returns `false` if it is unchanged.
+    pub fn approve(&mut self, owner: ActorId, spender: ActorId, value: U256) -> bool {
+        if self.allowance(owner, spender) == value {
+            return false;
+        }
+        if value.is_zero() {
+            self.allowances.remove(&(owner, spender));
+        } else {
+            self.allowances.insert((owner, spender), value);
+        }
+        true
+    }
+
+    /// Moves `value` tokens from `from` to `to`, returns `false` if nothing is moved.
+    pub fn transfer(&mut self, from: ActorId, to: ActorId, value: U256) -> Result<bool, VftError> {
+        let from_balance = self.balance_of(from);
+        if from_balance < value {
+            return Err(VftError::InsufficientBalance);
+        }
+        if from == to || value.is_zero() {
+            return Ok(false);
+        }
+        self.set_balance(from, from_balance - value);
+        // Cannot overflow, the sum of all balances is the total supply.
+        self.set_balance(to, self.balance_of(to) + value);
+        Ok(true)
+    }
+
+    /// Moves `value` tokens from `from` to `to` spending the allowance of `spender`,
+    /// returns `false` if nothing is moved.
+    pub fn transfer_from(
+        &mut self,
+        spender: ActorId,
+        from: ActorId,
+        to: ActorId,
+        value: U256,
+    ) -> Result<bool, VftError> {
+        if spender == from {
+            return self.transfer(from, to, value);
+        }
+        let allowance = self.allowance(from, spender);
+        if allowance < value {
+            return Err(VftError::InsufficientAllowance);
+        }
+        let transferred = self.transfer(from, to, value)?;
+        self.approve(from, spender, allowance - value);
+        Ok(transferred)
+    }
+
+    /// Creates `value` tokens owned by `to`, returns `false` if nothing is created.
+    pub fn mint(&mut self, to: ActorId, value: U256) -> Result<bool, VftError> {
+        if value.is_zero() {
+            return Ok(false);
+        }
+        self.total_supply = self
+            .total_supply
+            .checked_add(value)
+            .ok_or(VftError::Overflow)?;
+        self.set_balance(to, self.balance_of(to) + value);
+        Ok(true)
+    }
+
+    /// Destroys `value` tokens owned by `from`, returns `false` if nothing is destroyed.
+    pub fn burn(&mut self, from: ActorId, value: U256) -> Result<bool, VftError> {
+        let balance = self.balance_of(from);
+        if balance < value {
+            return Err(VftError::InsufficientBalance);
+        }
+        if value.is_zero() {
+            return Ok(false);
+        }
+        self.set_balance(from, balance - value);
+        self.total_supply -= value;
+        Ok(true)
+    }
+
+    /// Destroys `value` tokens of `from` spending the allowance of `spender`,
+    /// returns `false` if nothing is destroyed.
+    pub fn burn_from(
+        &mut self,
+        spender: ActorId,
+        from: ActorId,
+        value: U256,
+    ) -> Result<bool, VftError> {
+        if spender == from {
+            return self.burn(from, value);
+        }
+        let allowance = self.allowance(from, spender);
+        if allowance < value {
+            return Err(VftError::InsufficientAllowance);
+        }
+        let burned = self.burn(from, value)?;
+        self.approve(from, spender, allowance - value);
+        Ok(burned)
+    }
+
+    fn set_balance(&mut self, account: ActorId, balance: U256) {
+        if balance.is_zero() {
+            self.balances.remove(&account);
+        } else {
+            self.balances.insert(account, balance);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: ActorId = ActorId::new([1; 32]);
+    const BOB: ActorId = ActorId::new([2; 32]);
+    const CAROL: ActorId = ActorId::new([3; 32]);
+
+    #[test]
+    fn mint_transfer_and_burn() {
+        let mut data = VftData::default();
+
+        assert_eq!(data.mint(ALICE, 100.into()), Ok(true));
+        assert_eq!(data.transfer(ALICE, BOB, 30.into()), Ok(true));
+        assert_eq!(
+            data.transfer(BOB, ALICE, 31.into()),
+            Err(VftError::InsufficientBalance)
+        );
+        assert_eq!(data.burn(ALICE, 70.into()), Ok(true));
+
+        assert_eq!(data.balance_of(ALICE), U256::zero());
+        assert_eq!(data.balance_of(BOB), 30.into());
+        assert_eq!(data.total_supply(), 30.into());
+    }
+
+    #[test]
+    fn transfer_from_spends_allowance() {
+        let mut data = VftData::default();
+        data.mint(ALICE, 100.into()).unwrap();
+
+        assert!(data.approve(ALICE, BOB, 50.into()));
+        assert!(!data.approve(ALICE, BOB, 50.into()));
+        assert_eq!(data.transfer_from(BOB, ALICE, CAROL, 20.into()), Ok(true));
+        assert_eq!(data.allowance(ALICE, BOB), 30.into());
+        assert_eq!(
+            data.transfer_from(BOB, ALICE, CAROL, 31.into()),
+            Err(VftError::InsufficientAllowance)
+        );
+        assert_eq!(data.balance_of(CAROL), 20.into());
+    }
+
+    #[test]
+    fn mint_fails_on_overflow() {
+        let mut data = VftData::default();
+        data.mint(ALICE, U256::MAX).unwrap();
+
+        assert_eq!(data.mint(BOB, 1.into()), Err(VftError::Overflow));
+        assert_eq!(data.balance_of(BOB), U256::zero());
+    }
+}
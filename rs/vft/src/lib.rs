@@ -0,0 +1,47 @@
+//! Fungible token services built with Sails.
+//!
+//! [`VftService`] implements balances, allowances and metadata. The extension services
+//! add minting ([`VftMintableService`]), burning ([`VftBurnableService`]) and pausing
+//! ([`VftPausableService`]) and are meant to be composed via `extends`, so programs
+//! sharing them expose the same interfaces to clients.
+//!
+//! All services are generic over the state handle of [`VftData`]; a pausable token
+//! passes `sails::state::Paused<&RefCell<VftData>>` so that the writes of all services
+//! are rejected while it is paused.
+//!
+//! ```rust,ignore
+//! use sails::{cell::{Cell, RefCell}, prelude::*, access_control::RolesData, state::Paused};
+//! use sails_vft::*;
+//!
+//! type TokenState<'a> = Paused<'a, &'a RefCell<VftData>>;
+//!
+//! pub struct TokenService<'a> {
+//!     data: &'a RefCell<VftData>,
+//!     roles: &'a RefCell<RolesData>,
+//!     paused: &'a Cell<bool>,
+//! }
+//!
+//! // `From<TokenService>` impls for every extended service
+//!
+//! #[service(extends = [
+//!     VftService<TokenState<'a>>,
+//!     VftMintableService<TokenState<'a>, &'a RefCell<RolesData>>,
+//!     VftBurnableService<TokenState<'a>>,
+//!     VftPausableService<'a, &'a RefCell<RolesData>>,
+//! ])]
+//! impl<'a> TokenService<'a> {}
+//! ```
+
+#![no_std]
+
+pub use burnable::{VftBurnableEvents, VftBurnableService};
+pub use data::{Metadata, VftData, VftError};
+pub use mintable::{MINTER_ROLE, VftMintableEvents, VftMintableService};
+pub use pausable::{PAUSER_ROLE, VftPausableEvents, VftPausableService};
+pub use vft::{VftEvents, VftService};
+
+mod burnable;
+mod data;
+mod mintable;
+mod pausable;
+mod vft;
@@ -0,0 +1,69 @@
+use crate::{VftData, VftError};
+use sails::{
+    access_control::{RoleGuard, RolesData},
+    cell::RefCell,
+    prelude::*,
+};
+
+/// The role allowed to mint tokens.
+pub const MINTER_ROLE: &str = "MINTER";
+
+/// Events emitted by [`VftMintableService`].
+#[event]
+#[sails_type]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VftMintableEvents {
+    /// `value` tokens were created for `to`.
+    Minted {
+        #[indexed]
+        to: ActorId,
+        value: U256,
+    },
+}
+
+/// Fungible token extension minting tokens, the caller must have [`MINTER_ROLE`].
+pub struct VftMintableService<
+    S: StateMut<Item = VftData, Error: Into<VftError>> = RefCell<VftData>,
+    R: State<Item = RolesData, Error = Infallible> = RefCell<RolesData>,
+> {
+    data: S,
+    roles: R,
+}
+
+impl<S, R> VftMintableService<S, R>
+where
+    S: StateMut<Item = VftData, Error: Into<VftError>>,
+    R: State<Item = RolesData, Error = Infallible>,
+{
+    pub fn new(data: S, roles: R) -> Self {
+        Self { data, roles }
+    }
+}
+
+impl<S, R> RoleGuard for VftMintableService<S, R>
+where
+    S: StateMut<Item = VftData, Error: Into<VftError>>,
+    R: State<Item = RolesData, Error = Infallible>,
+{
+    fn has_role(&self, role: &str, account: ActorId) -> bool {
+        self.roles.get().has_role(role, account)
+    }
+}
+
+#[service(events = VftMintableEvents)]
+impl<S, R> VftMintableService<S, R>
+where
+    S: StateMut<Item = VftData, Error: Into<VftError>>,
+    R: State<Item = RolesData, Error = Infallible>,
+{
+    /// Creates `value` tokens for `to`, returns `false` if nothing is created
+    #[export(requires_role = "MINTER", unwrap_result)]
+    pub fn mint(&mut self, to: ActorId, value: U256) -> Result<bool, VftError> {
+        let minted = self.data.write().map_err(Into::into)?.mint(to, value)?;
+        if minted {
+            self.emit_event(VftMintableEvents::Minted { to, value })
+                .expect("Failed to emit event");
+        }
+        Ok(minted)
+    }
+}
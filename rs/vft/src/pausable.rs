@@ -0,0 +1,81 @@
+use sails::{
+    access_control::{RoleGuard, RolesData},
+    cell::{Cell, RefCell},
+    prelude::*,
+};
+
+/// The role allowed to pause and unpause the token.
+pub const PAUSER_ROLE: &str = "PAUSER";
+
+/// Events emitted by [`VftPausableService`].
+#[event]
+#[sails_type]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VftPausableEvents {
+    /// The token was paused by `account`.
+    Paused {
+        #[indexed]
+        account: ActorId,
+    },
+    /// The token was unpaused by `account`.
+    Unpaused {
+        #[indexed]
+        account: ActorId,
+    },
+}
+
+/// Fungible token extension managing the pause flag shared with the
+/// `sails::state::Paused` state of the token, the caller must have [`PAUSER_ROLE`].
+pub struct VftPausableService<
+    'a,
+    R: State<Item = RolesData, Error = Infallible> = RefCell<RolesData>,
+> {
+    paused: &'a Cell<bool>,
+    roles: R,
+}
+
+impl<'a, R: State<Item = RolesData, Error = Infallible>> VftPausableService<'a, R> {
+    pub fn new(paused: &'a Cell<bool>, roles: R) -> Self {
+        Self { paused, roles }
+    }
+}
+
+impl<R: State<Item = RolesData, Error = Infallible>> RoleGuard for VftPausableService<'_, R> {
+    fn has_role(&self, role: &str, account: ActorId) -> bool {
+        self.roles.get().has_role(role, account)
+    }
+}
+
+#[service(events = VftPausableEvents)]
+impl<'a, R: State<Item = RolesData, Error = Infallible>> VftPausableService<'a, R> {
+    #[export]
+    pub fn is_paused(&self) -> bool {
+        self.paused.get()
+    }
+
+    /// Pauses the token, returns `false` if it is already paused
+    #[export(requires_role = "PAUSER")]
+    pub fn pause(&mut self) -> bool {
+        self.set_paused(true)
+    }
+
+    /// Unpauses the token, returns `false` if it is not paused
+    #[export(requires_role = "PAUSER")]
+    pub fn unpause(&mut self) -> bool {
+        self.set_paused(false)
+    }
+
+    fn set_paused(&mut self, paused: bool) -> bool {
+        if self.paused.replace(paused) == paused {
+            return false;
+        }
+        let account = Syscall::message_source();
+        let event = if paused {
+            VftPausableEvents::Paused { account }
+        } else {
+            VftPausableEvents::Unpaused { account }
+        };
+        self.emit_event(event).expect("Failed to emit event");
+        true
+    }
+}
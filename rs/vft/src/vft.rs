@@ -0,0 +1,126 @@
+use crate::{VftData, VftError};
+use sails::{cell::RefCell, prelude::*};
+
+/// Events emitted by [`VftService`].
+#[event]
+#[sails_type]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VftEvents {
+    /// The allowance of `spender` over the tokens of `owner` was set to `value`.
+    Approval {
+        #[indexed]
+        owner: ActorId,
+        #[indexed]
+        spender: ActorId,
+        value: U256,
+    },
+    /// `value` tokens were moved from `from` to `to`.
+    Transfer {
+        #[indexed]
+        from: ActorId,
+        #[indexed]
+        to: ActorId,
+        value: U256,
+    },
+}
+
+/// Fungible token service: balances, allowances and metadata.
+pub struct VftService<S: StateMut<Item = VftData, Error: Into<VftError>> = RefCell<VftData>> {
+    data: S,
+}
+
+impl<S: StateMut<Item = VftData, Error: Into<VftError>>> VftService<S> {
+    pub fn new(data: S) -> Self {
+        Self { data }
+    }
+
+    fn data(&self) -> impl ops::Deref<Target = VftData> + '_ {
+        self.data
+            .read()
+            .unwrap_or_else(|err| panic!("{}", err.into()))
+    }
+
+    fn data_mut(&mut self) -> Result<impl ops::DerefMut<Target = VftData> + '_, VftError> {
+        self.data.write().map_err(Into::into)
+    }
+}
+
+#[service(events = VftEvents)]
+impl<S: StateMut<Item = VftData, Error: Into<VftError>>> VftService<S> {
+    #[export]
+    pub fn name(&self) -> String {
+        self.data().metadata().name.clone()
+    }
+
+    #[export]
+    pub fn symbol(&self) -> String {
+        self.data().metadata().symbol.clone()
+    }
+
+    #[export]
+    pub fn decimals(&self) -> u8 {
+        self.data().metadata().decimals
+    }
+
+    #[export]
+    pub fn total_supply(&self) -> U256 {
+        self.data().total_supply()
+    }
+
+    #[export]
+    pub fn balance_of(&self, account: ActorId) -> U256 {
+        self.data().balance_of(account)
+    }
+
+    #[export]
+    pub fn allowance(&self, owner: ActorId, spender: ActorId) -> U256 {
+        self.data().allowance(owner, spender)
+    }
+
+    /// Sets the allowance of `spender` over the tokens of the caller,
+    /// returns `false` if it is unchanged
+    #[export(unwrap_result)]
+    pub fn approve(&mut self, spender: ActorId, value: U256) -> Result<bool, VftError> {
+        let owner = Syscall::message_source();
+        let approved = self.data_mut()?.approve(owner, spender, value);
+        if approved {
+            self.emit_event(VftEvents::Approval {
+                owner,
+                spender,
+                value,
+            })
+            .expect("Failed to emit event");
+        }
+        Ok(approved)
+    }
+
+    /// Moves `value` tokens of the caller to `to`, returns `false` if nothing is moved
+    #[export(unwrap_result)]
+    pub fn transfer(&mut self, to: ActorId, value: U256) -> Result<bool, VftError> {
+        let from = Syscall::message_source();
+        let transferred = self.data_mut()?.transfer(from, to, value)?;
+        if transferred {
+            self.emit_event(VftEvents::Transfer { from, to, value })
+                .expect("Failed to emit event");
+        }
+        Ok(transferred)
+    }
+
+    /// Moves `value` tokens of `from` to `to` spending the allowance of the caller,
+    /// returns `false` if nothing is moved
+    #[export(unwrap_result)]
+    pub fn transfer_from(
+        &mut self,
+        from: ActorId,
+        to: ActorId,
+        value: U256,
+    ) -> Result<bool, VftError> {
+        let spender = Syscall::message_source();
+        let transferred = self.data_mut()?.transfer_from(spender, from, to, value)?;
+        if transferred {
+            self.emit_event(VftEvents::Transfer { from, to, value })
+                .expect("Failed to emit event");
+        }
+        Ok(transferred)
+    }
+}
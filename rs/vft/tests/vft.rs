@@ -0,0 +1,194 @@
+use sails::{
+    access_control::RolesData,
+    cell::{Cell, RefCell},
+    gstd::{Syscall, services::Service},
+    prelude::*,
+    state::Paused,
+};
+use sails_vft::*;
+
+const ADMIN: ActorId = ActorId::new([1; 32]);
+const ALICE: ActorId = ActorId::new([2; 32]);
+const BOB: ActorId = ActorId::new([3; 32]);
+
+type TokenState<'a> = Paused<'a, &'a RefCell<VftData>>;
+
+pub struct TokenService<'a> {
+    data: &'a RefCell<VftData>,
+    roles: &'a RefCell<RolesData>,
+    paused: &'a Cell<bool>,
+}
+
+impl<'a> TokenService<'a> {
+    fn state(&self) -> TokenState<'a> {
+        Paused::new(self.data, self.paused)
+    }
+}
+
+impl<'a> From<TokenService<'a>> for VftService<TokenState<'a>> {
+    fn from(value: TokenService<'a>) -> Self {
+        VftService::new(value.state())
+    }
+}
+
+impl<'a> From<TokenService<'a>> for VftMintableService<TokenState<'a>, &'a RefCell<RolesData>> {
+    fn from(value: TokenService<'a>) -> Self {
+        VftMintableService::new(value.state(), value.roles)
+    }
+}
+
+impl<'a> From<TokenService<'a>> for VftBurnableService<TokenState<'a>> {
+    fn from(value: TokenService<'a>) -> Self {
+        VftBurnableService::new(value.state())
+    }
+}
+
+impl<'a> From<TokenService<'a>> for VftPausableService<'a, &'a RefCell<RolesData>> {
+    fn from(value: TokenService<'a>) -> Self {
+        VftPausableService::new(value.paused, value.roles)
+    }
+}
+
+#[service(extends = [
+    VftService<TokenState<'a>>,
+    VftMintableService<TokenState<'a>, &'a RefCell<RolesData>>,
+    VftBurnableService<TokenState<'a>>,
+    VftPausableService<'a, &'a RefCell<RolesData>>,
+])]
+impl<'a> TokenService<'a> {}
+
+pub struct TokenProgram {
+    data: RefCell<VftData>,
+    roles: RefCell<RolesData>,
+    paused: Cell<bool>,
+}
+
+#[program]
+impl TokenProgram {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        let mut roles = RolesData::new(ADMIN);
+        roles.grant_role(MINTER_ROLE, ADMIN);
+        roles.grant_role(PAUSER_ROLE, ADMIN);
+        Self {
+            data: RefCell::new(VftData::new(Metadata {
+                name: "Token".into(),
+                symbol: "TKN".into(),
+                decimals: 18,
+            })),
+            roles: RefCell::new(roles),
+            paused: Cell::new(false),
+        }
+    }
+
+    pub fn token(&self) -> TokenService<'_> {
+        token(self)
+    }
+}
+
+fn token(program: &TokenProgram) -> TokenService<'_> {
+    TokenService {
+        data: &program.data,
+        roles: &program.roles,
+        paused: &program.paused,
+    }
+}
+
+#[test]
+fn token_composes_extensions() {
+    let program = TokenProgram::new();
+    Syscall::with_message_source(ADMIN);
+
+    let mut mintable = VftMintableService::from(token(&program)).expose(1);
+    let mut emitter = mintable.emitter();
+    assert_eq!(mintable.mint(ALICE, 100.into()), Ok(true));
+    assert_eq!(
+        emitter.take_events(),
+        vec![VftMintableEvents::Minted {
+            to: ALICE,
+            value: 100.into(),
+        }]
+    );
+
+    Syscall::with_message_source(ALICE);
+    let mut vft = VftService::from(token(&program)).expose(1);
+    let mut emitter = vft.emitter();
+    assert_eq!(vft.transfer(BOB, 40.into()), Ok(true));
+    assert_eq!(vft.approve(BOB, 10.into()), Ok(true));
+    assert_eq!(
+        emitter.take_events(),
+        vec![
+            VftEvents::Transfer {
+                from: ALICE,
+                to: BOB,
+                value: 40.into(),
+            },
+            VftEvents::Approval {
+                owner: ALICE,
+                spender: BOB,
+                value: 10.into(),
+            },
+        ]
+    );
+
+    Syscall::with_message_source(BOB);
+    let mut burnable = VftBurnableService::from(token(&program)).expose(1);
+    assert_eq!(burnable.burn_from(ALICE, 10.into()), Ok(true));
+    assert_eq!(
+        burnable.burn_from(ALICE, 1.into()),
+        Err(VftError::InsufficientAllowance)
+    );
+
+    assert_eq!(vft.name(), "Token");
+    assert_eq!(vft.decimals(), 18);
+    assert_eq!(vft.balance_of(ALICE), 50.into());
+    assert_eq!(vft.balance_of(BOB), 40.into());
+    assert_eq!(vft.total_supply(), 90.into());
+}
+
+#[test]
+fn paused_token_rejects_writes() {
+    let program = TokenProgram::new();
+    Syscall::with_message_source(ADMIN);
+
+    let mut mintable = VftMintableService::from(token(&program)).expose(1);
+    mintable.mint(ADMIN, 10.into()).unwrap();
+
+    let mut pausable = VftPausableService::from(token(&program)).expose(1);
+    assert!(pausable.pause());
+    assert!(!pausable.pause());
+    assert!(pausable.is_paused());
+
+    let mut vft = VftService::from(token(&program)).expose(1);
+    assert_eq!(vft.transfer(ALICE, 1.into()), Err(VftError::Paused));
+    assert_eq!(mintable.mint(ALICE, 1.into()), Err(VftError::Paused));
+    assert_eq!(vft.balance_of(ADMIN), 10.into());
+
+    assert!(pausable.unpause());
+    assert_eq!(vft.transfer(ALICE, 1.into()), Ok(true));
+}
+
+#[test]
+#[should_panic(expected = "is missing role MINTER")]
+fn mint_requires_minter_role() {
+    let program = TokenProgram::new();
+    Syscall::with_message_source(ALICE);
+
+    let mut mintable = VftMintableService::from(token(&program)).expose(1);
+    _ = mintable.mint(ALICE, 1.into());
+}
+
+#[test]
+fn token_idl() {
+    let mut idl = String::new();
+    sails_idl_gen::program::generate_idl::<TokenProgram>(None, &mut idl).unwrap();
+    insta::assert_snapshot!(idl);
+}
+
+#[test]
+fn interface_ids_do_not_depend_on_state() {
+    assert_eq!(
+        <VftService<TokenState<'_>> as Identifiable>::INTERFACE_ID,
+        <VftService as Identifiable>::INTERFACE_ID
+    );
+}
@@ -0,0 +1,46 @@
+use crate::{TokenId, VnftData, VnftError};
+use sails::{cell::RefCell, prelude::*};
+
+/// Events emitted by [`VnftBurnableService`].
+#[event]
+#[sails_type]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VnftBurnableEvents {
+    /// `token_id` of `from` was destroyed.
+    Burned {
+        #[indexed]
+        from: ActorId,
+        #[indexed]
+        token_id: TokenId,
+    },
+}
+
+/// Non-fungible token extension burning tokens the caller owns or is approved to manage.
+pub struct VnftBurnableService<
+    S: StateMut<Item = VnftData, Error: Into<VnftError>> = RefCell<VnftData>,
+> {
+    data: S,
+}
+
+impl<S: StateMut<Item = VnftData, Error: Into<VnftError>>> VnftBurnableService<S> {
+    pub fn new(data: S) -> Self {
+        Self { data }
+    }
+}
+
+#[service(events = VnftBurnableEvents)]
+impl<S: StateMut<Item = VnftData, Error: Into<VnftError>>> VnftBurnableService<S> {
+    /// Destroys `token_id`, the caller must be the owner, approved or an operator
+    #[export(unwrap_result)]
+    pub fn burn(&mut self, token_id: TokenId) -> Result<(), VnftError> {
+        let spender = Syscall::message_source();
+        let from = self
+            .data
+            .write()
+            .map_err(Into::into)?
+            .burn(spender, token_id)?;
+        self.emit_event(VnftBurnableEvents::Burned { from, token_id })
+            .expect("Failed to emit event");
+        Ok(())
+    }
+}
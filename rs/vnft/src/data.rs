@@ -0,0 +1,253 @@
+use sails::prelude::{
+    collections::{BTreeMap, BTreeSet},
+    *,
+};
+
+/// Identifier of a non-fungible token.
+pub type TokenId = U256;
+
+/// Errors of non-fungible token operations.
+#[sails_type]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VnftError {
+    /// The token does not exist.
+    TokenNotFound,
+    /// The token already exists.
+    TokenExists,
+    /// The account is not the owner of the token.
+    NotOwner,
+    /// The caller is neither the owner of the token nor approved to manage it.
+    NotApproved,
+    /// The token is paused.
+    Paused,
+}
+
+impl fmt::Display for VnftError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::TokenNotFound => "token not found",
+            Self::TokenExists => "token already exists",
+            Self::NotOwner => "account is not the token owner",
+            Self::NotApproved => "caller is not approved",
+            Self::Paused => "token is paused",
+        })
+    }
+}
+
+impl core::error::Error for VnftError {}
+
+impl From<Infallible> for VnftError {
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
+}
+
+impl<E: Into<VnftError>> From<sails::state::PausedError<E>> for VnftError {
+    fn from(value: sails::state::PausedError<E>) -> Self {
+        match value {
+            sails::state::PausedError::Paused => Self::Paused,
+            sails::state::PausedError::Inner(err) => err.into(),
+        }
+    }
+}
+
+/// Name and symbol of a collection.
+#[sails_type]
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Metadata {
+    pub name: String,
+    pub symbol: String,
+}
+
+/// Owners, approvals and metadata of a non-fungible token collection.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct VnftData {
+    metadata: Metadata,
+    owners: BTreeMap<TokenId, ActorId>,
+    balances: BTreeMap<ActorId, U256>,
+    token_uris: BTreeMap<TokenId, String>,
+    token_approvals: BTreeMap<TokenId, ActorId>,
+    operator_approvals: BTreeSet<(ActorId, ActorId)>,
+}
+
+impl VnftData {
+    pub fn new(metadata: Metadata) -> Self {
+        Self {
+            metadata,
+            ..Default::default()
+        }
+    }
+
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    pub fn balance_of(&self, owner: ActorId) -> U256 {
+        self.balances.get(&owner).copied().unwrap_or_default()
+    }
+
+    pub fn owner_of(&self, token_id: TokenId) -> Option<ActorId> {
+        self.owners.get(&token_id).copied()
+    }
+
+    pub fn token_uri(&self, token_id: TokenId) -> Option<&str> {
+        self.token_uris.get(&token_id).map(String::as_str)
+    }
+
+    pub fn get_approved(&self, token_id: TokenId) -> Option<ActorId> {
+        self.token_approvals.get(&token_id).copied()
+    }
+
+    pub fn is_approved_for_all(&self, owner: ActorId, operator: ActorId) -> bool {
+        self.operator_approvals.contains(&(owner, operator))
+    }
+
+    /// Approves `approved` to manage `token_id`, `caller` must be the owner or its operator.
+    /// Returns the owner of the token.
+    pub fn approve(
+        &mut self,
+        caller: ActorId,
+        approved: ActorId,
+        token_id: TokenId,
+    ) -> Result<ActorId, VnftError> {
+        let owner = self.owner_of(token_id).ok_or(VnftError::TokenNotFound)?;
+        if caller != owner && !self.is_approved_for_all(owner, caller) {
+            return Err(VnftError::NotApproved);
+        }
+        self.token_approvals.insert(token_id, approved);
+        Ok(owner)
+    }
+
+    /// Allows or disallows `operator` to manage all tokens of `owner`, returns `false` if unchanged.
+    pub fn set_approval_for_all(
+        &mut self,
+        owner: ActorId,
+        operator: ActorId,
+        approved: bool,
+    ) -> bool {
+        if approved {
+            self.operator_approvals.insert((owner, operator))
+        } else {
+            self.operator_approvals.remove(&(owner, operator))
+        }
+    }
+
+    /// Moves `token_id` from `from` to `to`, `spender` must be the owner, approved or an operator.
+    pub fn transfer_from(
+        &mut self,
+        spender: ActorId,
+        from: ActorId,
+        to: ActorId,
+        token_id: TokenId,
+    ) -> Result<(), VnftError> {
+        let owner = self.check_spender(spender, token_id)?;
+        if owner != from {
+            return Err(VnftError::NotOwner);
+        }
+        self.token_approvals.remove(&token_id);
+        self.owners.insert(token_id, to);
+        self.set_balance(from, self.balance_of(from) - 1);
+        self.set_balance(to, self.balance_of(to) + 1);
+        Ok(())
+    }
+
+    /// Creates `token_id` owned by `to`.
+    pub fn mint(
+        &mut self,
+        to: ActorId,
+        token_id: TokenId,
+        token_uri: String,
+    ) -> Result<(), VnftError> {
+        if self.owners.contains_key(&token_id) {
+            return Err(VnftError::TokenExists);
+        }
+        self.owners.insert(token_id, to);
+        self.token_uris.insert(token_id, token_uri);
+        self.set_balance(to, self.balance_of(to) + 1);
+        Ok(())
+    }
+
+    /// Destroys `token_id`, `spender` must be the owner, approved or an operator.
+    /// Returns the owner of the token.
+    pub fn burn(&mut self, spender: ActorId, token_id: TokenId) -> Result<ActorId, VnftError> {
+        let owner = self.check_spender(spender, token_id)?;
+        self.token_approvals.remove(&token_id);
+        self.token_uris.remove(&token_id);
+        self.owners.remove(&token_id);
+        self.set_balance(owner, self.balance_of(owner) - 1);
+        Ok(owner)
+    }
+
+    fn check_spender(&self, spender: ActorId, token_id: TokenId) -> Result<ActorId, VnftError> {
+        let owner = self.owner_of(token_id).ok_or(VnftError::TokenNotFound)?;
+        if spender != owner
+            && self.get_approved(token_id) != Some(spender)
+            && !self.is_approved_for_all(owner, spender)
+        {
+            return Err(VnftError::NotApproved);
+        }
+        Ok(owner)
+    }
+
+    fn set_balance(&mut self, owner: ActorId, balance: U256) {
+        if balance.is_zero() {
+            self.balances.remove(&owner);
+        } else {
+            self.balances.insert(owner, balance);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALICE: ActorId = ActorId::new([1; 32]);
+    const BOB: ActorId = ActorId::new([2; 32]);
+    const CAROL: ActorId = ActorId::new([3; 32]);
+
+    #[test]
+    fn mint_transfer_and_burn() {
+        let mut data = VnftData::default();
+        let token_id = TokenId::from(1);
+
+        data.mint(ALICE, token_id, "ipfs://1".into()).unwrap();
+        assert_eq!(
+            data.mint(BOB, token_id, String::new()),
+            Err(VnftError::TokenExists)
+        );
+        assert_eq!(
+            data.transfer_from(BOB, ALICE, BOB, token_id),
+            Err(VnftError::NotApproved)
+        );
+        data.transfer_from(ALICE, ALICE, BOB, token_id).unwrap();
+        assert_eq!(data.owner_of(token_id), Some(BOB));
+        assert_eq!(data.balance_of(ALICE), U256::zero());
+        assert_eq!(data.balance_of(BOB), 1.into());
+
+        assert_eq!(data.burn(BOB, token_id), Ok(BOB));
+        assert_eq!(data.owner_of(token_id), None);
+        assert_eq!(data.token_uri(token_id), None);
+        assert_eq!(data.balance_of(BOB), U256::zero());
+    }
+
+    #[test]
+    fn approvals_allow_transfers() {
+        let mut data = VnftData::default();
+        data.mint(ALICE, 1.into(), String::new()).unwrap();
+        data.mint(ALICE, 2.into(), String::new()).unwrap();
+
+        assert_eq!(data.approve(ALICE, BOB, 1.into()), Ok(ALICE));
+        data.transfer_from(BOB, ALICE, CAROL, 1.into()).unwrap();
+        assert_eq!(data.get_approved(1.into()), None);
+
+        assert!(data.set_approval_for_all(ALICE, BOB, true));
+        assert!(!data.set_approval_for_all(ALICE, BOB, true));
+        assert_eq!(
+            data.transfer_from(BOB, CAROL, BOB, 2.into()),
+            Err(VnftError::NotOwner)
+        );
+        data.transfer_from(BOB, ALICE, BOB, 2.into()).unwrap();
+        assert_eq!(data.owner_of(2.into()), Some(BOB));
+    }
+}
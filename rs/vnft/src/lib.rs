@@ -0,0 +1,46 @@
+//! Non-fungible token services built with Sails.
+//!
+//! [`VnftService`] implements owners, approvals and metadata. The extension services
+//! add minting ([`VnftMintableService`]), burning ([`VnftBurnableService`]) and pausing
+//! ([`VnftPausableService`]) and are meant to be composed via `extends`, so programs
+//! sharing them expose the same interfaces to clients.
+//!
+//! All services are generic over the state handle of [`VnftData`]; a pausable collection
+//! passes `sails::state::Paused<&RefCell<VnftData>>` so that the writes of all services
+//! are rejected while it is paused.
+//!
+//! ```rust,ignore
+//! use sails::{cell::{Cell, RefCell}, prelude::*, access_control::RolesData, state::Paused};
+//! use sails_vnft::*;
+//!
+//! type CollectionState<'a> = Paused<'a, &'a RefCell<VnftData>>;
+//!
+//! pub struct CollectionService<'a> {
+//!     data: &'a RefCell<VnftData>,
+//!     roles: &'a RefCell<RolesData>,
+//!     paused: &'a Cell<bool>,
+//! }
+//!
+//! // `From<CollectionService>` impls for every extended service
+//!
+//! #[service(extends = [
+//!     VnftService<CollectionState<'a>>,
+//!     VnftMintableService<CollectionState<'a>, &'a RefCell<RolesData>>,
+//!     VnftPausableService<'a, &'a RefCell<RolesData>>,
+//! ])]
+//! impl<'a> CollectionService<'a> {}
+//! ```
+
+#![no_std]
+
+pub use burnable::{VnftBurnableEvents, VnftBurnableService};
+pub use data::{Metadata, TokenId, VnftData, VnftError};
+pub use mintable::{MINTER_ROLE, VnftMintableEvents, VnftMintableService};
+pub use pausable::{PAUSER_ROLE, VnftPausableEvents, VnftPausableService};
+pub use vnft::{VnftEvents, VnftService};
+
+mod burnable;
+mod data;
+mod mintable;
+mod pausable;
+mod vnft;
@@ -0,0 +1,76 @@
+use crate::{TokenId, VnftData, VnftError};
+use sails::{
+    access_control::{RoleGuard, RolesData},
+    cell::RefCell,
+    prelude::*,
+};
+
+/// The role allowed to mint tokens.
+pub const MINTER_ROLE: &str = "MINTER";
+
+/// Events emitted by [`VnftMintableService`].
+#[event]
+#[sails_type]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VnftMintableEvents {
+    /// `token_id` was created for `to`.
+    Minted {
+        #[indexed]
+        to: ActorId,
+        #[indexed]
+        token_id: TokenId,
+    },
+}
+
+/// Non-fungible token extension minting tokens, the caller must have [`MINTER_ROLE`].
+pub struct VnftMintableService<
+    S: StateMut<Item = VnftData, Error: Into<VnftError>> = RefCell<VnftData>,
+    R: State<Item = RolesData, Error = Infallible> = RefCell<RolesData>,
+> {
+    data: S,
+    roles: R,
+}
+
+impl<S, R> VnftMintableService<S, R>
+where
+    S: StateMut<Item = VnftData, Error: Into<VnftError>>,
+    R: State<Item = RolesData, Error = Infallible>,
+{
+    pub fn new(data: S, roles: R) -> Self {
+        Self { data, roles }
+    }
+}
+
+impl<S, R> RoleGuard for VnftMintableService<S, R>
+where
+    S: StateMut<Item = VnftData, Error: Into<VnftError>>,
+    R: State<Item = RolesData, Error = Infallible>,
+{
+    fn has_role(&self, role: &str, account: ActorId) -> bool {
+        self.roles.get().has_role(role, account)
+    }
+}
+
+#[service(events = VnftMintableEvents)]
+impl<S, R> VnftMintableService<S, R>
+where
+    S: StateMut<Item = VnftData, Error: Into<VnftError>>,
+    R: State<Item = RolesData, Error = Infallible>,
+{
+    /// Creates `token_id` with `token_uri` for `to`
+    #[export(requires_role = "MINTER", unwrap_result)]
+    pub fn mint(
+        &mut self,
+        to: ActorId,
+        token_id: TokenId,
+        token_uri: String,
+    ) -> Result<(), VnftError> {
+        self.data
+            .write()
+            .map_err(Into::into)?
+            .mint(to, token_id, token_uri)?;
+        self.emit_event(VnftMintableEvents::Minted { to, token_id })
+            .expect("Failed to emit event");
+        Ok(())
+    }
+}
@@ -0,0 +1,147 @@
+use crate::{TokenId, VnftData, VnftError};
+use sails::{cell::RefCell, prelude::*};
+
+/// Events emitted by [`VnftService`].
+#[event]
+#[sails_type]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum VnftEvents {
+    /// `approved` was approved by `owner` to manage `token_id`.
+    Approval {
+        #[indexed]
+        owner: ActorId,
+        #[indexed]
+        approved: ActorId,
+        #[indexed]
+        token_id: TokenId,
+    },
+    /// `operator` was allowed or disallowed to manage all tokens of `owner`.
+    ApprovalForAll {
+        #[indexed]
+        owner: ActorId,
+        #[indexed]
+        operator: ActorId,
+        approved: bool,
+    },
+    /// `token_id` was moved from `from` to `to`.
+    Transfer {
+        #[indexed]
+        from: ActorId,
+        #[indexed]
+        to: ActorId,
+        #[indexed]
+        token_id: TokenId,
+    },
+}
+
+/// Non-fungible token service: owners, approvals and metadata.
+pub struct VnftService<S: StateMut<Item = VnftData, Error: Into<VnftError>> = RefCell<VnftData>> {
+    data: S,
+}
+
+impl<S: StateMut<Item = VnftData, Error: Into<VnftError>>> VnftService<S> {
+    pub fn new(data: S) -> Self {
+        Self { data }
+    }
+
+    fn data(&self) -> impl ops::Deref<Target = VnftData> + '_ {
+        self.data
+            .read()
+            .unwrap_or_else(|err| panic!("{}", err.into()))
+    }
+
+    fn data_mut(&mut self) -> Result<impl ops::DerefMut<Target = VnftData> + '_, VnftError> {
+        self.data.write().map_err(Into::into)
+    }
+}
+
+#[service(events = VnftEvents)]
+impl<S: StateMut<Item = VnftData, Error: Into<VnftError>>> VnftService<S> {
+    #[export]
+    pub fn name(&self) -> String {
+        self.data().metadata().name.clone()
+    }
+
+    #[export]
+    pub fn symbol(&self) -> String {
+        self.data().metadata().symbol.clone()
+    }
+
+    #[export]
+    pub fn balance_of(&self, owner: ActorId) -> U256 {
+        self.data().balance_of(owner)
+    }
+
+    #[export]
+    pub fn owner_of(&self, token_id: TokenId) -> Option<ActorId> {
+        self.data().owner_of(token_id)
+    }
+
+    #[export]
+    pub fn token_uri(&self, token_id: TokenId) -> Option<String> {
+        self.data().token_uri(token_id).map(Into::into)
+    }
+
+    #[export]
+    pub fn get_approved(&self, token_id: TokenId) -> Option<ActorId> {
+        self.data().get_approved(token_id)
+    }
+
+    #[export]
+    pub fn is_approved_for_all(&self, owner: ActorId, operator: ActorId) -> bool {
+        self.data().is_approved_for_all(owner, operator)
+    }
+
+    /// Approves `approved` to manage `token_id`, the caller must be the owner or its operator
+    #[export(unwrap_result)]
+    pub fn approve(&mut self, approved: ActorId, token_id: TokenId) -> Result<(), VnftError> {
+        let caller = Syscall::message_source();
+        let owner = self.data_mut()?.approve(caller, approved, token_id)?;
+        self.emit_event(VnftEvents::Approval {
+            owner,
+            approved,
+            token_id,
+        })
+        .expect("Failed to emit event");
+        Ok(())
+    }
+
+    /// Allows or disallows `operator` to manage all tokens of the caller,
+    /// returns `false` if unchanged
+    #[export(unwrap_result)]
+    pub fn set_approval_for_all(
+        &mut self,
+        operator: ActorId,
+        approved: bool,
+    ) -> Result<bool, VnftError> {
+        let owner = Syscall::message_source();
+        let changed = self
+            .data_mut()?
+            .set_approval_for_all(owner, operator, approved);
+        if changed {
+            self.emit_event(VnftEvents::ApprovalForAll {
+                owner,
+                operator,
+                approved,
+            })
+            .expect("Failed to emit event");
+        }
+        Ok(changed)
+    }
+
+    /// Moves `token_id` from `from` to `to`, the caller must be the owner, approved or an operator
+    #[export(unwrap_result)]
+    pub fn transfer_from(
+        &mut self,
+        from: ActorId,
+        to: ActorId,
+        token_id: TokenId,
+    ) -> Result<(), VnftError> {
+        let spender = Syscall::message_source();
+        self.data_mut()?
+            .transfer_from(spender, from, to, token_id)?;
+        self.emit_event(VnftEvents::Transfer { from, to, token_id })
+            .expect("Failed to emit event");
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use sails::{
+    access_control::RolesData,
+    cell::{Cell, RefCell},
+    gstd::{Syscall, services::Service},
+    prelude::*,
+    state::Paused,
+};
+use sails_vnft::*;
+
+const ADMIN: ActorId = ActorId::new([1; 32]);
+const ALICE: ActorId = ActorId::new([2; 32]);
+const BOB: ActorId = ActorId::new([3; 32]);
+
+type CollectionState<'a> = Paused<'a, &'a RefCell<VnftData>>;
+
+pub struct CollectionService<'a> {
+    data: &'a RefCell<VnftData>,
+    roles: &'a RefCell<RolesData>,
+    paused: &'a Cell<bool>,
+}
+
+impl<'a> CollectionService<'a> {
+    fn state(&self) -> CollectionState<'a> {
+        Paused::new(self.data, self.paused)
+    }
+}
+
+impl<'a> From<CollectionService<'a>> for VnftService<CollectionState<'a>> {
+    fn from(value: CollectionService<'a>) -> Self {
+        VnftService::new(value.state())
+    }
+}
+
+impl<'a> From<CollectionService<'a>>
+    for VnftMintableService<CollectionState<'a>, &'a RefCell<RolesData>>
+{
+    fn from(value: CollectionService<'a>) -> Self {
+        VnftMintableService::new(value.state(), value.roles)
+    }
+}
+
+impl<'a> From<CollectionService<'a>> for VnftBurnableService<CollectionState<'a>> {
+    fn from(value: CollectionService<'a>) -> Self {
+        VnftBurnableService::new(value.state())
+    }
+}
+
+impl<'a> From<CollectionService<'a>> for VnftPausableService<'a, &'a RefCell<RolesData>> {
+    fn from(value: CollectionService<'a>) -> Self {
+        VnftPausableService::new(value.paused, value.roles)
+    }
+}
+
+#[service(extends = [
+    VnftService<CollectionState<'a>>,
+    VnftMintableService<CollectionState<'a>, &'a RefCell<RolesData>>,
+    VnftBurnableService<CollectionState<'a>>,
+    VnftPausableService<'a, &'a RefCell<RolesData>>,
+])]
+impl<'a> CollectionService<'a> {}
+
+pub struct CollectionProgram {
+    data: RefCell<VnftData>,
+    roles: RefCell<RolesData>,
+    paused: Cell<bool>,
+}
+
+#[program]
+impl CollectionProgram {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        let mut roles = RolesData::new(ADMIN);
+        roles.grant_role(MINTER_ROLE, ADMIN);
+        roles.grant_role(PAUSER_ROLE, ADMIN);
+        Self {
+            data: RefCell::new(VnftData::new(Metadata {
+                name: "Collection".into(),
+                symbol: "COL".into(),
+            })),
+            roles: RefCell::new(roles),
+            paused: Cell::new(false),
+        }
+    }
+
+    pub fn collection(&self) -> CollectionService<'_> {
+        collection(self)
+    }
+}
+
+fn collection(program: &CollectionProgram) -> CollectionService<'_> {
+    CollectionService {
+        data: &program.data,
+        roles: &program.roles,
+        paused: &program.paused,
+    }
+}
+
+#[test]
+fn collection_composes_extensions() {
+    let program = CollectionProgram::new();
+    let token_id = TokenId::from(7);
+    Syscall::with_message_source(ADMIN);
+
+    let mut mintable = VnftMintableService::from(collection(&program)).expose(1);
+    let mut emitter = mintable.emitter();
+    mintable.mint(ALICE, token_id, "ipfs://7".into()).unwrap();
+    assert_eq!(
+        emitter.take_events(),
+        vec![VnftMintableEvents::Minted {
+            to: ALICE,
+            token_id,
+        }]
+    );
+
+    Syscall::with_message_source(ALICE);
+    let mut vnft = VnftService::from(collection(&program)).expose(1);
+    let mut emitter = vnft.emitter();
+    vnft.approve(BOB, token_id).unwrap();
+    Syscall::with_message_source(BOB);
+    vnft.transfer_from(ALICE, BOB, token_id).unwrap();
+    assert_eq!(
+        emitter.take_events(),
+        vec![
+            VnftEvents::Approval {
+                owner: ALICE,
+                approved: BOB,
+                token_id,
+            },
+            VnftEvents::Transfer {
+                from: ALICE,
+                to: BOB,
+                token_id,
+            },
+        ]
+    );
+    assert_eq!(vnft.owner_of(token_id), Some(BOB));
+    assert_eq!(vnft.token_uri(token_id), Some("ipfs://7".into()));
+
+    let mut burnable = VnftBurnableService::from(collection(&program)).expose(1);
+    burnable.burn(token_id).unwrap();
+    assert_eq!(vnft.owner_of(token_id), None);
+    assert_eq!(vnft.balance_of(BOB), U256::zero());
+    assert_eq!(vnft.name(), "Collection");
+}
+
+#[test]
+fn paused_collection_rejects_writes() {
+    let program = CollectionProgram::new();
+    Syscall::with_message_source(ADMIN);
+
+    let mut mintable = VnftMintableService::from(collection(&program)).expose(1);
+    mintable.mint(ADMIN, 1.into(), String::new()).unwrap();
+
+    let mut pausable = VnftPausableService::from(collection(&program)).expose(1);
+    assert!(pausable.pause());
+
+    let mut vnft = VnftService::from(collection(&program)).expose(1);
+    assert_eq!(
+        vnft.transfer_from(ADMIN, ALICE, 1.into()),
+        Err(VnftError::Paused)
+    );
+    assert_eq!(
+        mintable.mint(ALICE, 2.into(), String::new()),
+        Err(VnftError::Paused)
+    );
+
+    assert!(pausable.unpause());
+    vnft.transfer_from(ADMIN, ALICE, 1.into()).unwrap();
+    assert_eq!(vnft.owner_of(1.into()), Some(ALICE));
+}
+
+#[test]
+#[should_panic(expected = "is missing role PAUSER")]
+fn pause_requires_pauser_role() {
+    let program = CollectionProgram::new();
+    Syscall::with_message_source(ALICE);
+
+    let mut pausable = VnftPausableService::from(collection(&program)).expose(1);
+    pausable.pause();
+}
+
+#[test]
+fn collection_idl() {
+    let mut idl = String::new();
+    sails_idl_gen::program::generate_idl::<CollectionProgram>(None, &mut idl).unwrap();
+    insta::assert_snapshot!(idl);
+}